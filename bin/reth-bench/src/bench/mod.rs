@@ -10,6 +10,7 @@ mod new_payload_fcu;
 mod new_payload_only;
 mod output;
 mod send_payload;
+mod stage;
 
 /// `reth bench` command
 #[derive(Debug, Parser)]
@@ -41,6 +42,10 @@ pub enum Subcommands {
     /// `cast block latest --full --json | reth-bench send-payload --rpc-url localhost:5000
     /// --jwt-secret $(cat ~/.local/share/reth/mainnet/jwt.hex)`
     SendPayload(send_payload::Command),
+
+    /// Benchmark which runs a single pipeline stage with `reth stage run` on snapshots of a
+    /// datadir, reporting the throughput and the database growth of the stage.
+    Stage(stage::Command),
 }
 
 impl BenchmarkCommand {
@@ -53,6 +58,7 @@ impl BenchmarkCommand {
             Subcommands::NewPayloadFcu(command) => command.execute(ctx).await,
             Subcommands::NewPayloadOnly(command) => command.execute(ctx).await,
             Subcommands::SendPayload(command) => command.execute(ctx).await,
+            Subcommands::Stage(command) => command.execute(ctx).await,
         }
     }
 
@@ -3,7 +3,8 @@
 
 use eyre::OptionExt;
 use reth_primitives_traits::constants::GIGAGAS;
-use serde::{ser::SerializeStruct, Serialize};
+use reth_stages_types::StageRunReport;
+use serde::{ser::SerializeStruct, Deserialize, Serialize};
 use std::time::Duration;
 
 /// This is the suffix for gas output csv files.
@@ -186,10 +187,109 @@ impl Serialize for TotalGasRow {
     }
 }
 
+/// The aggregated result of a `reth-bench stage` benchmark, using the median of all runs.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct StageBenchResult {
+    /// The benchmarked stage.
+    pub(crate) stage: String,
+    /// The block the stage was run from.
+    pub(crate) from: u64,
+    /// The block the stage was run to.
+    pub(crate) to: u64,
+    /// The median number of blocks executed per second.
+    pub(crate) blocks_per_second: f64,
+    /// The median gas processed per second.
+    pub(crate) gas_per_second: f64,
+    /// The median growth of the storage in bytes.
+    pub(crate) bytes_written: u64,
+    /// The reports of the individual runs.
+    pub(crate) runs: Vec<StageRunReport>,
+}
+
+impl StageBenchResult {
+    /// Aggregates the reports of the runs of a stage over the given range.
+    pub(crate) fn new(stage: String, from: u64, to: u64, runs: Vec<StageRunReport>) -> Self {
+        let blocks_per_second = median(runs.iter().map(StageRunReport::blocks_per_second));
+        let gas_per_second = median(runs.iter().map(StageRunReport::gas_per_second));
+        let bytes_written =
+            median(runs.iter().map(|run| run.bytes_written() as f64)).round() as u64;
+
+        Self { stage, from, to, blocks_per_second, gas_per_second, bytes_written, runs }
+    }
+
+    /// Returns the change of the throughput compared to the baseline in percent.
+    ///
+    /// Fails if the baseline was taken for a different stage or range.
+    pub(crate) fn compare(&self, baseline: &Self) -> eyre::Result<f64> {
+        if (&self.stage, self.from, self.to) != (&baseline.stage, baseline.from, baseline.to) {
+            eyre::bail!(
+                "baseline is for stage {} from {} to {}",
+                baseline.stage,
+                baseline.from,
+                baseline.to
+            )
+        }
+
+        Ok((self.blocks_per_second / baseline.blocks_per_second - 1.0) * 100.0)
+    }
+}
+
+impl std::fmt::Display for StageBenchResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Stage {} from {} to {}, median of {} runs:",
+            self.stage,
+            self.from,
+            self.to,
+            self.runs.len()
+        )?;
+        writeln!(f, "  {:.2} blocks/s", self.blocks_per_second)?;
+        writeln!(f, "  {:.4} Ggas/s", self.gas_per_second / GIGAGAS as f64)?;
+        write!(f, "  {} bytes written", self.bytes_written)?;
+
+        // The page statistics are deterministic, so the last run is representative
+        if let Some(run) = self.runs.last() {
+            let (before, after) = (&run.storage_before, &run.storage_after);
+            write!(
+                f,
+                "\n  pages: leaf {} -> {}, branch {} -> {}, overflow {} -> {}, freelist {} -> {}",
+                before.leaf_pages,
+                after.leaf_pages,
+                before.branch_pages,
+                after.branch_pages,
+                before.overflow_pages,
+                after.overflow_pages,
+                before.freelist_pages,
+                after.freelist_pages
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the median of the values, or zero if there are none.
+fn median(values: impl Iterator<Item = f64>) -> f64 {
+    let mut values = values.collect::<Vec<_>>();
+    if values.is_empty() {
+        return 0.0
+    }
+
+    values.sort_by(f64::total_cmp);
+    let middle = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[middle - 1] + values[middle]) / 2.0
+    } else {
+        values[middle]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use csv::Writer;
+    use reth_stages_types::StorageReport;
     use std::io::BufRead;
 
     #[test]
@@ -217,4 +317,60 @@ mod tests {
         let second_line = result.next().unwrap().unwrap();
         assert_eq!(second_line, expected_second_line);
     }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median([].into_iter()), 0.0);
+        assert_eq!(median([3.0, 1.0, 2.0].into_iter()), 2.0);
+        assert_eq!(median([4.0, 1.0, 2.0, 3.0].into_iter()), 2.5);
+    }
+
+    fn stage_run(elapsed: f64, gas_used: u64, bytes_written: u64) -> StageRunReport {
+        StageRunReport {
+            stage: "execution".to_string(),
+            from: 0,
+            to: 100,
+            elapsed,
+            gas_used,
+            storage_before: Default::default(),
+            storage_after: StorageReport { database: bytes_written, ..Default::default() },
+        }
+    }
+
+    #[test]
+    fn test_stage_bench_result() {
+        let result = StageBenchResult::new(
+            "execution".to_string(),
+            0,
+            100,
+            vec![stage_run(1.0, 1_000, 10), stage_run(4.0, 2_000, 30), stage_run(2.0, 1_000, 20)],
+        );
+        assert_eq!(result.blocks_per_second, 50.0);
+        assert_eq!(result.gas_per_second, 500.0);
+        assert_eq!(result.bytes_written, 20);
+
+        // The runs are serialized with the result, so that it can be used as a baseline
+        let json = serde_json::to_string(&result).unwrap();
+        let baseline: StageBenchResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(baseline.runs, result.runs);
+        assert_eq!(result.compare(&baseline).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_stage_bench_compare() {
+        let baseline =
+            StageBenchResult::new("execution".to_string(), 0, 100, vec![stage_run(2.0, 0, 0)]);
+
+        let faster =
+            StageBenchResult::new("execution".to_string(), 0, 100, vec![stage_run(1.0, 0, 0)]);
+        assert_eq!(faster.compare(&baseline).unwrap(), 100.0);
+        let slower =
+            StageBenchResult::new("execution".to_string(), 0, 100, vec![stage_run(4.0, 0, 0)]);
+        assert_eq!(slower.compare(&baseline).unwrap(), -50.0);
+
+        // Results of a different stage or range can't be compared
+        let other =
+            StageBenchResult::new("execution".to_string(), 0, 200, vec![stage_run(1.0, 0, 0)]);
+        assert!(other.compare(&baseline).is_err());
+    }
 }
@@ -0,0 +1,233 @@
+//! Runs the `reth bench stage` command, benchmarking a single pipeline stage.
+//!
+//! The stage is run with `reth stage run` on a copy-on-write copy of the datadir, so that every run
+//! starts from the same database. A datadir created with `reth stage dump` keeps the snapshot
+//! small, as it only contains the data the stage needs.
+
+use crate::bench::output::StageBenchResult;
+use clap::{Parser, ValueEnum};
+use eyre::{bail, Context};
+use reth_cli_runner::CliContext;
+use reth_node_core::args::StageEnum;
+use reth_primitives_traits::constants::GIGAGAS;
+use reth_stages_types::StageRunReport;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command as ProcessCommand, Stdio},
+};
+use tracing::{info, warn};
+
+/// `reth-bench stage` command
+///
+/// The node must not be running while the benchmark is run.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The stage to benchmark.
+    #[arg(value_enum)]
+    stage: StageEnum,
+
+    /// The datadir to take the snapshot of the database from.
+    #[arg(long, value_name = "DATA_DIR")]
+    datadir: PathBuf,
+
+    /// The chain of the datadir.
+    #[arg(long, value_name = "CHAIN_OR_PATH", default_value = "mainnet")]
+    chain: String,
+
+    /// The block to run the stage from.
+    #[arg(long)]
+    from: u64,
+
+    /// The block to run the stage to.
+    #[arg(long)]
+    to: u64,
+
+    /// The number of times the stage is run. The reported throughput is the median of all runs.
+    #[arg(long, default_value_t = 3)]
+    runs: usize,
+
+    /// Path to the reth binary running the stage.
+    #[arg(long, value_name = "PATH", default_value = "reth")]
+    reth_bin: PathBuf,
+
+    /// Directory for the snapshot, the runs and the results.
+    ///
+    /// An existing snapshot in the directory is reused.
+    #[arg(long, value_name = "WORK_DIR")]
+    work_dir: PathBuf,
+
+    /// Records a CPU profile of every run with `samply`, which must be installed.
+    ///
+    /// Use a reth binary compiled with the `profiling` profile for accurate flamegraphs.
+    #[arg(long)]
+    profile: bool,
+
+    /// Results of a previous benchmark to compare against.
+    #[arg(long, value_name = "FILE")]
+    baseline: Option<PathBuf>,
+
+    /// Maximum allowed throughput regression against the baseline in percent, after which the
+    /// command fails.
+    #[arg(long, value_name = "PERCENT", default_value_t = 5.0, requires = "baseline")]
+    max_regression: f64,
+}
+
+impl Command {
+    /// Execute `benchmark stage` command
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        if self.runs == 0 {
+            bail!("--runs must be at least 1")
+        }
+        if self.to <= self.from {
+            bail!("--to must be greater than --from")
+        }
+
+        let baseline = self
+            .baseline
+            .as_ref()
+            .map(|path| reth_fs_util::read_json_file::<StageBenchResult>(path))
+            .transpose()?;
+
+        fs::create_dir_all(&self.work_dir)?;
+        let snapshot = self.work_dir.join("snapshot");
+        if snapshot.exists() {
+            info!(?snapshot, "Reusing existing snapshot");
+        } else {
+            info!(datadir = ?self.datadir, ?snapshot, "Taking snapshot of the datadir");
+            copy_on_write(&self.datadir, &snapshot)?;
+        }
+
+        let mut reports = Vec::with_capacity(self.runs);
+        for run in 0..self.runs {
+            let run_dir = self.work_dir.join(format!("run-{run}"));
+            if run_dir.exists() {
+                fs::remove_dir_all(&run_dir)?;
+            }
+            copy_on_write(&snapshot, &run_dir)?;
+
+            let report_path = self.work_dir.join(format!("run-{run}.json"));
+            self.run_stage(run, &run_dir, &report_path)?;
+            fs::remove_dir_all(&run_dir)?;
+
+            let report: StageRunReport = reth_fs_util::read_json_file(&report_path)?;
+            info!(
+                run,
+                elapsed = ?report.elapsed(),
+                blocks_per_second = report.blocks_per_second(),
+                ggas_per_second = report.gas_per_second() / GIGAGAS as f64,
+                bytes_written = report.bytes_written(),
+                "Finished run"
+            );
+            reports.push(report);
+        }
+
+        let result = StageBenchResult::new(self.stage.to_string(), self.from, self.to, reports);
+        println!("{result}");
+
+        let output = self.work_dir.join("result.json");
+        reth_fs_util::write_json_file(&output, &result)?;
+        info!(?output, "Wrote benchmark result");
+
+        if let Some(baseline) = baseline {
+            let change = result.compare(&baseline)?;
+            println!("Throughput change against the baseline: {change:+.2}%");
+            if change < -self.max_regression {
+                bail!(
+                    "Throughput regressed by {:.2}%, more than the allowed {:.2}%",
+                    -change,
+                    self.max_regression
+                )
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs the stage once on the given datadir with `reth stage run`.
+    fn run_stage(&self, run: usize, datadir: &Path, report: &Path) -> eyre::Result<()> {
+        let mut cmd = if self.profile {
+            let profile = self.work_dir.join(format!("run-{run}.json.gz"));
+            let mut cmd = ProcessCommand::new("samply");
+            cmd.args(["record", "--save-only", "-o"]).arg(profile).arg("--").arg(&self.reth_bin);
+            cmd
+        } else {
+            ProcessCommand::new(&self.reth_bin)
+        };
+
+        cmd.args(["stage", "run"])
+            .arg(self.stage.to_possible_value().expect("no skipped variants").get_name())
+            .arg("--datadir")
+            .arg(datadir)
+            .args(["--chain", &self.chain])
+            .args(["--from", &self.from.to_string(), "--to", &self.to.to_string()])
+            .args(["--commit", "--checkpoints", "--report"])
+            .arg(report);
+
+        info!(run, ?cmd, "Running stage");
+        let status = cmd.status().wrap_err("Failed to run reth")?;
+        if !status.success() {
+            bail!("Run {run} failed with {status}")
+        }
+
+        Ok(())
+    }
+}
+
+/// Copies a directory, sharing the data with the source on file systems that support it.
+///
+/// Falls back to a full copy if the file system doesn't support copy-on-write copies, which makes
+/// every run copy the whole snapshot.
+fn copy_on_write(from: &Path, to: &Path) -> eyre::Result<()> {
+    let clone = if cfg!(target_os = "macos") { "-c" } else { "--reflink=always" };
+    let status = ProcessCommand::new("cp")
+        .args([clone, "-R"])
+        .arg(from)
+        .arg(to)
+        .stderr(Stdio::null())
+        .status()
+        .wrap_err("Failed to run cp")?;
+    if status.success() {
+        return Ok(())
+    }
+
+    warn!(
+        ?from,
+        ?to,
+        "File system doesn't support copy-on-write copies, falling back to a full copy"
+    );
+    if to.exists() {
+        fs::remove_dir_all(to)?;
+    }
+    let status = ProcessCommand::new("cp")
+        .arg("-R")
+        .arg(from)
+        .arg(to)
+        .status()
+        .wrap_err("Failed to run cp")?;
+    if !status.success() {
+        bail!("Failed to copy {} to {}", from.display(), to.display())
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let from = temp_dir.path().join("from");
+        fs::create_dir_all(from.join("db")).unwrap();
+        fs::write(from.join("db").join("mdbx.dat"), [1; 1024]).unwrap();
+        fs::write(from.join("reth.toml"), "config").unwrap();
+
+        // The copy succeeds whether or not the file system supports copy-on-write copies
+        let to = temp_dir.path().join("to");
+        copy_on_write(&from, &to).unwrap();
+        assert_eq!(fs::read(to.join("db").join("mdbx.dat")).unwrap(), [1; 1024]);
+        assert_eq!(fs::read_to_string(to.join("reth.toml")).unwrap(), "config");
+    }
+}
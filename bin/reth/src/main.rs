@@ -37,6 +37,7 @@ fn main() {
                     node.network,
                     node.task_executor,
                     node.add_ons_handle.engine_events.new_listener(),
+                    node.config.metrics.is_enabled(),
                 )?;
             }
 
@@ -13,6 +13,9 @@ use tokio::sync::mpsc;
 use tracing::*;
 
 /// Install `ress` subprotocol if it's enabled.
+///
+/// Witness metrics are only recorded if `witness_metrics` is set, see
+/// [`RethRessProtocolProvider::new`].
 pub fn install_ress_subprotocol<P, E, N>(
     args: RessArgs,
     provider: BlockchainProvider<P>,
@@ -20,6 +23,7 @@ pub fn install_ress_subprotocol<P, E, N>(
     network: N,
     task_executor: TaskExecutor,
     engine_events: EventStream<ConsensusEngineEvent<EthPrimitives>>,
+    witness_metrics: bool,
 ) -> eyre::Result<()>
 where
     P: ProviderNodeTypes<Primitives = EthPrimitives>,
@@ -44,6 +48,7 @@ where
         args.max_witness_window,
         args.witness_max_parallel,
         args.witness_cache_size,
+        witness_metrics,
         pending_state,
     )?;
     network.add_rlpx_sub_protocol(
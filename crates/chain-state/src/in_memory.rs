@@ -6,7 +6,7 @@ use crate::{
 };
 use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
 use alloy_eips::{BlockHashOrNumber, BlockNumHash};
-use alloy_primitives::{map::HashMap, BlockNumber, TxHash, B256};
+use alloy_primitives::{map::HashMap, BlockNumber, Bytes, TxHash, B256};
 use parking_lot::RwLock;
 use reth_chainspec::ChainInfo;
 use reth_ethereum_primitives::EthPrimitives;
@@ -757,6 +757,8 @@ pub struct ExecutedBlock<N: NodePrimitives = EthPrimitives> {
     /// This allows deferring the computation of the trie data which can be expensive.
     /// The data can be populated asynchronously after the block was validated.
     pub trie_data: DeferredTrieData,
+    /// RLP encoded EIP-7928 block access list, if it was recorded during execution.
+    pub block_access_list: Option<Bytes>,
 }
 
 impl<N: NodePrimitives> Default for ExecutedBlock<N> {
@@ -765,6 +767,7 @@ impl<N: NodePrimitives> Default for ExecutedBlock<N> {
             recovered_block: Default::default(),
             execution_output: Default::default(),
             trie_data: DeferredTrieData::ready(ComputedTrieData::default()),
+            block_access_list: None,
         }
     }
 }
@@ -787,7 +790,12 @@ impl<N: NodePrimitives> ExecutedBlock<N> {
         execution_output: Arc<ExecutionOutcome<N::Receipt>>,
         trie_data: ComputedTrieData,
     ) -> Self {
-        Self { recovered_block, execution_output, trie_data: DeferredTrieData::ready(trie_data) }
+        Self {
+            recovered_block,
+            execution_output,
+            trie_data: DeferredTrieData::ready(trie_data),
+            block_access_list: None,
+        }
     }
 
     /// Create a new [`ExecutedBlock`] with deferred trie data.
@@ -809,7 +817,13 @@ impl<N: NodePrimitives> ExecutedBlock<N> {
         execution_output: Arc<ExecutionOutcome<N::Receipt>>,
         trie_data: DeferredTrieData,
     ) -> Self {
-        Self { recovered_block, execution_output, trie_data }
+        Self { recovered_block, execution_output, trie_data, block_access_list: None }
+    }
+
+    /// Sets the RLP encoded block access list recorded during the execution of the block.
+    pub fn with_block_access_list(mut self, block_access_list: Option<Bytes>) -> Self {
+        self.block_access_list = block_access_list;
+        self
     }
 
     /// Returns a reference to an inner [`SealedBlock`]
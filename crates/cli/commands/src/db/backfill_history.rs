@@ -0,0 +1,739 @@
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{map::HashMap, Address, BlockNumber, StorageKey, StorageValue, B256};
+use clap::Parser;
+use eyre::{eyre, WrapErr};
+use futures::executor::block_on_stream;
+use reth_chainspec::EthChainSpec;
+use reth_consensus::FullConsensus;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    models::{
+        sharded_key::NUM_OF_INDICES_IN_SHARD, storage_sharded_key::StorageShardedKey,
+        AccountBeforeTx, BlockNumberAddress, ShardedKey,
+    },
+    table::Table,
+    tables,
+    transaction::DbTxMut,
+    BlockNumberList,
+};
+use reth_db_common::DbTool;
+use reth_era::common::file_ops::StreamReader;
+use reth_era_downloader::read_dir;
+use reth_era_utils as era;
+use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_node_api::{BlockTy, BodyTy, HeaderTy, ReceiptTy};
+use reth_primitives_traits::{
+    Account, Block, BlockBody, Bytecode, NodePrimitives, RecoveredBlock, StorageEntry,
+};
+use reth_provider::{
+    changesets_utils::StorageRevertsIter,
+    providers::{
+        HistoricalStateProviderRef, ProviderNodeTypes, StaticFileProvider, StaticFileProviderRW,
+    },
+    resolve_prune_modes, BlockBodyIndicesProvider, BlockExecutionResult, BlockHashReader,
+    BlockNumReader, DBProvider, EitherWriter, EitherWriterDestination, OriginalValuesKnown,
+    ProviderError, ProviderResult, PruneCheckpointReader, PruneCheckpointWriter,
+    StaticFileProviderFactory, StaticFileSegment, StorageSettingsCache,
+};
+use reth_prune::{PruneCheckpoint, PruneMode, PruneModes, PruneSegment};
+use reth_revm::{
+    database::{EvmStateProvider, StateProviderDatabase},
+    db::states::{PlainStorageChangeset, PlainStorageRevert},
+};
+use std::{
+    collections::BTreeMap,
+    mem,
+    ops::RangeInclusive,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+const PROGRESS_PERIOD: Duration = Duration::from_secs(10);
+
+/// Number of most recent block hashes that are available to the EVM.
+const BLOCK_HASH_HISTORY: u64 = 256;
+
+/// The arguments for the `reth db backfill-history` command
+///
+/// Restores the bodies, receipts and account and storage history that were deleted by the pruner,
+/// by reading the blocks from ERA1 files and re-executing them. The prune checkpoints of the
+/// restored segments are moved back to the start of the restored blocks.
+///
+/// If account or storage history is pruned below the restored receipts or history, the state to
+/// re-execute the blocks with is not available in the database, so the blocks are re-executed from
+/// genesis.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The first block to backfill.
+    ///
+    /// Static files are restored whole, so the backfill of bodies and receipts stored in static
+    /// files starts at the first block of the static file containing this block.
+    #[arg(long, value_name = "BLOCK_NUMBER")]
+    from: BlockNumber,
+
+    /// The last block to backfill.
+    ///
+    /// Must be at or above the last pruned block of every backfilled segment, so that no gap is
+    /// left in their history.
+    #[arg(long, value_name = "BLOCK_NUMBER")]
+    to: BlockNumber,
+
+    /// The path to a directory with the ERA1 files of the backfilled blocks and their
+    /// `checksums.txt` file.
+    #[arg(long, value_name = "ERA1_PATH", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+impl Command {
+    /// Execute `db backfill-history` command
+    pub fn execute<N, E, C>(self, tool: &DbTool<N>, evm_config: E, consensus: C) -> eyre::Result<()>
+    where
+        N: ProviderNodeTypes,
+        E: ConfigureEvm<Primitives = N::Primitives>,
+        C: FullConsensus<N::Primitives>,
+    {
+        eyre::ensure!(self.from <= self.to, "--from must not be greater than --to");
+
+        let provider = tool.provider_factory.database_provider_rw()?;
+        let static_file_provider = provider.static_file_provider();
+
+        let plan = BackfillPlan::new(&*provider, self.from, self.to)?;
+        let Some(blocks) = plan.blocks() else {
+            info!(target: "reth::cli", from = self.from, to = self.to, "Nothing to backfill");
+            return Ok(())
+        };
+        let execution = plan.execution_blocks();
+        info!(target: "reth::cli", ?plan, "Backfilling history");
+
+        // State of the blocks below the lowest block with account and storage history is not
+        // available in the database, so it's rebuilt in memory starting from genesis.
+        let from_genesis = execution
+            .as_ref()
+            .is_some_and(|blocks| plan.history_pruned_to >= Some(*blocks.start()));
+        let blocks = if from_genesis { 0..=*blocks.end() } else { blocks };
+
+        let mut genesis_changesets = BlockChangeSets::default();
+        let mut in_memory_state = None;
+        let mut executor = None;
+        let state_at = |block_number| {
+            StateProviderDatabase::new(HistoricalStateProviderRef::new(&*provider, block_number))
+        };
+        if from_genesis {
+            warn!(target: "reth::cli", "State is pruned, re-executing blocks from genesis");
+            let (state, changesets) = InMemoryState::genesis(tool.chain().as_ref())?;
+            in_memory_state = Some(state);
+            genesis_changesets = changesets;
+        } else if let Some(execution) = &execution {
+            executor = Some(evm_config.batch_executor(state_at(*execution.start())));
+        }
+
+        let mut bodies = plan.bodies.clone().map(|blocks| {
+            StaticFileBackfill::new(
+                static_file_provider.clone(),
+                StaticFileSegment::Transactions,
+                blocks,
+            )
+        });
+        let mut receipts =
+            plan.receipts.clone().filter(|_| plan.receipts_in_static_files).map(|blocks| {
+                StaticFileBackfill::new(
+                    static_file_provider.clone(),
+                    StaticFileSegment::Receipts,
+                    blocks,
+                )
+            });
+        let mut account_indices = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        let mut storage_indices = BTreeMap::<(Address, B256), Vec<BlockNumber>>::new();
+
+        let mut next_block = *blocks.start();
+        let mut last_log = Instant::now();
+        'files: for meta in block_on_stream(read_dir(self.path, next_block)?) {
+            let meta = meta?;
+            for block in era::open(&meta)?.iter() {
+                let (header, body) = era::decode::<HeaderTy<N>, BodyTy<N>, _>(block)?;
+                let number = header.number();
+                if number < next_block {
+                    continue
+                }
+                if number > *blocks.end() {
+                    break 'files
+                }
+                eyre::ensure!(number == next_block, "ERA1 files are missing block {next_block}");
+
+                let block = BlockTy::<N>::new(header, body).seal_slow();
+                let expected_hash = provider
+                    .block_hash(number)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+                eyre::ensure!(
+                    block.hash() == expected_hash,
+                    "Block {number} of ERA1 files has hash {}, expected {expected_hash}",
+                    block.hash()
+                );
+                if number > 0 {
+                    consensus.validate_block_pre_execution(&block)?;
+                }
+                let block = block
+                    .try_recover()
+                    .map_err(|_| eyre!("Failed to recover senders of block {number}"))?;
+
+                let (block_receipts, changesets) = if let Some(state) = &mut in_memory_state {
+                    let output = if number == 0 {
+                        (Vec::new(), mem::take(&mut genesis_changesets))
+                    } else {
+                        let (result, changesets) = state.execute(&evm_config, &block)?;
+                        consensus.validate_block_post_execution(&block, &result).wrap_err_with(
+                            || format!("Failed to validate block {number} {}", block.hash()),
+                        )?;
+                        (result.receipts, changesets)
+                    };
+                    state.insert_block_hash(number, block.hash());
+                    output
+                } else if let Some(executor) = executor.as_mut() &&
+                    execution.as_ref().is_some_and(|blocks| blocks.contains(&number))
+                {
+                    let result = executor.execute_one(&block)?;
+                    consensus.validate_block_post_execution(&block, &result).wrap_err_with(
+                        || format!("Failed to validate block {number} {}", block.hash()),
+                    )?;
+
+                    // Reset the executor once in a while to avoid running out of memory
+                    if executor.size_hint() > 1_000_000 {
+                        *executor = evm_config.batch_executor(state_at(number + 1));
+                    }
+
+                    (result.receipts, BlockChangeSets::default())
+                } else {
+                    (Vec::new(), BlockChangeSets::default())
+                };
+
+                let body_indices = provider
+                    .block_body_indices(number)?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+
+                if let Some(bodies) = &mut bodies &&
+                    let Some(writer) = bodies.writer(number)?
+                {
+                    for (tx_num, tx) in
+                        body_indices.tx_num_range().zip(block.body().transactions_iter())
+                    {
+                        writer.append_transaction(tx_num, tx)?;
+                    }
+                }
+
+                if let Some(receipts) = &mut receipts {
+                    if let Some(writer) = receipts.writer(number)? {
+                        for (tx_num, receipt) in body_indices.tx_num_range().zip(&block_receipts) {
+                            writer.append_receipt(tx_num, receipt)?;
+                        }
+                    }
+                } else if plan.receipts.as_ref().is_some_and(|blocks| blocks.contains(&number)) {
+                    for (tx_num, receipt) in body_indices.tx_num_range().zip(block_receipts) {
+                        provider.tx_ref().put::<tables::Receipts<ReceiptTy<N>>>(tx_num, receipt)?;
+                    }
+                }
+
+                if plan.account_history.as_ref().is_some_and(|blocks| blocks.contains(&number)) {
+                    for changeset in changesets.accounts {
+                        account_indices.entry(changeset.address).or_default().push(number);
+                        provider.tx_ref().put::<tables::AccountChangeSets>(number, changeset)?;
+                    }
+                }
+
+                if plan.storage_history.as_ref().is_some_and(|blocks| blocks.contains(&number)) {
+                    for (address, entry) in changesets.storages {
+                        storage_indices.entry((address, entry.key)).or_default().push(number);
+                        provider.tx_ref().put::<tables::StorageChangeSets>(
+                            BlockNumberAddress((number, address)),
+                            entry,
+                        )?;
+                    }
+                }
+
+                if last_log.elapsed() > PROGRESS_PERIOD {
+                    info!(target: "reth::cli", block = number, to = blocks.end(), "Backfilling history");
+                    last_log = Instant::now();
+                }
+
+                next_block += 1;
+            }
+        }
+        eyre::ensure!(next_block > *blocks.end(), "ERA1 files are missing block {next_block}");
+        drop(executor);
+
+        if let Some(mut bodies) = bodies {
+            bodies.commit()?;
+        }
+        if let Some(mut receipts) = receipts {
+            receipts.commit()?;
+        }
+
+        merge_history_index::<tables::AccountsHistory, _>(
+            provider.tx_ref(),
+            account_indices,
+            ShardedKey::new,
+            |key| key.key,
+        )?;
+        merge_history_index::<tables::StoragesHistory, _>(
+            provider.tx_ref(),
+            storage_indices,
+            |(address, storage_key), highest_block_number| {
+                StorageShardedKey::new(address, storage_key, highest_block_number)
+            },
+            |key| (key.address, key.sharded_key.key),
+        )?;
+
+        let tip = provider.best_block_number()?;
+        let prune_modes = resolve_prune_modes(&provider, provider.prune_modes_ref(), tip)?;
+        for (segment, blocks, prune_mode) in plan.segments(&prune_modes) {
+            let checkpoint = provider
+                .get_prune_checkpoint(segment)?
+                .ok_or_else(|| eyre!("Prune checkpoint of {segment} is missing"))?;
+            let block_number = blocks.start().checked_sub(1);
+            let tx_number = match block_number {
+                Some(_) if checkpoint.tx_number.is_some() => provider
+                    .block_body_indices(*blocks.start())?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(*blocks.start()))?
+                    .first_tx_num()
+                    .checked_sub(1),
+                _ => None,
+            };
+            provider.save_prune_checkpoint(
+                segment,
+                PruneCheckpoint { block_number, tx_number, prune_mode: checkpoint.prune_mode },
+            )?;
+
+            if prune_mode.is_some_and(|mode| mode.should_prune(*blocks.start(), tip)) {
+                warn!(
+                    target: "reth::cli",
+                    %segment,
+                    ?prune_mode,
+                    "Backfilled blocks will be pruned again by the configured prune mode"
+                );
+            }
+            info!(target: "reth::cli", %segment, ?blocks, "Backfilled segment");
+        }
+
+        provider.commit()?;
+        static_file_provider.initialize_index()?;
+
+        Ok(())
+    }
+}
+
+/// Blocks of the pruned segments to backfill.
+#[derive(Debug, Default)]
+struct BackfillPlan {
+    /// Blocks of the transactions static files to restore.
+    bodies: Option<RangeInclusive<BlockNumber>>,
+    /// Blocks of the receipts to restore.
+    receipts: Option<RangeInclusive<BlockNumber>>,
+    /// Whether receipts are stored in static files.
+    receipts_in_static_files: bool,
+    /// Blocks of the account changesets and history to restore.
+    account_history: Option<RangeInclusive<BlockNumber>>,
+    /// Blocks of the storage changesets and history to restore.
+    storage_history: Option<RangeInclusive<BlockNumber>>,
+    /// Highest block up to which account or storage history is pruned.
+    history_pruned_to: Option<BlockNumber>,
+}
+
+impl BackfillPlan {
+    /// Creates the plan to backfill the segments pruned in `from..=to`.
+    fn new<Provider>(provider: &Provider, from: BlockNumber, to: BlockNumber) -> eyre::Result<Self>
+    where
+        Provider:
+            DBProvider + PruneCheckpointReader + StaticFileProviderFactory + StorageSettingsCache,
+    {
+        let static_file_provider = provider.static_file_provider();
+        let pruned_to = |segment: PruneSegment| -> ProviderResult<Option<BlockNumber>> {
+            Ok(provider
+                .get_prune_checkpoint(segment)?
+                .and_then(|checkpoint| checkpoint.block_number))
+        };
+
+        // Static files are deleted whole, so the pruned blocks are the ones below the lowest
+        // remaining static file.
+        let static_file_blocks = |segment: PruneSegment, static_file_segment| {
+            let blocks = pruned_to(segment)?.and_then(|_| {
+                let start =
+                    static_file_provider.find_fixed_range(static_file_segment, from).start();
+                let end = static_file_provider
+                    .get_lowest_range_start(static_file_segment)?
+                    .checked_sub(1)?;
+                Some(start..=end)
+            });
+            check_blocks(segment, blocks, from, to)
+        };
+        let database_blocks = |segment: PruneSegment| {
+            check_blocks(segment, pruned_to(segment)?.map(|pruned_to| from..=pruned_to), from, to)
+        };
+
+        let receipts_in_static_files = matches!(
+            EitherWriter::receipts_destination(provider),
+            EitherWriterDestination::StaticFile
+        );
+        let plan = Self {
+            bodies: static_file_blocks(PruneSegment::Bodies, StaticFileSegment::Transactions)?,
+            receipts: if receipts_in_static_files {
+                static_file_blocks(PruneSegment::Receipts, StaticFileSegment::Receipts)?
+            } else {
+                database_blocks(PruneSegment::Receipts)?
+            },
+            receipts_in_static_files,
+            account_history: database_blocks(PruneSegment::AccountHistory)?,
+            storage_history: database_blocks(PruneSegment::StorageHistory)?,
+            history_pruned_to: pruned_to(PruneSegment::AccountHistory)?
+                .max(pruned_to(PruneSegment::StorageHistory)?),
+        };
+
+        let settings = provider.cached_storage_settings();
+        if plan.account_history.is_some() {
+            eyre::ensure!(
+                !settings.account_changesets_in_static_files &&
+                    !settings.account_history_in_rocksdb,
+                "Backfilling account history is only supported for account changesets and history \
+                 stored in the database"
+            );
+        }
+        if plan.storage_history.is_some() {
+            eyre::ensure!(
+                !settings.storages_history_in_rocksdb,
+                "Backfilling storage history is only supported for storage history stored in the \
+                 database"
+            );
+        }
+
+        Ok(plan)
+    }
+
+    /// Returns the segments to backfill with their blocks and configured prune modes.
+    fn segments(
+        &self,
+        prune_modes: &PruneModes,
+    ) -> impl Iterator<Item = (PruneSegment, RangeInclusive<BlockNumber>, Option<PruneMode>)> {
+        [
+            (PruneSegment::Bodies, &self.bodies, prune_modes.bodies_history),
+            (PruneSegment::Receipts, &self.receipts, prune_modes.receipts),
+            (PruneSegment::AccountHistory, &self.account_history, prune_modes.account_history),
+            (PruneSegment::StorageHistory, &self.storage_history, prune_modes.storage_history),
+        ]
+        .into_iter()
+        .filter_map(|(segment, blocks, prune_mode)| {
+            blocks.clone().map(|blocks| (segment, blocks, prune_mode))
+        })
+    }
+
+    /// Returns all blocks to backfill, or `None` if nothing is pruned.
+    fn blocks(&self) -> Option<RangeInclusive<BlockNumber>> {
+        merge_ranges([&self.bodies, &self.receipts, &self.account_history, &self.storage_history])
+    }
+
+    /// Returns the blocks that need to be executed, or `None` if nothing is pruned. The genesis
+    /// block is never executed.
+    fn execution_blocks(&self) -> Option<RangeInclusive<BlockNumber>> {
+        merge_ranges([&self.receipts, &self.account_history, &self.storage_history])
+            .map(|blocks| (*blocks.start()).max(1)..=*blocks.end())
+            .filter(|blocks| !blocks.is_empty())
+    }
+}
+
+/// Returns the pruned blocks of the segment if they reach `from`, checking that the backfill up to
+/// `to` leaves no gap in the history of the segment.
+fn check_blocks(
+    segment: PruneSegment,
+    blocks: Option<RangeInclusive<BlockNumber>>,
+    from: BlockNumber,
+    to: BlockNumber,
+) -> eyre::Result<Option<RangeInclusive<BlockNumber>>> {
+    let Some(blocks) = blocks.filter(|blocks| *blocks.end() >= from) else { return Ok(None) };
+    eyre::ensure!(
+        *blocks.end() <= to,
+        "{segment} is pruned up to block {}, backfilling up to block {to} would leave a gap",
+        blocks.end()
+    );
+    Ok(Some(blocks))
+}
+
+/// Returns the smallest range containing all the ranges.
+fn merge_ranges<'a>(
+    ranges: impl IntoIterator<Item = &'a Option<RangeInclusive<BlockNumber>>>,
+) -> Option<RangeInclusive<BlockNumber>> {
+    ranges.into_iter().flatten().fold(None, |merged, range| {
+        Some(match merged {
+            Some(merged) => {
+                (*range.start()).min(*merged.start())..=(*range.end()).max(*merged.end())
+            }
+            None => range.clone(),
+        })
+    })
+}
+
+/// Writes blocks to the static files of a segment that are backfilled below its lowest static
+/// file.
+#[derive(Debug)]
+struct StaticFileBackfill<N> {
+    static_file_provider: StaticFileProvider<N>,
+    segment: StaticFileSegment,
+    blocks: RangeInclusive<BlockNumber>,
+    writer: Option<StaticFileProviderRW<N>>,
+}
+
+impl<N: NodePrimitives> StaticFileBackfill<N> {
+    const fn new(
+        static_file_provider: StaticFileProvider<N>,
+        segment: StaticFileSegment,
+        blocks: RangeInclusive<BlockNumber>,
+    ) -> Self {
+        Self { static_file_provider, segment, blocks, writer: None }
+    }
+
+    /// Returns the writer of the static file containing the block, with the block incremented.
+    ///
+    /// Returns `None` if the block is not backfilled.
+    fn writer(
+        &mut self,
+        block: BlockNumber,
+    ) -> ProviderResult<Option<&mut StaticFileProviderRW<N>>> {
+        if !self.blocks.contains(&block) {
+            return Ok(None)
+        }
+
+        let writer = match self.writer.take() {
+            Some(writer) if block <= writer.user_header().expected_block_end() => writer,
+            previous => {
+                if let Some(mut previous) = previous {
+                    previous.commit()?;
+                }
+                self.static_file_provider.backfill_writer(block, self.segment)?
+            }
+        };
+        let writer = self.writer.insert(writer);
+        writer.increment_block(block)?;
+
+        Ok(Some(writer))
+    }
+
+    /// Commits the static file that is currently written.
+    fn commit(&mut self) -> ProviderResult<()> {
+        if let Some(mut writer) = self.writer.take() {
+            writer.commit()?;
+        }
+        Ok(())
+    }
+}
+
+/// Changesets of a block, in the format of the database tables.
+#[derive(Debug, Default)]
+struct BlockChangeSets {
+    accounts: Vec<AccountBeforeTx>,
+    storages: Vec<(Address, StorageEntry)>,
+}
+
+/// State of the chain held in memory, used to re-execute blocks whose state is pruned from the
+/// database.
+#[derive(Debug, Default)]
+struct InMemoryState {
+    accounts: HashMap<Address, Account>,
+    storages: HashMap<Address, BTreeMap<B256, StorageValue>>,
+    bytecodes: HashMap<B256, Bytecode>,
+    block_hashes: HashMap<BlockNumber, B256>,
+}
+
+impl InMemoryState {
+    /// Creates the state of the genesis block, returning it with the changesets of the genesis
+    /// block.
+    fn genesis(chain_spec: &impl EthChainSpec) -> eyre::Result<(Self, BlockChangeSets)> {
+        let mut state = Self::default();
+        let mut changesets = BlockChangeSets::default();
+
+        for (address, account) in &chain_spec.genesis().alloc {
+            let bytecode_hash = account
+                .code
+                .as_ref()
+                .map(|code| {
+                    let bytecode = Bytecode::new_raw_checked(code.clone())
+                        .map_err(|err| eyre!("Invalid genesis bytecode of {address}: {err}"))?;
+                    let hash = bytecode.hash_slow();
+                    state.bytecodes.insert(hash, bytecode);
+                    eyre::Ok(hash)
+                })
+                .transpose()?;
+            state.accounts.insert(
+                *address,
+                Account {
+                    nonce: account.nonce.unwrap_or_default(),
+                    balance: account.balance,
+                    bytecode_hash,
+                },
+            );
+            changesets.accounts.push(AccountBeforeTx { address: *address, info: None });
+
+            for (key, value) in account.storage.iter().flatten() {
+                let value = StorageValue::from_be_bytes(value.0);
+                if !value.is_zero() {
+                    state.storages.entry(*address).or_default().insert(*key, value);
+                }
+                changesets.storages.push((*address, StorageEntry::new(*key, StorageValue::ZERO)));
+            }
+        }
+        state.insert_block_hash(0, chain_spec.genesis_hash());
+
+        Ok((state, changesets))
+    }
+
+    /// Records the hash of the block, forgetting the hashes that are no longer available to the
+    /// EVM.
+    fn insert_block_hash(&mut self, number: BlockNumber, hash: B256) {
+        self.block_hashes.insert(number, hash);
+        if let Some(expired) = number.checked_sub(BLOCK_HASH_HISTORY) {
+            self.block_hashes.remove(&expired);
+        }
+    }
+
+    /// Executes the block on top of the state and applies its state changes, returning the
+    /// execution result and the changesets of the block.
+    fn execute<E: ConfigureEvm>(
+        &mut self,
+        evm_config: &E,
+        block: &RecoveredBlock<<E::Primitives as NodePrimitives>::Block>,
+    ) -> eyre::Result<(
+        BlockExecutionResult<<E::Primitives as NodePrimitives>::Receipt>,
+        BlockChangeSets,
+    )> {
+        let mut executor = evm_config.batch_executor(StateProviderDatabase::new(mem::take(self)));
+        let result = executor.execute_one(block)?;
+        let mut db = executor.into_state();
+        let bundle = db.take_bundle();
+        *self = db.database.into_inner();
+
+        let (changes, reverts) = bundle.to_plain_state_and_reverts(OriginalValuesKnown::Yes);
+
+        let mut changesets = BlockChangeSets::default();
+        for (address, info) in reverts.accounts.into_iter().flatten() {
+            changesets.accounts.push(AccountBeforeTx { address, info: info.map(Into::into) });
+        }
+        for PlainStorageRevert { address, wiped, storage_revert } in
+            reverts.storage.into_iter().flatten()
+        {
+            let mut storage = storage_revert
+                .into_iter()
+                .map(|(key, revert)| (B256::new(key.to_be_bytes()), revert))
+                .collect::<Vec<_>>();
+            storage.sort_unstable_by_key(|(key, _)| *key);
+
+            // Storage wiped by the block is reverted to the storage before the block
+            let wiped_storage = wiped
+                .then(|| self.storages.get(&address))
+                .flatten()
+                .into_iter()
+                .flatten()
+                .map(|(key, value)| (*key, *value));
+            for (key, value) in StorageRevertsIter::new(storage, wiped_storage) {
+                changesets.storages.push((address, StorageEntry { key, value }));
+            }
+        }
+
+        for (address, info) in changes.accounts {
+            if let Some(info) = info {
+                self.accounts.insert(address, info.into());
+            } else {
+                self.accounts.remove(&address);
+            }
+        }
+        for PlainStorageChangeset { address, wipe_storage, storage } in changes.storage {
+            if wipe_storage {
+                self.storages.remove(&address);
+            }
+            let slots = self.storages.entry(address).or_default();
+            for (key, value) in storage {
+                let key = B256::new(key.to_be_bytes());
+                if value.is_zero() {
+                    slots.remove(&key);
+                } else {
+                    slots.insert(key, value);
+                }
+            }
+        }
+        for (hash, bytecode) in changes.contracts {
+            self.bytecodes.insert(hash, Bytecode(bytecode));
+        }
+
+        Ok((result, changesets))
+    }
+}
+
+impl EvmStateProvider for InMemoryState {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        Ok(self.accounts.get(address).copied())
+    }
+
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        Ok(self.block_hashes.get(&number).copied())
+    }
+
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        Ok(self.bytecodes.get(code_hash).cloned())
+    }
+
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        Ok(self.storages.get(&account).and_then(|storage| storage.get(&storage_key)).copied())
+    }
+}
+
+/// Merges the block numbers into the history shards of each key of the history table `T`.
+///
+/// Backfilled block numbers are lower than the ones already indexed, so unlike appending to the
+/// last shard, all shards of the key are rewritten.
+fn merge_history_index<T, P>(
+    tx: &impl DbTxMut,
+    index_updates: BTreeMap<P, Vec<BlockNumber>>,
+    sharded_key_factory: impl Fn(P, BlockNumber) -> T::Key,
+    partial_key: impl Fn(&T::Key) -> P,
+) -> eyre::Result<()>
+where
+    T: Table<Value = BlockNumberList>,
+    P: Copy + Eq,
+{
+    let mut cursor = tx.cursor_write::<T>()?;
+
+    for (key, mut indices) in index_updates {
+        let mut shards = Vec::new();
+        let mut entry = cursor.seek(sharded_key_factory(key, 0))?;
+        while let Some((shard_key, list)) =
+            entry.filter(|(shard_key, _)| partial_key(shard_key) == key)
+        {
+            indices.extend(list.iter());
+            shards.push(shard_key);
+            entry = cursor.next()?;
+        }
+        for shard_key in shards {
+            if cursor.seek_exact(shard_key)?.is_some() {
+                cursor.delete_current()?;
+            }
+        }
+
+        indices.sort_unstable();
+        indices.dedup();
+
+        let mut chunks = indices.chunks(NUM_OF_INDICES_IN_SHARD).peekable();
+        while let Some(chunk) = chunks.next() {
+            let highest_block_number = if chunks.peek().is_some() {
+                *chunk.last().expect("`chunks` does not return empty slices")
+            } else {
+                // Insert last list with `u64::MAX`.
+                u64::MAX
+            };
+            cursor.upsert(
+                sharded_key_factory(key, highest_block_number),
+                &BlockNumberList::new_pre_sorted(chunk.iter().copied()),
+            )?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use crate::common::{AccessRights, CliNodeComponents, CliNodeTypes, Environment, EnvironmentArgs};
 use clap::{Parser, Subcommand};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
@@ -10,6 +10,7 @@ use std::{
     sync::Arc,
 };
 mod account_storage;
+mod backfill_history;
 mod checksum;
 mod clear;
 mod diff;
@@ -17,6 +18,7 @@ mod get;
 mod list;
 mod repair_trie;
 mod settings;
+mod state_root;
 mod static_file_header;
 mod stats;
 /// DB List TUI
@@ -55,6 +57,8 @@ pub enum Subcommands {
     Clear(clear::Command),
     /// Verifies trie consistency and outputs any inconsistencies
     RepairTrie(repair_trie::Command),
+    /// Recomputes the state root of historical blocks and compares it to their headers
+    StateRoot(state_root::Command),
     /// Reads and displays the static file segment header
     StaticFileHeader(static_file_header::Command),
     /// Lists current and local database versions
@@ -65,6 +69,8 @@ pub enum Subcommands {
     Settings(settings::Command),
     /// Gets storage size information for an account
     AccountStorage(account_storage::Command),
+    /// Backfills pruned bodies, receipts and history from ERA1 files
+    BackfillHistory(backfill_history::Command),
 }
 
 /// Initializes a provider factory with specified access rights, and then execute with the provided
@@ -80,10 +86,15 @@ macro_rules! db_exec {
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
     /// Execute `db` command
-    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(
+    pub async fn execute<N, Comp>(
         self,
         ctx: CliContext,
-    ) -> eyre::Result<()> {
+        components: impl FnOnce(Arc<C::ChainSpec>) -> Comp,
+    ) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        Comp: CliNodeComponents<N>,
+    {
         let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain());
         let db_path = data_dir.db();
         let static_files_path = data_dir.static_files();
@@ -165,6 +176,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool, ctx.task_executor.clone(), &data_dir)?;
                 });
             }
+            Subcommands::StateRoot(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RO, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::StaticFileHeader(command) => {
                 db_exec!(self.env, tool, N, AccessRights::RoInconsistent, {
                     command.execute(&tool)?;
@@ -198,6 +214,16 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::BackfillHistory(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RW, {
+                    let components = components(tool.chain());
+                    command.execute(
+                        &tool,
+                        components.evm_config().clone(),
+                        components.consensus().clone(),
+                    )?;
+                });
+            }
         }
 
         Ok(())
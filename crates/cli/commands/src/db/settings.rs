@@ -54,6 +54,14 @@ pub enum SetCommand {
         #[clap(action(ArgAction::Set))]
         value: bool,
     },
+    /// Maintain the address and topic log index used by `eth_getLogs`
+    ///
+    /// Enabling it on an existing node builds the index for past blocks through the pipeline on
+    /// the next startup.
+    LogIndex {
+        #[clap(action(ArgAction::Set))]
+        value: bool,
+    },
 }
 
 impl Command {
@@ -100,6 +108,7 @@ impl Command {
             transaction_hash_numbers_in_rocksdb: _,
             account_history_in_rocksdb: _,
             account_changesets_in_static_files: _,
+            log_index: _,
         } = settings.unwrap_or_else(StorageSettings::legacy);
 
         // Update the setting based on the key
@@ -128,6 +137,14 @@ impl Command {
                 settings.account_changesets_in_static_files = value;
                 println!("Set account_changesets_in_static_files = {}", value);
             }
+            SetCommand::LogIndex { value } => {
+                if settings.log_index == value {
+                    println!("log_index is already set to {}", value);
+                    return Ok(());
+                }
+                settings.log_index = value;
+                println!("Set log_index = {}", value);
+            }
         }
 
         // Write updated settings
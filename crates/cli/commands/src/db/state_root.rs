@@ -0,0 +1,297 @@
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{BlockNumber, B256};
+use clap::Parser;
+use reth_db_api::DatabaseError;
+use reth_db_common::DbTool;
+use reth_provider::{
+    providers::{OverlayStateProviderFactory, ProviderNodeTypes},
+    BlockNumReader, ChangeSetReader, DBProvider, DatabaseProviderROFactory, HeaderProvider,
+    ProviderFactory, ProviderResult, PruneCheckpointReader, StageCheckpointReader,
+};
+use reth_prune::PruneSegment;
+use reth_stages::StageId;
+use reth_trie::{
+    hashed_cursor::HashedCursorFactory,
+    trie_cursor::{
+        noop::{NoopAccountTrieCursor, NoopStorageTrieCursor},
+        TrieCursorFactory,
+    },
+    HashedPostStateSorted, KeccakKeyHasher,
+};
+use reth_trie_db::DatabaseHashedPostState;
+use reth_trie_parallel::root::ParallelStateRoot;
+use std::{sync::Arc, time::Instant};
+use tracing::{info, warn};
+
+/// The arguments for the `reth db state-root` command
+///
+/// The hashed state at each block is rebuilt by reverting the account and storage changesets of
+/// all later blocks on top of the hashed state of the database tip. The blocks are verified from
+/// the last to the first, so the changesets of every block are only read once. The database is
+/// not modified.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The first block to verify.
+    #[arg(long, value_name = "BLOCK")]
+    from: BlockNumber,
+
+    /// The last block to verify. Defaults to `--from`.
+    #[arg(long, value_name = "BLOCK")]
+    to: Option<BlockNumber>,
+
+    /// Only verify every n-th block of the range, e.g. to narrow down a mismatch first.
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    step: u64,
+
+    /// Compute the whole trie from the hashed state, instead of reusing the stored trie nodes of
+    /// subtries that didn't change since the block.
+    ///
+    /// This doesn't depend on the trie tables at all, but is as slow as rebuilding the trie.
+    #[arg(long)]
+    full: bool,
+}
+
+impl Command {
+    /// Execute `db state-root` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        let factory = &tool.provider_factory;
+        let to = self.to.unwrap_or(self.from);
+        if self.from > to {
+            eyre::bail!("Invalid block range {}..={to}", self.from)
+        }
+
+        let provider = factory.provider()?;
+        let tip = provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
+        if to > tip {
+            eyre::bail!("Block {to} is beyond the database tip {tip}")
+        }
+        // Reverting to a block requires the changesets of all later blocks
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            let checkpoint = provider.get_prune_checkpoint(segment)?;
+            if let Some(pruned) = checkpoint.and_then(|checkpoint| checkpoint.block_number) &&
+                self.from <= pruned
+            {
+                eyre::bail!(
+                    "{segment} is pruned up to block {pruned}, cannot revert the state to block {}",
+                    self.from
+                )
+            }
+        }
+        drop(provider);
+
+        info!(target: "reth::cli", from = self.from, to, step = self.step, tip, full = self.full, "Verifying state roots");
+
+        let blocks = (self.from..=to).step_by(self.step as usize).collect::<Vec<_>>();
+        let mut reverts = StateReverts::new(tip);
+        let mut mismatches = Vec::new();
+        for number in blocks.into_iter().rev() {
+            let header = factory
+                .sealed_header(number)?
+                .ok_or_else(|| eyre::eyre!("Header of block {number} not found"))?;
+
+            let start = Instant::now();
+            let state = reverts.revert_to(&factory.provider()?, number)?;
+            let state_root = self.state_root(factory, state)?;
+            let elapsed = start.elapsed();
+
+            if state_root == header.state_root() {
+                info!(target: "reth::cli", number, %state_root, ?elapsed, "State root matches");
+            } else {
+                warn!(
+                    target: "reth::cli",
+                    number,
+                    hash = %header.hash(),
+                    expected = %header.state_root(),
+                    got = %state_root,
+                    ?elapsed,
+                    "State root mismatch"
+                );
+                mismatches.push(number);
+            }
+        }
+
+        if let Some(first) = mismatches.iter().min() {
+            eyre::bail!(
+                "Found {} state root mismatches, the first one at block {first}",
+                mismatches.len()
+            )
+        }
+
+        info!(target: "reth::cli", "All state roots match");
+        Ok(())
+    }
+
+    /// Computes the state root with the [`ParallelStateRoot`], with the given reverts applied on
+    /// top of the hashed state of the database tip.
+    fn state_root<N: ProviderNodeTypes>(
+        &self,
+        factory: &ProviderFactory<N>,
+        reverts: Arc<HashedPostStateSorted>,
+    ) -> eyre::Result<B256> {
+        let prefix_sets = reverts.construct_prefix_sets().freeze();
+        let factory = OverlayStateProviderFactory::new(factory.clone())
+            .with_hashed_state_overlay(Some(reverts));
+
+        let state_root = if self.full {
+            ParallelStateRoot::new(WithoutTrieNodes(factory), prefix_sets).incremental_root()?
+        } else {
+            ParallelStateRoot::new(factory, prefix_sets).incremental_root()?
+        };
+        Ok(state_root)
+    }
+}
+
+/// The hashed state reverts from the database tip down to a block.
+#[derive(Debug)]
+struct StateReverts {
+    /// The block the state is reverted to.
+    block: BlockNumber,
+    /// The state before the changes of all blocks after [`Self::block`].
+    state: Arc<HashedPostStateSorted>,
+}
+
+impl StateReverts {
+    /// Creates empty reverts at the database tip.
+    fn new(tip: BlockNumber) -> Self {
+        Self { block: tip, state: Default::default() }
+    }
+
+    /// Reverts the state down to the given block, which must not be above the previous one.
+    ///
+    /// Only the changesets of the blocks that weren't reverted yet are read.
+    fn revert_to(
+        &mut self,
+        provider: &(impl ChangeSetReader + BlockNumReader + DBProvider),
+        block: BlockNumber,
+    ) -> ProviderResult<Arc<HashedPostStateSorted>> {
+        if block < self.block {
+            let older = HashedPostStateSorted::from_reverts::<KeccakKeyHasher>(
+                provider,
+                block + 1..=self.block,
+            )?;
+            // The changesets of the older blocks hold the earlier values
+            Arc::make_mut(&mut self.state).extend_ref(&older);
+            self.block = block;
+        }
+        Ok(self.state.clone())
+    }
+}
+
+/// Wraps a provider (factory) so that no stored trie nodes are visible, which makes the
+/// [`ParallelStateRoot`] compute the entire trie from the hashed state.
+#[derive(Debug, Clone)]
+struct WithoutTrieNodes<T>(T);
+
+impl<F: DatabaseProviderROFactory> DatabaseProviderROFactory for WithoutTrieNodes<F> {
+    type Provider = WithoutTrieNodes<F::Provider>;
+
+    fn database_provider_ro(&self) -> ProviderResult<Self::Provider> {
+        Ok(WithoutTrieNodes(self.0.database_provider_ro()?))
+    }
+}
+
+impl<P> TrieCursorFactory for WithoutTrieNodes<P> {
+    type AccountTrieCursor<'a>
+        = NoopAccountTrieCursor
+    where
+        Self: 'a;
+
+    type StorageTrieCursor<'a>
+        = NoopStorageTrieCursor
+    where
+        Self: 'a;
+
+    fn account_trie_cursor(&self) -> Result<Self::AccountTrieCursor<'_>, DatabaseError> {
+        Ok(NoopAccountTrieCursor::default())
+    }
+
+    fn storage_trie_cursor(
+        &self,
+        _hashed_address: B256,
+    ) -> Result<Self::StorageTrieCursor<'_>, DatabaseError> {
+        Ok(NoopStorageTrieCursor::default())
+    }
+}
+
+impl<P: HashedCursorFactory> HashedCursorFactory for WithoutTrieNodes<P> {
+    type AccountCursor<'a>
+        = P::AccountCursor<'a>
+    where
+        Self: 'a;
+
+    type StorageCursor<'a>
+        = P::StorageCursor<'a>
+    where
+        Self: 'a;
+
+    fn hashed_account_cursor(&self) -> Result<Self::AccountCursor<'_>, DatabaseError> {
+        self.0.hashed_account_cursor()
+    }
+
+    fn hashed_storage_cursor(
+        &self,
+        hashed_address: B256,
+    ) -> Result<Self::StorageCursor<'_>, DatabaseError> {
+        self.0.hashed_storage_cursor(hashed_address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, U256};
+    use reth_db_api::{
+        models::{AccountBeforeTx, BlockNumberAddress},
+        tables,
+        transaction::DbTxMut,
+    };
+    use reth_primitives_traits::{Account, StorageEntry};
+    use reth_provider::test_utils::create_test_provider_factory;
+    use reth_trie::KeyHasher;
+
+    #[test]
+    fn revert_incrementally() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        // Every block changes the same account and slot, and an account of its own
+        let address = Address::with_last_byte(1);
+        for block in 1..=10 {
+            let tx = provider.tx_ref();
+            tx.put::<tables::AccountChangeSets>(
+                block,
+                AccountBeforeTx {
+                    address,
+                    info: Some(Account { nonce: block, ..Default::default() }),
+                },
+            )
+            .unwrap();
+            tx.put::<tables::AccountChangeSets>(
+                block,
+                AccountBeforeTx { address: Address::with_last_byte(block as u8 + 1), info: None },
+            )
+            .unwrap();
+            tx.put::<tables::StorageChangeSets>(
+                BlockNumberAddress((block, address)),
+                StorageEntry { key: B256::with_last_byte(1), value: U256::from(block) },
+            )
+            .unwrap();
+        }
+
+        let mut reverts = StateReverts::new(10);
+        for block in [10, 9, 6, 2, 0] {
+            let expected =
+                HashedPostStateSorted::from_reverts::<KeccakKeyHasher>(&*provider, block + 1..=10)
+                    .unwrap();
+            assert_eq!(*reverts.revert_to(&*provider, block).unwrap(), expected);
+        }
+
+        // The state at block 0 is the one before the first block
+        let state = reverts.revert_to(&*provider, 0).unwrap();
+        assert_eq!(state.accounts.len(), 11);
+        assert!(state.accounts.contains(&(
+            KeccakKeyHasher::hash_key(address),
+            Some(Account { nonce: 1, ..Default::default() })
+        )));
+    }
+}
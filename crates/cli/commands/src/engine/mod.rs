@@ -0,0 +1,50 @@
+//! `reth engine` command.
+
+use crate::{launcher::Launcher, node::NoArgs};
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_runner::CliContext;
+use std::{fmt, sync::Arc};
+
+mod replay;
+
+/// `reth engine` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs> {
+    #[command(subcommand)]
+    pub command: Subcommands<C, Ext>,
+}
+
+/// `reth engine` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs> {
+    /// Replays stored engine API messages into a node launched from the datadir
+    Replay(Box<replay::Command<C, Ext>>),
+}
+
+impl<C, Ext> Command<C, Ext>
+where
+    C: ChainSpecParser,
+    C::ChainSpec: EthChainSpec + EthereumHardforks,
+    Ext: clap::Args + fmt::Debug,
+{
+    /// Execute `engine` command
+    pub async fn execute<L>(self, ctx: CliContext, launcher: L) -> eyre::Result<()>
+    where
+        L: Launcher<C, Ext>,
+    {
+        match self.command {
+            Subcommands::Replay(command) => command.execute(ctx, launcher).await,
+        }
+    }
+}
+
+impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Command<C, Ext> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        match &self.command {
+            Subcommands::Replay(command) => command.chain_spec(),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{
+    launcher::Launcher,
+    node::{NoArgs, NodeCommand},
+};
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_runner::CliContext;
+use reth_node_core::args::EngineApiReplay;
+use std::{fmt, path::PathBuf, sync::Arc};
+
+/// The arguments for the `reth engine replay` command
+///
+/// Launches the node like `reth node` and sends the engine API messages stored with
+/// `--debug.engine-api-store` to its engine, in the order and with the timing they were received
+/// in. The latency of every response and any divergence from the recorded response status are
+/// logged. The node keeps running after the replay, so its state can be inspected.
+///
+/// The node should be launched from a copy of the database at the block the recording started
+/// at, and without a consensus client.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs> {
+    /// The directory of the stored engine API messages.
+    #[arg(value_name = "DIR")]
+    path: PathBuf,
+
+    /// The replay speed relative to the recording, e.g. `10` replays the messages ten times as
+    /// fast.
+    ///
+    /// With `0`, every message is sent as soon as the engine responded to the previous one.
+    #[arg(long, value_name = "FACTOR", default_value_t = 1)]
+    speed: u64,
+
+    #[command(flatten)]
+    node: NodeCommand<C, Ext>,
+}
+
+impl<C, Ext> Command<C, Ext>
+where
+    C: ChainSpecParser,
+    C::ChainSpec: EthChainSpec + EthereumHardforks,
+    Ext: clap::Args + fmt::Debug,
+{
+    /// Execute `engine replay` command
+    pub async fn execute<L>(self, ctx: CliContext, launcher: L) -> eyre::Result<()>
+    where
+        L: Launcher<C, Ext>,
+    {
+        let Self { path, speed, mut node } = self;
+        if !path.is_dir() {
+            eyre::bail!("Engine API message directory {} does not exist", path.display())
+        }
+
+        node.debug.engine_api_replay = Some(EngineApiReplay { path, speed });
+        node.execute(ctx, launcher).await
+    }
+}
+
+impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Command<C, Ext> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        self.node.chain_spec()
+    }
+}
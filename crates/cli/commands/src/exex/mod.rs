@@ -0,0 +1,41 @@
+//! `reth exex` command.
+
+use crate::common::CliNodeTypes;
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use std::sync::Arc;
+
+mod wal;
+
+/// `reth exex` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(subcommand)]
+    pub command: Subcommands<C>,
+}
+
+/// `reth exex` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands<C: ChainSpecParser> {
+    /// Inspects and repairs the `ExEx` write-ahead log
+    Wal(wal::Command<C>),
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `exex` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Wal(command) => command.execute::<N>().await,
+        }
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        match self.command {
+            Subcommands::Wal(ref command) => command.chain_spec(),
+        }
+    }
+}
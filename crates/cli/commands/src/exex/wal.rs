@@ -0,0 +1,447 @@
+//! `reth exex wal` command.
+
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{BlockNumber, B256};
+use clap::{Parser, Subcommand};
+use human_bytes::human_bytes;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_exex::{ExExNotification, Storage, WalError};
+use reth_node_api::NodePrimitives;
+use reth_provider::{BlockHashReader, BlockNumReader, Chain};
+use std::{collections::BTreeMap, path::Path, sync::Arc};
+use tracing::{info, warn};
+
+/// `reth exex wal` command
+///
+/// The node must not be running while the WAL is modified.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth exex wal` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands {
+    /// Lists all WAL files with the chains of their notifications
+    List,
+    /// Decodes and displays the notification of a WAL file
+    Show(ShowCommand),
+    /// Removes notifications from the WAL
+    Prune(PruneCommand),
+    /// Checks that all WAL files can be decoded and that the WAL is consistent with the canonical
+    /// chain in the database
+    Verify,
+}
+
+/// `reth exex wal show` command
+#[derive(Debug, Parser)]
+pub struct ShowCommand {
+    /// The ID of the WAL file.
+    id: u32,
+}
+
+/// `reth exex wal prune` command
+#[derive(Debug, Parser)]
+#[group(required = true, multiple = false)]
+pub struct PruneCommand {
+    /// Removes notifications from the start of the WAL whose blocks are at or below the given
+    /// block number, the same way the node finalizes the WAL.
+    ///
+    /// Stops at the first notification with a higher block, so that the WAL stays contiguous.
+    #[arg(long, value_name = "BLOCK_NUMBER")]
+    to_block: Option<BlockNumber>,
+
+    /// Removes the notification in the file with the given ID and all notifications after it.
+    #[arg(long, value_name = "FILE_ID")]
+    from_file: Option<u32>,
+
+    /// Removes notifications that can't be decoded from the start and the end of the WAL.
+    ///
+    /// Corrupted notifications in the middle of the WAL are kept, because removing them would
+    /// leave a gap that the node can't open. Use `--from-file` to remove them together with all
+    /// notifications after them.
+    #[arg(long)]
+    corrupted: bool,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `exex wal` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain());
+
+        match self.command {
+            Subcommands::List => list(&open_storage::<N::Primitives>(&data_dir.exex_wal())?)?,
+            Subcommands::Show(command) => {
+                command.execute(&open_storage::<N::Primitives>(&data_dir.exex_wal())?)?
+            }
+            Subcommands::Prune(command) => {
+                command.execute(&open_storage::<N::Primitives>(&data_dir.exex_wal())?)?
+            }
+            Subcommands::Verify => {
+                let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+                verify(
+                    &open_storage::<N::Primitives>(&data_dir.exex_wal())?,
+                    &provider_factory.provider()?,
+                )?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+impl ShowCommand {
+    fn execute<N: NodePrimitives>(self, storage: &Storage<N>) -> eyre::Result<()> {
+        let (notification, size) =
+            storage.read_notification(self.id)?.ok_or(WalError::FileNotFound(self.id))?;
+
+        println!("File: {}", storage.file_path(self.id).display());
+        println!("Size: {}", human_bytes(size as f64));
+        println!("Notification: {}", describe(&notification));
+
+        if let Some(chain) = notification.reverted_chain() {
+            print_chain("Reverted", &chain);
+        }
+        if let Some(chain) = notification.committed_chain() {
+            print_chain("Committed", &chain);
+        }
+
+        Ok(())
+    }
+}
+
+impl PruneCommand {
+    fn execute<N: NodePrimitives>(self, storage: &Storage<N>) -> eyre::Result<()> {
+        let file_ids = self.file_ids_to_remove(storage)?;
+
+        let (removed_notifications, removed_size) = storage.remove_notifications(file_ids)?;
+        info!(
+            target: "reth::cli",
+            removed_notifications,
+            removed_size = %human_bytes(removed_size as f64),
+            "Pruned the WAL"
+        );
+
+        Ok(())
+    }
+
+    /// Returns the IDs of the files to remove.
+    ///
+    /// The node expects the WAL file IDs to be contiguous, so only a prefix or a suffix of the WAL
+    /// is ever removed.
+    fn file_ids_to_remove<N: NodePrimitives>(
+        &self,
+        storage: &Storage<N>,
+    ) -> eyre::Result<Vec<u32>> {
+        let file_ids = storage.file_ids()?;
+
+        if let Some(from_file) = self.from_file {
+            return Ok(file_ids.into_iter().filter(|file_id| *file_id >= from_file).collect())
+        }
+
+        let should_remove = file_ids
+            .iter()
+            .map(|file_id| self.should_remove(storage, *file_id))
+            .collect::<Vec<_>>();
+
+        let prefix = should_remove.iter().take_while(|remove| **remove).count();
+        // Notifications at the end of the WAL are only removed if they're corrupted, because the
+        // node finalizes the WAL from the start.
+        let suffix = if self.corrupted {
+            should_remove[prefix..].iter().rev().take_while(|remove| **remove).count()
+        } else {
+            0
+        };
+
+        let kept = should_remove[prefix..should_remove.len() - suffix]
+            .iter()
+            .filter(|remove| **remove)
+            .count();
+        if kept > 0 {
+            warn!(
+                target: "reth::cli",
+                kept,
+                "Keeping notifications in the middle of the WAL, removing them would leave a gap"
+            );
+        }
+
+        Ok(file_ids[..prefix].iter().chain(&file_ids[file_ids.len() - suffix..]).copied().collect())
+    }
+
+    fn should_remove<N: NodePrimitives>(&self, storage: &Storage<N>, file_id: u32) -> bool {
+        match storage.read_notification(file_id) {
+            Ok(Some((notification, _))) => {
+                self.to_block.is_some_and(|to_block| highest_block(&notification) <= Some(to_block))
+            }
+            Ok(None) => false,
+            Err(err) => {
+                if !self.corrupted {
+                    warn!(target: "reth::cli", file_id, %err, "Skipping notification that can't be decoded");
+                }
+                self.corrupted
+            }
+        }
+    }
+}
+
+/// Opens the WAL storage, without creating the directory if it doesn't exist.
+fn open_storage<N: NodePrimitives>(path: &Path) -> eyre::Result<Storage<N>> {
+    if !path.exists() {
+        eyre::bail!("ExEx WAL not found at {}", path.display())
+    }
+
+    Ok(Storage::new(path)?)
+}
+
+fn list<N: NodePrimitives>(storage: &Storage<N>) -> eyre::Result<()> {
+    println!("{:>10}  {:>10}  NOTIFICATION", "FILE", "SIZE");
+
+    for file_id in storage.file_ids()? {
+        match storage.read_notification(file_id) {
+            Ok(Some((notification, size))) => {
+                println!(
+                    "{file_id:>10}  {:>10}  {}",
+                    human_bytes(size as f64),
+                    describe(&notification)
+                )
+            }
+            Ok(None) => {}
+            Err(err) => println!("{file_id:>10}  {:>10}  {err}", "corrupted"),
+        }
+    }
+
+    Ok(())
+}
+
+/// Replays the notifications to reconstruct the chain the WAL ends on, and compares it with the
+/// canonical chain in the database.
+fn verify<N: NodePrimitives>(
+    storage: &Storage<N>,
+    provider: &(impl BlockHashReader + BlockNumReader),
+) -> eyre::Result<()> {
+    let file_ids = storage.file_ids()?;
+    let mut errors = 0;
+
+    if let (Some(first), Some(last)) = (file_ids.first(), file_ids.last()) &&
+        (last - first) as usize + 1 != file_ids.len()
+    {
+        warn!(target: "reth::cli", first, last, files = file_ids.len(), "WAL file IDs are not contiguous");
+        errors += 1;
+    }
+
+    let mut chain = BTreeMap::<BlockNumber, B256>::new();
+    for file_id in file_ids {
+        let notification = match storage.read_notification(file_id) {
+            Ok(Some((notification, _))) => notification,
+            Ok(None) => continue,
+            Err(err) => {
+                warn!(target: "reth::cli", file_id, %err, "Failed to decode notification");
+                errors += 1;
+                continue
+            }
+        };
+
+        if let Some(reverted) = notification.reverted_chain() {
+            for block in reverted.blocks().values() {
+                if let Some(hash) = chain.remove(&block.number()) &&
+                    hash != block.hash()
+                {
+                    warn!(target: "reth::cli", file_id, number = block.number(), %hash, reverted = %block.hash(), "Notification reverts a block that was not committed");
+                    errors += 1;
+                }
+            }
+        }
+        if let Some(committed) = notification.committed_chain() {
+            for block in committed.blocks().values() {
+                chain.insert(block.number(), block.hash());
+            }
+        }
+    }
+
+    let last_block = provider.last_block_number()?;
+    let mut not_persisted = 0;
+    for (number, hash) in chain {
+        if number > last_block {
+            not_persisted += 1;
+            continue
+        }
+
+        let canonical = provider.block_hash(number)?;
+        if canonical != Some(hash) {
+            warn!(target: "reth::cli", number, %hash, ?canonical, "WAL block is not canonical");
+            errors += 1;
+        }
+    }
+
+    if not_persisted > 0 {
+        info!(target: "reth::cli", not_persisted, last_block, "WAL contains blocks above the database tip");
+    }
+
+    if errors > 0 {
+        eyre::bail!("WAL verification failed with {errors} errors")
+    }
+
+    info!(target: "reth::cli", "WAL is consistent with the canonical chain");
+
+    Ok(())
+}
+
+fn describe<N: NodePrimitives>(notification: &ExExNotification<N>) -> String {
+    match notification {
+        ExExNotification::ChainCommitted { new } => format!("committed {:?}", new.range()),
+        ExExNotification::ChainReorged { old, new } => {
+            format!("reorged {:?} to {:?}", old.range(), new.range())
+        }
+        ExExNotification::ChainReverted { old } => format!("reverted {:?}", old.range()),
+    }
+}
+
+fn print_chain<N: NodePrimitives>(kind: &str, chain: &Chain<N>) {
+    println!("{kind} chain {:?}", chain.range());
+    for block in chain.blocks().values() {
+        println!(
+            "  #{} {} ({} transactions)",
+            block.number(),
+            block.hash(),
+            block.transaction_count()
+        );
+    }
+
+    let outcome = chain.execution_outcome();
+    println!(
+        "  {} changed accounts, {} receipts",
+        outcome.bundle.state.len(),
+        outcome.receipts.iter().map(Vec::len).sum::<usize>()
+    );
+}
+
+/// Returns the highest block of the notification, which is what the node finalizes by.
+fn highest_block<N: NodePrimitives>(notification: &ExExNotification<N>) -> Option<BlockNumber> {
+    notification
+        .reverted_chain()
+        .iter()
+        .chain(&notification.committed_chain())
+        .map(|chain| chain.tip().number())
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+    use reth_exex::Wal;
+    use reth_testing_utils::generators::{
+        self, random_block, random_block_range, BlockParams, BlockRangeParams,
+    };
+
+    /// Reopens the WAL the same way the node does and returns all of its notifications.
+    fn read_wal(path: &Path) -> eyre::Result<Vec<ExExNotification>> {
+        let wal: Wal = Wal::new(path)?;
+        Ok(wal.iter_notifications()?.collect::<Result<_, _>>()?)
+    }
+
+    #[test]
+    fn parse_prune_target() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "prune",
+            "--to-block",
+            "10",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cmd.command,
+            Subcommands::Prune(PruneCommand {
+                to_block: Some(10),
+                from_file: None,
+                corrupted: false
+            })
+        ));
+
+        // Exactly one target is required
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from(["reth", "prune"]).is_err());
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "prune",
+            "--from-file",
+            "1",
+            "--corrupted"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn prune_keeps_wal_contiguous() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let temp_dir = tempfile::tempdir()?;
+
+        let blocks = random_block_range(&mut rng, 0..=3, BlockRangeParams::default())
+            .into_iter()
+            .map(|block| block.try_recover())
+            .collect::<Result<Vec<_>, _>>()?;
+        let block_2_reorged = random_block(
+            &mut rng,
+            2,
+            BlockParams { parent: Some(blocks[1].hash()), ..Default::default() },
+        )
+        .try_recover()?;
+
+        // The highest blocks of the notifications are 1, 3, 3 and 2
+        let notifications = [
+            ExExNotification::ChainCommitted {
+                new: Arc::new(Chain::new(blocks[..2].to_vec(), Default::default(), None)),
+            },
+            ExExNotification::ChainCommitted {
+                new: Arc::new(Chain::new(blocks[2..].to_vec(), Default::default(), None)),
+            },
+            ExExNotification::ChainReverted {
+                old: Arc::new(Chain::new(blocks[2..].to_vec(), Default::default(), None)),
+            },
+            ExExNotification::ChainCommitted {
+                new: Arc::new(Chain::new(vec![block_2_reorged], Default::default(), None)),
+            },
+        ];
+
+        let wal = Wal::new(&temp_dir)?;
+        for notification in &notifications {
+            wal.commit(notification)?;
+        }
+        drop(wal);
+
+        let storage: Storage = Storage::new(&temp_dir)?;
+        let prune = |to_block, from_file, corrupted| {
+            PruneCommand { to_block, from_file, corrupted }.execute(&storage)
+        };
+
+        // Only the first notification is removed, even though the last one is also below the
+        // block number
+        prune(Some(2), None, false)?;
+        assert_eq!(storage.file_ids()?, vec![1, 2, 3]);
+        assert_eq!(read_wal(temp_dir.path())?, notifications[1..]);
+
+        // The corrupted notification in the middle is kept
+        std::fs::write(storage.file_path(2), b"corrupted")?;
+        prune(None, None, true)?;
+        assert_eq!(storage.file_ids()?, vec![1, 2, 3]);
+
+        // The corrupted notification in the middle is removed together with the ones after it
+        prune(None, Some(2), false)?;
+        assert_eq!(storage.file_ids()?, vec![1]);
+        assert_eq!(read_wal(temp_dir.path())?, notifications[1..2]);
+
+        Ok(())
+    }
+}
@@ -8,7 +8,7 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_era_downloader::{read_dir, EraClient, EraStream, EraStreamConfig};
 use reth_era_utils as era;
-use reth_etl::Collector;
+use reth_etl::{Collector, DiskBudget};
 use reth_fs_util as fs;
 use reth_node_core::version::version_metadata;
 use reth_provider::StaticFileProviderFactory;
@@ -72,7 +72,10 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraC
 
         let Environment { provider_factory, config, .. } = self.env.init::<N>(AccessRights::RW)?;
 
-        let mut hash_collector = Collector::new(config.stages.etl.file_size, config.stages.etl.dir);
+        let etl = config.stages.etl;
+        let mut hash_collector = Collector::new(etl.file_size, etl.dir)
+            .with_compression(etl.compression)
+            .with_disk_budget(etl.disk_budget.map(DiskBudget::new));
 
         let next_block = provider_factory
             .static_file_provider()
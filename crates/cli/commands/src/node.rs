@@ -9,7 +9,7 @@ use reth_db::init_db;
 use reth_node_builder::NodeBuilder;
 use reth_node_core::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, MetricArgs,
+        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, ExExArgs, MetricArgs,
         NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, StaticFilesArgs, TxPoolArgs,
     },
     node_config::NodeConfig,
@@ -110,6 +110,10 @@ pub struct NodeCommand<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs
     #[command(flatten, next_help_heading = "ERA")]
     pub era: EraArgs,
 
+    /// All `ExEx` related arguments with --exex prefix
+    #[command(flatten, next_help_heading = "ExEx")]
+    pub exex: ExExArgs,
+
     /// All static files related arguments
     #[command(flatten, next_help_heading = "Static Files")]
     pub static_files: StaticFilesArgs,
@@ -168,6 +172,7 @@ where
             pruning,
             engine,
             era,
+            exex,
             static_files,
             ext,
         } = self;
@@ -189,6 +194,7 @@ where
             pruning,
             engine,
             era,
+            exex,
             static_files,
         };
 
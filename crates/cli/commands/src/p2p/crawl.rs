@@ -0,0 +1,470 @@
+//! Network crawler producing a census of reachable peers.
+
+use alloy_primitives::{FixedBytes, B256};
+use clap::{Parser, ValueEnum};
+use futures::{stream::FuturesUnordered, SinkExt};
+use reth_chainspec::{EthChainSpec, EthereumHardforks, ForkFilter, Hardforks, Head};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_util::{get_secret_key, load_secret_key::rng_secret_key};
+use reth_discv4::{DiscoveryUpdate, Discv4, Discv4Config};
+use reth_discv5::Discv5;
+use reth_ecies::stream::ECIESStream;
+use reth_eth_wire::{
+    DisconnectReason, EthMessage, EthNetworkPrimitives, HelloMessage, ProtocolMessage,
+    StatusMessage, UnauthedP2PStream, UnifiedStatus,
+};
+use reth_network_peers::{pk2id, NodeRecord, PeerId};
+use secp256k1::{SecretKey, SECP256K1};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashSet, VecDeque},
+    fs::File,
+    io::BufWriter,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{net::TcpStream, time::timeout};
+use tokio_stream::StreamExt;
+use tracing::{debug, info};
+
+/// Crawl the discovery tables and handshake with every reachable peer.
+///
+/// Each discovered peer is dialed once. The resulting census contains the client version,
+/// capabilities, `Status` fork id and head as well as the observed latency of every peer that
+/// completed the handshake, and the error for every peer that did not.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    /// The chain to crawl.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = C::help_message(),
+        default_value = C::default_value(),
+        value_parser = C::parser()
+    )]
+    pub chain: Arc<C::ChainSpec>,
+
+    /// Listen address for discovery.
+    #[arg(long, default_value = "0.0.0.0:30305")]
+    addr: SocketAddr,
+
+    /// Also crawl the discv5 table.
+    #[arg(long)]
+    v5: bool,
+
+    /// Secret key to use for the crawler.
+    ///
+    /// If no path is specified, a new ephemeral random secret will be used.
+    #[arg(long, value_name = "PATH")]
+    p2p_secret_key: Option<PathBuf>,
+
+    /// How long to crawl for, e.g. `10m`.
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "5m")]
+    duration: Duration,
+
+    /// Interval between discovery lookups.
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "5s")]
+    lookup_interval: Duration,
+
+    /// Maximum number of concurrent handshakes.
+    #[arg(long, default_value_t = 64)]
+    concurrency: usize,
+
+    /// Timeout for dialing and handshaking a single peer.
+    #[arg(long, value_parser = humantime::parse_duration, default_value = "10s")]
+    handshake_timeout: Duration,
+
+    /// The file to write the census to.
+    #[arg(long, short, value_name = "FILE")]
+    output: PathBuf,
+
+    /// The census format. Inferred from the output file extension if not set.
+    #[arg(long, value_enum)]
+    format: Option<CensusFormat>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
+    /// Execute the `p2p crawl` command.
+    pub async fn execute(self) -> eyre::Result<()> {
+        let format = self.format.unwrap_or_else(|| CensusFormat::from_path(&self.output));
+        let sk = match &self.p2p_secret_key {
+            Some(path) => get_secret_key(path)?,
+            None => rng_secret_key(),
+        };
+
+        let head = Head {
+            hash: self.chain.genesis_hash(),
+            number: 0,
+            timestamp: self.chain.genesis().timestamp,
+            difficulty: self.chain.genesis().difficulty,
+            total_difficulty: self.chain.genesis().difficulty,
+        };
+        let prober = Prober {
+            sk,
+            status: UnifiedStatus::spec_builder(&self.chain, &head),
+            fork_filter: self.chain.fork_filter(head),
+            timeout: self.handshake_timeout,
+        };
+        let boot_nodes = self.chain.bootnodes().unwrap_or_default();
+
+        let local_enr = NodeRecord::from_secret_key(self.addr, &sk);
+        let config = Discv4Config::builder()
+            .add_boot_nodes(boot_nodes.clone())
+            .lookup_interval(self.lookup_interval)
+            .build();
+        let (_discv4, mut discv4_service) = Discv4::bind(self.addr, local_enr, sk, config).await?;
+        let mut discv4_updates = discv4_service.update_stream();
+        discv4_service.spawn();
+        info!(target: "reth::cli", addr = %self.addr, "Started discv4 crawler");
+
+        let mut discv5 = None;
+        if self.v5 {
+            let config = reth_discv5::Config::builder(self.addr)
+                .add_unsigned_boot_nodes(boot_nodes)
+                .lookup_interval(self.lookup_interval.as_secs())
+                .build();
+            discv5 = Some(Discv5::start(&sk, config).await?);
+            info!(target: "reth::cli", "Started discv5 crawler");
+        }
+
+        let deadline = tokio::time::sleep(self.duration);
+        tokio::pin!(deadline);
+
+        let mut seen = HashSet::<PeerId>::new();
+        let mut queue = VecDeque::<(NodeRecord, DiscoverySource)>::new();
+        let mut probes = FuturesUnordered::new();
+        let mut census = Vec::new();
+
+        loop {
+            while probes.len() < self.concurrency.max(1) {
+                let Some((record, source)) = queue.pop_front() else { break };
+                probes.push(prober.probe(record, source));
+            }
+
+            tokio::select! {
+                _ = &mut deadline => break,
+                Some(update) = discv4_updates.next() => {
+                    for record in discv4_records(update) {
+                        if seen.insert(record.id) {
+                            queue.push_back((record, DiscoverySource::Discv4));
+                        }
+                    }
+                }
+                Some(event) = async {
+                    match &mut discv5 {
+                        Some((_, updates)) => updates.recv().await,
+                        None => futures::future::pending().await,
+                    }
+                } => {
+                    let Some((discv5, _)) = &discv5 else { continue };
+                    if let Some(peer) = discv5.on_discv5_update(event) &&
+                        seen.insert(peer.node_record.id)
+                    {
+                        queue.push_back((peer.node_record, DiscoverySource::Discv5));
+                    }
+                }
+                Some(entry) = probes.next() => {
+                    debug!(target: "reth::cli", ?entry, "Probed peer");
+                    census.push(entry);
+                }
+            }
+        }
+
+        info!(
+            target: "reth::cli",
+            in_flight = probes.len(),
+            queued = queue.len(),
+            "Crawl duration elapsed, waiting for in-flight handshakes"
+        );
+        while let Some(entry) = probes.next().await {
+            census.push(entry);
+        }
+
+        let summary = CensusSummary::new(&census);
+        write_census(&self.output, format, &census)?;
+
+        info!(
+            target: "reth::cli",
+            discovered = seen.len(),
+            probed = summary.probed,
+            reachable = summary.reachable,
+            compatible = summary.compatible,
+            output = %self.output.display(),
+            "Wrote census"
+        );
+        for (client, count) in summary.clients {
+            info!(target: "reth::cli", %client, count, "Client");
+        }
+
+        Ok(())
+    }
+}
+
+/// The format of the census file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CensusFormat {
+    /// A JSON array of census entries.
+    Json,
+    /// A CSV file with one row per census entry.
+    Csv,
+}
+
+impl CensusFormat {
+    /// Infers the format from the file extension, defaulting to JSON.
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => Self::Csv,
+            _ => Self::Json,
+        }
+    }
+}
+
+/// The discovery protocol a peer was found through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiscoverySource {
+    /// Discovered via discv4.
+    Discv4,
+    /// Discovered via discv5.
+    Discv5,
+}
+
+/// A single row of the census.
+///
+/// Fields that could not be determined, because the handshake failed before they were exchanged,
+/// are left empty.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CensusEntry {
+    /// The peer id.
+    pub peer_id: PeerId,
+    /// The advertised RLPx socket.
+    pub address: String,
+    /// The discovery protocol the peer was found through.
+    pub source: Option<DiscoverySource>,
+    /// The client version from the `Hello` message.
+    pub client_version: Option<String>,
+    /// The capabilities from the `Hello` message, separated by spaces.
+    pub capabilities: Option<String>,
+    /// The negotiated `eth` version.
+    pub eth_version: Option<u8>,
+    /// The chain id from the `Status` message.
+    pub chain_id: Option<u64>,
+    /// The genesis hash from the `Status` message.
+    pub genesis: Option<B256>,
+    /// The fork hash from the `Status` message.
+    pub fork_hash: Option<FixedBytes<4>>,
+    /// The next fork from the `Status` message.
+    pub fork_next: Option<u64>,
+    /// Whether the fork id is compatible with the local chain.
+    pub fork_compatible: Option<bool>,
+    /// The head block hash from the `Status` message.
+    pub head_hash: Option<B256>,
+    /// The latest block number, only advertised by `eth/69` peers.
+    pub head_number: Option<u64>,
+    /// Time to establish the TCP connection, in milliseconds.
+    pub connect_latency_ms: Option<u64>,
+    /// Time to complete the full RLPx, `Hello` and `Status` exchange, in milliseconds.
+    pub handshake_latency_ms: Option<u64>,
+    /// The error that ended the handshake, if any.
+    pub error: Option<String>,
+}
+
+/// Aggregated statistics over a census.
+#[derive(Debug, Default)]
+struct CensusSummary {
+    /// Number of peers that were dialed.
+    probed: usize,
+    /// Number of peers that completed the `Hello` exchange.
+    reachable: usize,
+    /// Number of peers with a compatible fork id.
+    compatible: usize,
+    /// Number of peers per client name.
+    clients: BTreeMap<String, usize>,
+}
+
+impl CensusSummary {
+    fn new(census: &[CensusEntry]) -> Self {
+        let mut summary = Self { probed: census.len(), ..Default::default() };
+        for entry in census {
+            let Some(client_version) = &entry.client_version else { continue };
+            summary.reachable += 1;
+            if entry.fork_compatible == Some(true) {
+                summary.compatible += 1;
+            }
+            let client = client_version.split('/').next().unwrap_or_default().to_string();
+            *summary.clients.entry(client).or_default() += 1;
+        }
+        summary
+    }
+}
+
+/// Dials and handshakes with discovered peers.
+#[derive(Debug)]
+struct Prober {
+    sk: SecretKey,
+    status: UnifiedStatus,
+    fork_filter: ForkFilter,
+    timeout: Duration,
+}
+
+impl Prober {
+    /// Probes the peer, recording everything learned before the first error.
+    async fn probe(&self, record: NodeRecord, source: DiscoverySource) -> CensusEntry {
+        let mut entry = CensusEntry {
+            peer_id: record.id,
+            address: record.tcp_addr().to_string(),
+            source: Some(source),
+            ..Default::default()
+        };
+        if let Err(err) = timeout(self.timeout, self.handshake(record, &mut entry))
+            .await
+            .unwrap_or_else(|_| Err(eyre::eyre!("handshake timed out")))
+        {
+            entry.error = Some(err.to_string());
+        }
+        entry
+    }
+
+    async fn handshake(&self, record: NodeRecord, entry: &mut CensusEntry) -> eyre::Result<()> {
+        let start = Instant::now();
+        let outgoing = TcpStream::connect(record.tcp_addr()).await?;
+        entry.connect_latency_ms = Some(start.elapsed().as_millis() as u64);
+
+        let ecies_stream = ECIESStream::connect(outgoing, self.sk, record.id).await?;
+        let hello = HelloMessage::builder(pk2id(&self.sk.public_key(SECP256K1))).build();
+        let (mut p2p_stream, their_hello) =
+            UnauthedP2PStream::new(ecies_stream).handshake(hello).await?;
+
+        entry.client_version = Some(their_hello.client_version.clone());
+        entry.capabilities = Some(
+            their_hello.capabilities.iter().map(ToString::to_string).collect::<Vec<_>>().join(" "),
+        );
+
+        let version = p2p_stream.shared_capabilities().eth_version()?;
+        entry.eth_version = Some(version as u8);
+
+        // The status exchange is done by hand instead of through `UnauthedEthStream` so that the
+        // remote status is recorded even if it does not match ours.
+        let mut status = self.status;
+        status.set_eth_version(version);
+        p2p_stream
+            .send(
+                alloy_rlp::encode(ProtocolMessage::<EthNetworkPrimitives>::from(
+                    EthMessage::Status(status.into_message()),
+                ))
+                .into(),
+            )
+            .await?;
+        let msg = p2p_stream.next().await.ok_or_else(|| eyre::eyre!("no status received"))??;
+        let EthMessage::Status(their_status) =
+            ProtocolMessage::<EthNetworkPrimitives>::decode_message(version, &mut msg.as_ref())?
+                .message
+        else {
+            eyre::bail!("expected status message")
+        };
+        entry.handshake_latency_ms = Some(start.elapsed().as_millis() as u64);
+
+        entry.chain_id = Some(their_status.chain().id());
+        entry.genesis = Some(their_status.genesis());
+        entry.fork_hash = Some(FixedBytes(their_status.forkid().hash.0));
+        entry.fork_next = Some(their_status.forkid().next);
+        entry.fork_compatible = Some(self.fork_filter.validate(their_status.forkid()).is_ok());
+        entry.head_hash = Some(their_status.blockhash());
+        if let StatusMessage::Eth69(status) = their_status {
+            entry.head_number = Some(status.latest);
+        }
+
+        // best effort, the census is complete at this point
+        let _ = p2p_stream.disconnect(DisconnectReason::ClientQuitting).await;
+
+        Ok(())
+    }
+}
+
+/// Returns all node records announced by a discv4 update.
+fn discv4_records(update: DiscoveryUpdate) -> Vec<NodeRecord> {
+    match update {
+        DiscoveryUpdate::Added(record) | DiscoveryUpdate::DiscoveredAtCapacity(record) => {
+            vec![record]
+        }
+        DiscoveryUpdate::Batch(updates) => updates.into_iter().flat_map(discv4_records).collect(),
+        DiscoveryUpdate::EnrForkId(..) | DiscoveryUpdate::Removed(_) => Vec::new(),
+    }
+}
+
+/// Writes the census to the given file.
+fn write_census(path: &Path, format: CensusFormat, census: &[CensusEntry]) -> eyre::Result<()> {
+    match format {
+        CensusFormat::Json => {
+            serde_json::to_writer_pretty(BufWriter::new(File::create(path)?), census)?
+        }
+        CensusFormat::Csv => {
+            let mut writer = csv::Writer::from_path(path)?;
+            for entry in census {
+                writer.serialize(entry)?;
+            }
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infer_census_format() {
+        assert_eq!(CensusFormat::from_path(Path::new("census.csv")), CensusFormat::Csv);
+        assert_eq!(CensusFormat::from_path(Path::new("census.CSV")), CensusFormat::Csv);
+        assert_eq!(CensusFormat::from_path(Path::new("census.json")), CensusFormat::Json);
+        assert_eq!(CensusFormat::from_path(Path::new("census")), CensusFormat::Json);
+    }
+
+    #[test]
+    fn census_summary_counts_clients() {
+        let census = vec![
+            CensusEntry {
+                client_version: Some("reth/v1.9.3/x86_64-unknown-linux-gnu".to_string()),
+                fork_compatible: Some(true),
+                ..Default::default()
+            },
+            CensusEntry {
+                client_version: Some("Geth/v1.16.0-stable/linux-amd64/go1.24.4".to_string()),
+                fork_compatible: Some(false),
+                ..Default::default()
+            },
+            CensusEntry { error: Some("connection refused".to_string()), ..Default::default() },
+        ];
+
+        let summary = CensusSummary::new(&census);
+        assert_eq!(summary.probed, 3);
+        assert_eq!(summary.reachable, 2);
+        assert_eq!(summary.compatible, 1);
+        assert_eq!(summary.clients.get("reth"), Some(&1));
+        assert_eq!(summary.clients.get("Geth"), Some(&1));
+    }
+
+    #[test]
+    fn write_csv_census() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("census.csv");
+        let census = vec![CensusEntry {
+            address: "127.0.0.1:30303".to_string(),
+            source: Some(DiscoverySource::Discv4),
+            client_version: Some("reth/v1.9.3".to_string()),
+            capabilities: Some("eth/68 eth/69".to_string()),
+            ..Default::default()
+        }];
+        write_census(&path, CensusFormat::Csv, &census).unwrap();
+
+        let contents = std::fs::read_to_string(path).unwrap();
+        let mut lines = contents.lines();
+        assert!(lines.next().unwrap().starts_with("peer_id,address,source,client_version"));
+        assert!(lines.next().unwrap().contains("127.0.0.1:30303,discv4,reth/v1.9.3,eth/68 eth/69"));
+    }
+}
@@ -18,6 +18,7 @@ use reth_node_core::{
 };
 
 pub mod bootnode;
+pub mod crawl;
 pub mod rlpx;
 
 /// `reth p2p` command
@@ -85,6 +86,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             Subcommands::Bootnode(command) => {
                 command.execute().await?;
             }
+            Subcommands::Crawl(command) => {
+                command.execute().await?;
+            }
         }
 
         Ok(())
@@ -99,6 +103,7 @@ impl<C: ChainSpecParser> Command<C> {
             Subcommands::Body { args, .. } => Some(&args.chain),
             Subcommands::Rlpx(_) => None,
             Subcommands::Bootnode(_) => None,
+            Subcommands::Crawl(command) => Some(&command.chain),
         }
     }
 }
@@ -126,6 +131,8 @@ pub enum Subcommands<C: ChainSpecParser> {
     Rlpx(rlpx::Command),
     /// Bootnode command
     Bootnode(bootnode::Command),
+    /// Crawl the network and write a census of reachable peers
+    Crawl(crawl::Command<C>),
 }
 
 #[derive(Debug, Clone, Parser)]
@@ -225,4 +232,19 @@ mod tests {
         let _args: Command<EthereumChainSpecParser> =
             Command::parse_from(["reth", "body", "--chain", "mainnet", "1000"]);
     }
+
+    #[test]
+    fn parse_crawl_cmd() {
+        let _args: Command<EthereumChainSpecParser> = Command::parse_from([
+            "reth",
+            "crawl",
+            "--chain",
+            "mainnet",
+            "--v5",
+            "--duration",
+            "10m",
+            "--output",
+            "census.csv",
+        ]);
+    }
 }
@@ -1,9 +1,10 @@
 //! Command that runs pruning without any limits.
-use crate::common::{AccessRights, CliNodeTypes, EnvironmentArgs};
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
 use clap::Parser;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
-use reth_prune::PrunerBuilder;
+use reth_provider::PruneCheckpointReader;
+use reth_prune::{DiskBudget, NodeStorageSize, PrunerBuilder};
 use reth_static_file::StaticFileProducer;
 use std::sync::Arc;
 use tracing::info;
@@ -13,33 +14,76 @@ use tracing::info;
 pub struct PruneCommand<C: ChainSpecParser> {
     #[command(flatten)]
     env: EnvironmentArgs<C>,
+
+    /// Only shows the disk budget state and the blocks that every segment would be pruned to,
+    /// without copying data to static files or pruning anything.
+    #[arg(long)]
+    dry_run: bool,
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> PruneCommand<C> {
     /// Execute the `prune` command
     pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
-        let env = self.env.init::<N>(AccessRights::RW)?;
-        let provider_factory = env.provider_factory;
-        let config = env.config.prune;
-
-        // Copy data from database to static files
-        info!(target: "reth::cli", "Copying data from database to static files...");
-        let static_file_producer =
-            StaticFileProducer::new(provider_factory.clone(), config.segments.clone());
-        let lowest_static_file_height =
-            static_file_producer.lock().copy_to_static_files()?.min_block_num();
-        info!(target: "reth::cli", ?lowest_static_file_height, "Copied data from database to static files");
+        let access = if self.dry_run { AccessRights::RO } else { AccessRights::RW };
+        let Environment { config, provider_factory, data_dir } = self.env.init::<N>(access)?;
+        let config = config.prune;
+
+        // Run the pruner according to the configuration, and don't enforce any limits on it
+        let mut pruner_builder = PrunerBuilder::new(config.clone()).delete_limit(usize::MAX);
+        if let Some(max_size) = config.disk_budget {
+            let disk_budget = DiskBudget::new(
+                max_size,
+                NodeStorageSize::new(
+                    provider_factory.db_ref().clone(),
+                    provider_factory.static_file_provider().directory().to_path_buf(),
+                    Some(data_dir.rocksdb()),
+                ),
+            );
+            let storage_size = disk_budget.storage_size()?;
+            info!(
+                target: "reth::cli",
+                ?storage_size,
+                total = storage_size.total(),
+                max_size,
+                over_budget = storage_size.total() > max_size,
+                "Disk budget"
+            );
+            pruner_builder = pruner_builder.disk_budget(disk_budget);
+        }
+        let mut pruner = pruner_builder.build_with_provider_factory(provider_factory.clone());
+
+        let prune_tip = if self.dry_run {
+            provider_factory.static_file_provider().get_highest_static_files().min_block_num()
+        } else {
+            // Copy data from database to static files
+            info!(target: "reth::cli", "Copying data from database to static files...");
+            let static_file_producer =
+                StaticFileProducer::new(provider_factory.clone(), config.segments.clone());
+            let lowest_static_file_height =
+                static_file_producer.lock().copy_to_static_files()?.min_block_num();
+            info!(target: "reth::cli", ?lowest_static_file_height, "Copied data from database to static files");
+            lowest_static_file_height
+        };
 
         // Delete data which has been copied to static files.
-        if let Some(prune_tip) = lowest_static_file_height {
-            info!(target: "reth::cli", ?prune_tip, ?config, "Pruning data from database...");
-            // Run the pruner according to the configuration, and don't enforce any limits on it
-            let mut pruner = PrunerBuilder::new(config)
-                .delete_limit(usize::MAX)
-                .build_with_provider_factory(provider_factory);
-
-            pruner.run(prune_tip)?;
-            info!(target: "reth::cli", "Pruned data from database");
+        if let Some(prune_tip) = prune_tip {
+            for (segment, to_block, prune_mode) in
+                pruner.prune_targets(&provider_factory.provider()?, prune_tip)?
+            {
+                info!(target: "reth::cli", %segment, to_block, ?prune_mode, "Segment pruning target");
+            }
+
+            if !self.dry_run {
+                info!(target: "reth::cli", ?prune_tip, ?config, "Pruning data from database...");
+                pruner.run(prune_tip)?;
+                info!(target: "reth::cli", "Pruned data from database");
+            }
+        }
+
+        // Custom segments of node extensions are only pruned by the node, but their checkpoints
+        // are reported with all others
+        for (segment, checkpoint) in provider_factory.provider()?.get_prune_checkpoints()? {
+            info!(target: "reth::cli", %segment, ?checkpoint, "Prune checkpoint");
         }
 
         Ok(())
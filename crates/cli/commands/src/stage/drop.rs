@@ -113,6 +113,7 @@ impl<C: ChainSpecParser> Command<C> {
                 tx.clear::<tables::TransactionBlocks>()?;
                 tx.clear::<tables::BlockOmmers<HeaderTy<N>>>()?;
                 tx.clear::<tables::BlockWithdrawals>()?;
+                tx.clear::<tables::BlockAccessLists>()?;
                 reset_stage_checkpoint(tx, StageId::Bodies)?;
 
                 insert_genesis_header(&provider_rw, &self.env.chain)?;
@@ -180,6 +181,12 @@ impl<C: ChainSpecParser> Command<C> {
 
                 insert_genesis_history(&provider_rw, self.env.chain.genesis().alloc.iter())?;
             }
+            StageEnum::LogIndex => {
+                tx.clear::<tables::LogAddressIndex>()?;
+                tx.clear::<tables::LogTopicIndex>()?;
+
+                reset_stage_checkpoint(tx, StageId::IndexLogs)?;
+            }
             StageEnum::TxLookup => {
                 tx.clear::<tables::TransactionHashNumbers>()?;
                 reset_prune_checkpoint(tx, PruneSegment::TransactionLookup)?;
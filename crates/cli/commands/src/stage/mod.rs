@@ -10,6 +10,7 @@ use reth_cli_runner::CliContext;
 
 pub mod drop;
 pub mod dump;
+pub mod plan;
 pub mod run;
 pub mod unwind;
 
@@ -36,6 +37,9 @@ pub enum Subcommands<C: ChainSpecParser> {
     Dump(dump::Command<C>),
     /// Unwinds a certain block range, deleting it from the database.
     Unwind(unwind::Command<C>),
+    /// Checks the progress of all stages for inconsistencies and plans the unwinds and runs needed
+    /// to reach a consistent tip.
+    Plan(plan::Command<C>),
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
@@ -54,6 +58,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             Subcommands::Drop(command) => command.execute::<N>().await,
             Subcommands::Dump(command) => command.execute::<N, _, _>(components).await,
             Subcommands::Unwind(command) => command.execute::<N, _, _>(components).await,
+            Subcommands::Plan(command) => command.execute::<N, _, _>(components).await,
         }
     }
 }
@@ -66,6 +71,7 @@ impl<C: ChainSpecParser> Command<C> {
             Subcommands::Drop(ref command) => command.chain_spec(),
             Subcommands::Dump(ref command) => command.chain_spec(),
             Subcommands::Unwind(ref command) => command.chain_spec(),
+            Subcommands::Plan(ref command) => command.chain_spec(),
         }
     }
 }
@@ -0,0 +1,685 @@
+//! `reth stage plan` command
+//!
+//! Reads the progress of all stages and finds the unwinds, prune checkpoint resets and runs that
+//! are needed to bring the database to a consistent tip.
+
+use crate::{
+    common::{AccessRights, CliNodeComponents, CliNodeTypes, Environment, EnvironmentArgs},
+    stage::unwind::build_pipeline,
+};
+use alloy_primitives::BlockNumber;
+use clap::Parser;
+use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_provider::{
+    BlockBodyIndicesProvider, BlockNumReader, EitherWriter, PruneCheckpointReader,
+    PruneCheckpointWriter, StageCheckpointReader, StaticFileProviderFactory, StorageSettingsCache,
+};
+use reth_prune::{PruneCheckpoint, PruneSegment};
+use reth_stages::{sets::OfflineStages, Pipeline, StageId};
+use reth_static_file::StaticFileProducer;
+use reth_static_file_types::StaticFileSegment;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::Arc,
+};
+use tracing::{info, warn};
+
+/// `reth stage plan` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// Executes the plan after printing it.
+    ///
+    /// Only the offline stages are run, the headers and bodies are downloaded by the node.
+    #[arg(long)]
+    execute: bool,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `stage plan` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>, F, Comp>(
+        self,
+        components: F,
+    ) -> eyre::Result<()>
+    where
+        Comp: CliNodeComponents<N>,
+        F: FnOnce(Arc<C::ChainSpec>) -> Comp,
+    {
+        let access = if self.execute { AccessRights::RW } else { AccessRights::RO };
+        let Environment { provider_factory, config, .. } = self.env.init::<N>(access)?;
+
+        let provider = provider_factory.provider()?;
+        let static_file_provider = provider_factory.static_file_provider();
+
+        let mut progress = Progress {
+            receipts_in_static_files: EitherWriter::receipts_destination(&provider)
+                .is_static_file(),
+            prune_checkpoints: provider.get_prune_checkpoints()?,
+            ..Default::default()
+        };
+        // The log index stage is skipped if the log index is not enabled
+        let log_index_enabled = provider.cached_storage_settings().log_index;
+        for stage in
+            StageId::ALL.into_iter().filter(|id| log_index_enabled || id != &StageId::IndexLogs)
+        {
+            if let Some(checkpoint) = provider.get_stage_checkpoint(stage)? {
+                progress.checkpoints.insert(stage, checkpoint.block_number);
+            }
+        }
+        for segment in [
+            StaticFileSegment::Headers,
+            StaticFileSegment::Transactions,
+            StaticFileSegment::Receipts,
+        ] {
+            if let Some(block) = static_file_provider.get_highest_static_file_block(segment) {
+                progress.static_files.insert(segment, block);
+            }
+        }
+        let latest_block = provider.last_block_number()?;
+        drop(provider);
+
+        progress.print();
+
+        let plan = Plan::new(&progress);
+        plan.print();
+
+        if let Some(unwind) = &plan.unwind &&
+            let Err(err) = config.prune.segments.ensure_unwind_target_unpruned(
+                latest_block,
+                unwind.to,
+                &progress.prune_checkpoints,
+            )
+        {
+            warn!(target: "reth::cli", %err, "The unwind can't be executed, the node must be resynced");
+            return Ok(())
+        }
+
+        if !self.execute || plan.is_empty() {
+            return Ok(())
+        }
+
+        let components = components(provider_factory.chain_spec());
+
+        if let Some(unwind) = plan.unwind {
+            info!(target: "reth::cli", to = unwind.to, offline = unwind.offline, "Unwinding");
+            let mut pipeline = build_pipeline(
+                config.clone(),
+                provider_factory.clone(),
+                components.evm_config().clone(),
+                unwind.offline,
+            )?;
+            pipeline.move_to_static_files()?;
+            pipeline.unwind(unwind.to, None)?;
+        }
+
+        if !plan.prune_resets.is_empty() {
+            let provider_rw = provider_factory.provider_rw()?;
+            for PruneReset { segment, to } in &plan.prune_resets {
+                let Some(checkpoint) = provider_rw.get_prune_checkpoint(*segment)? else {
+                    continue
+                };
+                info!(target: "reth::cli", %segment, to, "Resetting prune checkpoint");
+                let tx_number = provider_rw.block_body_indices(*to)?.map(|body| body.last_tx_num());
+                provider_rw.save_prune_checkpoint(
+                    *segment,
+                    PruneCheckpoint { block_number: Some(*to), tx_number, ..checkpoint },
+                )?;
+            }
+            provider_rw.commit()?;
+        }
+
+        if let Some(tip) = plan.runs.iter().map(|run| run.to).max() {
+            info!(target: "reth::cli", tip, "Running offline stages");
+            let prune_modes = config.prune.segments.clone();
+            let mut pipeline = Pipeline::<N>::builder()
+                .with_max_block(tip)
+                .add_stages(OfflineStages::new(
+                    components.evm_config().clone(),
+                    Arc::new(components.consensus().clone()),
+                    config.stages,
+                    prune_modes.clone(),
+                ))
+                .build(
+                    provider_factory.clone(),
+                    StaticFileProducer::new(provider_factory, prune_modes),
+                );
+            pipeline.run().await?;
+        }
+
+        info!(target: "reth::cli", "Executed the plan");
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// Stages whose data is derived from the data of other stages, with the stages they depend on.
+const DEPENDENCIES: &[(StageId, &[StageId])] = &[
+    (StageId::Bodies, &[StageId::Headers]),
+    (StageId::SenderRecovery, &[StageId::Bodies]),
+    (StageId::Execution, &[StageId::Bodies]),
+    (StageId::PruneSenderRecovery, &[StageId::Execution]),
+    (StageId::MerkleUnwind, &[StageId::Execution]),
+    (StageId::AccountHashing, &[StageId::Execution]),
+    (StageId::StorageHashing, &[StageId::Execution]),
+    (StageId::MerkleExecute, &[StageId::AccountHashing, StageId::StorageHashing]),
+    (StageId::TransactionLookup, &[StageId::Bodies]),
+    (StageId::IndexStorageHistory, &[StageId::Execution]),
+    (StageId::IndexAccountHistory, &[StageId::Execution]),
+    (StageId::IndexLogs, &[StageId::Execution]),
+    (StageId::MerkleChangeSets, &[StageId::MerkleExecute]),
+    (StageId::Prune, &[StageId::Execution]),
+    (
+        StageId::Finish,
+        &[
+            StageId::MerkleExecute,
+            StageId::TransactionLookup,
+            StageId::IndexStorageHistory,
+            StageId::IndexAccountHistory,
+        ],
+    ),
+];
+
+/// Stages that are run without the network.
+const OFFLINE_STAGES: [StageId; 13] = [
+    StageId::SenderRecovery,
+    StageId::Execution,
+    StageId::PruneSenderRecovery,
+    StageId::MerkleUnwind,
+    StageId::AccountHashing,
+    StageId::StorageHashing,
+    StageId::MerkleExecute,
+    StageId::MerkleChangeSets,
+    StageId::TransactionLookup,
+    StageId::IndexStorageHistory,
+    StageId::IndexAccountHistory,
+    StageId::IndexLogs,
+    StageId::Prune,
+];
+
+/// Returns `true` if the given stage is unwound without the network.
+///
+/// Sender recovery is run without the network, but the offline unwind leaves it as is, see
+/// [`build_pipeline`].
+fn is_unwound_offline(stage: StageId) -> bool {
+    stage != StageId::SenderRecovery && OFFLINE_STAGES.contains(&stage)
+}
+
+/// Returns the stages the given stage depends on.
+fn dependencies(stage: StageId) -> &'static [StageId] {
+    DEPENDENCIES
+        .iter()
+        .find(|(dependent, _)| *dependent == stage)
+        .map(|(_, dependencies)| *dependencies)
+        .unwrap_or_default()
+}
+
+/// Returns the stage that writes the given prune segment, and is followed by the pruner.
+const fn pruned_stage(segment: PruneSegment) -> Option<StageId> {
+    match segment {
+        PruneSegment::SenderRecovery => Some(StageId::SenderRecovery),
+        PruneSegment::TransactionLookup => Some(StageId::TransactionLookup),
+        PruneSegment::Receipts | PruneSegment::ContractLogs => Some(StageId::Execution),
+        PruneSegment::AccountHistory => Some(StageId::IndexAccountHistory),
+        PruneSegment::StorageHistory => Some(StageId::IndexStorageHistory),
+        _ => None,
+    }
+}
+
+/// Progress of the stages, static files and pruner, as read from the database.
+#[derive(Debug, Default)]
+struct Progress {
+    /// Block numbers of the stage checkpoints.
+    checkpoints: HashMap<StageId, BlockNumber>,
+    /// Highest blocks of the static file segments.
+    static_files: BTreeMap<StaticFileSegment, BlockNumber>,
+    /// Whether the execution stage writes receipts to static files.
+    receipts_in_static_files: bool,
+    /// Checkpoints of the pruned segments.
+    prune_checkpoints: Vec<(PruneSegment, PruneCheckpoint)>,
+}
+
+impl Progress {
+    /// Returns the static file segment written by the given stage, with its highest block.
+    fn static_file(&self, stage: StageId) -> Option<(StaticFileSegment, BlockNumber)> {
+        let segment = match stage {
+            StageId::Headers => StaticFileSegment::Headers,
+            StageId::Bodies => StaticFileSegment::Transactions,
+            StageId::Execution if self.receipts_in_static_files => StaticFileSegment::Receipts,
+            _ => return None,
+        };
+        self.static_files.get(&segment).map(|block| (segment, *block))
+    }
+
+    fn print(&self) {
+        println!("{:<24}  CHECKPOINT", "STAGE");
+        for stage in StageId::ALL {
+            if let Some(checkpoint) = self.checkpoints.get(&stage) {
+                println!("{:<24}  {checkpoint}", stage.to_string());
+            }
+        }
+
+        println!();
+        println!("{:<24}  HIGHEST BLOCK", "STATIC FILES");
+        for (segment, block) in &self.static_files {
+            println!("{:<24}  {block}", segment.to_string());
+        }
+
+        if !self.prune_checkpoints.is_empty() {
+            println!();
+            println!("{:<24}  PRUNED TO", "PRUNE SEGMENT");
+            for (segment, checkpoint) in &self.prune_checkpoints {
+                let pruned_to = checkpoint.block_number.map_or("-".to_string(), |b| b.to_string());
+                println!("{:<24}  {pruned_to}", segment.to_string());
+            }
+        }
+    }
+}
+
+/// An inconsistency between the progress of the stages, static files or pruner.
+#[derive(Debug, PartialEq, Eq)]
+enum Inconsistency {
+    /// A stage is ahead of a stage it depends on.
+    AheadOfDependency {
+        stage: StageId,
+        checkpoint: BlockNumber,
+        dependency: StageId,
+        dependency_checkpoint: BlockNumber,
+    },
+    /// Static files are behind the checkpoint of the stage that writes them.
+    StaticFilesBehind {
+        segment: StaticFileSegment,
+        highest_block: BlockNumber,
+        stage: StageId,
+        checkpoint: BlockNumber,
+    },
+    /// A segment is pruned beyond the checkpoint of the stage that writes it, so the data of the
+    /// blocks in between won't be pruned after the stage is run again.
+    PrunedAheadOfStage {
+        segment: PruneSegment,
+        pruned_to: BlockNumber,
+        stage: StageId,
+        checkpoint: BlockNumber,
+    },
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AheadOfDependency { stage, checkpoint, dependency, dependency_checkpoint } => {
+                write!(
+                    f,
+                    "{stage} is at block {checkpoint}, ahead of {dependency} at block {dependency_checkpoint}"
+                )
+            }
+            Self::StaticFilesBehind { segment, highest_block, stage, checkpoint } => {
+                write!(
+                    f,
+                    "{segment} static files end at block {highest_block}, behind {stage} at block {checkpoint}"
+                )
+            }
+            Self::PrunedAheadOfStage { segment, pruned_to, stage, checkpoint } => {
+                write!(
+                    f,
+                    "{segment} is pruned to block {pruned_to}, ahead of {stage} at block {checkpoint}"
+                )
+            }
+        }
+    }
+}
+
+/// Unwind of the pipeline to a block.
+#[derive(Debug, PartialEq, Eq)]
+struct Unwind {
+    /// The block to unwind to, which stays in the database.
+    to: BlockNumber,
+    /// Whether only the offline stages are unwound.
+    offline: bool,
+}
+
+/// Reset of the checkpoint of a prune segment to the checkpoint of the stage that writes it, so
+/// that the data written when the stage is run again gets pruned.
+#[derive(Debug, PartialEq, Eq)]
+struct PruneReset {
+    segment: PruneSegment,
+    /// The block to reset the checkpoint to.
+    to: BlockNumber,
+}
+
+/// Run of a stage over a range of blocks.
+#[derive(Debug, PartialEq, Eq)]
+struct Run {
+    stage: StageId,
+    from: BlockNumber,
+    to: BlockNumber,
+}
+
+/// The unwinds, prune checkpoint resets and runs needed to bring the database to a consistent
+/// tip.
+#[derive(Debug, PartialEq, Eq)]
+struct Plan {
+    inconsistencies: Vec<Inconsistency>,
+    unwind: Option<Unwind>,
+    prune_resets: Vec<PruneReset>,
+    runs: Vec<Run>,
+}
+
+impl Plan {
+    /// Creates the plan for the given progress.
+    ///
+    /// The data of every stage is consistent up to the lowest of its checkpoint, the blocks of
+    /// its static files and the consistent blocks of the stages it depends on. If any stage is
+    /// ahead of that block, the pipeline is unwound to the lowest such block. Prune segments that
+    /// are pruned beyond the checkpoint of their stage are then reset to it, and the offline
+    /// stages are run up to the bodies, which are the last downloaded block data.
+    fn new(progress: &Progress) -> Self {
+        let mut inconsistencies = Vec::new();
+        let mut consistent = HashMap::<StageId, BlockNumber>::new();
+        let mut unwind_to = None::<BlockNumber>;
+        let mut offline = true;
+
+        // Stages are ordered such that every stage comes after the stages it depends on
+        for stage in StageId::ALL {
+            let Some(&checkpoint) = progress.checkpoints.get(&stage) else { continue };
+            let mut consistent_block = checkpoint;
+
+            for dependency in dependencies(stage) {
+                let (Some(&dependency_checkpoint), Some(&dependency_consistent)) =
+                    (progress.checkpoints.get(dependency), consistent.get(dependency))
+                else {
+                    continue
+                };
+
+                if checkpoint > dependency_checkpoint {
+                    inconsistencies.push(Inconsistency::AheadOfDependency {
+                        stage,
+                        checkpoint,
+                        dependency: *dependency,
+                        dependency_checkpoint,
+                    });
+                }
+                consistent_block = consistent_block.min(dependency_consistent);
+            }
+
+            if let Some((segment, highest_block)) = progress.static_file(stage) &&
+                highest_block < checkpoint
+            {
+                inconsistencies.push(Inconsistency::StaticFilesBehind {
+                    segment,
+                    highest_block,
+                    stage,
+                    checkpoint,
+                });
+                consistent_block = consistent_block.min(highest_block);
+            }
+
+            if consistent_block < checkpoint {
+                unwind_to = Some(unwind_to.map_or(consistent_block, |to| to.min(consistent_block)));
+                offline &= is_unwound_offline(stage);
+            }
+            consistent.insert(stage, consistent_block);
+        }
+
+        for (segment, prune_checkpoint) in &progress.prune_checkpoints {
+            let Some(stage) = pruned_stage(*segment) else { continue };
+            let (Some(pruned_to), Some(&checkpoint)) =
+                (prune_checkpoint.block_number, progress.checkpoints.get(&stage))
+            else {
+                continue
+            };
+
+            if pruned_to > checkpoint {
+                inconsistencies.push(Inconsistency::PrunedAheadOfStage {
+                    segment: *segment,
+                    pruned_to,
+                    stage,
+                    checkpoint,
+                });
+            }
+        }
+
+        let unwind = unwind_to.map(|to| Unwind { to, offline });
+
+        // Checkpoints after the unwind
+        let checkpoint = |stage: StageId| {
+            let checkpoint = progress.checkpoints.get(&stage).copied();
+            match &unwind {
+                Some(unwind) if !unwind.offline || is_unwound_offline(stage) => {
+                    checkpoint.map(|checkpoint| checkpoint.min(unwind.to))
+                }
+                _ => checkpoint,
+            }
+        };
+
+        let prune_resets = progress
+            .prune_checkpoints
+            .iter()
+            .filter_map(|(segment, prune_checkpoint)| {
+                let to = checkpoint(pruned_stage(*segment)?)?;
+                (prune_checkpoint.block_number? > to)
+                    .then_some(PruneReset { segment: *segment, to })
+            })
+            .collect();
+
+        let tip = checkpoint(StageId::Bodies).unwrap_or_default();
+        let runs = OFFLINE_STAGES
+            .into_iter()
+            .filter_map(|stage| {
+                let from = checkpoint(stage).filter(|from| *from < tip)?;
+                Some(Run { stage, from, to: tip })
+            })
+            .collect();
+
+        Self { inconsistencies, unwind, prune_resets, runs }
+    }
+
+    /// Returns `true` if nothing needs to be unwound, reset or run.
+    const fn is_empty(&self) -> bool {
+        self.unwind.is_none() && self.prune_resets.is_empty() && self.runs.is_empty()
+    }
+
+    fn print(&self) {
+        println!();
+        if self.inconsistencies.is_empty() {
+            println!("No inconsistencies found");
+        } else {
+            println!("INCONSISTENCIES");
+            for inconsistency in &self.inconsistencies {
+                println!("  {inconsistency}");
+            }
+        }
+
+        println!();
+        if self.is_empty() {
+            println!("All stages are at the tip, nothing to do");
+            return
+        }
+
+        println!("PLAN");
+        let mut step = 1;
+        if let Some(Unwind { to, offline }) = self.unwind {
+            let stages = if offline { "offline stages" } else { "all stages" };
+            println!("  {step}. Unwind {stages} to block {to}");
+            step += 1;
+        }
+        for PruneReset { segment, to } in &self.prune_resets {
+            println!("  {step}. Reset {segment} prune checkpoint to block {to}");
+            step += 1;
+        }
+        for Run { stage, from, to } in &self.runs {
+            println!("  {step}. Run {stage} from block {from} to block {to}");
+            step += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_prune::PruneMode;
+
+    fn progress(checkpoints: &[(StageId, BlockNumber)]) -> Progress {
+        Progress { checkpoints: checkpoints.iter().copied().collect(), ..Default::default() }
+    }
+
+    #[test]
+    fn plan_consistent() {
+        let progress = progress(&[
+            (StageId::Headers, 100),
+            (StageId::Bodies, 100),
+            (StageId::SenderRecovery, 100),
+            (StageId::Execution, 80),
+            (StageId::AccountHashing, 80),
+            (StageId::StorageHashing, 80),
+            (StageId::MerkleExecute, 80),
+        ]);
+
+        // Stages behind their dependencies are only run
+        assert_eq!(
+            Plan::new(&progress),
+            Plan {
+                inconsistencies: vec![],
+                unwind: None,
+                prune_resets: vec![],
+                runs: vec![
+                    Run { stage: StageId::Execution, from: 80, to: 100 },
+                    Run { stage: StageId::AccountHashing, from: 80, to: 100 },
+                    Run { stage: StageId::StorageHashing, from: 80, to: 100 },
+                    Run { stage: StageId::MerkleExecute, from: 80, to: 100 },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn plan_stage_ahead() {
+        let progress = progress(&[
+            (StageId::Headers, 100),
+            (StageId::Bodies, 100),
+            (StageId::Execution, 50),
+            (StageId::AccountHashing, 80),
+            (StageId::StorageHashing, 50),
+            (StageId::MerkleExecute, 80),
+        ]);
+
+        let plan = Plan::new(&progress);
+        assert_eq!(
+            plan.inconsistencies,
+            vec![
+                Inconsistency::AheadOfDependency {
+                    stage: StageId::AccountHashing,
+                    checkpoint: 80,
+                    dependency: StageId::Execution,
+                    dependency_checkpoint: 50,
+                },
+                Inconsistency::AheadOfDependency {
+                    stage: StageId::MerkleExecute,
+                    checkpoint: 80,
+                    dependency: StageId::StorageHashing,
+                    dependency_checkpoint: 50,
+                },
+            ]
+        );
+        // The downloaded headers and bodies are kept
+        assert_eq!(plan.unwind, Some(Unwind { to: 50, offline: true }));
+        assert_eq!(plan.runs.len(), 4);
+        assert!(plan.runs.iter().all(|run| run.from == 50 && run.to == 100));
+    }
+
+    #[test]
+    fn plan_sender_recovery() {
+        // Sender recovery is left as is by the offline unwind, so it's not run again
+        let plan = Plan::new(&progress(&[
+            (StageId::Headers, 100),
+            (StageId::Bodies, 100),
+            (StageId::SenderRecovery, 100),
+            (StageId::Execution, 50),
+            (StageId::AccountHashing, 80),
+            (StageId::StorageHashing, 50),
+            (StageId::MerkleExecute, 50),
+        ]));
+        assert_eq!(plan.unwind, Some(Unwind { to: 50, offline: true }));
+        assert_eq!(plan.runs.len(), 4);
+        assert!(plan.runs.iter().all(|run| run.stage != StageId::SenderRecovery));
+
+        // Sender recovery ahead of the bodies can only be unwound together with all stages
+        let plan = Plan::new(&progress(&[
+            (StageId::Headers, 100),
+            (StageId::Bodies, 100),
+            (StageId::SenderRecovery, 120),
+        ]));
+        assert_eq!(plan.unwind, Some(Unwind { to: 100, offline: false }));
+    }
+
+    #[test]
+    fn plan_prune_reset() {
+        let mut progress = progress(&[
+            (StageId::Headers, 100),
+            (StageId::Bodies, 100),
+            (StageId::Execution, 100),
+            (StageId::AccountHashing, 100),
+            (StageId::StorageHashing, 50),
+            (StageId::MerkleExecute, 100),
+            (StageId::TransactionLookup, 60),
+        ]);
+        let prune_checkpoint = |block_number| PruneCheckpoint {
+            block_number: Some(block_number),
+            tx_number: None,
+            prune_mode: PruneMode::Full,
+        };
+        progress.prune_checkpoints = vec![
+            (PruneSegment::TransactionLookup, prune_checkpoint(80)),
+            (PruneSegment::Receipts, prune_checkpoint(90)),
+            (PruneSegment::AccountHistory, prune_checkpoint(40)),
+        ];
+
+        let plan = Plan::new(&progress);
+        assert!(plan.inconsistencies.contains(&Inconsistency::PrunedAheadOfStage {
+            segment: PruneSegment::TransactionLookup,
+            pruned_to: 80,
+            stage: StageId::TransactionLookup,
+            checkpoint: 60,
+        }));
+        assert_eq!(plan.unwind, Some(Unwind { to: 50, offline: true }));
+        // Receipts are pruned beyond the execution checkpoint after the unwind
+        assert_eq!(
+            plan.prune_resets,
+            vec![
+                PruneReset { segment: PruneSegment::TransactionLookup, to: 50 },
+                PruneReset { segment: PruneSegment::Receipts, to: 50 },
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_static_files_behind() {
+        let mut progress =
+            progress(&[(StageId::Headers, 100), (StageId::Bodies, 100), (StageId::Execution, 100)]);
+        progress.static_files.insert(StaticFileSegment::Headers, 100);
+        progress.static_files.insert(StaticFileSegment::Transactions, 90);
+
+        let plan = Plan::new(&progress);
+        assert_eq!(
+            plan.inconsistencies,
+            vec![Inconsistency::StaticFilesBehind {
+                segment: StaticFileSegment::Transactions,
+                highest_block: 90,
+                stage: StageId::Bodies,
+                checkpoint: 100,
+            }]
+        );
+        // Bodies have to be downloaded again, so nothing can be run afterwards
+        assert_eq!(plan.unwind, Some(Unwind { to: 90, offline: false }));
+        assert!(plan.runs.is_empty());
+    }
+}
@@ -3,6 +3,7 @@
 //! Stage debugging tool
 
 use crate::common::{AccessRights, CliNodeComponents, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::BlockHeader;
 use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::Sealable;
 use clap::Parser;
@@ -30,18 +31,20 @@ use reth_node_metrics::{
     version::VersionInfo,
 };
 use reth_provider::{
-    ChainSpecProvider, DBProvider, DatabaseProviderFactory, StageCheckpointReader,
+    ChainSpecProvider, DBProvider, DatabaseProviderFactory, HeaderProvider, StageCheckpointReader,
     StageCheckpointWriter, StaticFileProviderFactory,
 };
+use reth_prune::{NodeStorageSize, StorageSize, StorageSizeReader};
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, HeaderStage, IndexAccountHistoryStage,
-        IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        IndexLogsStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
+        StorageHashingStage, TransactionLookupStage,
     },
     ExecInput, ExecOutput, ExecutionStageThresholds, Stage, StageExt, UnwindInput, UnwindOutput,
 };
-use std::{any::Any, net::SocketAddr, sync::Arc, time::Instant};
+use reth_stages_types::{StageRunReport, StorageReport};
+use std::{any::Any, net::SocketAddr, path::PathBuf, sync::Arc, time::Instant};
 use tokio::sync::watch;
 use tracing::*;
 
@@ -96,6 +99,13 @@ pub struct Command<C: ChainSpecParser> {
     #[arg(long)]
     checkpoints: bool,
 
+    /// Writes a JSON report of the stage execution to the given file.
+    ///
+    /// The report contains the duration of the execution, excluding the unwind, the gas used by
+    /// the executed blocks and the size of the storage before and after the execution.
+    #[arg(long, value_name = "FILE", requires = "commit")]
+    report: Option<PathBuf>,
+
     #[command(flatten)]
     network: NetworkArgs,
 }
@@ -267,8 +277,8 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     })),
                     None,
                 ),
-                StageEnum::Execution => (
-                    Box::new(ExecutionStage::new(
+                StageEnum::Execution => {
+                    let mut stage = ExecutionStage::new(
                         components.evm_config().clone(),
                         Arc::new(components.consensus().clone()),
                         ExecutionStageThresholds {
@@ -279,9 +289,17 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                         },
                         config.stages.merkle.incremental_threshold,
                         ExExManagerHandle::empty(),
-                    )),
-                    None,
-                ),
+                    );
+                    // Prewarming reads the committed state, so it requires committing after
+                    // every batch.
+                    if self.commit && config.stages.execution.prewarm_threads > 0 {
+                        stage = stage.with_parallel_execution(
+                            provider_factory.clone(),
+                            config.stages.execution.prewarm_threads,
+                        );
+                    }
+                    (Box::new(stage), None)
+                }
                 StageEnum::TxLookup => (
                     Box::new(TransactionLookupStage::new(
                         TransactionLookupConfig { chunk_size: batch_size },
@@ -327,6 +345,14 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     )),
                     None,
                 ),
+                StageEnum::LogIndex => (
+                    Box::new(IndexLogsStage::new(
+                        config.stages.index_logs,
+                        etl_config,
+                        prune_modes.receipts,
+                    )),
+                    None,
+                ),
                 _ => return Ok(()),
             };
         if let Some(unwind_stage) = &unwind_stage {
@@ -364,6 +390,25 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             checkpoint: Some(checkpoint.with_block_number(self.from)),
         };
 
+        let storage_size = NodeStorageSize::new(
+            provider_factory.db_ref().clone(),
+            data_dir.static_files(),
+            Some(data_dir.rocksdb()),
+        );
+        let report = self
+            .report
+            .as_ref()
+            .map(|path| -> eyre::Result<_> {
+                let gas_used = provider_factory
+                    .provider()?
+                    .headers_range(self.from + 1..=self.to)?
+                    .iter()
+                    .map(|header| header.gas_used())
+                    .sum::<u64>();
+                Ok((path, gas_used, storage_report(provider_factory.db_ref(), &storage_size)?))
+            })
+            .transpose()?;
+
         let start = Instant::now();
         info!(target: "reth::cli", stage = %self.stage, "Executing stage");
         loop {
@@ -384,7 +429,22 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                 break
             }
         }
-        info!(target: "reth::cli", stage = %self.stage, time = ?start.elapsed(), "Finished stage");
+        let elapsed = start.elapsed();
+        info!(target: "reth::cli", stage = %self.stage, time = ?elapsed, "Finished stage");
+
+        if let Some((path, gas_used, storage_before)) = report {
+            let report = StageRunReport {
+                stage: self.stage.to_string(),
+                from: self.from,
+                to: self.to,
+                elapsed: elapsed.as_secs_f64(),
+                gas_used,
+                storage_before,
+                storage_after: storage_report(provider_factory.db_ref(), &storage_size)?,
+            };
+            reth_fs_util::write_json_file(path, &report)?;
+            info!(target: "reth::cli", ?path, "Wrote stage run report");
+        }
 
         Ok(())
     }
@@ -405,3 +465,84 @@ impl<C: ChainSpecParser> Command<C> {
         matches!(self.stage, StageEnum::Headers | StageEnum::Bodies | StageEnum::Execution)
     }
 }
+
+/// Reads the size of the node storage, with the page statistics of the database.
+fn storage_report<DB: DatabaseMetrics>(
+    db: &DB,
+    storage_size: &impl StorageSizeReader,
+) -> eyre::Result<StorageReport> {
+    let StorageSize { database, static_files, rocksdb } = storage_size.storage_size()?;
+    let mut report = StorageReport { database, static_files, rocksdb, ..Default::default() };
+
+    for (name, value, labels) in db.gauge_metrics() {
+        let pages = match name {
+            "db.table_pages" => {
+                match labels.iter().find(|label| label.key() == "type").map(|label| label.value()) {
+                    Some("leaf") => &mut report.leaf_pages,
+                    Some("branch") => &mut report.branch_pages,
+                    Some("overflow") => &mut report.overflow_pages,
+                    _ => continue,
+                }
+            }
+            "db.freelist" => &mut report.freelist_pages,
+            _ => continue,
+        };
+        *pages += value as u64;
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::ProviderResult;
+
+    #[derive(Debug)]
+    struct TestDatabase;
+
+    impl DatabaseMetrics for TestDatabase {
+        fn gauge_metrics(&self) -> Vec<(&'static str, f64, Vec<metrics::Label>)> {
+            let pages = |table, kind, pages| {
+                (
+                    "db.table_pages",
+                    pages,
+                    vec![metrics::Label::new("table", table), metrics::Label::new("type", kind)],
+                )
+            };
+            vec![
+                ("db.table_size", 100.0, vec![metrics::Label::new("table", "Headers")]),
+                pages("Headers", "leaf", 10.0),
+                pages("Headers", "branch", 2.0),
+                pages("Receipts", "leaf", 5.0),
+                pages("Receipts", "overflow", 1.0),
+                ("db.freelist", 3.0, vec![]),
+            ]
+        }
+    }
+
+    #[derive(Debug)]
+    struct FixedStorageSize;
+
+    impl StorageSizeReader for FixedStorageSize {
+        fn storage_size(&self) -> ProviderResult<StorageSize> {
+            Ok(StorageSize { database: 100, static_files: 20, rocksdb: 5 })
+        }
+    }
+
+    #[test]
+    fn read_storage_report() {
+        assert_eq!(
+            storage_report(&TestDatabase, &FixedStorageSize).unwrap(),
+            StorageReport {
+                database: 100,
+                static_files: 20,
+                rocksdb: 5,
+                leaf_pages: 15,
+                branch_pages: 2,
+                overflow_pages: 1,
+                freelist_pages: 3,
+            }
+        );
+    }
+}
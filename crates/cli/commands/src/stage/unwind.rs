@@ -65,8 +65,12 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         info!(target: "reth::cli", ?target, ?highest_static_file_block, prune_config=?config.prune,  "Executing a pipeline unwind.");
 
         // This will build an offline-only pipeline if the `offline` flag is enabled
-        let mut pipeline =
-            self.build_pipeline(config, provider_factory, components.evm_config().clone())?;
+        let mut pipeline = build_pipeline(
+            config,
+            provider_factory,
+            components.evm_config().clone(),
+            self.offline,
+        )?;
 
         // Move all applicable data from database to static files.
         pipeline.move_to_static_files()?;
@@ -77,63 +81,64 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
 
         Ok(())
     }
+}
 
-    fn build_pipeline<N: ProviderNodeTypes<ChainSpec = C::ChainSpec>>(
-        self,
-        config: Config,
-        provider_factory: ProviderFactory<N>,
-        evm_config: impl ConfigureEvm<Primitives = N::Primitives> + 'static,
-    ) -> Result<Pipeline<N>, eyre::Error> {
-        let stage_conf = &config.stages;
-        let prune_modes = config.prune.segments.clone();
-
-        let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
-
-        let builder = if self.offline {
-            Pipeline::<N>::builder().add_stages(
-                OfflineStages::new(
-                    evm_config,
-                    NoopConsensus::arc(),
-                    config.stages,
-                    prune_modes.clone(),
-                )
-                .builder()
-                .disable(reth_stages::StageId::SenderRecovery),
+/// Builds the pipeline used to unwind the database.
+///
+/// If `offline` is enabled, all stages except headers, bodies, and sender recovery are unwound.
+pub(crate) fn build_pipeline<N: ProviderNodeTypes<ChainSpec: EthereumHardforks>>(
+    config: Config,
+    provider_factory: ProviderFactory<N>,
+    evm_config: impl ConfigureEvm<Primitives = N::Primitives> + 'static,
+    offline: bool,
+) -> Result<Pipeline<N>, eyre::Error> {
+    let stage_conf = &config.stages;
+    let prune_modes = config.prune.segments.clone();
+
+    let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
+
+    let builder = if offline {
+        Pipeline::<N>::builder().add_stages(
+            OfflineStages::new(
+                evm_config,
+                NoopConsensus::arc(),
+                config.stages,
+                prune_modes.clone(),
             )
-        } else {
-            Pipeline::<N>::builder().with_tip_sender(tip_tx).add_stages(
-                DefaultStages::new(
-                    provider_factory.clone(),
-                    tip_rx,
-                    Arc::new(NoopConsensus::default()),
-                    NoopHeaderDownloader::default(),
-                    NoopBodiesDownloader::default(),
-                    evm_config.clone(),
-                    stage_conf.clone(),
-                    prune_modes.clone(),
-                    None,
-                )
-                .set(ExecutionStage::new(
-                    evm_config,
-                    Arc::new(NoopConsensus::default()),
-                    ExecutionStageThresholds {
-                        max_blocks: None,
-                        max_changes: None,
-                        max_cumulative_gas: None,
-                        max_duration: None,
-                    },
-                    stage_conf.execution_external_clean_threshold(),
-                    ExExManagerHandle::empty(),
-                )),
+            .builder()
+            .disable(reth_stages::StageId::SenderRecovery),
+        )
+    } else {
+        Pipeline::<N>::builder().with_tip_sender(tip_tx).add_stages(
+            DefaultStages::new(
+                provider_factory.clone(),
+                tip_rx,
+                Arc::new(NoopConsensus::default()),
+                NoopHeaderDownloader::default(),
+                NoopBodiesDownloader::default(),
+                evm_config.clone(),
+                stage_conf.clone(),
+                prune_modes.clone(),
+                None,
             )
-        };
-
-        let pipeline = builder.build(
-            provider_factory.clone(),
-            StaticFileProducer::new(provider_factory, prune_modes),
-        );
-        Ok(pipeline)
-    }
+            .set(ExecutionStage::new(
+                evm_config,
+                Arc::new(NoopConsensus::default()),
+                ExecutionStageThresholds {
+                    max_blocks: None,
+                    max_changes: None,
+                    max_cumulative_gas: None,
+                    max_duration: None,
+                },
+                stage_conf.execution_external_clean_threshold(),
+                ExExManagerHandle::empty(),
+            )),
+        )
+    };
+
+    let pipeline = builder
+        .build(provider_factory.clone(), StaticFileProducer::new(provider_factory, prune_modes));
+    Ok(pipeline)
 }
 
 impl<C: ChainSpecParser> Command<C> {
@@ -129,6 +129,8 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Logs stage configuration.
+    pub index_logs: IndexHistoryConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -282,6 +284,12 @@ pub struct ExecutionConfig {
         )
     )]
     pub max_duration: Option<Duration>,
+    /// The number of threads used to prewarm the state of upcoming blocks.
+    ///
+    /// If non-zero, blocks are fetched and prewarmed ahead of execution on separate threads, and
+    /// the state changes are written while the following blocks are executed. If zero, blocks are
+    /// fetched, executed and written one after another.
+    pub prewarm_threads: usize,
 }
 
 impl Default for ExecutionConfig {
@@ -293,6 +301,7 @@ impl Default for ExecutionConfig {
             max_cumulative_gas: Some(30_000_000 * 50_000),
             // 10 minutes
             max_duration: Some(Duration::from_secs(10 * 60)),
+            prewarm_threads: 0,
         }
     }
 }
@@ -389,18 +398,31 @@ pub struct EtlConfig {
     pub dir: Option<PathBuf>,
     /// The maximum size in bytes of data held in memory before being flushed to disk as a file.
     pub file_size: usize,
+    /// Whether the files are compressed with zstd.
+    pub compression: bool,
+    /// Maximum total size in bytes of the files of a stage, shared by all of its collectors.
+    ///
+    /// Stages fail before writing files that would exceed the budget, or that don't fit in the
+    /// available space of the disk.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub disk_budget: Option<u64>,
 }
 
 impl Default for EtlConfig {
     fn default() -> Self {
-        Self { dir: None, file_size: Self::default_file_size() }
+        Self {
+            dir: None,
+            file_size: Self::default_file_size(),
+            compression: false,
+            disk_budget: None,
+        }
     }
 }
 
 impl EtlConfig {
     /// Creates an ETL configuration
     pub const fn new(dir: Option<PathBuf>, file_size: usize) -> Self {
-        Self { dir, file_size }
+        Self { dir, file_size, compression: false, disk_budget: None }
     }
 
     /// Return default ETL directory from datadir path.
@@ -528,11 +550,22 @@ pub struct PruneConfig {
     /// Pruning configuration for every part of the data that can be pruned.
     #[cfg_attr(feature = "serde", serde(alias = "parts"))]
     pub segments: PruneModes,
+    /// Maximum size of the node storage in bytes.
+    ///
+    /// While the storage is over the budget, the pruner prunes the oldest blocks of every
+    /// configured segment beyond its prune mode, in proportion to how far the storage is over the
+    /// budget.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub disk_budget: Option<u64>,
 }
 
 impl Default for PruneConfig {
     fn default() -> Self {
-        Self { block_interval: DEFAULT_BLOCK_INTERVAL, segments: PruneModes::default() }
+        Self {
+            block_interval: DEFAULT_BLOCK_INTERVAL,
+            segments: PruneModes::default(),
+            disk_budget: None,
+        }
     }
 }
 
@@ -551,11 +584,14 @@ impl PruneConfig {
     /// - `Option<PruneMode>` fields: set from `other` only if `self` is `None`.
     /// - `block_interval`: set from `other` only if `self.block_interval ==
     ///   DEFAULT_BLOCK_INTERVAL`.
-    /// - `merkle_changesets`: always set from `other`.
+    /// - `merkle_changesets`: set from `other`, unless `self` is in archive mode.
     /// - `receipts_log_filter`: set from `other` only if `self` is empty and `other` is non-empty.
+    /// - `history_retention`: set from `other` only if `self` is empty and `other` is non-empty.
+    /// - `disk_budget`: set from `other` only if `self` is `None`.
     pub fn merge(&mut self, other: Self) {
         let Self {
             block_interval,
+            disk_budget,
             segments:
                 PruneModes {
                     sender_recovery,
@@ -566,6 +602,7 @@ impl PruneConfig {
                     bodies_history,
                     merkle_changesets,
                     receipts_log_filter,
+                    history_retention,
                 },
         } = other;
 
@@ -581,12 +618,21 @@ impl PruneConfig {
         self.segments.account_history = self.segments.account_history.or(account_history);
         self.segments.storage_history = self.segments.storage_history.or(storage_history);
         self.segments.bodies_history = self.segments.bodies_history.or(bodies_history);
-        // Merkle changesets is not optional; always take the value from `other`
-        self.segments.merkle_changesets = merkle_changesets;
+        // Merkle changesets are always configured, so take the value from `other`, unless the
+        // archive mode was explicitly requested
+        if !self.segments.is_merkle_changesets_archive() {
+            self.segments.merkle_changesets = merkle_changesets;
+        }
 
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
         }
+
+        if self.segments.history_retention.is_empty() && !history_retention.is_empty() {
+            self.segments.history_retention = history_retention;
+        }
+
+        self.disk_budget = self.disk_budget.or(disk_budget);
     }
 }
 
@@ -615,7 +661,7 @@ mod tests {
     use crate::PruneConfig;
     use alloy_primitives::Address;
     use reth_network_peers::TrustedPeer;
-    use reth_prune_types::{PruneMode, PruneModes, ReceiptsLogPruneConfig};
+    use reth_prune_types::{HistoryRetentionConfig, PruneMode, PruneModes, ReceiptsLogPruneConfig};
     use std::{collections::BTreeMap, path::Path, str::FromStr, time::Duration};
 
     fn with_tempdir(filename: &str, proc: fn(&std::path::Path)) {
@@ -878,6 +924,30 @@ storage_history = { distance = 16384 }
 #";
         let _conf: Config = toml::from_str(alpha_0_0_11).unwrap();
 
+        let history_retention = r"#
+[prune.segments]
+account_history = { distance = 16384 }
+storage_history = { distance = 16384 }
+history_retention = ['0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48']
+#";
+        let conf: Config = toml::from_str(history_retention).unwrap();
+        assert_eq!(conf.prune.segments.history_retention.len(), 1);
+
+        let age_and_disk_budget = r"#
+[prune]
+disk_budget = 1500000000000
+
+[prune.segments]
+receipts = { age = '30d' }
+account_history = { age = '2w' }
+#";
+        let conf: Config = toml::from_str(age_and_disk_budget).unwrap();
+        assert_eq!(conf.prune.disk_budget, Some(1_500_000_000_000));
+        assert_eq!(
+            conf.prune.segments.receipts,
+            Some(PruneMode::Age(Duration::from_secs(30 * 24 * 60 * 60)))
+        );
+
         let alpha_0_0_18 = r"#
 [stages.headers]
 downloader_max_concurrent_requests = 100
@@ -1104,12 +1174,14 @@ receipts = 'full'
                 account_history: None,
                 storage_history: Some(PruneMode::Before(5000)),
                 bodies_history: None,
-                merkle_changesets: PruneMode::Before(0),
+                merkle_changesets: Some(PruneMode::Before(0)),
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([(
                     Address::random(),
                     PruneMode::Full,
                 )])),
+                history_retention: Default::default(),
             },
+            disk_budget: None,
         };
 
         let config2 = PruneConfig {
@@ -1121,15 +1193,18 @@ receipts = 'full'
                 account_history: Some(PruneMode::Distance(2000)),
                 storage_history: Some(PruneMode::Distance(3000)),
                 bodies_history: None,
-                merkle_changesets: PruneMode::Distance(10000),
+                merkle_changesets: Some(PruneMode::Distance(10000)),
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
                 ])),
+                history_retention: HistoryRetentionConfig::from_iter([Address::random()]),
             },
+            disk_budget: Some(1_500_000_000_000),
         };
 
         let original_filter = config1.segments.receipts_log_filter.clone();
+        let other_retention = config2.segments.history_retention.clone();
         config1.merge(config2);
 
         // Check that the configuration has been merged. Any configuration present in config1
@@ -1140,8 +1215,16 @@ receipts = 'full'
         assert_eq!(config1.segments.receipts, Some(PruneMode::Distance(1000)));
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
-        assert_eq!(config1.segments.merkle_changesets, PruneMode::Distance(10000));
+        assert_eq!(config1.segments.merkle_changesets, Some(PruneMode::Distance(10000)));
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
+        assert_eq!(config1.segments.history_retention, other_retention);
+        assert_eq!(config1.disk_budget, Some(1_500_000_000_000));
+
+        // The archive mode is kept
+        let mut archive = PruneConfig::default();
+        archive.segments.merkle_changesets = None;
+        archive.merge(PruneConfig::default());
+        assert!(archive.segments.is_merkle_changesets_archive());
     }
 
     #[test]
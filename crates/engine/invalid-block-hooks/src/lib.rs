@@ -1,5 +1,7 @@
 //! Invalid block hook implementations.
 
+mod trace;
 mod witness;
 
+pub use trace::{InvalidBlockTraceHook, InvalidBlockTracer};
 pub use witness::InvalidBlockWitnessHook;
@@ -0,0 +1,477 @@
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{map::HashMap, Bytes, B256, U256, U64};
+use alloy_rpc_types_trace::geth::{
+    DefaultFrame, GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingOptions,
+    GethTrace, StructLog, TraceResult,
+};
+use pretty_assertions::Comparison;
+use reth_engine_primitives::InvalidBlockHook;
+use reth_evm::{block::BlockExecutor, ConfigureEvm, Evm};
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader, SignedTransaction};
+use reth_provider::{BlockExecutionOutput, StateProviderFactory};
+use reth_revm::{database::StateProviderDatabase, db::State};
+use reth_rpc_api::DebugApiClient;
+use reth_tracing::tracing::warn;
+use reth_trie::updates::TrieUpdates;
+use revm_bytecode::OpCode;
+use revm_inspectors::tracing::{DebugInspector, TransactionContext};
+use serde::Serialize;
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// The trace captured by the [`InvalidBlockTraceHook`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidBlockTracer {
+    /// Prestate trace of every transaction, containing the accounts and storage slots it read
+    /// before it was executed.
+    PreState,
+    /// Opcode trace of every transaction, written in the
+    /// [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) format.
+    Opcode,
+}
+
+impl InvalidBlockTracer {
+    /// Returns the tracing options that produce the trace, both locally and on the healthy node.
+    fn tracing_options(self) -> GethDebugTracingOptions {
+        match self {
+            Self::PreState => GethDebugTracingOptions {
+                tracer: Some(GethDebugTracerType::BuiltInTracer(
+                    GethDebugBuiltInTracerType::PreStateTracer,
+                )),
+                ..Default::default()
+            },
+            Self::Opcode => GethDebugTracingOptions::default(),
+        }
+    }
+
+    /// Returns the name of the tracer used in file names and logs.
+    const fn name(self) -> &'static str {
+        match self {
+            Self::PreState => "prestate",
+            Self::Opcode => "opcode",
+        }
+    }
+}
+
+/// Hook for tracing the transactions of invalid blocks.
+///
+/// The block is re-executed on top of its parent with the configured [`InvalidBlockTracer`], and
+/// the traces of all transactions are written to the output directory. If a healthy node is
+/// configured, the same traces are fetched from it with `debug_traceBlockByNumber`, and the first
+/// diverging transaction, and for opcode traces the first diverging opcode, are written to a diff
+/// file.
+#[derive(Debug)]
+pub struct InvalidBlockTraceHook<P, E> {
+    /// The provider to read the historical state and do the EVM execution.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    evm_config: E,
+    /// The trace to capture.
+    tracer: InvalidBlockTracer,
+    /// The directory to write the traces and diffs to.
+    output_directory: PathBuf,
+    /// The healthy node client to compare the traces against.
+    healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+}
+
+impl<P, E> InvalidBlockTraceHook<P, E> {
+    /// Creates a new trace hook.
+    pub const fn new(
+        provider: P,
+        evm_config: E,
+        tracer: InvalidBlockTracer,
+        output_directory: PathBuf,
+        healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    ) -> Self {
+        Self { provider, evm_config, tracer, output_directory, healthy_node_client }
+    }
+}
+
+impl<P, E, N> InvalidBlockTraceHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+    N: NodePrimitives,
+{
+    /// Re-executes the block on top of its parent and traces every transaction.
+    fn trace_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) -> eyre::Result<Vec<TraceResult>> {
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(
+                self.provider.state_by_block_hash(parent_header.hash())?,
+            ))
+            .with_bundle_update()
+            .build();
+
+        let evm_env = self.evm_config.evm_env(block.header())?;
+        let inspector = DebugInspector::new(self.tracer.tracing_options())?;
+        let evm = self.evm_config.evm_with_env_and_inspector(&mut db, evm_env.clone(), inspector);
+        let ctx = self.evm_config.context_for_block(block.sealed_block())?;
+        let mut executor = self.evm_config.create_executor(evm, ctx);
+
+        executor.apply_pre_execution_changes()?;
+
+        let mut results = Vec::with_capacity(block.transaction_count());
+        for (index, tx) in block.transactions_recovered().enumerate() {
+            let tx_hash = *tx.tx_hash();
+            let tx_env = self.evm_config.tx_env(tx);
+
+            let res = executor.execute_transaction_without_commit(tx)?;
+            // The state changes of the transaction are not committed yet, so the database still
+            // contains the state the transaction was executed on.
+            let (db, inspector, _) = executor.evm_mut().components_mut();
+            let result = inspector.get_result(
+                Some(TransactionContext {
+                    block_hash: Some(block.hash()),
+                    tx_hash: Some(tx_hash),
+                    tx_index: Some(index),
+                }),
+                &tx_env,
+                &evm_env.block_env,
+                &res,
+                &mut **db,
+            )?;
+            inspector.fuse()?;
+            executor.commit_transaction(res, tx)?;
+
+            results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
+        }
+
+        Ok(results)
+    }
+
+    /// Writes the traces to the output directory and compares them with the healthy node.
+    fn handle_traces(
+        &self,
+        traces: &[TraceResult],
+        block_prefix: &str,
+        block_number: u64,
+    ) -> eyre::Result<()> {
+        let name = self.tracer.name();
+        let re_executed_path =
+            self.save_traces(&format!("{block_prefix}.{name}.re_executed"), traces)?;
+
+        let Some(healthy_node_client) = &self.healthy_node_client else { return Ok(()) };
+
+        let healthy_traces = futures::executor::block_on(async move {
+            DebugApiClient::<()>::debug_trace_block_by_number(
+                healthy_node_client,
+                block_number.into(),
+                Some(self.tracer.tracing_options()),
+            )
+            .await
+        })?;
+        let healthy_path =
+            self.save_traces(&format!("{block_prefix}.{name}.healthy"), &healthy_traces)?;
+
+        let Some(index) = first_diverging_transaction(traces, &healthy_traces) else {
+            return Ok(())
+        };
+        let (re_executed, healthy) = (traces.get(index), healthy_traces.get(index));
+        let tx_hash = re_executed.or(healthy).and_then(trace_tx_hash);
+
+        let diff_path = self.output_directory.join(format!("{block_prefix}.{name}.diff"));
+        let mut diff = BufWriter::new(File::create(&diff_path)?);
+        writeln!(diff, "First diverging transaction: {index} ({tx_hash:?})")?;
+
+        // Narrow opcode traces down to the first diverging opcode, as the full traces of a
+        // transaction are usually too large to diff
+        let mut diverging_opcode = None;
+        if let (Some(re_executed), Some(healthy)) =
+            (re_executed.and_then(default_frame), healthy.and_then(default_frame))
+        {
+            if let Some(step) = first_diverging_step(&re_executed.struct_logs, &healthy.struct_logs)
+            {
+                let (re_executed, healthy) =
+                    (re_executed.struct_logs.get(step), healthy.struct_logs.get(step));
+                diverging_opcode =
+                    Some((step, re_executed.or(healthy).map(|log| (log.pc, log.op.clone()))));
+                writeln!(
+                    diff,
+                    "First diverging opcode: {step}\n{}",
+                    Comparison::new(&re_executed, &healthy)
+                )?;
+            }
+        }
+        if diverging_opcode.is_none() {
+            writeln!(diff, "{}", Comparison::new(&re_executed, &healthy))?;
+        }
+        diff.flush()?;
+
+        warn!(
+            target: "engine::invalid_block_hooks::trace",
+            tracer = name,
+            tx_index = index,
+            ?tx_hash,
+            ?diverging_opcode,
+            diff_path = %diff_path.display(),
+            re_executed_path = %re_executed_path.display(),
+            healthy_path = %healthy_path.display(),
+            "Trace mismatch against healthy node"
+        );
+
+        Ok(())
+    }
+
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) -> eyre::Result<()> {
+        let traces = self.trace_block(parent_header, block)?;
+
+        let block_prefix = format!("{}_{}", block.number(), block.hash());
+        self.handle_traces(&traces, &block_prefix, block.number())
+    }
+
+    /// Saves the traces to the output directory and returns the path of the written file or
+    /// directory.
+    ///
+    /// Prestate traces are written to a single JSON file, opcode traces to a directory with an
+    /// EIP-3155 trace file per transaction.
+    fn save_traces(&self, name: &str, traces: &[TraceResult]) -> eyre::Result<PathBuf> {
+        match self.tracer {
+            InvalidBlockTracer::PreState => {
+                let path = self.output_directory.join(format!("{name}.json"));
+                File::create(&path)?.write_all(serde_json::to_string(traces)?.as_bytes())?;
+                Ok(path)
+            }
+            InvalidBlockTracer::Opcode => {
+                let path = self.output_directory.join(name);
+                std::fs::create_dir_all(&path)?;
+                for (index, trace) in traces.iter().enumerate() {
+                    let tx_hash = trace_tx_hash(trace).unwrap_or_default();
+                    let file = path.join(format!("{index}_{tx_hash}.jsonl"));
+                    match trace {
+                        TraceResult::Success { result: GethTrace::Default(frame), .. } => {
+                            write_eip3155(&file, frame)?
+                        }
+                        trace => {
+                            File::create(&file)?
+                                .write_all(serde_json::to_string(trace)?.as_bytes())?;
+                        }
+                    }
+                }
+                Ok(path)
+            }
+        }
+    }
+}
+
+impl<P, E, N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockTraceHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        if let Err(err) = self.on_invalid_block(parent_header, block) {
+            warn!(target: "engine::invalid_block_hooks::trace", tracer = self.tracer.name(), %err, "Failed to invoke hook");
+        }
+    }
+}
+
+/// Returns the index of the first transaction whose traces differ, including transactions that
+/// are missing on either side.
+fn first_diverging_transaction(a: &[TraceResult], b: &[TraceResult]) -> Option<usize> {
+    a.iter()
+        .zip(b)
+        .position(|(a, b)| a != b)
+        .or_else(|| (a.len() != b.len()).then(|| a.len().min(b.len())))
+}
+
+/// Returns the index of the first differing step of two opcode traces, including steps that are
+/// missing on either side.
+fn first_diverging_step(a: &[StructLog], b: &[StructLog]) -> Option<usize> {
+    a.iter()
+        .zip(b)
+        .position(|(a, b)| a != b)
+        .or_else(|| (a.len() != b.len()).then(|| a.len().min(b.len())))
+}
+
+fn trace_tx_hash(trace: &TraceResult) -> Option<B256> {
+    match trace {
+        TraceResult::Success { tx_hash, .. } | TraceResult::Error { tx_hash, .. } => *tx_hash,
+    }
+}
+
+const fn default_frame(trace: &TraceResult) -> Option<&DefaultFrame> {
+    match trace {
+        TraceResult::Success { result: GethTrace::Default(frame), .. } => Some(frame),
+        _ => None,
+    }
+}
+
+/// A single step of an [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) trace.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Eip3155Step<'a> {
+    pc: u64,
+    op: u8,
+    gas: U64,
+    gas_cost: U64,
+    mem_size: u64,
+    stack: &'a [U256],
+    depth: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    return_data: Option<&'a Bytes>,
+    refund: u64,
+    op_name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+/// The summary line closing an [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) trace.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Eip3155Summary<'a> {
+    output: &'a Bytes,
+    gas_used: U64,
+    pass: bool,
+}
+
+/// Writes the struct logs of an opcode trace as an EIP-3155 trace, one JSON object per line.
+fn write_eip3155(path: &Path, frame: &DefaultFrame) -> eyre::Result<()> {
+    let opcodes = (0..=u8::MAX)
+        .filter_map(|opcode| OpCode::new(opcode).map(|op| (op.as_str(), opcode)))
+        .collect::<HashMap<_, _>>();
+
+    let mut file = BufWriter::new(File::create(path)?);
+    for log in &frame.struct_logs {
+        let step = Eip3155Step {
+            pc: log.pc,
+            // Unknown opcodes are traced as `INVALID`
+            op: opcodes.get(&*log.op).copied().unwrap_or(0xfe),
+            gas: U64::from(log.gas),
+            gas_cost: U64::from(log.gas_cost),
+            mem_size: log.memory_size.unwrap_or_default(),
+            stack: log.stack.as_deref().unwrap_or_default(),
+            depth: log.depth,
+            return_data: log.return_data.as_ref(),
+            refund: log.refund_counter.unwrap_or_default(),
+            op_name: &log.op,
+            error: log.error.as_deref(),
+        };
+        serde_json::to_writer(&mut file, &step)?;
+        writeln!(file)?;
+    }
+
+    let summary = Eip3155Summary {
+        output: &frame.return_value,
+        gas_used: U64::from(frame.gas),
+        pass: !frame.failed,
+    };
+    serde_json::to_writer(&mut file, &summary)?;
+    writeln!(file)?;
+    file.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn struct_log(pc: u64, op: &str, gas: u64) -> StructLog {
+        serde_json::from_value(json!({
+            "pc": pc,
+            "op": op,
+            "gas": gas,
+            "gasCost": 3,
+            "depth": 1,
+            "stack": ["0x1"],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_first_diverging_step() {
+        let logs = vec![struct_log(0, "PUSH1", 100), struct_log(2, "PUSH1", 97)];
+        assert_eq!(first_diverging_step(&logs, &logs), None);
+
+        let mut diverging = logs.clone();
+        diverging[1].gas = 98;
+        assert_eq!(first_diverging_step(&logs, &diverging), Some(1));
+
+        assert_eq!(first_diverging_step(&logs, &logs[..1]), Some(1));
+        assert_eq!(first_diverging_step(&[], &logs), Some(0));
+    }
+
+    #[test]
+    fn test_first_diverging_transaction() {
+        let trace = |tx_hash: B256, gas: u64| TraceResult::Success {
+            result: GethTrace::Default(DefaultFrame { gas, ..Default::default() }),
+            tx_hash: Some(tx_hash),
+        };
+        let traces =
+            vec![trace(B256::with_last_byte(1), 21000), trace(B256::with_last_byte(2), 21000)];
+        assert_eq!(first_diverging_transaction(&traces, &traces), None);
+
+        let mut diverging = traces.clone();
+        diverging[1] = trace(B256::with_last_byte(2), 42000);
+        assert_eq!(first_diverging_transaction(&traces, &diverging), Some(1));
+
+        assert_eq!(first_diverging_transaction(&traces[..1], &traces), Some(1));
+    }
+
+    #[test]
+    fn test_write_eip3155() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("trace.jsonl");
+
+        let frame = DefaultFrame {
+            failed: false,
+            gas: 21006,
+            return_value: Bytes::new(),
+            struct_logs: vec![struct_log(0, "PUSH1", 100), struct_log(2, "STOP", 97)],
+        };
+        write_eip3155(&path, &frame).unwrap();
+
+        let lines = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![
+                json!({
+                    "pc": 0,
+                    "op": 0x60,
+                    "gas": "0x64",
+                    "gasCost": "0x3",
+                    "memSize": 0,
+                    "stack": ["0x1"],
+                    "depth": 1,
+                    "refund": 0,
+                    "opName": "PUSH1",
+                }),
+                json!({
+                    "pc": 2,
+                    "op": 0x00,
+                    "gas": "0x61",
+                    "gasCost": "0x3",
+                    "memSize": 0,
+                    "stack": ["0x1"],
+                    "depth": 1,
+                    "refund": 0,
+                    "opName": "STOP",
+                }),
+                json!({ "output": "0x", "gasUsed": "0x520e", "pass": true }),
+            ]
+        );
+    }
+}
@@ -137,6 +137,10 @@ pub struct TreeConfig {
     account_worker_count: usize,
     /// Whether to enable V2 storage proofs.
     enable_proof_v2: bool,
+    /// Whether to save the execution cache on shutdown and restore it on startup.
+    persist_execution_cache: bool,
+    /// Whether to record and store the block access list of every executed block.
+    record_block_access_lists: bool,
 }
 
 impl Default for TreeConfig {
@@ -166,6 +170,8 @@ impl Default for TreeConfig {
             storage_worker_count: default_storage_worker_count(),
             account_worker_count: default_account_worker_count(),
             enable_proof_v2: false,
+            persist_execution_cache: false,
+            record_block_access_lists: false,
         }
     }
 }
@@ -198,6 +204,8 @@ impl TreeConfig {
         storage_worker_count: usize,
         account_worker_count: usize,
         enable_proof_v2: bool,
+        persist_execution_cache: bool,
+        record_block_access_lists: bool,
     ) -> Self {
         Self {
             persistence_threshold,
@@ -224,6 +232,8 @@ impl TreeConfig {
             storage_worker_count,
             account_worker_count,
             enable_proof_v2,
+            persist_execution_cache,
+            record_block_access_lists,
         }
     }
 
@@ -516,4 +526,29 @@ impl TreeConfig {
         self.enable_proof_v2 = enable_proof_v2;
         self
     }
+
+    /// Return whether the execution cache is saved on shutdown and restored on startup.
+    pub const fn persist_execution_cache(&self) -> bool {
+        self.persist_execution_cache
+    }
+
+    /// Setter for whether to save the execution cache on shutdown and restore it on startup.
+    pub const fn with_persist_execution_cache(mut self, persist_execution_cache: bool) -> Self {
+        self.persist_execution_cache = persist_execution_cache;
+        self
+    }
+
+    /// Return whether the block access list of every executed block is recorded and stored.
+    ///
+    /// Block access lists provided by payloads are always compared against the recorded ones,
+    /// regardless of this setting. A mismatch is logged, but does not invalidate the block.
+    pub const fn record_block_access_lists(&self) -> bool {
+        self.record_block_access_lists
+    }
+
+    /// Setter for whether to record and store the block access list of every executed block.
+    pub const fn with_record_block_access_lists(mut self, record_block_access_lists: bool) -> Self {
+        self.record_block_access_lists = record_block_access_lists;
+        self
+    }
 }
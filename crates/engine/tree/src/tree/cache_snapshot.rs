@@ -0,0 +1,260 @@
+//! On-disk snapshot of the execution cache.
+//!
+//! The snapshot is written on graceful shutdown and restored on startup, so that the first blocks
+//! after a restart don't have to be executed with a cold cache. A snapshot is only valid for the
+//! state it was taken at, which is why it records the block and state root of that state and is
+//! discarded if they don't match the persisted head on startup.
+//!
+//! Only the cached accounts and storage slots are persisted. The hot upper levels of the sparse
+//! trie are not: the payload processor clears the sparse trie after every block and only reuses
+//! its allocations, so no trie nodes are retained across blocks that could be saved.
+
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{Address, B256, U256};
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use reth_fs_util::FsPathError;
+use reth_primitives_traits::Account;
+use std::{io::Write, path::Path};
+use tracing::{info, warn};
+
+/// Version of the snapshot encoding, bumped on incompatible changes.
+const SNAPSHOT_VERSION: u64 = 1;
+
+/// Errors that can occur when reading an [`ExecutionCacheSnapshot`].
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum CacheSnapshotError {
+    /// Failed to read or write the snapshot file.
+    #[error(transparent)]
+    Fs(#[from] FsPathError),
+    /// The snapshot file could not be decoded.
+    #[error("failed to decode execution cache snapshot: {0}")]
+    Decode(#[from] alloy_rlp::Error),
+    /// The snapshot was written with an incompatible encoding.
+    #[error("unsupported execution cache snapshot version {0}, expected {SNAPSHOT_VERSION}")]
+    Version(u64),
+}
+
+/// Accounts and storage slots of the execution cache at a specific block.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub(crate) struct ExecutionCacheSnapshot {
+    /// Version of the encoding.
+    pub(crate) version: u64,
+    /// Number of the block the cache was saved at.
+    pub(crate) block_number: u64,
+    /// Hash of the block the cache was saved at.
+    pub(crate) block_hash: B256,
+    /// State root of the block the cache was saved at.
+    pub(crate) state_root: B256,
+    /// Cached existing accounts.
+    pub(crate) accounts: Vec<SnapshotAccount>,
+    /// Cached accounts that don't exist.
+    pub(crate) missing_accounts: Vec<Address>,
+    /// Cached storage slots by account.
+    pub(crate) storages: Vec<SnapshotStorage>,
+}
+
+impl ExecutionCacheSnapshot {
+    /// Creates an empty snapshot for the given block.
+    pub(crate) const fn new(block: BlockNumHash, state_root: B256) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            block_number: block.number,
+            block_hash: block.hash,
+            state_root,
+            accounts: Vec::new(),
+            missing_accounts: Vec::new(),
+            storages: Vec::new(),
+        }
+    }
+
+    /// Returns the block the cache was saved at.
+    pub(crate) const fn block(&self) -> BlockNumHash {
+        BlockNumHash::new(self.block_number, self.block_hash)
+    }
+
+    /// Reads the snapshot from the given file.
+    pub(crate) fn read(path: &Path) -> Result<Self, CacheSnapshotError> {
+        let data = reth_fs_util::read(path)?;
+        let snapshot = Self::decode(&mut data.as_slice())?;
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(CacheSnapshotError::Version(snapshot.version))
+        }
+        Ok(snapshot)
+    }
+
+    /// Reads the snapshot from the given file if it was taken at the given head block and state
+    /// root, and removes the file.
+    ///
+    /// The snapshot is discarded if it can't be read or was taken at a different state.
+    pub(crate) fn take(path: &Path, head: Option<(BlockNumHash, B256)>) -> Option<Self> {
+        let snapshot = match Self::read(path) {
+            Ok(snapshot)
+                if head.is_some_and(|(block, state_root)| {
+                    block.hash == snapshot.block_hash && state_root == snapshot.state_root
+                }) =>
+            {
+                Some(snapshot)
+            }
+            Ok(snapshot) => {
+                info!(
+                    target: "engine::caching",
+                    snapshot = ?snapshot.block(),
+                    head = ?head.map(|(block, _)| block),
+                    "Discarding execution cache snapshot taken at a different block"
+                );
+                None
+            }
+            Err(err) => {
+                warn!(target: "engine::caching", %err, ?path, "Failed to read execution cache snapshot");
+                None
+            }
+        };
+
+        // The snapshot is outdated as soon as the next block is executed
+        if let Err(err) = reth_fs_util::remove_file(path) {
+            warn!(target: "engine::caching", %err, "Failed to remove execution cache snapshot");
+        }
+
+        snapshot
+    }
+
+    /// Writes the snapshot to the given file, creating the parent directory if necessary.
+    pub(crate) fn write(&self, path: &Path) -> Result<(), CacheSnapshotError> {
+        if let Some(parent) = path.parent() {
+            reth_fs_util::create_dir_all(parent)?;
+        }
+        let mut buf = Vec::with_capacity(self.length());
+        self.encode(&mut buf);
+        reth_fs_util::atomic_write_file(path, |file| file.write_all(&buf))?;
+        Ok(())
+    }
+}
+
+/// A cached account of an [`ExecutionCacheSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub(crate) struct SnapshotAccount {
+    /// Address of the account.
+    pub(crate) address: Address,
+    /// Account nonce.
+    pub(crate) nonce: u64,
+    /// Account balance.
+    pub(crate) balance: U256,
+    /// Hash of the account's bytecode, [`KECCAK_EMPTY`] if the account has no code.
+    pub(crate) code_hash: B256,
+}
+
+impl SnapshotAccount {
+    /// Creates a snapshot entry for the given account.
+    pub(crate) fn new(address: Address, account: &Account) -> Self {
+        Self {
+            address,
+            nonce: account.nonce,
+            balance: account.balance,
+            code_hash: account.get_bytecode_hash(),
+        }
+    }
+
+    /// Returns the cached [`Account`].
+    pub(crate) fn account(&self) -> Account {
+        Account {
+            nonce: self.nonce,
+            balance: self.balance,
+            bytecode_hash: (self.code_hash != KECCAK_EMPTY).then_some(self.code_hash),
+        }
+    }
+}
+
+/// The cached storage slots of an account of an [`ExecutionCacheSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub(crate) struct SnapshotStorage {
+    /// Address of the account.
+    pub(crate) address: Address,
+    /// Cached slots, empty slots have a zero value.
+    pub(crate) slots: Vec<SnapshotSlot>,
+}
+
+/// A cached storage slot of an [`ExecutionCacheSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub(crate) struct SnapshotSlot {
+    /// Storage key.
+    pub(crate) key: B256,
+    /// Storage value.
+    pub(crate) value: U256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(block: BlockNumHash, state_root: B256) -> ExecutionCacheSnapshot {
+        let mut snapshot = ExecutionCacheSnapshot::new(block, state_root);
+        snapshot.accounts.push(SnapshotAccount::new(
+            Address::random(),
+            &Account { nonce: 1, balance: U256::from(2), bytecode_hash: None },
+        ));
+        snapshot.missing_accounts.push(Address::random());
+        snapshot.storages.push(SnapshotStorage {
+            address: Address::random(),
+            slots: vec![SnapshotSlot { key: B256::random(), value: U256::from(3) }],
+        });
+        snapshot
+    }
+
+    #[test]
+    fn take_snapshot_at_head() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("engine").join("execution-cache.rlp");
+        let block = BlockNumHash::new(1, B256::random());
+        let state_root = B256::random();
+
+        let snapshot = snapshot(block, state_root);
+        snapshot.write(&path).unwrap();
+        assert_eq!(ExecutionCacheSnapshot::take(&path, Some((block, state_root))), Some(snapshot));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn discard_snapshot_at_different_head() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("execution-cache.rlp");
+        let block = BlockNumHash::new(1, B256::random());
+        let state_root = B256::random();
+
+        for head in [
+            None,
+            Some((BlockNumHash::new(1, B256::random()), state_root)),
+            Some((block, B256::random())),
+        ] {
+            snapshot(block, state_root).write(&path).unwrap();
+            assert_eq!(ExecutionCacheSnapshot::take(&path, head), None);
+            assert!(!path.exists());
+        }
+    }
+
+    #[test]
+    fn discard_corrupt_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("execution-cache.rlp");
+        let block = BlockNumHash::new(1, B256::random());
+        let state_root = B256::random();
+
+        let mut encoded = alloy_rlp::encode(snapshot(block, state_root));
+        encoded.truncate(encoded.len() / 2);
+        reth_fs_util::write(&path, &encoded).unwrap();
+        assert!(matches!(ExecutionCacheSnapshot::read(&path), Err(CacheSnapshotError::Decode(_))));
+        assert_eq!(ExecutionCacheSnapshot::take(&path, Some((block, state_root))), None);
+        assert!(!path.exists());
+
+        // Snapshots of an incompatible version are discarded as well
+        let snapshot =
+            ExecutionCacheSnapshot { version: SNAPSHOT_VERSION + 1, ..snapshot(block, state_root) };
+        snapshot.write(&path).unwrap();
+        assert!(matches!(
+            ExecutionCacheSnapshot::read(&path),
+            Err(CacheSnapshotError::Version(version)) if version == SNAPSHOT_VERSION + 1
+        ));
+        assert_eq!(ExecutionCacheSnapshot::take(&path, Some((block, state_root))), None);
+        assert!(!path.exists());
+    }
+}
@@ -1,4 +1,8 @@
 //! Execution cache implementation for block processing.
+use crate::tree::cache_snapshot::{
+    ExecutionCacheSnapshot, SnapshotAccount, SnapshotSlot, SnapshotStorage,
+};
+use alloy_eips::BlockNumHash;
 use alloy_primitives::{Address, StorageKey, StorageValue, B256};
 use metrics::Gauge;
 use mini_moka::sync::CacheBuilder;
@@ -423,6 +427,59 @@ impl ExecutionCache {
         self.storage_cache.iter().map(|addr| addr.len()).sum()
     }
 
+    /// Returns a snapshot of the cached accounts and storage slots, which reflect the state of the
+    /// given block.
+    ///
+    /// Bytecodes are not part of the snapshot.
+    pub(crate) fn to_snapshot(
+        &self,
+        block: BlockNumHash,
+        state_root: B256,
+    ) -> ExecutionCacheSnapshot {
+        let mut snapshot = ExecutionCacheSnapshot::new(block, state_root);
+
+        for entry in self.account_cache.iter() {
+            match entry.value() {
+                Some(account) => {
+                    snapshot.accounts.push(SnapshotAccount::new(*entry.key(), account))
+                }
+                None => snapshot.missing_accounts.push(*entry.key()),
+            }
+        }
+
+        for entry in self.storage_cache.iter() {
+            let slots = entry
+                .value()
+                .slots
+                .iter()
+                .map(|slot| SnapshotSlot {
+                    key: *slot.key(),
+                    value: slot.value().unwrap_or_default(),
+                })
+                .collect();
+            snapshot.storages.push(SnapshotStorage { address: *entry.key(), slots });
+        }
+
+        snapshot
+    }
+
+    /// Inserts the accounts and storage slots of the snapshot into the cache.
+    pub(crate) fn insert_snapshot(&self, snapshot: ExecutionCacheSnapshot) {
+        for account in snapshot.accounts {
+            self.account_cache.insert(account.address, Some(account.account()));
+        }
+        for address in snapshot.missing_accounts {
+            self.account_cache.insert(address, None);
+        }
+        for storage in snapshot.storages {
+            let storage_entries = storage
+                .slots
+                .into_iter()
+                .map(|slot| (slot.key, (!slot.value.is_zero()).then_some(slot.value)));
+            self.insert_storage_bulk(storage.address, storage_entries);
+        }
+    }
+
     /// Inserts the post-execution state changes into the cache.
     ///
     /// This method is called after transaction execution to update the cache with
@@ -713,6 +770,7 @@ impl Default for AccountStorageCache {
 mod tests {
     use super::*;
     use alloy_primitives::{B256, U256};
+    use alloy_rlp::Decodable;
     use rand::Rng;
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
     use std::mem::size_of;
@@ -901,6 +959,37 @@ mod tests {
         assert_eq!(slot_status, SlotStatus::Empty);
     }
 
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let address = Address::random();
+        let missing_address = Address::random();
+        let account =
+            Account { nonce: 1, balance: U256::from(2), bytecode_hash: Some(B256::random()) };
+        let storage_key = StorageKey::random();
+        let empty_storage_key = StorageKey::random();
+
+        let caches = ExecutionCacheBuilder::default().build_caches(1_000_000);
+        caches.account_cache.insert(address, Some(account));
+        caches.account_cache.insert(missing_address, None);
+        caches.insert_storage(address, storage_key, Some(U256::from(3)));
+        caches.insert_storage(address, empty_storage_key, None);
+
+        let snapshot = caches.to_snapshot(BlockNumHash::new(1, B256::random()), B256::random());
+        let decoded =
+            ExecutionCacheSnapshot::decode(&mut alloy_rlp::encode(&snapshot).as_slice()).unwrap();
+        assert_eq!(decoded, snapshot);
+
+        // restore into empty caches
+        let restored = ExecutionCacheBuilder::default().build_caches(1_000_000);
+        restored.insert_snapshot(decoded);
+        assert_eq!(restored.account_cache.get(&address), Some(Some(account)));
+        assert_eq!(restored.account_cache.get(&missing_address), Some(None));
+        let (slot_status, _) = restored.get_storage(&address, &storage_key);
+        assert_eq!(slot_status, SlotStatus::Value(U256::from(3)));
+        let (slot_status, _) = restored.get_storage(&address, &empty_storage_key);
+        assert_eq!(slot_status, SlotStatus::Empty);
+    }
+
     // Tests for SavedCache locking mechanism
     #[test]
     fn test_saved_cache_is_available() {
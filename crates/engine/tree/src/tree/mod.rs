@@ -47,6 +47,7 @@ use tokio::sync::{
 use tracing::*;
 
 mod block_buffer;
+mod cache_snapshot;
 mod cached_state;
 pub mod error;
 pub mod instrumented_state;
@@ -1295,6 +1296,9 @@ where
     ) -> Result<(), AdvancePersistenceError> {
         trace!(target: "engine::tree", "finishing termination, persisting remaining blocks");
         let result = self.persist_until_complete();
+        if result.is_ok() {
+            self.payload_validator.on_shutdown();
+        }
         let _ = pending_termination.send(());
         result
     }
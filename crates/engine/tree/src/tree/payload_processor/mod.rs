@@ -2,6 +2,7 @@
 
 use super::precompile_cache::PrecompileCacheMap;
 use crate::tree::{
+    cache_snapshot::ExecutionCacheSnapshot,
     cached_state::{
         CachedStateMetrics, CachedStateProvider, ExecutionCache as StateExecutionCache,
         ExecutionCacheBuilder, SavedCache,
@@ -14,7 +15,7 @@ use crate::tree::{
     StateProviderBuilder, TreeConfig,
 };
 use alloy_eip7928::BlockAccessList;
-use alloy_eips::eip1898::BlockWithParent;
+use alloy_eips::{eip1898::BlockWithParent, BlockNumHash};
 use alloy_evm::{block::StateChangeSource, ToTxEnv};
 use alloy_primitives::B256;
 use crossbeam_channel::Sender as CrossbeamSender;
@@ -590,6 +591,30 @@ where
             debug!(target: "engine::caching", ?block_with_parent, "Updated execution cache for inserted block");
         });
     }
+
+    /// Returns a snapshot of the execution cache, if the cache was last updated for the given
+    /// block.
+    pub(crate) fn execution_cache_snapshot(
+        &self,
+        block: BlockNumHash,
+        state_root: B256,
+    ) -> Option<ExecutionCacheSnapshot> {
+        let cache = self.execution_cache.get_cache_for(block.hash)?;
+        Some(cache.cache().to_snapshot(block, state_root))
+    }
+
+    /// Replaces the execution cache with the accounts and storage slots of the snapshot.
+    ///
+    /// The snapshot must reflect the state of the block it was taken at.
+    pub(crate) fn restore_execution_cache(&self, snapshot: ExecutionCacheSnapshot) {
+        let caches = ExecutionCacheBuilder::default().build_caches(self.cross_block_cache_size);
+        let block_hash = snapshot.block_hash;
+        caches.insert_snapshot(snapshot);
+
+        let cache = SavedCache::new(block_hash, caches, CachedStateMetrics::zeroed());
+        cache.update_metrics();
+        self.execution_cache.update_with_guard(|cached| *cached = Some(cache));
+    }
 }
 
 /// Handle to all the spawned tasks.
@@ -1,6 +1,7 @@
 //! Types and traits for validating blocks and payloads.
 
 use crate::tree::{
+    cache_snapshot::ExecutionCacheSnapshot,
     cached_state::CachedStateProvider,
     error::{InsertBlockError, InsertBlockErrorKind, InsertPayloadError},
     instrumented_state::InstrumentedStateProvider,
@@ -13,8 +14,9 @@ use crate::tree::{
 use alloy_consensus::transaction::Either;
 use alloy_eip7928::BlockAccessList;
 use alloy_eips::{eip1898::BlockWithParent, NumHash};
-use alloy_evm::Evm;
+use alloy_evm::{block::StateChangeSource, Evm};
 use alloy_primitives::B256;
+use parking_lot::Mutex;
 use rayon::prelude::*;
 use reth_chain_state::{CanonicalInMemoryState, DeferredTrieData, ExecutedBlock};
 use reth_consensus::{ConsensusError, FullConsensus};
@@ -23,8 +25,8 @@ use reth_engine_primitives::{
 };
 use reth_errors::{BlockExecutionError, ProviderResult};
 use reth_evm::{
-    block::BlockExecutor, execute::ExecutableTxFor, ConfigureEvm, EvmEnvFor, ExecutionCtxFor,
-    SpecFor,
+    block::BlockExecutor, block_access_list::BlockAccessListBuilder, execute::ExecutableTxFor,
+    ConfigureEvm, EvmEnvFor, ExecutionCtxFor, OnStateHook, SpecFor,
 };
 use reth_payload_primitives::{
     BuiltPayload, InvalidPayloadAttributesError, NewPayloadError, PayloadTypes,
@@ -39,13 +41,14 @@ use reth_provider::{
     HashedPostStateProvider, ProviderError, PruneCheckpointReader, StageCheckpointReader,
     StateProvider, StateProviderFactory, StateReader, TrieReader,
 };
-use reth_revm::db::State;
+use reth_revm::{db::State, state::EvmState};
 use reth_trie::{updates::TrieUpdates, HashedPostState, StateRoot, TrieInputSorted};
 use reth_trie_parallel::root::{ParallelStateRoot, ParallelStateRootError};
 use revm_primitives::Address;
 use std::{
     collections::HashMap,
     panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
     sync::Arc,
     time::Instant,
 };
@@ -129,6 +132,8 @@ where
     metrics: EngineApiMetrics,
     /// Validator for the payload.
     validator: V,
+    /// Path of the execution cache snapshot, if the cache is persisted across restarts.
+    execution_cache_snapshot: Option<PathBuf>,
 }
 
 impl<N, P, Evm, V> BasicEngineValidator<P, Evm, V>
@@ -179,6 +184,86 @@ where
             invalid_block_hook,
             metrics: EngineApiMetrics::default(),
             validator,
+            execution_cache_snapshot: None,
+        }
+    }
+
+    /// Persists the execution cache across restarts in the snapshot file at the given path.
+    ///
+    /// The cache is restored from an existing snapshot if it was taken at the current head block,
+    /// and saved again on shutdown, see [`EngineValidator::on_shutdown`].
+    pub fn with_execution_cache_snapshot(mut self, path: PathBuf) -> Self {
+        self.restore_execution_cache(&path);
+        self.execution_cache_snapshot = Some(path);
+        self
+    }
+
+    /// Restores the execution cache from the snapshot at the given path and removes the snapshot.
+    fn restore_execution_cache(&self, path: &Path) {
+        if !path.exists() {
+            return
+        }
+
+        // The snapshot is only valid for the state it was taken at
+        let head = match self
+            .provider
+            .best_block_number()
+            .and_then(|number| self.provider.sealed_header(number))
+        {
+            Ok(head) => head.map(|head| (head.num_hash(), head.state_root())),
+            Err(err) => {
+                warn!(target: "engine::caching", %err, "Failed to read head block, discarding execution cache snapshot");
+                None
+            }
+        };
+
+        if let Some(snapshot) = ExecutionCacheSnapshot::take(path, head) {
+            info!(
+                target: "engine::caching",
+                block = ?snapshot.block(),
+                accounts = snapshot.accounts.len() + snapshot.missing_accounts.len(),
+                storages = snapshot.storages.len(),
+                "Restored execution cache from snapshot"
+            );
+            self.payload_processor.restore_execution_cache(snapshot);
+        }
+    }
+
+    /// Saves a snapshot of the execution cache to the given path, if the cache was last updated
+    /// for the current head block.
+    fn save_execution_cache(&self, path: &Path) {
+        let head = match self
+            .provider
+            .best_block_number()
+            .and_then(|number| self.provider.sealed_header(number))
+        {
+            Ok(Some(head)) => head,
+            Ok(None) => return,
+            Err(err) => {
+                warn!(target: "engine::caching", %err, "Failed to read head block, skipping execution cache snapshot");
+                return
+            }
+        };
+
+        let Some(snapshot) =
+            self.payload_processor.execution_cache_snapshot(head.num_hash(), head.state_root())
+        else {
+            debug!(target: "engine::caching", head = ?head.num_hash(), "No execution cache for head block, skipping snapshot");
+            return
+        };
+
+        match snapshot.write(path) {
+            Ok(()) => info!(
+                target: "engine::caching",
+                block = ?snapshot.block(),
+                accounts = snapshot.accounts.len() + snapshot.missing_accounts.len(),
+                storages = snapshot.storages.len(),
+                ?path,
+                "Saved execution cache snapshot"
+            ),
+            Err(err) => {
+                warn!(target: "engine::caching", %err, ?path, "Failed to save execution cache snapshot");
+            }
         }
     }
 
@@ -410,15 +495,22 @@ where
         // Get an iterator over the transactions in the payload
         let txs = self.tx_iterator_for(&input)?;
 
-        // Extract the BAL, if valid and available
-        let block_access_list = ensure_ok!(input
-            .block_access_list()
-            .transpose()
-            // Eventually gets converted to a `InsertBlockErrorKind::Other`
-            .map_err(Box::<dyn std::error::Error + Send + Sync>::from))
-        .map(Arc::new);
-
-        // Spawn the appropriate processor based on strategy
+        // Extract the BAL, if available. It is only compared against the recorded one, so an
+        // undecodable BAL does not invalidate the block.
+        let expected_block_access_list = input.block_access_list().and_then(|bal| {
+            bal.inspect_err(|err| {
+                warn!(target: "engine::tree::payload_validator", %err, "Failed to decode block access list");
+            })
+            .ok()
+        });
+
+        // Record the BAL of the block to compare the provided one, or to store it
+        let block_access_list_builder =
+            (expected_block_access_list.is_some() || self.config.record_block_access_lists())
+                .then(|| Arc::new(Mutex::new(BlockAccessListBuilder::default())));
+
+        // Spawn the appropriate processor based on strategy. The provided BAL is not passed
+        // to the processor, because it is not validated until the block has been executed.
         let mut handle = ensure_ok!(self.spawn_payload_processor(
             env.clone(),
             txs,
@@ -426,7 +518,7 @@ where
             parent_hash,
             ctx.state(),
             strategy,
-            block_access_list,
+            None,
         ));
 
         // Use cached state provider before executing, used in execution after prewarming threads
@@ -441,7 +533,13 @@ where
         }
 
         // Execute the block and handle any execution errors
-        let (output, senders) = match self.execute_block(state_provider, env, &input, &mut handle) {
+        let (output, senders) = match self.execute_block(
+            state_provider,
+            env,
+            &input,
+            &mut handle,
+            block_access_list_builder.clone(),
+        ) {
             Ok(output) => output,
             Err(err) => return self.handle_execution_error(input, err, &parent_block),
         };
@@ -456,6 +554,20 @@ where
             block
         );
 
+        let block_access_list = block_access_list_builder
+            .map(|builder| core::mem::take(&mut *builder.lock()).finish(&output.state));
+        if let Some((expected, recorded)) =
+            expected_block_access_list.as_ref().zip(block_access_list.as_ref()) &&
+            expected != recorded
+        {
+            warn!(
+                target: "engine::tree::payload_validator",
+                block = ?block.num_hash(),
+                address = %block_access_list_mismatch(expected, recorded),
+                "Provided block access list does not match the recorded one"
+            );
+        }
+
         let root_time = Instant::now();
         let mut maybe_state_root = None;
 
@@ -562,7 +674,10 @@ where
         // Terminate prewarming task with the shared execution outcome
         handle.terminate_caching(Some(Arc::clone(&execution_outcome)));
 
-        Ok(self.spawn_deferred_trie_task(block, execution_outcome, &ctx, hashed_state, trie_output))
+        let block_access_list = block_access_list.map(|bal| alloy_rlp::encode(bal).into());
+        Ok(self
+            .spawn_deferred_trie_task(block, execution_outcome, &ctx, hashed_state, trie_output)
+            .with_block_access_list(block_access_list))
     }
 
     /// Return sealed block header from database or in-memory state by hash.
@@ -606,6 +721,7 @@ where
         env: ExecutionEnv<Evm>,
         input: &BlockOrPayload<T>,
         handle: &mut PayloadHandle<impl ExecutableTxFor<Evm>, Err, N::Receipt>,
+        block_access_list: Option<Arc<Mutex<BlockAccessListBuilder>>>,
     ) -> Result<(BlockExecutionOutput<N::Receipt>, Vec<Address>), InsertBlockErrorKind>
     where
         S: StateProvider + Send,
@@ -645,7 +761,14 @@ where
         }
 
         let execution_start = Instant::now();
-        let state_hook = Box::new(handle.state_hook());
+        let mut state_hook = handle.state_hook();
+        let state_hook: Box<dyn OnStateHook> = match block_access_list {
+            Some(builder) => Box::new(move |source: StateChangeSource, state: &EvmState| {
+                builder.lock().on_state(source, state);
+                state_hook.on_state(source, state);
+            }),
+            None => Box::new(state_hook),
+        };
         let (output, senders) = self.metrics.execute_metered(
             executor,
             handle.iter_transactions().map(|res| res.map_err(BlockExecutionError::other)),
@@ -1116,6 +1239,20 @@ where
     }
 }
 
+/// Returns the address of the first account at which the given block access lists differ.
+fn block_access_list_mismatch(expected: &BlockAccessList, recorded: &BlockAccessList) -> Address {
+    let len = expected.len().min(recorded.len());
+    let index = (0..len).find(|&i| expected[i] != recorded[i]).unwrap_or(len);
+    // The lists differ, so at least one of them has an account at the index
+    expected
+        .get(index)
+        .into_iter()
+        .chain(recorded.get(index))
+        .map(|account| account.address)
+        .min()
+        .unwrap_or_default()
+}
+
 /// Output of block or payload validation.
 pub type ValidationOutcome<N, E = InsertPayloadError<BlockTy<N>>> = Result<ExecutedBlock<N>, E>;
 
@@ -1185,6 +1322,11 @@ pub trait EngineValidator<
     /// This is invoked when blocks are inserted via `InsertExecutedBlock` (e.g., locally built
     /// blocks by sequencers) to allow implementations to update internal state such as caches.
     fn on_inserted_executed_block(&self, block: ExecutedBlock<N>);
+
+    /// Hook called on graceful shutdown, after all canonical blocks have been persisted.
+    ///
+    /// This allows implementations to save internal state, such as caches, across restarts.
+    fn on_shutdown(&self) {}
 }
 
 impl<N, Types, P, Evm, V> EngineValidator<Types> for BasicEngineValidator<P, Evm, V>
@@ -1247,6 +1389,12 @@ where
             block.execution_output.state(),
         );
     }
+
+    fn on_shutdown(&self) {
+        if let Some(path) = &self.execution_cache_snapshot {
+            self.save_execution_cache(path);
+        }
+    }
 }
 
 /// Enum representing either block or payload being validated.
@@ -1300,9 +1448,17 @@ impl<T: PayloadTypes> BlockOrPayload<T> {
     }
 
     /// Returns the block access list if available.
-    pub const fn block_access_list(&self) -> Option<Result<BlockAccessList, alloy_rlp::Error>> {
-        // TODO decode and return `BlockAccessList`
-        None
+    pub fn block_access_list(&self) -> Option<Result<BlockAccessList, alloy_rlp::Error>>
+    where
+        T::ExecutionData: ExecutionPayload,
+    {
+        match self {
+            Self::Payload(payload) => payload
+                .block_access_list()
+                .map(|bal| alloy_rlp::Decodable::decode(&mut bal.as_ref())),
+            // Block access lists are not part of the block body
+            Self::Block(_) => None,
+        }
     }
 
     /// Returns the number of transactions in the payload or block.
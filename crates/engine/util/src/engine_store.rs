@@ -1,18 +1,24 @@
 //! Stores engine API messages to disk for later inspection and replay.
 
-use alloy_rpc_types_engine::ForkchoiceState;
-use futures::{Stream, StreamExt};
-use reth_engine_primitives::{BeaconEngineMessage, ExecutionPayload};
+use alloy_rpc_types_engine::{ForkchoiceState, PayloadStatus, PayloadStatusEnum};
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use reth_engine_primitives::{
+    BeaconEngineMessage, BeaconOnNewPayloadError, ExecutionPayload, ForkchoiceStatus,
+    OnForkChoiceUpdated,
+};
+use reth_errors::RethResult;
 use reth_fs_util as fs;
-use reth_payload_primitives::PayloadTypes;
+use reth_payload_primitives::{EngineApiMessageVersion, PayloadTypes};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
-    path::PathBuf,
+    future::Future,
+    path::{Path, PathBuf},
     pin::Pin,
     task::{ready, Context, Poll},
     time::SystemTime,
 };
+use tokio::sync::oneshot;
 use tracing::*;
 
 /// A message from the engine API that has been stored to disk.
@@ -25,20 +31,96 @@ pub enum StoredEngineApiMessage<T: PayloadTypes> {
         state: ForkchoiceState,
         /// The payload attributes sent in the persisted call, if any.
         payload_attrs: Option<T::PayloadAttributes>,
+        /// The version of the persisted call.
+        ///
+        /// Defaults to the latest version for messages stored without it.
+        #[serde(default)]
+        version: EngineApiMessageVersion,
+        /// The status of the engine's response, if it responded.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        status: Option<StoredEngineApiStatus>,
     },
     /// The on-disk representation of an `engine_newPayload` method call.
     NewPayload {
         /// The [`PayloadTypes::ExecutionData`] sent in the persisted call.
         #[serde(flatten)]
         payload: T::ExecutionData,
+        /// The status of the engine's response, if it responded.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        status: Option<StoredEngineApiStatus>,
     },
 }
 
+impl<T: PayloadTypes> StoredEngineApiMessage<T> {
+    /// Returns the status of the engine's response, if it responded.
+    pub const fn status(&self) -> Option<StoredEngineApiStatus> {
+        match self {
+            Self::ForkchoiceUpdated { status, .. } | Self::NewPayload { status, .. } => *status,
+        }
+    }
+
+    /// Sets the status of the engine's response.
+    pub const fn set_status(&mut self, new_status: StoredEngineApiStatus) {
+        match self {
+            Self::ForkchoiceUpdated { status, .. } | Self::NewPayload { status, .. } => {
+                *status = Some(new_status)
+            }
+        }
+    }
+}
+
+/// The status of the engine's response to a stored engine API message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StoredEngineApiStatus {
+    /// The payload or forkchoice state is valid.
+    Valid,
+    /// The payload or forkchoice state is invalid.
+    Invalid,
+    /// The engine is syncing.
+    Syncing,
+    /// The payload was accepted without being validated.
+    Accepted,
+    /// The engine failed to process the message.
+    Error,
+}
+
+impl StoredEngineApiStatus {
+    /// Returns the status of an `engine_newPayload` response.
+    pub const fn from_new_payload(
+        response: &Result<PayloadStatus, BeaconOnNewPayloadError>,
+    ) -> Self {
+        match response {
+            Ok(status) => match status.status {
+                PayloadStatusEnum::Valid => Self::Valid,
+                PayloadStatusEnum::Invalid { .. } => Self::Invalid,
+                PayloadStatusEnum::Syncing => Self::Syncing,
+                PayloadStatusEnum::Accepted => Self::Accepted,
+            },
+            Err(_) => Self::Error,
+        }
+    }
+
+    /// Returns the status of an `engine_forkchoiceUpdated` response.
+    pub const fn from_forkchoice_updated(response: &RethResult<OnForkChoiceUpdated>) -> Self {
+        match response {
+            Ok(outcome) => match outcome.forkchoice_status() {
+                ForkchoiceStatus::Valid => Self::Valid,
+                ForkchoiceStatus::Invalid => Self::Invalid,
+                ForkchoiceStatus::Syncing => Self::Syncing,
+            },
+            Err(_) => Self::Error,
+        }
+    }
+}
+
 /// This can read and write engine API messages in a specific directory.
 #[derive(Debug)]
 pub struct EngineMessageStore {
     /// The path to the directory that stores the engine API messages.
     path: PathBuf,
+    /// The number of messages stored so far, which orders messages received at the same time.
+    stored: u64,
 }
 
 impl EngineMessageStore {
@@ -46,59 +128,75 @@ impl EngineMessageStore {
     ///
     /// The path is expected to be a directory, where individual message JSON files will be stored.
     pub const fn new(path: PathBuf) -> Self {
-        Self { path }
+        Self { path, stored: 0 }
     }
 
     /// Stores the received [`BeaconEngineMessage`] to disk, appending the `received_at` time to the
     /// path.
+    ///
+    /// Returns the path of the stored message.
     pub fn on_message<T>(
-        &self,
+        &mut self,
         msg: &BeaconEngineMessage<T>,
         received_at: SystemTime,
-    ) -> eyre::Result<()>
+    ) -> eyre::Result<PathBuf>
     where
         T: PayloadTypes,
     {
-        fs::create_dir_all(&self.path)?; // ensure that store path had been created
         let timestamp = received_at.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis();
-        match msg {
-            BeaconEngineMessage::ForkchoiceUpdated {
-                state,
-                payload_attrs,
-                tx: _tx,
-                version: _version,
-            } => {
-                let filename = format!("{}-fcu-{}.json", timestamp, state.head_block_hash);
-                fs::write(
-                    self.path.join(filename),
-                    serde_json::to_vec(&StoredEngineApiMessage::<T>::ForkchoiceUpdated {
-                        state: *state,
-                        payload_attrs: payload_attrs.clone(),
-                    })?,
-                )?;
-            }
-            BeaconEngineMessage::NewPayload { payload, tx: _tx } => {
-                let filename = format!("{}-new_payload-{}.json", timestamp, payload.block_hash());
-                fs::write(
-                    self.path.join(filename),
-                    serde_json::to_vec(&StoredEngineApiMessage::<T>::NewPayload {
-                        payload: payload.clone(),
-                    })?,
-                )?;
-            }
+        let sequence = self.stored;
+        let (filename, stored) = match msg {
+            BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx: _tx, version } => (
+                format!("{timestamp}-{sequence}-fcu-{}.json", state.head_block_hash),
+                StoredEngineApiMessage::<T>::ForkchoiceUpdated {
+                    state: *state,
+                    payload_attrs: payload_attrs.clone(),
+                    version: *version,
+                    status: None,
+                },
+            ),
+            BeaconEngineMessage::NewPayload { payload, tx: _tx } => (
+                format!("{timestamp}-{sequence}-new_payload-{}.json", payload.block_hash()),
+                StoredEngineApiMessage::<T>::NewPayload { payload: payload.clone(), status: None },
+            ),
         };
+        let path = self.path.join(filename);
+        self.write_message(&path, &stored)?;
+        self.stored += 1;
+        Ok(path)
+    }
+
+    /// Writes the stored engine API message to the given path.
+    pub fn write_message<T>(&self, path: &Path, msg: &StoredEngineApiMessage<T>) -> eyre::Result<()>
+    where
+        T: PayloadTypes,
+    {
+        fs::create_dir_all(&self.path)?; // ensure that store path had been created
+        fs::write(path, serde_json::to_vec(msg)?)?;
         Ok(())
     }
 
-    /// Finds and iterates through any stored engine API message files, ordered by timestamp.
-    pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = PathBuf>> {
-        let mut filenames_by_ts = BTreeMap::<u64, Vec<PathBuf>>::default();
+    /// Reads the stored engine API message at the given path.
+    pub fn read_message<T>(path: &Path) -> eyre::Result<StoredEngineApiMessage<T>>
+    where
+        T: PayloadTypes,
+    {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Finds and iterates through any stored engine API message files, ordered by the time they
+    /// were received at, returned as milliseconds since the unix epoch with the path.
+    pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = (u64, PathBuf)>> {
+        let mut filenames_by_ts = BTreeMap::<(u64, u64), Vec<PathBuf>>::default();
         for entry in fs::read_dir(&self.path)? {
             let entry = entry?;
             let filename = entry.file_name();
             if let Some(filename) = filename.to_str().filter(|n| n.ends_with(".json")) {
-                if let Some(Ok(timestamp)) = filename.split('-').next().map(|n| n.parse::<u64>()) {
-                    filenames_by_ts.entry(timestamp).or_default().push(entry.path());
+                let mut parts = filename.split('-');
+                if let Some(Ok(timestamp)) = parts.next().map(|n| n.parse::<u64>()) {
+                    // Messages stored without a sequence number are ordered by timestamp only
+                    let sequence = parts.next().and_then(|n| n.parse::<u64>().ok()).unwrap_or(0);
+                    filenames_by_ts.entry((timestamp, sequence)).or_default().push(entry.path());
                     tracing::debug!(target: "engine::store", timestamp, filename, "Queued engine API message");
                 } else {
                     tracing::warn!(target: "engine::store", %filename, "Could not parse timestamp from filename")
@@ -107,30 +205,41 @@ impl EngineMessageStore {
                 tracing::warn!(target: "engine::store", ?filename, "Skipping non json file");
             }
         }
-        Ok(filenames_by_ts.into_iter().flat_map(|(_, paths)| paths))
+        Ok(filenames_by_ts.into_iter().flat_map(|((timestamp, _), paths)| {
+            paths.into_iter().map(move |path| (timestamp, path))
+        }))
     }
 }
 
+/// Pending response of the engine to a stored message, resolving to the message with the status of
+/// the response once it was forwarded.
+type StoreResponseFut<T> =
+    Pin<Box<dyn Future<Output = Option<(PathBuf, StoredEngineApiMessage<T>)>> + Send + Sync>>;
+
 /// A wrapper stream that stores Engine API messages in
 /// the specified directory.
+///
+/// The responses of the engine are intercepted to store their status with the messages.
 #[derive(Debug)]
 #[pin_project::pin_project]
-pub struct EngineStoreStream<S> {
+pub struct EngineStoreStream<S, T: PayloadTypes> {
     /// Inner message stream.
     #[pin]
     stream: S,
     /// Engine message store.
     store: EngineMessageStore,
+    /// Pending engine responses to stored messages.
+    responses: FuturesUnordered<StoreResponseFut<T>>,
 }
 
-impl<S> EngineStoreStream<S> {
+impl<S, T: PayloadTypes> EngineStoreStream<S, T> {
     /// Create new engine store stream wrapper.
-    pub const fn new(stream: S, path: PathBuf) -> Self {
-        Self { stream, store: EngineMessageStore::new(path) }
+    pub fn new(stream: S, path: PathBuf) -> Self {
+        Self { stream, store: EngineMessageStore::new(path), responses: FuturesUnordered::new() }
     }
 }
 
-impl<S, T> Stream for EngineStoreStream<S>
+impl<S, T> Stream for EngineStoreStream<S, T>
 where
     S: Stream<Item = BeaconEngineMessage<T>>,
     T: PayloadTypes,
@@ -139,12 +248,61 @@ where
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
-        let next = ready!(this.stream.poll_next_unpin(cx));
-        if let Some(msg) = &next &&
-            let Err(error) = this.store.on_message(msg, SystemTime::now())
-        {
-            error!(target: "engine::stream::store", ?msg, %error, "Error handling Engine API message");
+
+        while let Poll::Ready(Some(response)) = this.responses.poll_next_unpin(cx) {
+            if let Some((path, msg)) = response &&
+                let Err(error) = this.store.write_message(&path, &msg)
+            {
+                error!(target: "engine::stream::store", ?path, %error, "Error storing Engine API response");
+            }
         }
-        Poll::Ready(next)
+
+        let next = ready!(this.stream.poll_next_unpin(cx));
+        let Some(msg) = next else { return Poll::Ready(None) };
+        let path = match this.store.on_message(&msg, SystemTime::now()) {
+            Ok(path) => path,
+            Err(error) => {
+                error!(target: "engine::stream::store", ?msg, %error, "Error handling Engine API message");
+                return Poll::Ready(Some(msg))
+            }
+        };
+
+        // Intercept the response to store its status, and forward it to the original sender
+        let msg = match msg {
+            BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx, version } => {
+                let (response_tx, response_rx) = oneshot::channel();
+                let mut stored = StoredEngineApiMessage::ForkchoiceUpdated {
+                    state,
+                    payload_attrs: payload_attrs.clone(),
+                    version,
+                    status: None,
+                };
+                this.responses.push(Box::pin(async move {
+                    let response = response_rx.await.ok()?;
+                    stored.set_status(StoredEngineApiStatus::from_forkchoice_updated(&response));
+                    let _ = tx.send(response);
+                    Some((path, stored))
+                }));
+                BeaconEngineMessage::ForkchoiceUpdated {
+                    state,
+                    payload_attrs,
+                    tx: response_tx,
+                    version,
+                }
+            }
+            BeaconEngineMessage::NewPayload { payload, tx } => {
+                let (response_tx, response_rx) = oneshot::channel();
+                let mut stored =
+                    StoredEngineApiMessage::NewPayload { payload: payload.clone(), status: None };
+                this.responses.push(Box::pin(async move {
+                    let response = response_rx.await.ok()?;
+                    stored.set_status(StoredEngineApiStatus::from_new_payload(&response));
+                    let _ = tx.send(response);
+                    Some((path, stored))
+                }));
+                BeaconEngineMessage::NewPayload { payload, tx: response_tx }
+            }
+        };
+        Poll::Ready(Some(msg))
     }
 }
@@ -24,11 +24,14 @@ pub mod skip_new_payload;
 use skip_new_payload::EngineSkipNewPayload;
 
 pub mod reorg;
-use reorg::EngineReorg;
+use reorg::{EngineReorg, EngineReorgConfig};
+
+pub mod replay;
+use replay::EngineReplay;
 
 /// The result type for `maybe_reorg` method.
-type MaybeReorgResult<S, T, Provider, Evm, Validator, E> =
-    Result<Either<EngineReorg<S, T, Provider, Evm, Validator>, S>, E>;
+type MaybeReorgResult<S, T, Provider, Evm, Validator, Pool, E> =
+    Result<Either<EngineReorg<S, T, Provider, Evm, Validator, Pool>, S>, E>;
 
 /// The collection of stream extensions for engine API message stream.
 pub trait EngineMessageStreamExt<T: PayloadTypes>: Stream<Item = BeaconEngineMessage<T>> {
@@ -80,7 +83,7 @@ pub trait EngineMessageStreamExt<T: PayloadTypes>: Stream<Item = BeaconEngineMes
     }
 
     /// Stores engine messages at the specified location.
-    fn store_messages(self, path: PathBuf) -> EngineStoreStream<Self>
+    fn store_messages(self, path: PathBuf) -> EngineStoreStream<Self, T>
     where
         Self: Sized,
     {
@@ -92,7 +95,7 @@ pub trait EngineMessageStreamExt<T: PayloadTypes>: Stream<Item = BeaconEngineMes
     fn maybe_store_messages(
         self,
         maybe_path: Option<PathBuf>,
-    ) -> Either<EngineStoreStream<Self>, Self>
+    ) -> Either<EngineStoreStream<Self, T>, Self>
     where
         Self: Sized,
     {
@@ -103,58 +106,72 @@ pub trait EngineMessageStreamExt<T: PayloadTypes>: Stream<Item = BeaconEngineMes
         }
     }
 
-    /// Creates reorgs with specified frequency.
-    fn reorg<Provider, Evm, Validator>(
+    /// Replays the engine messages stored at the specified location with the given speed relative
+    /// to the recording, `0` replaying them as fast as the engine responds.
+    fn replay_messages(self, path: PathBuf, speed: u64) -> eyre::Result<EngineReplay<Self>>
+    where
+        Self: Sized,
+    {
+        EngineReplay::new(self, path, speed)
+    }
+
+    /// If the path and speed are [Some], returns the stream that replays the engine messages stored
+    /// at the path with the speed. Otherwise, returns `Self`.
+    fn maybe_replay_messages(
+        self,
+        maybe_replay: Option<(PathBuf, u64)>,
+    ) -> eyre::Result<Either<EngineReplay<Self>, Self>>
+    where
+        Self: Sized,
+    {
+        if let Some((path, speed)) = maybe_replay {
+            Ok(Either::Left(self.replay_messages(path, speed)?))
+        } else {
+            Ok(Either::Right(self))
+        }
+    }
+
+    /// Creates reorgs as specified by the given [`EngineReorgConfig`].
+    fn reorg<Provider, Evm, Validator, Pool>(
         self,
         provider: Provider,
         evm_config: Evm,
         payload_validator: Validator,
-        frequency: usize,
-        depth: Option<usize>,
-    ) -> EngineReorg<Self, T, Provider, Evm, Validator>
+        pool: Pool,
+        config: EngineReorgConfig,
+    ) -> EngineReorg<Self, T, Provider, Evm, Validator, Pool>
     where
         Self: Sized,
     {
-        EngineReorg::new(
-            self,
-            provider,
-            evm_config,
-            payload_validator,
-            frequency,
-            depth.unwrap_or_default(),
-        )
+        EngineReorg::new(self, provider, evm_config, payload_validator, pool, config)
     }
 
-    /// If frequency is [Some], returns the stream that creates reorgs with
-    /// specified frequency. Otherwise, returns `Self`.
+    /// If config is [Some], returns the stream that creates reorgs as specified by it. Otherwise,
+    /// returns `Self`.
     ///
-    /// The `payload_validator_fn` closure is only called if `frequency` is `Some`,
+    /// The `payload_validator_fn` closure is only called if `config` is `Some`,
     /// allowing for lazy initialization of the validator.
-    fn maybe_reorg<Provider, Evm, Validator, E, F, Fut>(
+    fn maybe_reorg<Provider, Evm, Validator, Pool, E, F, Fut>(
         self,
         provider: Provider,
         evm_config: Evm,
+        pool: Pool,
         payload_validator_fn: F,
-        frequency: Option<usize>,
-        depth: Option<usize>,
-    ) -> impl Future<Output = MaybeReorgResult<Self, T, Provider, Evm, Validator, E>> + Send
+        config: Option<EngineReorgConfig>,
+    ) -> impl Future<Output = MaybeReorgResult<Self, T, Provider, Evm, Validator, Pool, E>> + Send
     where
         Self: Sized + Send,
         Provider: Send,
         Evm: Send,
+        Pool: Send,
         F: FnOnce() -> Fut + Send,
         Fut: Future<Output = Result<Validator, E>> + Send,
     {
         async move {
-            if let Some(frequency) = frequency {
+            if let Some(config) = config {
                 let validator = payload_validator_fn().await?;
                 Ok(Either::Left(reorg::EngineReorg::new(
-                    self,
-                    provider,
-                    evm_config,
-                    validator,
-                    frequency,
-                    depth.unwrap_or_default(),
+                    self, provider, evm_config, validator, pool, config,
                 )))
             } else {
                 Ok(Either::Right(self))
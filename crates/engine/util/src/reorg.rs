@@ -1,6 +1,8 @@
 //! Stream wrapper that simulates reorgs.
 
 use alloy_consensus::{BlockHeader, Transaction};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::B256;
 use alloy_rpc_types_engine::{ForkchoiceState, PayloadStatus};
 use futures::{stream::FuturesUnordered, Stream, StreamExt, TryFutureExt};
 use itertools::Either;
@@ -16,13 +18,20 @@ use reth_evm::{
 };
 use reth_payload_primitives::{BuiltPayload, EngineApiMessageVersion, PayloadTypes};
 use reth_primitives_traits::{
-    block::Block as _, BlockBody as _, BlockTy, HeaderTy, SealedBlock, SignedTransaction,
+    Block, BlockBody as _, BlockTy, HeaderTy, Recovered, SealedBlock, SealedHeader,
+    SignedTransaction, TxTy,
 };
 use reth_revm::{database::StateProviderDatabase, db::State};
-use reth_storage_api::{errors::ProviderError, BlockReader, StateProviderFactory};
+use reth_storage_api::{errors::ProviderError, BlockReader, StateProvider, StateProviderFactory};
+use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use serde::{Deserialize, Serialize};
 use std::{
+    any::Any,
     collections::VecDeque,
+    fs::OpenOptions,
     future::Future,
+    io::Write,
+    path::{Path, PathBuf},
     pin::Pin,
     task::{ready, Context, Poll},
 };
@@ -42,10 +51,76 @@ type EngineReorgResponse = Result<
 
 type ReorgResponseFut = Pin<Box<dyn Future<Output = EngineReorgResponse> + Send + Sync>>;
 
+/// Configuration of the reorgs simulated by [`EngineReorg`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EngineReorgConfig {
+    /// The number of forkchoice states to forward between reorgs.
+    pub frequency: usize,
+    /// The depth of reorgs, i.e. the number of canonical blocks below the head that are reorged
+    /// out together with it.
+    pub depth: usize,
+    /// The number of blocks of the alternative branch, at most `depth + 1`.
+    ///
+    /// Branches always consist of at least one block. Longer branches require the headers to be
+    /// [`alloy_consensus::Header`]s, whose parent hash can be changed.
+    pub branch_length: usize,
+    /// The number of times the forkchoice is flipped between the canonical and the alternative
+    /// branch after reorging to the alternative branch.
+    pub flips: usize,
+    /// The file to append a [`ReorgRecord`] of every simulated reorg to.
+    pub record: Option<PathBuf>,
+}
+
+/// A reorg simulated by [`EngineReorg`].
+///
+/// Records are appended to [`EngineReorgConfig::record`] as JSON lines, so that tests can assert
+/// that consumers of the chain, e.g. ExExes and indexers, reverted the reorged out blocks and
+/// followed the expected head.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorgRecord {
+    /// The common ancestor of both branches.
+    pub fork_block: BlockNumHash,
+    /// The canonical blocks on top of the fork block that were reorged out, oldest first.
+    pub canonical: Vec<BlockNumHash>,
+    /// The blocks of the alternative branch, oldest first.
+    pub branch: Vec<BlockNumHash>,
+    /// The number of forkchoice flips between the branches after the reorg.
+    pub flips: usize,
+    /// The head the forkchoice points to once all messages of the reorg are processed, until the
+    /// consensus layer moves it back to its canonical chain.
+    pub head: BlockNumHash,
+    /// The state root of the expected head.
+    pub state_root: B256,
+}
+
+impl ReorgRecord {
+    /// Reads all records of the given file.
+    pub fn read_all(path: &Path) -> eyre::Result<Vec<Self>> {
+        reth_fs_util::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| Ok(serde_json::from_str(line)?))
+            .collect()
+    }
+
+    /// Appends the record to the given file.
+    fn append(&self, path: &Path) -> eyre::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        serde_json::to_writer(&mut file, self)?;
+        writeln!(file)?;
+        Ok(())
+    }
+}
+
 /// Engine API stream wrapper that simulates reorgs with specified frequency.
+///
+/// Every reorg builds an alternative branch on top of an ancestor of the current head, with the
+/// transactions of the reorged out blocks and of the transaction pool, sends it to the engine and
+/// then flips the forkchoice between both branches as configured by [`EngineReorgConfig`].
 #[derive(Debug)]
 #[pin_project::pin_project]
-pub struct EngineReorg<S, T: PayloadTypes, Provider, Evm, Validator> {
+pub struct EngineReorg<S, T: PayloadTypes, Provider, Evm, Validator, Pool> {
     /// Underlying stream
     #[pin]
     stream: S,
@@ -55,10 +130,10 @@ pub struct EngineReorg<S, T: PayloadTypes, Provider, Evm, Validator> {
     evm_config: Evm,
     /// Payload validator.
     payload_validator: Validator,
-    /// The frequency of reorgs.
-    frequency: usize,
-    /// The depth of reorgs.
-    depth: usize,
+    /// Transaction pool.
+    pool: Pool,
+    /// The configuration of reorgs.
+    config: EngineReorgConfig,
     /// The number of forwarded forkchoice states.
     /// This is reset after a reorg.
     forkchoice_states_forwarded: usize,
@@ -70,23 +145,25 @@ pub struct EngineReorg<S, T: PayloadTypes, Provider, Evm, Validator> {
     reorg_responses: FuturesUnordered<ReorgResponseFut>,
 }
 
-impl<S, T: PayloadTypes, Provider, Evm, Validator> EngineReorg<S, T, Provider, Evm, Validator> {
+impl<S, T: PayloadTypes, Provider, Evm, Validator, Pool>
+    EngineReorg<S, T, Provider, Evm, Validator, Pool>
+{
     /// Creates new [`EngineReorg`] stream wrapper.
     pub fn new(
         stream: S,
         provider: Provider,
         evm_config: Evm,
         payload_validator: Validator,
-        frequency: usize,
-        depth: usize,
+        pool: Pool,
+        config: EngineReorgConfig,
     ) -> Self {
         Self {
             stream,
             provider,
             evm_config,
             payload_validator,
-            frequency,
-            depth,
+            pool,
+            config,
             state: EngineReorgState::Forward,
             forkchoice_states_forwarded: 0,
             last_forkchoice_state: None,
@@ -95,7 +172,8 @@ impl<S, T: PayloadTypes, Provider, Evm, Validator> EngineReorg<S, T, Provider, E
     }
 }
 
-impl<S, T, Provider, Evm, Validator> Stream for EngineReorg<S, T, Provider, Evm, Validator>
+impl<S, T, Provider, Evm, Validator, Pool> Stream
+    for EngineReorg<S, T, Provider, Evm, Validator, Pool>
 where
     S: Stream<Item = BeaconEngineMessage<T>>,
     T: PayloadTypes<BuiltPayload: BuiltPayload<Primitives = Evm::Primitives>>,
@@ -104,6 +182,7 @@ where
         + ChainSpecProvider,
     Evm: ConfigureEvm,
     Validator: EngineValidator<T, Evm::Primitives>,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Evm::Primitives>>>,
 {
     type Item = S::Item;
 
@@ -145,29 +224,32 @@ where
                 (
                     Some(BeaconEngineMessage::NewPayload { payload, tx }),
                     Some(last_forkchoice_state),
-                ) if this.forkchoice_states_forwarded > this.frequency &&
+                ) if this.forkchoice_states_forwarded > this.config.frequency &&
                         // Only enter reorg state if new payload attaches to current head.
                         last_forkchoice_state.head_block_hash == payload.parent_hash() =>
                 {
                     // Enter the reorg state.
                     // The current payload will be immediately forwarded by being in front of the
-                    // queue. Then we attempt to reorg the current head by generating a payload that
-                    // attaches to the head's parent and is based on the non-conflicting
+                    // queue. Then we attempt to reorg the current head by generating a branch that
+                    // attaches to an ancestor of the head and is based on the non-conflicting
                     // transactions (txs from block `n + 1` that are valid at block `n` according to
-                    // consensus checks) from the current payload as well as the corresponding
-                    // forkchoice state. We will rely on CL to reorg us back to canonical chain.
+                    // consensus checks) of the reorged out blocks and the current payload, as well
+                    // as the transactions of the pool, followed by the corresponding forkchoice
+                    // states. We will rely on CL to reorg us back to canonical chain.
                     // TODO: This is an expensive blocking operation, ideally it's spawned as a task
                     // so that the stream could yield the control back.
-                    let reorg_block = match create_reorg_head(
+                    let branch = match create_reorg_branch(
                         this.provider,
                         this.evm_config,
                         this.payload_validator,
-                        *this.depth,
+                        this.pool,
+                        this.config.depth,
+                        this.config.branch_length,
                         payload.clone(),
                     ) {
                         Ok(result) => result,
                         Err(error) => {
-                            error!(target: "engine::stream::reorg", %error, "Error attempting to create reorg head");
+                            error!(target: "engine::stream::reorg", %error, "Error attempting to create reorg branch");
                             // Forward the payload and attempt to create reorg on top of
                             // the next one
                             return Poll::Ready(Some(BeaconEngineMessage::NewPayload {
@@ -176,35 +258,69 @@ where
                             }))
                         }
                     };
-                    let reorg_forkchoice_state = ForkchoiceState {
-                        finalized_block_hash: last_forkchoice_state.finalized_block_hash,
-                        safe_block_hash: last_forkchoice_state.safe_block_hash,
-                        head_block_hash: reorg_block.hash(),
-                    };
 
-                    let (reorg_payload_tx, reorg_payload_rx) = oneshot::channel();
-                    let (reorg_fcu_tx, reorg_fcu_rx) = oneshot::channel();
-                    this.reorg_responses.extend([
-                        Box::pin(reorg_payload_rx.map_ok(Either::Left)) as ReorgResponseFut,
-                        Box::pin(reorg_fcu_rx.map_ok(Either::Right)) as ReorgResponseFut,
-                    ]);
+                    let canonical_head = branch.canonical.last().expect("branch reorgs the head");
+                    let branch_head = branch.blocks.last().expect("branch is not empty");
+                    // The forkchoice ends up at the branch after an even number of flips
+                    let head = if this.config.flips % 2 == 0 {
+                        branch_head.sealed_header()
+                    } else {
+                        canonical_head
+                    };
+                    let record = ReorgRecord {
+                        fork_block: BlockNumHash::new(
+                            branch.canonical[0].number() - 1,
+                            branch.canonical[0].parent_hash(),
+                        ),
+                        canonical: branch
+                            .canonical
+                            .iter()
+                            .map(|header| header.num_hash())
+                            .collect(),
+                        branch: branch.blocks.iter().map(|block| block.num_hash()).collect(),
+                        flips: this.config.flips,
+                        head: head.num_hash(),
+                        state_root: head.state_root(),
+                    };
+                    info!(target: "engine::stream::reorg", fork_block = ?record.fork_block, reorged = record.canonical.len(), length = record.branch.len(), flips = record.flips, "Simulating reorg");
+                    if let Some(path) = &this.config.record &&
+                        let Err(error) = record.append(path)
+                    {
+                        error!(target: "engine::stream::reorg", ?path, %error, "Failed to record reorg");
+                    }
 
-                    let queue = VecDeque::from([
+                    let mut queue = VecDeque::from([
                         // Current payload
                         BeaconEngineMessage::NewPayload { payload, tx },
-                        // Reorg payload
-                        BeaconEngineMessage::NewPayload {
-                            payload: T::block_to_payload(reorg_block),
+                    ]);
+                    let (canonical_head, branch_head) = (canonical_head.hash(), branch_head.hash());
+
+                    // Reorg payloads
+                    for block in branch.blocks {
+                        let (reorg_payload_tx, reorg_payload_rx) = oneshot::channel();
+                        this.reorg_responses.push(
+                            Box::pin(reorg_payload_rx.map_ok(Either::Left)) as ReorgResponseFut
+                        );
+                        queue.push_back(BeaconEngineMessage::NewPayload {
+                            payload: T::block_to_payload(block),
                             tx: reorg_payload_tx,
-                        },
-                        // Reorg forkchoice state
-                        BeaconEngineMessage::ForkchoiceUpdated {
-                            state: reorg_forkchoice_state,
+                        });
+                    }
+
+                    // Reorg forkchoice state, followed by the flips between both branches
+                    for flip in 0..=this.config.flips {
+                        let head_block_hash =
+                            if flip % 2 == 0 { branch_head } else { canonical_head };
+                        let (reorg_fcu_tx, reorg_fcu_rx) = oneshot::channel();
+                        this.reorg_responses
+                            .push(Box::pin(reorg_fcu_rx.map_ok(Either::Right)) as ReorgResponseFut);
+                        queue.push_back(BeaconEngineMessage::ForkchoiceUpdated {
+                            state: ForkchoiceState { head_block_hash, ..*last_forkchoice_state },
                             payload_attrs: None,
                             tx: reorg_fcu_tx,
                             version: EngineApiMessageVersion::default(),
-                        },
-                    ]);
+                        });
+                    }
                     *this.state = EngineReorgState::Reorg { queue };
                     continue
                 }
@@ -236,13 +352,24 @@ where
     }
 }
 
-fn create_reorg_head<Provider, Evm, T, Validator>(
+/// An alternative branch built by [`create_reorg_branch`].
+#[derive(Debug)]
+struct ReorgBranch<B: Block> {
+    /// The canonical blocks that are reorged out by the branch, oldest first.
+    canonical: Vec<SealedHeader<B::Header>>,
+    /// The blocks of the branch, oldest first.
+    blocks: Vec<SealedBlock<B>>,
+}
+
+fn create_reorg_branch<Provider, Evm, T, Validator, Pool>(
     provider: &Provider,
     evm_config: &Evm,
     payload_validator: &Validator,
-    mut depth: usize,
+    pool: &Pool,
+    depth: usize,
+    length: usize,
     next_payload: T::ExecutionData,
-) -> RethResult<SealedBlock<BlockTy<Evm::Primitives>>>
+) -> RethResult<ReorgBranch<BlockTy<Evm::Primitives>>>
 where
     Provider: BlockReader<Header = HeaderTy<Evm::Primitives>, Block = BlockTy<Evm::Primitives>>
         + StateProviderFactory
@@ -250,73 +377,288 @@ where
     Evm: ConfigureEvm,
     T: PayloadTypes<BuiltPayload: BuiltPayload<Primitives = Evm::Primitives>>,
     Validator: EngineValidator<T, Evm::Primitives>,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Evm::Primitives>>>,
 {
     // Ensure next payload is valid.
     let next_block =
         payload_validator.convert_payload_to_block(next_payload).map_err(RethError::msg)?;
 
-    // Fetch reorg target block depending on its depth and its parent.
+    // Fetch the canonical blocks down to the reorg target depending on its depth.
     let mut previous_hash = next_block.parent_hash();
-    let mut candidate_transactions = next_block.into_body().transactions().to_vec();
-    let reorg_target = 'target: {
-        loop {
-            let reorg_target = provider
-                .block_by_hash(previous_hash)?
-                .ok_or_else(|| ProviderError::HeaderNotFound(previous_hash.into()))?;
-            if depth == 0 {
-                break 'target reorg_target.seal_slow()
-            }
+    let mut canonical = Vec::with_capacity(depth + 1);
+    for _ in 0..=depth {
+        let block = provider
+            .block_by_hash(previous_hash)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(previous_hash.into()))?
+            .seal_unchecked(previous_hash);
+        previous_hash = block.header().parent_hash();
+        canonical.push(block);
+    }
+    canonical.reverse();
 
-            depth -= 1;
-            previous_hash = reorg_target.header().parent_hash();
-            candidate_transactions = reorg_target.into_body().into_transactions();
-        }
-    };
-    let reorg_target_parent = provider
+    let reorg_target = &canonical[0];
+    debug!(target: "engine::stream::reorg", number = reorg_target.header().number(), hash = %reorg_target.hash(), "Selected reorg target");
+
+    let parent = provider
         .sealed_header_by_hash(reorg_target.header().parent_hash())?
         .ok_or_else(|| ProviderError::HeaderNotFound(reorg_target.header().parent_hash().into()))?;
 
-    debug!(target: "engine::stream::reorg", number = reorg_target.header().number(), hash = %previous_hash, "Selected reorg target");
+    // Pool transactions that can't be included in a block are retried in the following ones
+    let pool_transactions =
+        pool.best_transactions().map(|tx| tx.to_consensus()).collect::<Vec<_>>();
 
-    // Configure state
-    let state_provider = provider.state_by_block_hash(reorg_target.header().parent_hash())?;
+    // Every block replaces the canonical block at the same height, with the transactions of the
+    // canonical block following it.
+    let length = length.clamp(1, canonical.len());
+    let mut templates = Vec::with_capacity(length);
+    for (index, canonical_block) in canonical.iter().take(length).enumerate() {
+        let candidate_transactions = canonical
+            .get(index + 1)
+            .map_or_else(|| next_block.body().transactions(), |block| block.body().transactions())
+            .iter()
+            .map(|tx| tx.clone().try_into_recovered())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ProviderError::SenderRecoveryError)?;
+        templates.push((canonical_block.clone(), candidate_transactions));
+    }
+
+    let state_provider = provider.state_by_block_hash(parent.hash())?;
+    let blocks = build_branch(evm_config, &state_provider, parent, templates, pool_transactions)?;
+
+    Ok(ReorgBranch {
+        canonical: canonical.into_iter().map(SealedBlock::into_sealed_header).collect(),
+        blocks,
+    })
+}
+
+/// Builds the blocks of a branch on top of `parent`, one for each of the given templates with the
+/// candidate transactions to include in it, followed by the pool transactions that fit.
+///
+/// All blocks are built on the same [`State`], which accumulates the changes of the previous
+/// blocks of the branch and resolves their hashes, so that `BLOCKHASH` returns the blocks of the
+/// branch instead of the canonical ones.
+fn build_branch<Evm, S>(
+    evm_config: &Evm,
+    state_provider: S,
+    mut parent: SealedHeader<HeaderTy<Evm::Primitives>>,
+    templates: Vec<(SealedBlock<BlockTy<Evm::Primitives>>, Vec<Recovered<TxTy<Evm::Primitives>>>)>,
+    mut pool_transactions: Vec<Recovered<TxTy<Evm::Primitives>>>,
+) -> RethResult<Vec<SealedBlock<BlockTy<Evm::Primitives>>>>
+where
+    Evm: ConfigureEvm,
+    S: StateProvider,
+{
     let mut state = State::builder()
         .with_database_ref(StateProviderDatabase::new(&state_provider))
         .with_bundle_update()
         .build();
 
-    let ctx = evm_config.context_for_block(&reorg_target).map_err(RethError::other)?;
-    let evm = evm_config.evm_for_block(&mut state, &reorg_target).map_err(RethError::other)?;
-    let mut builder = evm_config.create_block_builder(evm, &reorg_target_parent, ctx);
+    let mut blocks = Vec::with_capacity(templates.len());
+    for (template, candidate_transactions) in templates {
+        let (mut header, body) = template.split_header_body();
+        if header.parent_hash() != parent.hash() && !set_parent_hash(&mut header, parent.hash()) {
+            warn!(target: "engine::stream::reorg", length = blocks.len(), "Headers of this chain can't be built on a different parent, shortening reorg branch");
+            break
+        }
+        let template = SealedBlock::<BlockTy<Evm::Primitives>>::from_parts_unhashed(header, body);
 
-    builder.apply_pre_execution_changes()?;
+        let ctx = evm_config.context_for_block(&template).map_err(RethError::other)?;
+        let evm = evm_config.evm_for_block(&mut state, &template).map_err(RethError::other)?;
+        let mut builder = evm_config.create_block_builder(evm, &parent, ctx);
 
-    let mut cumulative_gas_used = 0;
-    for tx in candidate_transactions {
-        // ensure we still have capacity for this transaction
-        if cumulative_gas_used + tx.gas_limit() > reorg_target.gas_limit() {
-            continue
-        }
+        builder.apply_pre_execution_changes()?;
 
-        let tx_recovered =
-            tx.try_into_recovered().map_err(|_| ProviderError::SenderRecoveryError)?;
-        let gas_used = match builder.execute_transaction(tx_recovered) {
-            Ok(gas_used) => gas_used,
-            Err(BlockExecutionError::Validation(BlockValidationError::InvalidTx {
-                hash,
-                error,
-            })) => {
-                trace!(target: "engine::stream::reorg", hash = %hash, ?error, "Error executing transaction from next block");
-                continue
+        let mut cumulative_gas_used = 0;
+        let mut execute = |tx: Recovered<TxTy<Evm::Primitives>>| {
+            // ensure we still have capacity for this transaction
+            if cumulative_gas_used + tx.gas_limit() > template.gas_limit() {
+                return Ok(false)
+            }
+
+            match builder.execute_transaction(tx) {
+                Ok(gas_used) => {
+                    cumulative_gas_used += gas_used;
+                    Ok(true)
+                }
+                Err(BlockExecutionError::Validation(BlockValidationError::InvalidTx {
+                    hash,
+                    error,
+                })) => {
+                    trace!(target: "engine::stream::reorg", hash = %hash, ?error, "Error executing transaction for reorg block");
+                    Ok(false)
+                }
+                // Treat error as fatal
+                Err(error) => Err(RethError::Execution(error)),
             }
-            // Treat error as fatal
-            Err(error) => return Err(RethError::Execution(error)),
         };
 
-        cumulative_gas_used += gas_used;
+        for tx in candidate_transactions {
+            execute(tx)?;
+        }
+
+        let mut remaining = Vec::with_capacity(pool_transactions.len());
+        for tx in pool_transactions {
+            if !execute(tx.clone())? {
+                remaining.push(tx);
+            }
+        }
+        pool_transactions = remaining;
+
+        let BlockBuilderOutcome { block, .. } = builder.finish(&state_provider)?;
+        let block = block.into_sealed_block();
+
+        debug!(target: "engine::stream::reorg", number = block.header().number(), hash = %block.hash(), transactions = block.transaction_count(), "Built reorg block");
+        // The following blocks must see the hash of this block instead of the canonical one
+        state.block_hashes.insert(block.header().number(), block.hash());
+        parent = block.clone_sealed_header();
+        blocks.push(block);
     }
 
-    let BlockBuilderOutcome { block, .. } = builder.finish(&state_provider)?;
+    Ok(blocks)
+}
+
+/// Sets the parent hash of the header, returning `false` if the header type doesn't support it.
+///
+/// Only [`alloy_consensus::Header`] is supported, which keeps the reorg stream usable for node
+/// types with other headers, with branches of a single block.
+fn set_parent_hash<H: 'static>(header: &mut H, parent_hash: B256) -> bool {
+    (header as &mut dyn Any)
+        .downcast_mut::<alloy_consensus::Header>()
+        .map(|header| header.parent_hash = parent_hash)
+        .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{constants::ETH_TO_WEI, Header, TxEip1559, EMPTY_OMMER_ROOT_HASH};
+    use alloy_genesis::{ChainConfig, Genesis, GenesisAccount};
+    use alloy_primitives::{bytes, Address, TxKind, U256};
+    use reth_chainspec::ChainSpec;
+    use reth_db_common::init::init_genesis;
+    use reth_ethereum_primitives::{Block, BlockBody, Transaction};
+    use reth_evm::execute::Executor;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::{crypto::secp256k1::public_key_to_address, Block as _};
+    use reth_provider::{
+        test_utils::create_test_provider_factory_with_chain_spec, BlockWriter,
+        HashedPostStateProvider, StateRootProvider,
+    };
+    use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
+    use std::sync::Arc;
+
+    #[test]
+    fn branch_block_hashes() {
+        let key_pair = generators::generate_key(&mut generators::rng());
+        let sender = public_key_to_address(key_pair.public_key());
+
+        // Stores `BLOCKHASH(NUMBER - 1)` in slot 0
+        let contract = Address::with_last_byte(0xc0);
+        let code = bytes!("600143034060005500");
+
+        let genesis = Genesis {
+            config: ChainConfig {
+                chain_id: 1,
+                homestead_block: Some(0),
+                eip150_block: Some(0),
+                eip155_block: Some(0),
+                eip158_block: Some(0),
+                byzantium_block: Some(0),
+                constantinople_block: Some(0),
+                petersburg_block: Some(0),
+                istanbul_block: Some(0),
+                berlin_block: Some(0),
+                london_block: Some(0),
+                merge_netsplit_block: Some(0),
+                shanghai_time: Some(0),
+                terminal_total_difficulty: Some(U256::ZERO),
+                terminal_total_difficulty_passed: true,
+                ..Default::default()
+            },
+            gas_limit: 30_000_000,
+            alloc: [
+                (sender, GenesisAccount { balance: U256::from(ETH_TO_WEI), ..Default::default() }),
+                (contract, GenesisAccount { code: Some(code), ..Default::default() }),
+            ]
+            .into(),
+            ..Default::default()
+        };
+        let chain_spec = Arc::new(ChainSpec::from_genesis(genesis));
+        let evm_config = EthEvmConfig::new(chain_spec.clone());
 
-    Ok(block.into_sealed_block())
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&provider_factory).unwrap();
+
+        let genesis = chain_spec.sealed_genesis_header();
+        let template = |parent: &Header, parent_hash, transactions: Vec<_>| {
+            let timestamp = parent.timestamp + 12;
+            Block {
+                header: Header {
+                    parent_hash,
+                    ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                    number: parent.number + 1,
+                    gas_limit: parent.gas_limit,
+                    timestamp,
+                    base_fee_per_gas: chain_spec.next_block_base_fee(parent, timestamp),
+                    ..Default::default()
+                },
+                body: BlockBody {
+                    transactions,
+                    ommers: Vec::new(),
+                    withdrawals: Some(Default::default()),
+                },
+            }
+        };
+
+        // The second template still points to some other parent and calls the contract, which
+        // must see the hash of the first block of the branch
+        let first = template(genesis.header(), genesis.hash(), Vec::new());
+        let transaction = sign_tx_with_key_pair(
+            key_pair,
+            Transaction::Eip1559(TxEip1559 {
+                chain_id: 1,
+                nonce: 0,
+                gas_limit: 100_000,
+                max_fee_per_gas: 10_000_000_000,
+                max_priority_fee_per_gas: 1_000_000_000,
+                to: TxKind::Call(contract),
+                ..Default::default()
+            }),
+        );
+        let second = template(&first.header, B256::with_last_byte(0xaa), vec![transaction.clone()]);
+
+        let blocks = build_branch(
+            &evm_config,
+            provider_factory.latest().unwrap(),
+            genesis,
+            vec![
+                (first.seal_slow(), Vec::new()),
+                (second.seal_slow(), vec![transaction.try_into_recovered().unwrap()]),
+            ],
+            Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[1].parent_hash(), blocks[0].hash());
+
+        // Re-executing the second block on top of the first one results in the same state
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        provider_rw.insert_block(&blocks[0].clone().try_recover().unwrap()).unwrap();
+        provider_rw.commit().unwrap();
+
+        let output = evm_config
+            .executor(StateProviderDatabase::new(provider_factory.latest().unwrap()))
+            .execute(&blocks[1].clone().try_recover().unwrap())
+            .unwrap();
+        let stored = output.state.account(&contract).unwrap().storage[&U256::ZERO].present_value;
+        assert_eq!(stored, U256::from_be_bytes(blocks[0].hash().0));
+        assert_eq!(
+            provider_factory
+                .latest()
+                .unwrap()
+                .state_root(provider_factory.hashed_post_state(&output.state))
+                .unwrap(),
+            blocks[1].state_root()
+        );
+    }
 }
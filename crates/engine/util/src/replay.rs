@@ -0,0 +1,304 @@
+//! Stream wrapper that replays stored engine API messages.
+
+use crate::engine_store::{EngineMessageStore, StoredEngineApiMessage, StoredEngineApiStatus};
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use reth_engine_primitives::BeaconEngineMessage;
+use reth_payload_primitives::PayloadTypes;
+use std::{
+    collections::VecDeque,
+    future::Future,
+    path::PathBuf,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::{sync::oneshot, time::Sleep};
+use tracing::*;
+
+/// Response of the engine to a replayed message.
+#[derive(Debug)]
+struct ReplayResponse {
+    /// Path of the replayed message.
+    path: PathBuf,
+    /// Status of the recorded response, if it was stored.
+    expected: Option<StoredEngineApiStatus>,
+    /// Status of the response, `None` if the engine dropped the message.
+    status: Option<StoredEngineApiStatus>,
+    /// Time it took the engine to respond.
+    latency: Duration,
+}
+
+type ReplayResponseFut = Pin<Box<dyn Future<Output = ReplayResponse> + Send + Sync>>;
+
+/// Engine API stream wrapper that replays the messages stored by an
+/// [`EngineStoreStream`](crate::engine_store::EngineStoreStream) into the engine.
+///
+/// Messages are replayed in the order they were received in, each one only after the engine
+/// responded to the previous one. The time between messages is kept, divided by the replay speed,
+/// with a speed of `0` replaying messages as fast as the engine responds. The latency of every
+/// response and any divergence from the recorded status are reported.
+///
+/// Messages of the inner stream are forwarded as is.
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct EngineReplay<S> {
+    /// Underlying stream
+    #[pin]
+    stream: S,
+    /// Messages left to replay, with the time they were received at in milliseconds.
+    messages: VecDeque<(u64, PathBuf)>,
+    /// The replay speed relative to the recording.
+    speed: u64,
+    /// The start of the replay with the receive time of the first message.
+    start: Option<(Instant, u64)>,
+    /// Delay until the next message is due.
+    delay: Option<Pin<Box<Sleep>>>,
+    /// Pending engine response to the last replayed message.
+    response: FuturesUnordered<ReplayResponseFut>,
+    /// Statistics of the replay.
+    stats: ReplayStats,
+}
+
+impl<S> EngineReplay<S> {
+    /// Creates new [`EngineReplay`] stream wrapper, replaying the messages stored at the given
+    /// path.
+    pub fn new(stream: S, path: PathBuf, speed: u64) -> eyre::Result<Self> {
+        let messages: VecDeque<_> =
+            EngineMessageStore::new(path.clone()).engine_messages_iter()?.collect();
+        if messages.is_empty() {
+            warn!(target: "engine::stream::replay", ?path, "No engine API messages to replay");
+        } else {
+            info!(target: "engine::stream::replay", ?path, messages = messages.len(), speed, "Replaying engine API messages");
+        }
+        Ok(Self {
+            stream,
+            messages,
+            speed,
+            start: None,
+            delay: None,
+            response: FuturesUnordered::new(),
+            stats: ReplayStats::default(),
+        })
+    }
+}
+
+impl<S, T> Stream for EngineReplay<S>
+where
+    S: Stream<Item = BeaconEngineMessage<T>>,
+    T: PayloadTypes,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            if let Poll::Ready(Some(response)) = this.response.poll_next_unpin(cx) {
+                this.stats.on_response(response);
+                if this.messages.is_empty() {
+                    this.stats.finish();
+                }
+                continue
+            }
+
+            // The next message is only replayed once the engine responded to the previous one
+            if !this.response.is_empty() {
+                break
+            }
+            let Some(&(received_at, _)) = this.messages.front() else { break };
+
+            if let Some(delay) = this.delay.as_mut() {
+                if delay.as_mut().poll(cx).is_pending() {
+                    break
+                }
+                *this.delay = None;
+            } else {
+                let (start, first) =
+                    *this.start.get_or_insert_with(|| (Instant::now(), received_at));
+                let offset = match *this.speed {
+                    0 => Duration::ZERO,
+                    speed => Duration::from_millis(received_at.saturating_sub(first) / speed),
+                };
+                if start + offset > Instant::now() {
+                    *this.delay = Some(Box::pin(tokio::time::sleep_until((start + offset).into())));
+                    continue
+                }
+            }
+
+            let (_, path) = this.messages.pop_front().expect("message exists");
+            let msg = match EngineMessageStore::read_message::<T>(&path) {
+                Ok(msg) => msg,
+                Err(error) => {
+                    error!(target: "engine::stream::replay", ?path, %error, "Failed to read engine API message");
+                    if this.messages.is_empty() {
+                        this.stats.finish();
+                    }
+                    continue
+                }
+            };
+            debug!(target: "engine::stream::replay", ?path, "Replaying engine API message");
+
+            let expected = msg.status();
+            let sent_at = Instant::now();
+            let msg = match msg {
+                StoredEngineApiMessage::ForkchoiceUpdated {
+                    state, payload_attrs, version, ..
+                } => {
+                    let (tx, rx) = oneshot::channel();
+                    this.response.push(Box::pin(async move {
+                        let status = rx
+                            .await
+                            .ok()
+                            .map(|res| StoredEngineApiStatus::from_forkchoice_updated(&res));
+                        ReplayResponse { path, expected, status, latency: sent_at.elapsed() }
+                    }));
+                    BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx, version }
+                }
+                StoredEngineApiMessage::NewPayload { payload, .. } => {
+                    let (tx, rx) = oneshot::channel();
+                    this.response.push(Box::pin(async move {
+                        let status =
+                            rx.await.ok().map(|res| StoredEngineApiStatus::from_new_payload(&res));
+                        ReplayResponse { path, expected, status, latency: sent_at.elapsed() }
+                    }));
+                    BeaconEngineMessage::NewPayload { payload, tx }
+                }
+            };
+            return Poll::Ready(Some(msg))
+        }
+
+        // Forward the messages of the inner stream, which usually stays empty during a replay
+        this.stream.poll_next(cx)
+    }
+}
+
+/// Statistics of an [`EngineReplay`].
+#[derive(Debug, Default)]
+struct ReplayStats {
+    /// Number of replayed messages the engine responded to.
+    replayed: usize,
+    /// Number of responses with a different status than the recorded one.
+    diverged: usize,
+    /// Total latency of all responses.
+    total_latency: Duration,
+    /// Highest latency of a response.
+    max_latency: Duration,
+}
+
+impl ReplayStats {
+    /// Reports the response to a replayed message.
+    fn on_response(&mut self, response: ReplayResponse) {
+        let ReplayResponse { path, expected, status, latency } = response;
+        self.replayed += 1;
+        self.total_latency += latency;
+        self.max_latency = self.max_latency.max(latency);
+
+        if expected.is_some() && expected != status {
+            self.diverged += 1;
+            warn!(target: "engine::stream::replay", ?path, ?expected, ?status, ?latency, "Engine API response diverged from the recording");
+        } else {
+            info!(target: "engine::stream::replay", ?path, ?status, ?latency, "Replayed engine API message");
+        }
+    }
+
+    /// Reports the end of the replay.
+    fn finish(&self) {
+        let average_latency = self.total_latency / self.replayed.max(1) as u32;
+        info!(
+            target: "engine::stream::replay",
+            replayed = self.replayed,
+            diverged = self.diverged,
+            ?average_latency,
+            max_latency = ?self.max_latency,
+            "Finished replaying engine API messages"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine_store::EngineStoreStream;
+    use alloy_primitives::B256;
+    use alloy_rpc_types_engine::{
+        ExecutionData, ExecutionPayload, ForkchoiceState, PayloadStatus, PayloadStatusEnum,
+    };
+    use futures::channel::mpsc;
+    use reth_engine_primitives::{ExecutionPayload as _, ForkchoiceStatus, OnForkChoiceUpdated};
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_ethereum_primitives::Block;
+    use reth_payload_primitives::EngineApiMessageVersion;
+
+    #[tokio::test]
+    async fn store_and_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_path_buf();
+
+        let state =
+            ForkchoiceState { head_block_hash: B256::with_last_byte(1), ..Default::default() };
+        let (payload, sidecar) = ExecutionPayload::from_block_slow(&Block::default());
+        let payload = ExecutionData { payload, sidecar };
+
+        // Store a forkchoice update and a payload, with the responses of the engine
+        let (engine_tx, engine_rx) = mpsc::unbounded::<BeaconEngineMessage<EthEngineTypes>>();
+        let mut store = EngineStoreStream::new(engine_rx, path.clone());
+
+        let (tx, fcu_rx) = oneshot::channel();
+        engine_tx
+            .unbounded_send(BeaconEngineMessage::ForkchoiceUpdated {
+                state,
+                payload_attrs: None,
+                tx,
+                version: EngineApiMessageVersion::V3,
+            })
+            .unwrap();
+        let Some(BeaconEngineMessage::ForkchoiceUpdated { tx, version, .. }) = store.next().await
+        else {
+            panic!("expected a forkchoice update")
+        };
+        assert_eq!(version, EngineApiMessageVersion::V3);
+        let status = PayloadStatus::from_status(PayloadStatusEnum::Valid);
+        tx.send(Ok(OnForkChoiceUpdated::valid(status.clone()))).unwrap();
+
+        let (tx, new_payload_rx) = oneshot::channel();
+        engine_tx
+            .unbounded_send(BeaconEngineMessage::NewPayload { payload: payload.clone(), tx })
+            .unwrap();
+        let Some(BeaconEngineMessage::NewPayload { tx, .. }) = store.next().await else {
+            panic!("expected a new payload")
+        };
+        tx.send(Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing))).unwrap();
+
+        // The responses are stored and forwarded once the stream is polled again
+        drop(engine_tx);
+        assert!(store.next().await.is_none());
+        assert_eq!(fcu_rx.await.unwrap().unwrap().forkchoice_status(), ForkchoiceStatus::Valid);
+        assert_eq!(new_payload_rx.await.unwrap().unwrap().status, PayloadStatusEnum::Syncing);
+
+        // Replay the stored messages, with a diverging response to the payload
+        let (_engine_tx, engine_rx) = mpsc::unbounded::<BeaconEngineMessage<EthEngineTypes>>();
+        let mut replay = EngineReplay::new(engine_rx, path, 0).unwrap();
+
+        let Some(BeaconEngineMessage::ForkchoiceUpdated {
+            state: replayed_state, tx, version, ..
+        }) = replay.next().await
+        else {
+            panic!("expected a forkchoice update")
+        };
+        assert_eq!(replayed_state, state);
+        assert_eq!(version, EngineApiMessageVersion::V3);
+        tx.send(Ok(OnForkChoiceUpdated::valid(status))).unwrap();
+
+        let Some(BeaconEngineMessage::NewPayload { payload: replayed_payload, tx }) =
+            replay.next().await
+        else {
+            panic!("expected a new payload")
+        };
+        assert_eq!(replayed_payload.block_hash(), payload.block_hash());
+        tx.send(Ok(PayloadStatus::from_status(PayloadStatusEnum::Valid))).unwrap();
+
+        assert!(futures::poll!(replay.next()).is_pending());
+        assert_eq!(replay.stats.replayed, 2);
+        assert_eq!(replay.stats.diverged, 1);
+    }
+}
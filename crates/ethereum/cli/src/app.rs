@@ -166,8 +166,9 @@ where
         Commands::ExportEra(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
         Commands::DumpGenesis(command) => runner.run_blocking_until_ctrl_c(command.execute()),
         Commands::Db(command) => {
-            runner.run_blocking_command_until_exit(|ctx| command.execute::<N>(ctx))
+            runner.run_blocking_command_until_exit(|ctx| command.execute::<N, _>(ctx, components))
         }
+        Commands::ExEx(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
         Commands::Download(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
         Commands::Stage(command) => {
             runner.run_command_until_exit(|ctx| command.execute::<N, _>(ctx, components))
@@ -178,6 +179,12 @@ where
         #[cfg(feature = "dev")]
         Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
         Commands::ReExecute(command) => runner.run_until_ctrl_c(command.execute::<N>(components)),
+        Commands::Stateless(command) => {
+            runner.run_blocking_until_ctrl_c(command.execute::<N>(components))
+        }
+        Commands::Engine(command) => runner.run_command_until_exit(|ctx| {
+            command.execute(ctx, FnLauncher::new::<C, Ext>(launcher))
+        }),
         Commands::Ext(command) => command.execute(runner),
     }
 }
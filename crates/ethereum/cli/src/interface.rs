@@ -3,13 +3,15 @@
 use crate::{
     app::{run_commands_with, CliApp},
     chainspec::EthereumChainSpecParser,
+    stateless,
 };
 use clap::{Parser, Subcommand};
 use reth_chainspec::{ChainSpec, EthChainSpec, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
     common::{CliComponentsBuilder, CliNodeTypes, HeaderMut},
-    config_cmd, db, download, dump_genesis, export_era, import, import_era, init_cmd, init_state,
+    config_cmd, db, download, dump_genesis, engine, exex, export_era, import, import_era, init_cmd,
+    init_state,
     launcher::FnLauncher,
     node::{self, NoArgs},
     p2p, prune, re_execute, stage,
@@ -277,6 +279,9 @@ pub enum Commands<
     /// Database debugging utilities
     #[command(name = "db")]
     Db(Box<db::Command<C>>),
+    /// `ExEx` debugging utilities
+    #[command(name = "exex")]
+    ExEx(exex::Command<C>),
     /// Download public node snapshots
     #[command(name = "download")]
     Download(download::DownloadCommand<C>),
@@ -299,6 +304,12 @@ pub enum Commands<
     /// Re-execute blocks in parallel to verify historical sync correctness.
     #[command(name = "re-execute")]
     ReExecute(re_execute::Command<C>),
+    /// Export and validate blocks for stateless validation.
+    #[command(name = "stateless")]
+    Stateless(stateless::Command<C>),
+    /// Engine API debugging utilities
+    #[command(name = "engine")]
+    Engine(Box<engine::Command<C, Ext>>),
     /// Extension subcommands provided by consumers.
     #[command(flatten)]
     Ext(SubCmd),
@@ -331,6 +342,7 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug, SubCmd: Subcommand + fmt:
             Self::ImportEra(cmd) => cmd.chain_spec(),
             Self::DumpGenesis(cmd) => cmd.chain_spec(),
             Self::Db(cmd) => cmd.chain_spec(),
+            Self::ExEx(cmd) => cmd.chain_spec(),
             Self::Download(cmd) => cmd.chain_spec(),
             Self::Stage(cmd) => cmd.chain_spec(),
             Self::P2P(cmd) => cmd.chain_spec(),
@@ -339,6 +351,8 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug, SubCmd: Subcommand + fmt:
             Self::Config(_) => None,
             Self::Prune(cmd) => cmd.chain_spec(),
             Self::ReExecute(cmd) => cmd.chain_spec(),
+            Self::Stateless(cmd) => cmd.chain_spec(),
+            Self::Engine(cmd) => cmd.chain_spec(),
             Self::Ext(_) => None,
         }
     }
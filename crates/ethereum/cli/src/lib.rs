@@ -13,6 +13,7 @@ pub mod app;
 /// Chain specification parser.
 pub mod chainspec;
 pub mod interface;
+pub mod stateless;
 
 pub use app::{CliApp, ExtendedCommand};
 pub use interface::{Cli, Commands, NoSubCmd};
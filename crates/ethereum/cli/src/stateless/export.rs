@@ -0,0 +1,179 @@
+//! Command that exports the stateless validation inputs of a range of blocks.
+
+use super::StatelessBlockInput;
+use alloy_genesis::ChainConfig;
+use alloy_primitives::BlockNumber;
+use alloy_rlp::Decodable;
+use clap::Parser;
+use eyre::WrapErr;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::{
+    AccessRights, CliComponentsBuilder, CliNodeComponents, CliNodeTypes, Environment,
+    EnvironmentArgs,
+};
+use reth_ethereum_primitives::{Block, TransactionSigned};
+use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_primitives_traits::NodePrimitives;
+use reth_provider::{
+    BlockNumReader, BlockReader, ChainSpecProvider, StateProofProvider, StateProviderFactory,
+    TransactionVariant,
+};
+use reth_revm::{database::StateProviderDatabase, witness::ExecutionWitnessRecord, State};
+use reth_stateless::{ExecutionWitness, StatelessInput, UncompressedPublicKey};
+use secp256k1::{
+    ecdsa::{RecoverableSignature, RecoveryId},
+    Message, SECP256K1,
+};
+use std::{path::PathBuf, sync::Arc};
+use tracing::info;
+
+/// `reth stateless export` command
+///
+/// Re-executes each block on top of its parent state and writes the block, its execution witness
+/// (as returned by `debug_executionWitness`), the ancestor headers and the chain config to a
+/// self-contained `<number>.bin` file in the output directory.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The first block to export.
+    #[arg(long, default_value = "1")]
+    from: u64,
+
+    /// The last block to export. Defaults to the latest block.
+    #[arg(long)]
+    to: Option<u64>,
+
+    /// The directory to write the exported files to.
+    #[arg(long, short, value_name = "DIR")]
+    output: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `stateless export` command
+    pub async fn execute<N>(self, components: impl CliComponentsBuilder<N>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+
+        let chain_spec = provider_factory.chain_spec();
+        let components = components(chain_spec.clone());
+        let evm_config = components.evm_config();
+
+        let mut chain_config = chain_spec.genesis().config.clone();
+        // The deposit contract of the built-in chains is not part of their genesis config
+        if chain_config.deposit_contract_address.is_none() {
+            chain_config.deposit_contract_address =
+                chain_spec.deposit_contract().map(|contract| contract.address);
+        }
+
+        let best_block = provider_factory.best_block_number()?;
+        let to = self.to.unwrap_or(best_block);
+        if self.from == 0 {
+            eyre::bail!("The genesis block cannot be validated statelessly");
+        }
+        if to > best_block {
+            eyre::bail!("Requested --to {to} is beyond the best block {best_block}");
+        }
+        if self.from > to {
+            eyre::bail!("Invalid block range {}..={to}", self.from);
+        }
+
+        reth_fs_util::create_dir_all(&self.output)?;
+
+        for number in self.from..=to {
+            let input = export_block(&provider_factory, evm_config, &chain_config, number)?;
+
+            let path = self.output.join(format!("{number}.bin"));
+            input.write(&path)?;
+
+            info!(
+                target: "reth::cli",
+                number,
+                gas_used = input.input.block.header.gas_used,
+                state_nodes = input.input.witness.state.len(),
+                codes = input.input.witness.codes.len(),
+                headers = input.input.witness.headers.len(),
+                path = %path.display(),
+                "Exported block"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// Builds the stateless validation input of the block with the given number.
+///
+/// Same as `debug_executionWitness`: the block is executed on top of its parent state, and the
+/// witness is built for the state that was accessed.
+pub(super) fn export_block<P, E>(
+    provider: &P,
+    evm_config: &E,
+    chain_config: &ChainConfig,
+    number: BlockNumber,
+) -> eyre::Result<StatelessBlockInput>
+where
+    P: BlockReader + StateProviderFactory,
+    E: ConfigureEvm<Primitives: NodePrimitives<Block = P::Block>>,
+{
+    let block = provider
+        .recovered_block(number.into(), TransactionVariant::NoHash)?
+        .ok_or_else(|| eyre::eyre!("Block {number} not found"))?;
+
+    let state_provider = provider.history_by_block_number(number - 1)?;
+    let mut witness_record = ExecutionWitnessRecord::default();
+    evm_config
+        .executor(StateProviderDatabase(&state_provider))
+        .execute_with_state_closure(&block, |statedb: &State<_>| {
+            witness_record.record_executed_state(statedb);
+        })
+        .wrap_err_with(|| format!("Failed to execute block {number}"))?;
+
+    let ExecutionWitnessRecord { hashed_state, codes, keys, lowest_block_number } = witness_record;
+    let state = state_provider.witness(Default::default(), hashed_state)?;
+
+    // Include only the parent header, if there were no calls to the BLOCKHASH opcode.
+    let headers = provider
+        .headers_range(lowest_block_number.unwrap_or(number - 1)..number)?
+        .iter()
+        .map(|header| alloy_rlp::encode(header).into())
+        .collect();
+
+    // The stateless validation is only defined for Ethereum blocks
+    let block = Block::decode(&mut alloy_rlp::encode(block.into_block()).as_slice())
+        .wrap_err("Only Ethereum blocks can be exported for stateless validation")?;
+    let public_keys =
+        block.body.transactions.iter().map(recover_public_key).collect::<eyre::Result<Vec<_>>>()?;
+
+    Ok(StatelessBlockInput {
+        input: StatelessInput {
+            block,
+            witness: ExecutionWitness { state, codes, keys, headers },
+            chain_config: chain_config.clone(),
+        },
+        public_keys,
+    })
+}
+
+/// Recovers the uncompressed public key of the transaction signer.
+fn recover_public_key(tx: &TransactionSigned) -> eyre::Result<UncompressedPublicKey> {
+    let signature = tx.signature();
+    let recoverable = RecoverableSignature::from_compact(
+        &signature.as_bytes()[..64],
+        RecoveryId::try_from(signature.v() as i32)?,
+    )?;
+    let message = Message::from_digest(tx.signature_hash().0);
+    let public_key = SECP256K1.recover_ecdsa(&message, &recoverable)?;
+    Ok(UncompressedPublicKey(public_key.serialize_uncompressed()))
+}
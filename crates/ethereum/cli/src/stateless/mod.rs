@@ -0,0 +1,215 @@
+//! `reth stateless` command.
+
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::{CliComponentsBuilder, CliNodeTypes};
+use reth_stateless::{StatelessInput, UncompressedPublicKey};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+    sync::Arc,
+};
+
+mod export;
+mod validate;
+
+/// `reth stateless` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(subcommand)]
+    pub command: Subcommands<C>,
+}
+
+/// `reth stateless` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands<C: ChainSpecParser> {
+    /// Exports the inputs for stateless validation of a range of blocks
+    Export(export::Command<C>),
+    /// Validates exported blocks statelessly, without a database
+    Validate(validate::Command),
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `stateless` command
+    pub async fn execute<N>(self, components: impl CliComponentsBuilder<N>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        match self.command {
+            Subcommands::Export(command) => command.execute::<N>(components).await,
+            Subcommands::Validate(command) => command.execute().await,
+        }
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        match self.command {
+            Subcommands::Export(ref command) => command.chain_spec(),
+            Subcommands::Validate(_) => None,
+        }
+    }
+}
+
+/// Self-contained input for the stateless validation of a single block.
+///
+/// Written by `reth stateless export` and read by `reth stateless validate`, bincode encoded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatelessBlockInput {
+    /// The block, its execution witness including the ancestor headers, and the chain config.
+    pub input: StatelessInput,
+    /// The public keys of the transaction signers, in transaction order.
+    pub public_keys: Vec<UncompressedPublicKey>,
+}
+
+impl StatelessBlockInput {
+    /// Reads the input from the file at the given path.
+    pub fn read(path: &Path) -> eyre::Result<Self> {
+        let file = reth_fs_util::open(path)?;
+        Ok(bincode::deserialize_from(BufReader::new(file))?)
+    }
+
+    /// Writes the input to the file at the given path.
+    pub fn write(&self, path: &Path) -> eyre::Result<()> {
+        let mut writer = BufWriter::new(reth_fs_util::create_file(path)?);
+        bincode::serialize_into(&mut writer, self)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{
+        constants::ETH_TO_WEI, proofs, Header, TxEip1559, EMPTY_OMMER_ROOT_HASH,
+    };
+    use alloy_genesis::{ChainConfig, Genesis, GenesisAccount};
+    use alloy_primitives::{Address, TxKind, U256};
+    use reth_chainspec::{ChainSpec, MIN_TRANSACTION_GAS};
+    use reth_db_common::init::init_genesis;
+    use reth_ethereum_primitives::{Block, BlockBody, Receipt, Transaction};
+    use reth_evm::execute::Executor;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::{crypto::secp256k1::public_key_to_address, Block as _};
+    use reth_provider::{
+        test_utils::create_test_provider_factory_with_chain_spec, BlockWriter,
+        HashedPostStateProvider, StateProviderFactory, StateRootProvider,
+    };
+    use reth_revm::database::StateProviderDatabase;
+    use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
+
+    #[test]
+    fn export_validate_roundtrip() {
+        let key_pair = generators::generate_key(&mut generators::rng());
+        let sender = public_key_to_address(key_pair.public_key());
+
+        let genesis = Genesis {
+            config: ChainConfig {
+                chain_id: 1,
+                homestead_block: Some(0),
+                eip150_block: Some(0),
+                eip155_block: Some(0),
+                eip158_block: Some(0),
+                byzantium_block: Some(0),
+                constantinople_block: Some(0),
+                petersburg_block: Some(0),
+                istanbul_block: Some(0),
+                berlin_block: Some(0),
+                london_block: Some(0),
+                merge_netsplit_block: Some(0),
+                shanghai_time: Some(0),
+                terminal_total_difficulty: Some(U256::ZERO),
+                terminal_total_difficulty_passed: true,
+                ..Default::default()
+            },
+            gas_limit: 30_000_000,
+            alloc: [(
+                sender,
+                GenesisAccount { balance: U256::from(ETH_TO_WEI), ..Default::default() },
+            )]
+            .into(),
+            ..Default::default()
+        };
+        let chain_spec = Arc::new(ChainSpec::from_genesis(genesis.clone()));
+        let evm_config = EthEvmConfig::new(chain_spec.clone());
+
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(&provider_factory).unwrap();
+
+        // Block 1 transfers some ETH, execute it once to fill in the roots of the header
+        let parent = chain_spec.genesis_header();
+        let timestamp = parent.timestamp + 12;
+        let transaction = sign_tx_with_key_pair(
+            key_pair,
+            Transaction::Eip1559(TxEip1559 {
+                chain_id: 1,
+                nonce: 0,
+                gas_limit: MIN_TRANSACTION_GAS,
+                max_fee_per_gas: 10_000_000_000,
+                max_priority_fee_per_gas: 1_000_000_000,
+                to: TxKind::Call(Address::with_last_byte(1)),
+                value: U256::from(ETH_TO_WEI / 10),
+                ..Default::default()
+            }),
+        );
+        let mut block = Block {
+            header: Header {
+                parent_hash: chain_spec.genesis_hash(),
+                ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                beneficiary: Address::with_last_byte(2),
+                transactions_root: proofs::calculate_transaction_root(&[transaction.clone()]),
+                withdrawals_root: Some(proofs::calculate_withdrawals_root(&[])),
+                number: 1,
+                gas_limit: parent.gas_limit,
+                timestamp,
+                base_fee_per_gas: chain_spec.next_block_base_fee(parent, timestamp),
+                ..Default::default()
+            },
+            body: BlockBody {
+                transactions: vec![transaction],
+                ommers: Vec::new(),
+                withdrawals: Some(Default::default()),
+            },
+        };
+
+        let output = evm_config
+            .executor(StateProviderDatabase::new(provider_factory.latest().unwrap()))
+            .execute(&block.clone().try_into_recovered().unwrap())
+            .unwrap();
+        block.header.gas_used = output.gas_used;
+        block.header.receipts_root = Receipt::calculate_receipt_root_no_memo(&output.receipts);
+        block.header.state_root = provider_factory
+            .latest()
+            .unwrap()
+            .state_root(provider_factory.hashed_post_state(&output.state))
+            .unwrap();
+
+        let block = block.try_into_recovered().unwrap();
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        provider_rw.insert_block(&block).unwrap();
+        provider_rw.commit().unwrap();
+
+        let input =
+            export::export_block(&provider_factory, &evm_config, &genesis.config, 1).unwrap();
+        assert_eq!(input.public_keys.len(), 1);
+        assert_eq!(input.input.witness.headers.len(), 1);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("1.bin");
+        input.write(&path).unwrap();
+
+        let (hash, output) =
+            validate::validate_block(StatelessBlockInput::read(&path).unwrap()).unwrap();
+        assert_eq!(hash, block.hash());
+        assert_eq!(output.gas_used, MIN_TRANSACTION_GAS);
+
+        // A witness that is missing the parent header is rejected
+        let mut input = StatelessBlockInput::read(&path).unwrap();
+        input.input.witness.headers.clear();
+        assert!(validate::validate_block(input).is_err());
+    }
+}
@@ -0,0 +1,94 @@
+//! Command that validates exported blocks statelessly.
+
+use super::StatelessBlockInput;
+use clap::Parser;
+use eyre::WrapErr;
+
+use reth_chainspec::ChainSpec;
+use reth_ethereum_primitives::Receipt;
+use reth_evm::execute::BlockExecutionOutput;
+use reth_evm_ethereum::EthEvmConfig;
+use reth_primitives_traits::format_gas_throughput;
+use reth_stateless::{stateless_validation, Genesis, StatelessInput};
+use std::{path::PathBuf, sync::Arc, time::Instant};
+use tracing::info;
+
+/// `reth stateless validate` command
+///
+/// Runs the stateless validation of the files written by `reth stateless export`. Only the
+/// contents of each file are used, no database is opened.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The exported files to validate.
+    #[arg(required = true, value_name = "FILE")]
+    files: Vec<PathBuf>,
+}
+
+impl Command {
+    /// Execute `stateless validate` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let mut total_gas = 0;
+        let start = Instant::now();
+
+        for path in &self.files {
+            let input = StatelessBlockInput::read(path)
+                .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+
+            let block = &input.input.block;
+            let number = block.header.number;
+            let state_root = block.header.state_root;
+            let transactions = block.body.transactions.len();
+            let witness_nodes = input.input.witness.state.len();
+
+            let block_start = Instant::now();
+            let (hash, output) = validate_block(input)?;
+            let elapsed = block_start.elapsed();
+            total_gas += output.gas_used;
+
+            info!(
+                target: "reth::cli",
+                number,
+                %hash,
+                transactions,
+                witness_nodes,
+                gas_used = output.gas_used,
+                %state_root,
+                ?elapsed,
+                throughput = %format_gas_throughput(output.gas_used, elapsed),
+                "Validated block"
+            );
+        }
+
+        let elapsed = start.elapsed();
+        info!(
+            target: "reth::cli",
+            blocks = self.files.len(),
+            total_gas,
+            ?elapsed,
+            throughput = %format_gas_throughput(total_gas, elapsed),
+            "Validated all blocks"
+        );
+
+        Ok(())
+    }
+}
+
+/// Runs the stateless validation of the input, returning the block hash and execution output.
+pub(super) fn validate_block(
+    input: StatelessBlockInput,
+) -> eyre::Result<(B256, BlockExecutionOutput<Receipt>)> {
+    let StatelessBlockInput { input: StatelessInput { block, witness, chain_config }, public_keys } =
+        input;
+
+    let chain_spec =
+        Arc::new(ChainSpec::from_genesis(Genesis { config: chain_config, ..Default::default() }));
+    let number = block.header.number;
+    stateless_validation(
+        block,
+        public_keys,
+        witness,
+        chain_spec.clone(),
+        EthEvmConfig::new(chain_spec),
+    )
+    .wrap_err_with(|| format!("Stateless validation of block {number} failed"))
+}
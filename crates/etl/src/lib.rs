@@ -5,6 +5,11 @@
 //!
 //! This has multiple uses, such as optimizing database inserts (for Btree based databases) and
 //! memory management (as it moves the buffer to disk instead of memory).
+//!
+//! Files can optionally be compressed with zstd, see [`Collector::with_compression`]. The shared
+//! dictionaries of `reth-zstd-compressors` are trained on transactions and receipts, while the
+//! collected entries are arbitrary table keys and values, so the files are compressed without a
+//! dictionary.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -17,17 +22,96 @@
 use std::{
     cmp::Reverse,
     collections::BinaryHeap,
-    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    io::{self, BufReader, BufWriter, Cursor, Read, Seek, SeekFrom, Write},
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
 };
 
 /// Key len and Value len encode use [`usize::to_be_bytes()`] the length is 8.
 const KV_LEN: usize = 8;
 
+/// The number of entries that are compressed together as one block of a compressed ETL file.
+///
+/// Blocks are compressed in parallel, and decompressed one at a time while iterating.
+const COMPRESSION_BLOCK_ENTRIES: usize = 16 * 1024;
+
+/// The zstd compression level of ETL files, favoring speed as the files are short-lived.
+const COMPRESSION_LEVEL: i32 = 1;
+
 use rayon::prelude::*;
 use reth_db_api::table::{Compress, Encode, Key, Value};
+use sysinfo::Disks;
 use tempfile::{NamedTempFile, TempDir};
 
+/// A maximum total size in bytes of ETL files, shared by multiple collectors.
+///
+/// Collectors reserve the size of each file before writing it, and release their reservations
+/// when they're cleared or dropped. Reserving also fails if the file doesn't fit in the available
+/// space of the disk.
+#[derive(Debug, Clone)]
+pub struct DiskBudget {
+    /// Maximum total size in bytes of the files
+    max: u64,
+    /// Total size in bytes of the files of all collectors sharing the budget
+    used: Arc<AtomicU64>,
+}
+
+impl DiskBudget {
+    /// Creates a new disk budget of the given size in bytes.
+    pub fn new(max: u64) -> Self {
+        Self { max, used: Arc::default() }
+    }
+
+    /// Returns the total size in bytes of the files of all collectors sharing the budget.
+    pub fn used(&self) -> u64 {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    /// Reserves the given size for a file that will be written to the given directory.
+    fn reserve(&self, size: u64, dir: &Path) -> io::Result<()> {
+        self.used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                (used + size <= self.max).then_some(used + size)
+            })
+            .map_err(|used| {
+                io::Error::other(format!(
+                    "ETL files would take {} bytes, exceeding the disk budget of {} bytes",
+                    used + size,
+                    self.max
+                ))
+            })?;
+
+        if let Some(available) = available_space(dir) &&
+            available < size
+        {
+            self.release(size);
+            return Err(io::Error::other(format!(
+                "ETL file of {size} bytes doesn't fit in the {available} bytes available on disk"
+            )))
+        }
+
+        Ok(())
+    }
+
+    /// Releases a reservation of the given size.
+    fn release(&self, size: u64) {
+        self.used.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+/// Returns the available space in bytes of the disk that contains the given path, if it's known.
+fn available_space(path: &Path) -> Option<u64> {
+    let path = path.canonicalize().ok()?;
+    Disks::new_with_refreshed_list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
 /// An ETL (extract, transform, load) data collector.
 ///
 /// Data is pushed (extract) to the collector which internally flushes the data in a sorted
@@ -57,6 +141,12 @@ where
     buffer: Vec<(<K as Encode>::Encoded, <V as Compress>::Compressed)>,
     /// Total number of elements in the collector, including all files
     len: usize,
+    /// Whether the files are compressed
+    compression: bool,
+    /// Disk budget the files are reserved in, if any
+    disk_budget: Option<DiskBudget>,
+    /// Total size in bytes of the files
+    disk_size: u64,
 }
 
 impl<K, V> Collector<K, V>
@@ -76,9 +166,30 @@ where
             buffer_capacity_bytes,
             buffer: Vec::new(),
             len: 0,
+            compression: false,
+            disk_budget: None,
+            disk_size: 0,
         }
     }
 
+    /// Sets whether the files are compressed with zstd.
+    ///
+    /// Compression trades CPU time for less disk usage. The buffer is compressed in blocks on
+    /// multiple threads when it's flushed.
+    pub const fn with_compression(mut self, compression: bool) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets the disk budget of the files of this collector.
+    ///
+    /// The budget can be shared with other collectors by cloning it. Flushing a buffer that would
+    /// exceed the budget fails before anything is written.
+    pub fn with_disk_budget(mut self, disk_budget: Option<DiskBudget>) -> Self {
+        self.disk_budget = disk_budget;
+        self
+    }
+
     /// Returns number of elements currently in the collector.
     pub const fn len(&self) -> usize {
         self.len
@@ -97,6 +208,10 @@ where
         self.buffer = Vec::new();
         self.buffer_size_bytes = 0;
         self.len = 0;
+        if let Some(disk_budget) = &self.disk_budget {
+            disk_budget.release(self.disk_size);
+        }
+        self.disk_size = 0;
     }
 
     /// Insert an entry into the collector.
@@ -136,12 +251,26 @@ where
         let mut buf = Vec::with_capacity(self.buffer.len());
         std::mem::swap(&mut buf, &mut self.buffer);
 
+        let blocks =
+            if self.compression { compress_blocks(&buf)? } else { EtlBlocks::Uncompressed(buf) };
+
+        let size = blocks.size();
         let path = self.dir()?.path().to_path_buf();
-        self.files.push(EtlFile::new(path.as_path(), buf)?);
+        if let Some(disk_budget) = &self.disk_budget {
+            disk_budget.reserve(size, &path)?;
+        }
+        // Account for the reservation before writing, so it's released even if writing fails
+        self.disk_size += size;
+        self.files.push(EtlFile::new(path.as_path(), blocks)?);
 
         Ok(())
     }
 
+    /// Returns the total size in bytes of the files of the collector.
+    pub const fn disk_size(&self) -> u64 {
+        self.disk_size
+    }
+
     /// Returns an iterator over the collector data.
     ///
     /// The items of the iterator are sorted across all underlying files.
@@ -167,6 +296,18 @@ where
     }
 }
 
+impl<K, V> Drop for Collector<K, V>
+where
+    K: Encode + Ord,
+    V: Compress,
+{
+    fn drop(&mut self) {
+        if let Some(disk_budget) = &self.disk_budget {
+            disk_budget.release(self.disk_size);
+        }
+    }
+}
+
 /// Type alias for the items stored in the heap of [`EtlIter`].
 ///
 /// Each item in the heap is a tuple containing:
@@ -216,39 +357,134 @@ impl Iterator for EtlIter<'_> {
     }
 }
 
+/// The sorted entries of a buffer, ready to be written to an [`EtlFile`].
+#[derive(Debug)]
+enum EtlBlocks<K, V> {
+    /// The entries, written as they are.
+    Uncompressed(Vec<(K, V)>),
+    /// The entries, encoded and compressed in blocks.
+    Compressed {
+        /// The number of entries.
+        len: usize,
+        /// The compressed blocks.
+        blocks: Vec<CompressedBlock>,
+    },
+}
+
+impl<K: AsRef<[u8]>, V: AsRef<[u8]>> EtlBlocks<K, V> {
+    /// Returns the size in bytes of the entries when written to a file.
+    fn size(&self) -> u64 {
+        match self {
+            Self::Uncompressed(entries) => entries
+                .iter()
+                .map(|(k, v)| (2 * KV_LEN + k.as_ref().len() + v.as_ref().len()) as u64)
+                .sum(),
+            Self::Compressed { blocks, .. } => {
+                blocks.iter().map(|block| (2 * KV_LEN + block.data.len()) as u64).sum()
+            }
+        }
+    }
+}
+
+/// A block of encoded entries, compressed with zstd.
+#[derive(Debug)]
+struct CompressedBlock {
+    /// The size in bytes of the encoded entries.
+    raw_len: usize,
+    /// The compressed entries.
+    data: Vec<u8>,
+}
+
+/// Encodes and compresses the entries in blocks of [`COMPRESSION_BLOCK_ENTRIES`] in parallel.
+fn compress_blocks<K, V>(entries: &[(K, V)]) -> io::Result<EtlBlocks<K, V>>
+where
+    K: AsRef<[u8]> + Sync,
+    V: AsRef<[u8]> + Sync,
+{
+    let blocks = entries
+        .par_chunks(COMPRESSION_BLOCK_ENTRIES)
+        .map(|chunk| {
+            let mut raw = Vec::new();
+            for (k, v) in chunk {
+                write_entry(&mut raw, k.as_ref(), v.as_ref())?;
+            }
+            let data = zstd::bulk::compress(&raw, COMPRESSION_LEVEL)?;
+            Ok(CompressedBlock { raw_len: raw.len(), data })
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+
+    Ok(EtlBlocks::Compressed { len: entries.len(), blocks })
+}
+
+/// Writes a key-value pair prefixed with their lengths.
+fn write_entry(w: &mut impl Write, key: &[u8], value: &[u8]) -> io::Result<()> {
+    w.write_all(&key.len().to_be_bytes())?;
+    w.write_all(&value.len().to_be_bytes())?;
+    w.write_all(key)?;
+    w.write_all(value)
+}
+
+/// Reads a key-value pair written by [`write_entry`].
+fn read_entry(r: &mut impl Read) -> io::Result<(Vec<u8>, Vec<u8>)> {
+    let mut buffer_key_length = [0; KV_LEN];
+    let mut buffer_value_length = [0; KV_LEN];
+
+    r.read_exact(&mut buffer_key_length)?;
+    r.read_exact(&mut buffer_value_length)?;
+
+    let key_length = usize::from_be_bytes(buffer_key_length);
+    let value_length = usize::from_be_bytes(buffer_value_length);
+    let mut key = vec![0; key_length];
+    let mut value = vec![0; value_length];
+
+    r.read_exact(&mut key)?;
+    r.read_exact(&mut value)?;
+
+    Ok((key, value))
+}
+
 /// A temporary ETL file.
 #[derive(Debug)]
 struct EtlFile {
     file: BufReader<NamedTempFile>,
     len: usize,
+    /// Whether the file consists of compressed blocks.
+    compressed: bool,
+    /// The decompressed block that is currently read, if the file is compressed.
+    block: Cursor<Vec<u8>>,
 }
 
 impl EtlFile {
     /// Create a new file with the given data (which should be pre-sorted) at the given path.
     ///
     /// The file will be a temporary file.
-    pub(crate) fn new<K, V>(dir: &Path, buffer: Vec<(K, V)>) -> std::io::Result<Self>
+    pub(crate) fn new<K, V>(dir: &Path, blocks: EtlBlocks<K, V>) -> std::io::Result<Self>
     where
-        Self: Sized,
         K: AsRef<[u8]>,
         V: AsRef<[u8]>,
     {
         let file = NamedTempFile::new_in(dir)?;
         let mut w = BufWriter::new(file);
-        for entry in &buffer {
-            let k = entry.0.as_ref();
-            let v = entry.1.as_ref();
-
-            w.write_all(&k.len().to_be_bytes())?;
-            w.write_all(&v.len().to_be_bytes())?;
-            w.write_all(k)?;
-            w.write_all(v)?;
-        }
+        let (len, compressed) = match &blocks {
+            EtlBlocks::Uncompressed(entries) => {
+                for (k, v) in entries {
+                    write_entry(&mut w, k.as_ref(), v.as_ref())?;
+                }
+                (entries.len(), false)
+            }
+            EtlBlocks::Compressed { len, blocks } => {
+                for block in blocks {
+                    w.write_all(&block.raw_len.to_be_bytes())?;
+                    w.write_all(&block.data.len().to_be_bytes())?;
+                    w.write_all(&block.data)?;
+                }
+                (*len, true)
+            }
+        };
 
         let mut file = BufReader::new(w.into_inner()?);
         file.seek(SeekFrom::Start(0))?;
-        let len = buffer.len();
-        Ok(Self { file, len })
+        Ok(Self { file, len, compressed, block: Cursor::default() })
     }
 
     /// Read the next entry in the file.
@@ -259,23 +495,35 @@ impl EtlFile {
             return Ok(None)
         }
 
-        let mut buffer_key_length = [0; KV_LEN];
-        let mut buffer_value_length = [0; KV_LEN];
+        let entry = if self.compressed {
+            if self.block.position() == self.block.get_ref().len() as u64 {
+                self.read_block()?;
+            }
+            read_entry(&mut self.block)?
+        } else {
+            read_entry(&mut self.file)?
+        };
+
+        self.len -= 1;
 
-        self.file.read_exact(&mut buffer_key_length)?;
-        self.file.read_exact(&mut buffer_value_length)?;
+        Ok(Some(entry))
+    }
 
-        let key_length = usize::from_be_bytes(buffer_key_length);
-        let value_length = usize::from_be_bytes(buffer_value_length);
-        let mut key = vec![0; key_length];
-        let mut value = vec![0; value_length];
+    /// Reads and decompresses the next block of a compressed file.
+    fn read_block(&mut self) -> std::io::Result<()> {
+        let mut buffer_raw_length = [0; KV_LEN];
+        let mut buffer_data_length = [0; KV_LEN];
 
-        self.file.read_exact(&mut key)?;
-        self.file.read_exact(&mut value)?;
+        self.file.read_exact(&mut buffer_raw_length)?;
+        self.file.read_exact(&mut buffer_data_length)?;
 
-        self.len -= 1;
+        let mut data = vec![0; usize::from_be_bytes(buffer_data_length)];
+        self.file.read_exact(&mut data)?;
 
-        Ok(Some((key, value)))
+        let raw = zstd::bulk::decompress(&data, usize::from_be_bytes(buffer_raw_length))?;
+        self.block = Cursor::new(raw);
+
+        Ok(())
     }
 }
 
@@ -316,4 +564,55 @@ mod tests {
         assert!(collector.is_empty());
         assert!(!temp_dir_path.exists());
     }
+
+    #[test]
+    fn etl_compression() {
+        let mut entries: Vec<_> =
+            (0..100_000).map(|id| (id as TxNumber, TxHash::random())).collect();
+        entries.reverse();
+
+        let mut uncompressed = Collector::new(64 * 1024, None);
+        let mut compressed = Collector::new(64 * 1024, None).with_compression(true);
+        for (k, v) in entries.clone() {
+            uncompressed.insert(k, v).unwrap();
+            compressed.insert(k, v).unwrap();
+        }
+        entries.sort_unstable_by_key(|entry| entry.0);
+
+        let compressed_entries = compressed.iter().unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(compressed_entries.len(), entries.len());
+        for (entry, expected) in compressed_entries.into_iter().zip(entries) {
+            assert_eq!(entry, (expected.0.encode().to_vec(), expected.1.compress().clone()));
+        }
+
+        // Flush the remaining entries of the uncompressed collector
+        uncompressed.iter().unwrap();
+        assert!(compressed.disk_size() < uncompressed.disk_size());
+    }
+
+    #[test]
+    fn etl_disk_budget() {
+        let disk_budget = DiskBudget::new(4096);
+        let mut first = Collector::new(1024, None).with_disk_budget(Some(disk_budget.clone()));
+        let mut second = Collector::new(1024, None).with_disk_budget(Some(disk_budget.clone()));
+
+        // The budget is shared, so the second collector fails once the first one has used most
+        // of it
+        for id in 0..70 {
+            first.insert(TxHash::random(), id as TxNumber).unwrap();
+        }
+        assert!(first.disk_size() > 2048);
+        let result = (0..100)
+            .map(|id| second.insert(TxHash::random(), id as TxNumber))
+            .collect::<io::Result<Vec<_>>>();
+        assert!(result.is_err());
+        assert_eq!(disk_budget.used(), first.disk_size() + second.disk_size());
+        assert!(disk_budget.used() <= 4096);
+
+        // Clearing and dropping collectors releases their files from the budget
+        first.clear();
+        assert_eq!(disk_budget.used(), second.disk_size());
+        drop(second);
+        assert_eq!(disk_budget.used(), 0);
+    }
 }
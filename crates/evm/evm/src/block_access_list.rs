@@ -0,0 +1,283 @@
+//! Recording of EIP-7928 block access lists.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use alloy_eip7928::{
+    AccountChanges, BalanceChange, BlockAccessIndex, BlockAccessList, CodeChange, NonceChange,
+    SlotChanges, StorageChange,
+};
+use alloy_evm::block::StateChangeSource;
+use alloy_primitives::{Address, StorageKey, B256, U256};
+use revm::{
+    database::BundleState,
+    state::{AccountInfo, EvmState},
+};
+
+/// Builds the [`BlockAccessList`] of a block from the state changes of its execution.
+///
+/// The builder is fed with the state of every system call and transaction through
+/// [`Self::on_state`], e.g. from the [`OnStateHook`](crate::OnStateHook) of a block executor, and
+/// finished with the [`BundleState`] of the block, which provides the account values before the
+/// block.
+///
+/// Changes are indexed as defined by EIP-7928: pre-execution system calls at index `0`, the
+/// transaction at position `i` at index `i + 1` and post-execution changes, e.g. withdrawals and
+/// requests, at index `n + 1` for a block with `n` transactions.
+#[derive(Debug, Default)]
+pub struct BlockAccessListBuilder {
+    /// Number of transactions recorded so far.
+    transactions: usize,
+    /// Accounts accessed so far.
+    accounts: BTreeMap<Address, RecordedAccount>,
+}
+
+/// An account accessed during the execution of a block.
+#[derive(Debug, Default)]
+struct RecordedAccount {
+    /// Account info after each index the account was touched at.
+    infos: Vec<(BlockAccessIndex, AccountInfo)>,
+    /// Written slots with their value before and after each index they were written at.
+    writes: BTreeMap<StorageKey, Vec<(BlockAccessIndex, U256, U256)>>,
+    /// Slots that were read without being written.
+    reads: BTreeSet<StorageKey>,
+}
+
+impl BlockAccessListBuilder {
+    /// Records the state of the given source of state changes.
+    pub fn on_state(&mut self, source: StateChangeSource, state: &EvmState) {
+        let index = match source {
+            StateChangeSource::PreBlock(_) => 0,
+            StateChangeSource::Transaction(tx) => {
+                self.transactions = self.transactions.max(tx + 1);
+                tx + 1
+            }
+            StateChangeSource::PostBlock(_) => self.transactions + 1,
+        } as BlockAccessIndex;
+
+        for (address, account) in state {
+            let recorded = self.accounts.entry(*address).or_default();
+            let destroyed = account.is_selfdestructed();
+
+            if account.is_touched() {
+                // A destroyed account no longer exists after the index
+                let info = if destroyed { AccountInfo::default() } else { account.info.clone() };
+                match recorded.infos.last_mut() {
+                    Some((last, last_info)) if *last == index => *last_info = info,
+                    _ => recorded.infos.push((index, info)),
+                }
+            }
+
+            for (slot, value) in &account.storage {
+                let slot = B256::from(*slot);
+                // Writes to a destroyed account are discarded, which makes them reads
+                if !value.is_changed() || destroyed {
+                    recorded.reads.insert(slot);
+                    continue
+                }
+
+                // Multiple system calls share an index, so only the value before the first and
+                // after the last one matter
+                let writes = recorded.writes.entry(slot).or_default();
+                match writes.last_mut() {
+                    Some((last, _, after)) if *last == index => *after = value.present_value,
+                    _ => writes.push((index, value.original_value, value.present_value)),
+                }
+            }
+        }
+    }
+
+    /// Returns the [`BlockAccessList`] of the recorded state changes.
+    ///
+    /// The bundle state must only contain the changes of the recorded block, because its original
+    /// account values are used to tell which of the recorded values are changes.
+    pub fn finish(self, bundle: &BundleState) -> BlockAccessList {
+        self.accounts
+            .into_iter()
+            .map(|(address, account)| {
+                let mut balance_changes = Vec::new();
+                let mut nonce_changes = Vec::new();
+                let mut code_changes = Vec::new();
+
+                // Accounts that are not part of the bundle state didn't change
+                if let Some(bundle_account) = bundle.account(&address) {
+                    let mut previous = bundle_account.original_info.clone().unwrap_or_default();
+                    for (index, info) in account.infos {
+                        if info.balance != previous.balance {
+                            balance_changes.push(BalanceChange::new(index, info.balance));
+                        }
+                        if info.nonce != previous.nonce {
+                            nonce_changes.push(NonceChange::new(index, info.nonce));
+                        }
+                        if info.code_hash != previous.code_hash {
+                            let code = info.code.as_ref().map(|code| code.original_bytes());
+                            code_changes.push(CodeChange::new(index, code.unwrap_or_default()));
+                        }
+                        previous = info;
+                    }
+                }
+
+                let mut storage_reads = account.reads;
+                let mut storage_changes = Vec::new();
+                for (slot, writes) in account.writes {
+                    let changes = writes
+                        .into_iter()
+                        .filter(|(_, before, after)| before != after)
+                        .map(|(index, _, after)| StorageChange::new(index, B256::from(after)))
+                        .collect::<Vec<_>>();
+
+                    // Writes that don't change the value are reads
+                    if changes.is_empty() {
+                        storage_reads.insert(slot);
+                    } else {
+                        storage_reads.remove(&slot);
+                        storage_changes.push(SlotChanges { slot, changes });
+                    }
+                }
+
+                AccountChanges {
+                    address,
+                    storage_changes,
+                    storage_reads: storage_reads.into_iter().collect(),
+                    balance_changes,
+                    nonce_changes,
+                    code_changes,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::constants::KECCAK_EMPTY;
+    use alloy_evm::block::{StateChangePostBlockSource, StateChangePreBlockSource};
+    use revm::state::{Account, AccountStatus, EvmStorageSlot};
+
+    fn account(balance: u64, nonce: u64, storage: Vec<(u64, EvmStorageSlot)>) -> Account {
+        Account {
+            info: AccountInfo {
+                balance: U256::from(balance),
+                nonce,
+                code_hash: KECCAK_EMPTY,
+                code: None,
+            },
+            storage: storage.into_iter().map(|(slot, value)| (U256::from(slot), value)).collect(),
+            status: AccountStatus::Touched,
+            transaction_id: 0,
+        }
+    }
+
+    fn bundle(accounts: Vec<(Address, u64, u64)>) -> BundleState {
+        BundleState::new(
+            accounts.into_iter().map(|(address, balance, nonce)| {
+                let info =
+                    AccountInfo { balance: U256::from(balance), nonce, ..Default::default() };
+                (address, Some(info.clone()), Some(info), Default::default())
+            }),
+            Vec::<Vec<(Address, Option<Option<AccountInfo>>, Vec<(U256, U256)>)>>::new(),
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn records_changes_by_index() {
+        let sender = Address::with_last_byte(1);
+        let contract = Address::with_last_byte(2);
+
+        let mut builder = BlockAccessListBuilder::default();
+        builder.on_state(
+            StateChangeSource::PreBlock(StateChangePreBlockSource::BeaconRootContract),
+            &EvmState::from_iter([(
+                contract,
+                account(0, 1, vec![(1, EvmStorageSlot::new_changed(U256::ZERO, U256::ONE, 0))]),
+            )]),
+        );
+        builder.on_state(
+            StateChangeSource::Transaction(0),
+            &EvmState::from_iter([
+                (sender, account(90, 1, Vec::new())),
+                (
+                    contract,
+                    account(
+                        10,
+                        1,
+                        vec![
+                            (1, EvmStorageSlot::new(U256::ONE, 0)),
+                            (2, EvmStorageSlot::new_changed(U256::ONE, U256::from(2), 0)),
+                        ],
+                    ),
+                ),
+            ]),
+        );
+        builder.on_state(
+            StateChangeSource::PostBlock(StateChangePostBlockSource::BalanceIncrements),
+            &EvmState::from_iter([(sender, account(100, 1, Vec::new()))]),
+        );
+
+        let bal = builder.finish(&bundle(vec![(sender, 100, 0), (contract, 0, 1)]));
+
+        assert_eq!(bal.len(), 2);
+        assert_eq!(bal[0].address, sender);
+        assert_eq!(
+            bal[0].balance_changes,
+            vec![BalanceChange::new(1, U256::from(90)), BalanceChange::new(2, U256::from(100))]
+        );
+        assert_eq!(bal[0].nonce_changes, vec![NonceChange::new(1, 1)]);
+
+        assert_eq!(bal[1].address, contract);
+        assert_eq!(bal[1].balance_changes, vec![BalanceChange::new(1, U256::from(10))]);
+        assert!(bal[1].nonce_changes.is_empty());
+        assert_eq!(
+            bal[1].storage_changes,
+            vec![
+                SlotChanges {
+                    slot: B256::with_last_byte(1),
+                    changes: vec![StorageChange::new(0, B256::with_last_byte(1))]
+                },
+                SlotChanges {
+                    slot: B256::with_last_byte(2),
+                    changes: vec![StorageChange::new(1, B256::with_last_byte(2))]
+                },
+            ]
+        );
+        // Slot 1 was written before, so the read isn't recorded
+        assert!(bal[1].storage_reads.is_empty());
+    }
+
+    #[test]
+    fn unchanged_writes_are_reads() {
+        let address = Address::with_last_byte(1);
+
+        let mut builder = BlockAccessListBuilder::default();
+        // The second system call reverts the write of the first one
+        for (source, before, after) in [
+            (StateChangePreBlockSource::BeaconRootContract, 0, 1),
+            (StateChangePreBlockSource::BlockHashesContract, 1, 0),
+        ] {
+            builder.on_state(
+                StateChangeSource::PreBlock(source),
+                &EvmState::from_iter([(
+                    address,
+                    account(
+                        0,
+                        0,
+                        vec![(
+                            1,
+                            EvmStorageSlot::new_changed(U256::from(before), U256::from(after), 0),
+                        )],
+                    ),
+                )]),
+            );
+        }
+
+        let bal = builder.finish(&bundle(vec![(address, 0, 0)]));
+
+        assert_eq!(bal.len(), 1);
+        assert!(bal[0].storage_changes.is_empty());
+        assert_eq!(bal[0].storage_reads, vec![B256::with_last_byte(1)]);
+        assert!(bal[0].balance_changes.is_empty());
+    }
+}
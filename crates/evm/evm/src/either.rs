@@ -73,6 +73,13 @@ where
         }
     }
 
+    fn take_bundle(&mut self) -> revm::database::BundleState {
+        match self {
+            Self::Left(a) => a.take_bundle(),
+            Self::Right(b) => b.take_bundle(),
+        }
+    }
+
     fn size_hint(&self) -> usize {
         match self {
             Self::Left(a) => a.size_hint(),
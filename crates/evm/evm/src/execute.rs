@@ -142,6 +142,12 @@ pub trait Executor<DB: Database>: Sized {
     /// Consumes the executor and returns the [`State`] containing all state changes.
     fn into_state(self) -> State<DB>;
 
+    /// Takes the state changes of the blocks executed so far.
+    ///
+    /// The cached state is kept, so the executor can continue executing blocks on top of the
+    /// taken changes.
+    fn take_bundle(&mut self) -> BundleState;
+
     /// The size hint of the batch's tracked state size.
     ///
     /// This is used to optimize DB commits depending on the size of the state.
@@ -618,6 +624,10 @@ where
         self.db
     }
 
+    fn take_bundle(&mut self) -> BundleState {
+        self.db.take_bundle()
+    }
+
     fn size_hint(&self) -> usize {
         self.db.bundle_state.size_hint()
     }
@@ -715,6 +725,10 @@ mod tests {
             unreachable!()
         }
 
+        fn take_bundle(&mut self) -> BundleState {
+            unreachable!()
+        }
+
         fn size_hint(&self) -> usize {
             0
         }
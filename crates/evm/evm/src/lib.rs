@@ -37,6 +37,8 @@ use reth_primitives_traits::{
 };
 use revm::{context::TxEnv, database::State};
 
+#[cfg(feature = "std")]
+pub mod block_access_list;
 pub mod either;
 /// EVM environment configuration.
 pub mod execute;
@@ -43,7 +43,8 @@ pub trait ExExNotificationsStream<N: NodePrimitives = EthPrimitives>:
     /// Sets [`ExExNotificationsStream`] to a stream of [`ExExNotification`]s with the provided
     /// head.
     ///
-    /// It's a no-op if the stream has already been configured with a head.
+    /// If the stream has already been configured with a head, it's reset to the provided one and
+    /// catches up from it to the tip of the last notification emitted by the stream.
     ///
     /// See the documentation of [`ExExNotificationsWithHead`] for more details.
     fn set_with_head(&mut self, exex_head: ExExHead);
@@ -112,7 +113,7 @@ where
         self.inner = ExExNotificationsInner::WithoutHead(match current {
             ExExNotificationsInner::WithoutHead(notifications) => notifications,
             ExExNotificationsInner::WithHead(notifications) => ExExNotificationsWithoutHead::new(
-                notifications.initial_local_head,
+                notifications.local_head,
                 notifications.provider,
                 notifications.evm_config,
                 notifications.notifications,
@@ -130,7 +131,7 @@ where
             }
             ExExNotificationsInner::WithHead(notifications) => {
                 Box::new(ExExNotificationsWithHead::new(
-                    notifications.initial_local_head,
+                    notifications.local_head,
                     notifications.provider,
                     notifications.evm_config,
                     notifications.notifications,
@@ -179,6 +180,7 @@ pub struct ExExNotificationsWithoutHead<P, E>
 where
     E: ConfigureEvm,
 {
+    /// The node's head, updated with every notification emitted by the stream.
     node_head: BlockNumHash,
     provider: P,
     evm_config: E,
@@ -234,7 +236,12 @@ where
     type Item = ExExNotification<E::Primitives>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.get_mut().notifications.poll_recv(cx)
+        let this = self.get_mut();
+        let notification = ready!(this.notifications.poll_recv(cx));
+        if let Some(notification) = &notification {
+            this.node_head = resulting_tip(notification);
+        }
+        Poll::Ready(notification)
     }
 }
 
@@ -251,8 +258,9 @@ pub struct ExExNotificationsWithHead<P, E>
 where
     E: ConfigureEvm,
 {
-    /// The node's local head at launch.
-    initial_local_head: BlockNumHash,
+    /// The node's local head. It's the head at launch until the backfill is done, and then
+    /// updated with every notification received from the node.
+    local_head: BlockNumHash,
     provider: P,
     evm_config: E,
     notifications: Receiver<ExExNotification<E::Primitives>>,
@@ -284,7 +292,7 @@ where
         exex_head: ExExHead,
     ) -> Self {
         Self {
-            initial_local_head: node_head,
+            local_head: node_head,
             provider,
             evm_config,
             notifications,
@@ -309,7 +317,7 @@ where
     /// head block.
     fn check_canonical(&mut self) -> eyre::Result<Option<ExExNotification<E::Primitives>>> {
         if self.provider.is_known(self.initial_exex_head.block.hash)? &&
-            self.initial_exex_head.block.number <= self.initial_local_head.number
+            self.initial_exex_head.block.number <= self.local_head.number
         {
             // we have the targeted block and that block is below the current head
             debug!(target: "exex::notifications", "ExEx head is on the canonical chain");
@@ -325,7 +333,7 @@ where
             .get_committed_notification_by_block_hash(&self.initial_exex_head.block.hash)?
         else {
             // it's possible that the exex head is further ahead
-            if self.initial_exex_head.block.number > self.initial_local_head.number {
+            if self.initial_exex_head.block.number > self.local_head.number {
                 debug!(target: "exex::notifications", "ExEx head is ahead of the canonical chain");
                 return Ok(None);
             }
@@ -361,14 +369,12 @@ where
     fn check_backfill(&mut self) -> eyre::Result<()> {
         let backfill_job_factory =
             BackfillJobFactory::new(self.evm_config.clone(), self.provider.clone());
-        match self.initial_exex_head.block.number.cmp(&self.initial_local_head.number) {
+        match self.initial_exex_head.block.number.cmp(&self.local_head.number) {
             std::cmp::Ordering::Less => {
                 // ExEx is behind the node head, start backfill
                 debug!(target: "exex::notifications", "ExEx is behind the node head and on the canonical chain, starting backfill");
                 let backfill = backfill_job_factory
-                    .backfill(
-                        self.initial_exex_head.block.number + 1..=self.initial_local_head.number,
-                    )
+                    .backfill(self.initial_exex_head.block.number + 1..=self.local_head.number)
                     .into_stream();
                 self.backfill_job = Some(backfill);
             }
@@ -429,6 +435,7 @@ where
             let Some(notification) = ready!(this.notifications.poll_recv(cx)) else {
                 return Poll::Ready(None)
             };
+            this.local_head = resulting_tip(&notification);
 
             // 5. In case the exex is ahead of the new tip, we must skip it
             if let Some(committed) = notification.committed_chain() {
@@ -443,6 +450,19 @@ where
     }
 }
 
+/// Returns the tip of the chain after the notification is applied.
+pub(crate) fn resulting_tip<N: NodePrimitives>(notification: &ExExNotification<N>) -> BlockNumHash {
+    match notification {
+        ExExNotification::ChainCommitted { new } | ExExNotification::ChainReorged { new, .. } => {
+            new.tip().num_hash()
+        }
+        ExExNotification::ChainReverted { old } => {
+            let first = old.first();
+            BlockNumHash::new(first.number().saturating_sub(1), first.parent_hash())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -753,4 +773,79 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn exex_notifications_reset_head() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+        let genesis_hash = init_genesis(&provider_factory)?;
+        let provider = BlockchainProvider::new(provider_factory.clone())?;
+        let backfill_job_factory =
+            BackfillJobFactory::new(EthEvmConfig::mainnet(), provider.clone());
+
+        let mut parent = genesis_hash;
+        let mut blocks = Vec::new();
+        let provider_rw = provider_factory.provider_rw()?;
+        for number in 1..=2 {
+            let block = random_block(
+                &mut rng,
+                number,
+                BlockParams { parent: Some(parent), tx_count: Some(0), ..Default::default() },
+            )
+            .try_recover()?;
+            provider_rw.insert_block(&block)?;
+            parent = block.hash();
+            blocks.push(block);
+        }
+        provider_rw.commit()?;
+
+        // The node is launched at block 1, and block 2 is committed afterwards
+        let (notifications_tx, notifications_rx) = mpsc::channel(1);
+        let mut notifications = ExExNotifications::new(
+            blocks[0].num_hash(),
+            provider,
+            EthEvmConfig::mainnet(),
+            notifications_rx,
+            wal.handle(),
+        )
+        .with_head(ExExHead { block: BlockNumHash::new(0, genesis_hash) });
+
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![blocks[1].clone()], Default::default(), None)),
+        };
+        notifications_tx.send(notification.clone()).await?;
+
+        assert_eq!(
+            notifications.next().await.transpose()?,
+            Some(ExExNotification::ChainCommitted {
+                new: Arc::new(
+                    backfill_job_factory
+                        .backfill(1..=1)
+                        .next()
+                        .ok_or_eyre("failed to backfill")??
+                )
+            })
+        );
+        assert_eq!(notifications.next().await.transpose()?, Some(notification));
+
+        // Resetting the head to block 1 catches up to block 2, which was already emitted
+        notifications.set_with_head(ExExHead { block: blocks[0].num_hash() });
+        assert_eq!(
+            notifications.next().await.transpose()?,
+            Some(ExExNotification::ChainCommitted {
+                new: Arc::new(
+                    backfill_job_factory
+                        .backfill(2..=2)
+                        .next()
+                        .ok_or_eyre("failed to backfill")??
+                )
+            })
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,191 @@
+//! Remote `ExEx` transport.
+//!
+//! [`RemoteExEx`] is a regular `ExEx` that serves its notifications to a consumer running in a
+//! separate process over a Unix socket, so that indexers don't have to be compiled into the node
+//! binary.
+//!
+//! # Protocol
+//!
+//! Every message is a frame prefixed with its length as a big-endian `u32`. The payload of every
+//! frame is bincode-encoded.
+//!
+//! 1. The consumer connects and sends a [`RemoteExExRequest::Subscribe`] frame with the head it has
+//!    fully processed, if any.
+//! 2. The node streams [`ExExNotification`]s, encoded with the
+//!    [`serde_bincode_compat`](reth_exex_types::serde_bincode_compat) representation.
+//! 3. The consumer sends [`RemoteExExRequest::FinishedHeight`] frames for the blocks it has
+//!    processed. These are forwarded to the node as [`ExExEvent::FinishedHeight`](crate::ExExEvent)
+//!    events, so the consumer participates in pruning and WAL finalization the same way an
+//!    in-process `ExEx` does.
+//!
+//! Every subscription resumes the notifications stream from the head of the consumer, or from the
+//! last height it acknowledged if it doesn't send a head. The consumer receives everything after
+//! it from the WAL or the database, both after a node restart and when it reconnects while the node
+//! is running. A consumer that subscribes without a head and hasn't acknowledged anything yet only
+//! receives new notifications. While no consumer is connected, no notifications are consumed, which
+//! holds back pruning until the consumer catches up.
+
+use crate::ExExContext;
+use alloy_eips::BlockNumHash;
+use futures::{SinkExt, StreamExt};
+use reth_exex_types::{ExExHead, ExExNotification};
+use reth_node_api::{FullNodeComponents, NodePrimitives};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tokio::net::{UnixListener, UnixStream};
+use tokio_util::{
+    bytes::Bytes,
+    codec::{Framed, LengthDelimitedCodec},
+};
+use tracing::{debug, info, warn};
+
+/// Maximum length of a single frame, large enough for a notification with a long chain of full
+/// blocks.
+const MAX_FRAME_LENGTH: usize = 512 * 1024 * 1024;
+
+/// A request sent by the remote consumer to the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RemoteExExRequest {
+    /// Subscribes to notifications. Must be the first frame sent on a connection.
+    Subscribe {
+        /// The highest block the consumer has fully processed, if any.
+        head: Option<BlockNumHash>,
+    },
+    /// Acknowledges that all notifications up to the given block have been processed.
+    FinishedHeight(BlockNumHash),
+}
+
+/// An `ExEx` that forwards notifications to a remote consumer over a Unix socket.
+///
+/// Only one consumer is served at a time. See the [module documentation](self) for the protocol.
+#[derive(Debug)]
+pub struct RemoteExEx<Node: FullNodeComponents> {
+    ctx: ExExContext<Node>,
+    path: PathBuf,
+    /// The last height acknowledged by a consumer.
+    finished_height: Option<BlockNumHash>,
+}
+
+impl<Node: FullNodeComponents> RemoteExEx<Node> {
+    /// Creates a new remote `ExEx` that listens on the Unix socket at the given path.
+    pub fn new(ctx: ExExContext<Node>, path: impl Into<PathBuf>) -> Self {
+        Self { ctx, path: path.into(), finished_height: None }
+    }
+
+    /// Accepts consumers and streams notifications to them until the node shuts down.
+    pub async fn run(mut self) -> eyre::Result<()> {
+        if self.path.exists() {
+            std::fs::remove_file(&self.path)?;
+        }
+        let listener = UnixListener::bind(&self.path)?;
+        info!(target: "exex::remote", path = ?self.path, "Serving ExEx notifications");
+
+        loop {
+            let (stream, _) = listener.accept().await?;
+            debug!(target: "exex::remote", "Remote ExEx consumer connected");
+
+            match self.serve(stream).await {
+                Ok(true) => return Ok(()),
+                Ok(false) => debug!(target: "exex::remote", "Remote ExEx consumer disconnected"),
+                Err(err) => warn!(target: "exex::remote", %err, "Remote ExEx consumer failed"),
+            }
+        }
+    }
+
+    /// Serves a single consumer.
+    ///
+    /// Returns `true` if the node's notification stream has ended.
+    async fn serve(&mut self, stream: UnixStream) -> eyre::Result<bool> {
+        let codec = LengthDelimitedCodec::builder().max_frame_length(MAX_FRAME_LENGTH).new_codec();
+        let mut framed = Framed::new(stream, codec);
+
+        let head = match framed.next().await {
+            Some(frame) => match bincode::deserialize(&frame?)? {
+                RemoteExExRequest::Subscribe { head } => head,
+                request => eyre::bail!("expected subscription, got {request:?}"),
+            },
+            None => return Ok(false),
+        };
+
+        // The notifications that were sent to a previous consumer may not have been processed, so
+        // the stream is reset to catch up from the head of this one.
+        if let Some(head) = head.or(self.finished_height) {
+            debug!(target: "exex::remote", ?head, "Resuming ExEx notifications");
+            self.ctx.set_notifications_with_head(ExExHead::new(head));
+        }
+
+        loop {
+            tokio::select! {
+                frame = framed.next() => {
+                    let Some(frame) = frame else { return Ok(false) };
+                    match bincode::deserialize(&frame?)? {
+                        RemoteExExRequest::FinishedHeight(height) => {
+                            self.ctx.send_finished_height(height)?;
+                            self.finished_height = Some(height);
+                        }
+                        request => eyre::bail!("unexpected request {request:?}"),
+                    }
+                }
+                notification = self.ctx.notifications.next() => {
+                    let Some(notification) = notification else { return Ok(true) };
+                    framed.send(Bytes::from(encode_notification(&notification?)?)).await?;
+                }
+            }
+        }
+    }
+}
+
+/// Encodes a notification for the remote consumer.
+pub fn encode_notification<N: NodePrimitives>(
+    notification: &ExExNotification<N>,
+) -> bincode::Result<Vec<u8>> {
+    bincode::serialize(&reth_exex_types::serde_bincode_compat::ExExNotification::from(notification))
+}
+
+/// Decodes a notification received from the node.
+pub fn decode_notification<N: NodePrimitives>(
+    bytes: &[u8],
+) -> bincode::Result<ExExNotification<N>> {
+    let notification: reth_exex_types::serde_bincode_compat::ExExNotification<'_, N> =
+        bincode::deserialize(bytes)?;
+    Ok(notification.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notifications::resulting_tip;
+    use reth_ethereum_primitives::EthPrimitives;
+    use reth_provider::Chain;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use std::sync::Arc;
+
+    #[test]
+    fn notification_encoding() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=2, BlockRangeParams::default())
+            .into_iter()
+            .map(|block| block.try_recover())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let committed = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(blocks.clone(), Default::default(), None)),
+        };
+        let reverted = ExExNotification::ChainReverted {
+            old: Arc::new(Chain::new(vec![blocks[2].clone()], Default::default(), None)),
+        };
+
+        // Notifications survive the round trip through the wire encoding
+        for notification in [committed, reverted.clone()] {
+            let decoded =
+                decode_notification::<EthPrimitives>(&encode_notification(&notification)?)?;
+            assert_eq!(decoded, notification);
+        }
+
+        // After a revert, the consumer resumes from the parent of the first reverted block
+        assert_eq!(resulting_tip(&reverted), blocks[1].num_hash());
+
+        Ok(())
+    }
+}
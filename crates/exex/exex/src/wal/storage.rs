@@ -30,13 +30,14 @@ where
 {
     /// Creates a new instance of [`Storage`] backed by the file at the given path and creates
     /// it doesn't exist.
-    pub(super) fn new(path: impl AsRef<Path>) -> WalResult<Self> {
+    pub fn new(path: impl AsRef<Path>) -> WalResult<Self> {
         reth_fs_util::create_dir_all(&path)?;
 
         Ok(Self { path: path.as_ref().to_path_buf(), _pd: std::marker::PhantomData })
     }
 
-    fn file_path(&self, id: u32) -> PathBuf {
+    /// Returns the path of the file with the given ID.
+    pub fn file_path(&self, id: u32) -> PathBuf {
         self.path.join(format!("{id}.{FILE_EXTENSION}"))
     }
 
@@ -72,7 +73,7 @@ where
     /// Returns the range of file IDs in the storage.
     ///
     /// If there are no files in the storage, returns `None`.
-    pub(super) fn files_range(&self) -> WalResult<Option<RangeInclusive<u32>>> {
+    pub fn files_range(&self) -> WalResult<Option<RangeInclusive<u32>>> {
         let mut min_id = None;
         let mut max_id = None;
 
@@ -91,12 +92,30 @@ where
         Ok(min_id.zip(max_id).map(|(min_id, max_id)| min_id..=max_id))
     }
 
+    /// Returns the sorted IDs of all files in the storage.
+    ///
+    /// Unlike [`Self::files_range`], this doesn't assume that the IDs are contiguous.
+    pub fn file_ids(&self) -> WalResult<Vec<u32>> {
+        let mut file_ids = Vec::new();
+
+        for entry in reth_fs_util::read_dir(&self.path)? {
+            let entry = entry.map_err(|err| WalError::DirEntry(self.path.clone(), err))?;
+
+            if entry.path().extension() == Some(FILE_EXTENSION.as_ref()) {
+                file_ids.push(Self::parse_filename(&entry.file_name().to_string_lossy())?);
+            }
+        }
+
+        file_ids.sort_unstable();
+        Ok(file_ids)
+    }
+
     /// Removes notifications from the storage according to the given list of file IDs.
     ///
     /// # Returns
     ///
     /// Number of removed notifications and the total size of the removed files in bytes.
-    pub(super) fn remove_notifications(
+    pub fn remove_notifications(
         &self,
         file_ids: impl IntoIterator<Item = u32>,
     ) -> WalResult<(usize, u64)> {
@@ -126,11 +145,12 @@ where
     }
 
     /// Reads the notification from the file with the given ID.
+    ///
+    /// # Returns
+    ///
+    /// The notification and the size of the file in bytes, if the file exists.
     #[instrument(skip(self))]
-    pub(super) fn read_notification(
-        &self,
-        file_id: u32,
-    ) -> WalResult<Option<(ExExNotification<N>, u64)>> {
+    pub fn read_notification(&self, file_id: u32) -> WalResult<Option<(ExExNotification<N>, u64)>> {
         let file_path = self.file_path(file_id);
         debug!(target: "exex::wal::storage", ?file_path, "Reading notification from WAL");
 
@@ -248,4 +268,24 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_file_ids() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let storage: Storage = Storage::new(&temp_dir)?;
+
+        // Create WAL files with a gap
+        File::create(storage.file_path(5))?;
+        File::create(storage.file_path(1))?;
+        File::create(storage.file_path(2))?;
+
+        // Create non-WAL files that should be ignored
+        File::create(temp_dir.path().join("3.tmp"))?;
+
+        // Check file IDs
+        assert_eq!(storage.file_ids()?, vec![1, 2, 5]);
+        assert_eq!(storage.files_range()?, Some(1..=5));
+
+        Ok(())
+    }
 }
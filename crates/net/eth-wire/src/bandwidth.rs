@@ -0,0 +1,395 @@
+//! Bandwidth accounting and rate limiting for [`P2PStream`](crate::P2PStream)s.
+//!
+//! A [`StreamBandwidth`] can be attached to an established [`P2PStream`](crate::P2PStream). It
+//! records the bytes read from and written to the wire per shared capability in a
+//! [`BandwidthMeter`] and delays subprotocol messages while any of its [`BandwidthLimiter`]s is
+//! exhausted. Messages of the reserved `p2p` capability are accounted but don't count against the
+//! limits.
+
+use crate::{capability::SharedCapabilities, p2pstream::MAX_RESERVED_MESSAGE_ID};
+use reth_metrics::metrics::{counter, Counter};
+use std::{
+    borrow::Cow,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{ready, Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::time::Sleep;
+
+/// Name under which messages of the reserved `p2p` capability are accounted.
+pub const P2P_CAPABILITY_NAME: &str = "p2p";
+
+/// Byte counters of a single stream, one per shared capability and one for the reserved `p2p`
+/// capability.
+///
+/// The meter is cheap to clone, all clones share the same counters.
+#[derive(Debug, Clone)]
+pub struct BandwidthMeter {
+    protocols: Arc<[ProtocolCounters]>,
+}
+
+impl BandwidthMeter {
+    /// Creates a new meter for the given shared capabilities.
+    pub fn new(shared_capabilities: &SharedCapabilities) -> Self {
+        let protocols =
+            std::iter::once(ProtocolCounters::new(Cow::Borrowed(P2P_CAPABILITY_NAME), 0))
+                .chain(shared_capabilities.iter_caps().map(|cap| {
+                    ProtocolCounters::new(
+                        Cow::Owned(cap.name().to_string()),
+                        cap.message_id_offset(),
+                    )
+                }))
+                .collect();
+        Self { protocols }
+    }
+
+    /// Records `len` bytes received for the message with the given multiplexed message id.
+    pub fn record_ingress(&self, message_id: u8, len: usize) {
+        let protocol = self.protocol(message_id);
+        protocol.ingress.fetch_add(len as u64, Ordering::Relaxed);
+        protocol.ingress_total.increment(len as u64);
+    }
+
+    /// Records `len` bytes sent for the message with the given multiplexed message id.
+    pub fn record_egress(&self, message_id: u8, len: usize) {
+        let protocol = self.protocol(message_id);
+        protocol.egress.fetch_add(len as u64, Ordering::Relaxed);
+        protocol.egress_total.increment(len as u64);
+    }
+
+    /// Returns the name, received bytes and sent bytes of every capability.
+    pub fn protocols(&self) -> impl Iterator<Item = (&str, u64, u64)> + '_ {
+        self.protocols.iter().map(|protocol| {
+            (
+                protocol.name.as_ref(),
+                protocol.ingress.load(Ordering::Relaxed),
+                protocol.egress.load(Ordering::Relaxed),
+            )
+        })
+    }
+
+    /// Returns the total number of bytes received.
+    pub fn total_ingress(&self) -> u64 {
+        self.protocols().map(|(_, ingress, _)| ingress).sum()
+    }
+
+    /// Returns the total number of bytes sent.
+    pub fn total_egress(&self) -> u64 {
+        self.protocols().map(|(_, _, egress)| egress).sum()
+    }
+
+    /// Returns the counters of the capability the message id belongs to.
+    fn protocol(&self, message_id: u8) -> &ProtocolCounters {
+        if message_id <= MAX_RESERVED_MESSAGE_ID {
+            return &self.protocols[0]
+        }
+        // capabilities are ordered by their offset, so the last one that starts at or before the
+        // id is the owner
+        self.protocols
+            .iter()
+            .rev()
+            .find(|protocol| protocol.message_id_offset <= message_id)
+            .unwrap_or(&self.protocols[0])
+    }
+}
+
+/// Counters of a single capability.
+#[derive(Debug)]
+struct ProtocolCounters {
+    name: Cow<'static, str>,
+    message_id_offset: u8,
+    ingress: AtomicU64,
+    egress: AtomicU64,
+    /// Aggregated over all streams.
+    ingress_total: Counter,
+    /// Aggregated over all streams.
+    egress_total: Counter,
+}
+
+impl ProtocolCounters {
+    fn new(name: Cow<'static, str>, message_id_offset: u8) -> Self {
+        Self {
+            ingress_total: counter!("p2pstream.ingress_bytes", "capability" => name.clone()),
+            egress_total: counter!("p2pstream.egress_bytes", "capability" => name.clone()),
+            name,
+            message_id_offset,
+            ingress: AtomicU64::new(0),
+            egress: AtomicU64::new(0),
+        }
+    }
+}
+
+/// A token bucket that limits the throughput to a fixed number of bytes per second.
+///
+/// The bucket holds at most one second worth of bytes. A transfer is allowed as long as the
+/// bucket is not empty and may overdraw it, the following transfers are then delayed until the
+/// debt is paid off. The debt is capped at one second worth of bytes, so a single large message
+/// never delays the following transfers for much longer than a second.
+///
+/// The limiter is cheap to clone, all clones share the same bucket. This is used to enforce a
+/// global limit across all streams.
+#[derive(Debug, Clone)]
+pub struct BandwidthLimiter {
+    bucket: Arc<Mutex<TokenBucket>>,
+}
+
+impl BandwidthLimiter {
+    /// Creates a new limiter that allows `bytes_per_second` bytes per second.
+    ///
+    /// Returns `None` if `bytes_per_second` is `0`, since no transfer could ever be allowed.
+    pub fn new(bytes_per_second: u64) -> Option<Self> {
+        (bytes_per_second > 0).then(|| Self {
+            bucket: Arc::new(Mutex::new(TokenBucket {
+                rate: bytes_per_second,
+                tokens: bytes_per_second as i64,
+                last_refill: Instant::now(),
+            })),
+        })
+    }
+
+    /// Returns the configured number of bytes per second.
+    pub fn rate(&self) -> u64 {
+        self.bucket.lock().unwrap().rate
+    }
+
+    /// Returns how long to wait until the next transfer is allowed, or `None` if it is allowed
+    /// right away.
+    pub fn delay(&self) -> Option<Duration> {
+        let mut bucket = self.bucket.lock().unwrap();
+        bucket.refill(Instant::now());
+        (bucket.tokens <= 0)
+            .then(|| Duration::from_secs_f64((1 - bucket.tokens) as f64 / bucket.rate as f64))
+    }
+
+    /// Returns `true` if the limit is currently exhausted.
+    pub fn is_exhausted(&self) -> bool {
+        self.delay().is_some()
+    }
+
+    /// Takes `len` bytes from the bucket.
+    pub fn consume(&self, len: usize) {
+        let mut bucket = self.bucket.lock().unwrap();
+        bucket.refill(Instant::now());
+        bucket.tokens = bucket.tokens.saturating_sub(len as i64).max(-(bucket.rate as i64));
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    rate: u64,
+    tokens: i64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let refill = (elapsed.as_secs_f64() * self.rate as f64) as i64;
+        if refill > 0 {
+            self.tokens = self.tokens.saturating_add(refill).min(self.rate as i64);
+            self.last_refill = now;
+        }
+    }
+}
+
+/// Bandwidth accounting and limits of a single [`P2PStream`](crate::P2PStream).
+#[derive(Debug)]
+pub struct StreamBandwidth {
+    meter: BandwidthMeter,
+    ingress: Direction,
+    egress: Direction,
+}
+
+impl StreamBandwidth {
+    /// Creates a new instance that only records into the given meter.
+    pub fn new(meter: BandwidthMeter) -> Self {
+        Self { meter, ingress: Default::default(), egress: Default::default() }
+    }
+
+    /// Adds a limiter for received bytes.
+    pub fn with_ingress_limiter(mut self, limiter: BandwidthLimiter) -> Self {
+        self.ingress.limiters.push(limiter);
+        self
+    }
+
+    /// Adds a limiter for sent bytes.
+    pub fn with_egress_limiter(mut self, limiter: BandwidthLimiter) -> Self {
+        self.egress.limiters.push(limiter);
+        self
+    }
+
+    /// Returns the meter of the stream.
+    pub const fn meter(&self) -> &BandwidthMeter {
+        &self.meter
+    }
+
+    /// Returns `Poll::Ready` once all ingress limiters allow reading.
+    pub(crate) fn poll_ingress_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.ingress.poll_ready(cx)
+    }
+
+    /// Returns `Poll::Ready` once all egress limiters allow writing.
+    pub(crate) fn poll_egress_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.egress.poll_ready(cx)
+    }
+
+    /// Returns `true` if any of the egress limiters is currently exhausted.
+    pub fn is_egress_exhausted(&self) -> bool {
+        self.egress.limiters.iter().any(BandwidthLimiter::is_exhausted)
+    }
+
+    /// Records a message read from the wire.
+    ///
+    /// Messages of the reserved `p2p` capability don't count against the limits.
+    pub(crate) fn on_ingress(&self, message_id: u8, len: usize) {
+        self.meter.record_ingress(message_id, len);
+        if message_id > MAX_RESERVED_MESSAGE_ID {
+            self.ingress.consume(len);
+        }
+    }
+
+    /// Records a message written to the wire.
+    ///
+    /// Messages of the reserved `p2p` capability don't count against the limits.
+    pub(crate) fn on_egress(&self, message_id: u8, len: usize) {
+        self.meter.record_egress(message_id, len);
+        if message_id > MAX_RESERVED_MESSAGE_ID {
+            self.egress.consume(len);
+        }
+    }
+}
+
+/// The limiters of one direction of a stream.
+#[derive(Default)]
+struct Direction {
+    limiters: Vec<BandwidthLimiter>,
+    /// Wakes the stream once the limiters allow the next transfer.
+    delay: Option<Pin<Box<Sleep>>>,
+}
+
+impl Direction {
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        loop {
+            if let Some(delay) = &mut self.delay {
+                ready!(delay.as_mut().poll(cx));
+                self.delay = None;
+            }
+            let Some(delay) = self.limiters.iter().filter_map(BandwidthLimiter::delay).max() else {
+                return Poll::Ready(())
+            };
+            counter!("p2pstream.throttled").increment(1);
+            self.delay = Some(Box::pin(tokio::time::sleep(delay)));
+        }
+    }
+
+    fn consume(&self, len: usize) {
+        for limiter in &self.limiters {
+            limiter.consume(len);
+        }
+    }
+}
+
+impl std::fmt::Debug for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Direction")
+            .field("limiters", &self.limiters)
+            .field("throttled", &self.delay.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{protocol::Protocol, Capability, EthVersion};
+
+    fn shared_capabilities() -> SharedCapabilities {
+        SharedCapabilities::try_new(
+            vec![EthVersion::Eth68.into(), Protocol::new(Capability::new_static("snap", 1), 8)],
+            vec![EthVersion::Eth68.into(), Capability::new_static("snap", 1)],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn meter_accounts_per_capability() {
+        let shared = shared_capabilities();
+        let eth_offset = shared.eth().unwrap().message_id_offset();
+        let snap_offset = shared.iter_caps().find(|cap| cap.name() == "snap").unwrap();
+        let snap_offset = snap_offset.message_id_offset();
+
+        let meter = BandwidthMeter::new(&shared);
+        meter.record_ingress(0x02, 10);
+        meter.record_ingress(eth_offset + 3, 100);
+        meter.record_egress(eth_offset, 200);
+        meter.record_egress(snap_offset + 1, 300);
+
+        let protocols = meter.protocols().collect::<Vec<_>>();
+        assert_eq!(protocols, vec![("p2p", 10, 0), ("eth", 100, 200), ("snap", 0, 300)]);
+        assert_eq!(meter.total_ingress(), 110);
+        assert_eq!(meter.total_egress(), 500);
+
+        // clones share the counters
+        meter.clone().record_ingress(eth_offset, 1);
+        assert_eq!(meter.total_ingress(), 111);
+    }
+
+    #[test]
+    fn limiter_delays_once_exhausted() {
+        let limiter = BandwidthLimiter::new(1000).unwrap();
+        assert!(limiter.delay().is_none());
+
+        // overdraw the bucket
+        limiter.consume(1500);
+        let delay = limiter.delay().unwrap();
+        assert!(delay > Duration::from_millis(400) && delay <= Duration::from_millis(501));
+
+        // clones share the bucket
+        let clone = limiter.clone();
+        clone.consume(400);
+        assert!(limiter.delay().unwrap() > delay);
+    }
+
+    #[test]
+    fn limiter_rejects_zero_rate() {
+        assert!(BandwidthLimiter::new(0).is_none());
+    }
+
+    #[test]
+    fn limiter_caps_debt() {
+        let limiter = BandwidthLimiter::new(1000).unwrap();
+
+        // a single message far larger than the rate only overdraws the bucket by one second
+        limiter.consume(100_000);
+        assert!(limiter.delay().unwrap() <= Duration::from_millis(1001));
+    }
+
+    #[tokio::test]
+    async fn direction_waits_for_limiter() {
+        let mut bandwidth = StreamBandwidth::new(BandwidthMeter::new(&shared_capabilities()))
+            .with_egress_limiter(BandwidthLimiter::new(1000).unwrap());
+
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        assert!(bandwidth.poll_egress_ready(&mut cx).is_ready());
+
+        // p2p messages don't count against the limit
+        bandwidth.on_egress(0x02, 1010);
+        assert!(!bandwidth.is_egress_exhausted());
+        assert_eq!(bandwidth.meter().total_egress(), 1010);
+
+        bandwidth.on_egress(0x10, 1010);
+        assert!(bandwidth.is_egress_exhausted());
+        assert!(bandwidth.poll_egress_ready(&mut cx).is_pending());
+        // ingress is not limited
+        assert!(bandwidth.poll_ingress_ready(&mut cx).is_ready());
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(bandwidth.poll_egress_ready(&mut cx).is_ready());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    bandwidth::StreamBandwidth,
     capability::SharedCapabilities,
     disconnect::CanDisconnect,
     errors::{P2PHandshakeError, P2PStreamError},
@@ -256,6 +257,13 @@ pub struct P2PStream<S> {
     /// Whether this stream is currently in the process of disconnecting by sending a disconnect
     /// message.
     disconnecting: bool,
+
+    /// Bandwidth accounting and limits, if enabled.
+    bandwidth: Option<StreamBandwidth>,
+
+    /// A subprotocol message that was read while the ingress limit was exhausted, it is returned
+    /// once the limit allows it.
+    throttled_message: Option<BytesMut>,
 }
 
 impl<S> P2PStream<S> {
@@ -272,6 +280,8 @@ impl<S> P2PStream<S> {
             outgoing_messages: VecDeque::new(),
             outgoing_message_buffer_capacity: MAX_P2P_CAPACITY,
             disconnecting: false,
+            bandwidth: None,
+            throttled_message: None,
         }
     }
 
@@ -289,6 +299,21 @@ impl<S> P2PStream<S> {
         self.outgoing_message_buffer_capacity = capacity;
     }
 
+    /// Enables bandwidth accounting and limits for this stream.
+    ///
+    /// Messages of the reserved `p2p` capability are accounted but don't count against the limits
+    /// and are never delayed themselves, so that pings are answered in time. A subprotocol message
+    /// that is read while the ingress limit is exhausted is held back, and reading resumes once it
+    /// was returned.
+    pub fn set_bandwidth(&mut self, bandwidth: StreamBandwidth) {
+        self.bandwidth = Some(bandwidth);
+    }
+
+    /// Returns the bandwidth accounting of this stream, if enabled.
+    pub const fn bandwidth(&self) -> Option<&StreamBandwidth> {
+        self.bandwidth.as_ref()
+    }
+
     /// Returns the shared capabilities for this stream.
     ///
     /// This includes all the shared capabilities that were negotiated during the handshake and
@@ -398,6 +423,16 @@ where
             return Poll::Ready(None)
         }
 
+        if let Some(message) = this.throttled_message.take() {
+            if let Some(bandwidth) = &mut this.bandwidth &&
+                bandwidth.poll_ingress_ready(cx).is_pending()
+            {
+                this.throttled_message = Some(message);
+                return Poll::Pending
+            }
+            return Poll::Ready(Some(Ok(message)))
+        }
+
         // we should loop here to ensure we don't return Poll::Pending if we have a message to
         // return behind any pings we need to respond to
         while let Poll::Ready(res) = this.inner.poll_next_unpin(cx) {
@@ -412,6 +447,15 @@ where
                 return Poll::Ready(Some(Err(P2PStreamError::EmptyProtocolMessage)))
             }
 
+            // only subprotocol messages are held back, which is decided before they are
+            // accounted, so the message that exhausts the limit is still returned right away
+            let mut throttled = false;
+            if let Some(bandwidth) = &mut this.bandwidth {
+                throttled = bytes[0] > MAX_RESERVED_MESSAGE_ID &&
+                    bandwidth.poll_ingress_ready(cx).is_pending();
+                bandwidth.on_ingress(bytes[0], bytes.len());
+            }
+
             // first decode disconnect reasons, because they can be encoded in a variety of forms
             // over the wire, in both snappy compressed and uncompressed forms.
             //
@@ -521,6 +565,12 @@ where
                     //
                     decompress_buf[0] = bytes[0] - MAX_RESERVED_MESSAGE_ID - 1;
 
+                    if throttled {
+                        // the ingress limiter wakes us once the limit allows the message
+                        this.throttled_message = Some(decompress_buf);
+                        return Poll::Pending
+                    }
+
                     return Poll::Ready(Some(Ok(decompress_buf)))
                 }
             }
@@ -624,9 +674,20 @@ where
                 Poll::Pending => break Poll::Pending,
                 Poll::Ready(Err(err)) => break Poll::Ready(Err(err.into())),
                 Poll::Ready(Ok(())) => {
-                    let Some(message) = this.outgoing_messages.pop_front() else {
+                    let Some(message) = this.outgoing_messages.front() else {
                         break Poll::Ready(Ok(()))
                     };
+                    if let Some(bandwidth) = this.bandwidth.as_mut() {
+                        // p2p messages, including the disconnect, are never delayed
+                        if message[0] > MAX_RESERVED_MESSAGE_ID &&
+                            !*this.disconnecting &&
+                            bandwidth.poll_egress_ready(cx).is_pending()
+                        {
+                            break Poll::Pending
+                        }
+                        bandwidth.on_egress(message[0], message.len());
+                    }
+                    let message = this.outgoing_messages.pop_front().expect("message exists");
                     if let Err(err) = this.inner.as_mut().start_send(message) {
                         break Poll::Ready(Err(err.into()))
                     }
@@ -7,6 +7,9 @@ pub enum NetworkError {
     /// Indicates that the sender has been dropped.
     #[error("sender has been dropped")]
     ChannelClosed,
+    /// Failed to load the allow-list.
+    #[error("{0}")]
+    AllowList(String),
 }
 
 impl<T> From<mpsc::error::SendError<T>> for NetworkError {
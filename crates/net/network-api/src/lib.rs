@@ -221,6 +221,10 @@ pub trait Peers: PeersInfo {
         &self,
         peer_id: PeerId,
     ) -> impl Future<Output = Result<Option<Reputation>, NetworkError>> + Send;
+    /// Reloads the allow-list of the only peers that are allowed to connect from its file.
+    ///
+    /// Peers that are no longer allowed are disconnected.
+    fn reload_allow_list(&self) -> impl Future<Output = Result<(), NetworkError>> + Send;
 }
 
 /// Info about an active peer session.
@@ -250,6 +254,24 @@ pub struct PeerInfo {
     pub session_established: Instant,
     /// The peer's connection kind
     pub kind: PeerKind,
+    /// The peer's score in the range `0.0..=1.0` based on the quality of its responses, higher is
+    /// better.
+    pub score: f64,
+    /// Bytes exchanged with the peer per capability since the session has been established.
+    pub bandwidth: Vec<ProtocolBandwidth>,
+}
+
+/// Bytes exchanged with a peer over a single capability.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ProtocolBandwidth {
+    /// Name of the capability, `p2p` for the reserved base protocol.
+    pub protocol: String,
+    /// Number of bytes received.
+    pub ingress_bytes: u64,
+    /// Number of bytes sent.
+    pub egress_bytes: u64,
 }
 
 /// The direction of the connection.
@@ -167,6 +167,9 @@ where
     async fn reputation_by_id(&self, _peer_id: PeerId) -> Result<Option<Reputation>, NetworkError> {
         Ok(None)
     }
+    async fn reload_allow_list(&self) -> Result<(), NetworkError> {
+        Ok(())
+    }
 }
 
 impl<Net> BlockDownloaderProvider for NoopNetwork<Net>
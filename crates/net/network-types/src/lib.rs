@@ -30,6 +30,7 @@ pub use peers::{
         DEFAULT_REPUTATION,
     },
     state::PeerConnectionState,
-    ConnectionsConfig, Peer, PeersConfig,
+    AllowList, AllowListError, AllowedPeer, ConnectionsConfig, Peer, PeerScore, PeersConfig,
+    ResponseSample,
 };
-pub use session::{SessionLimits, SessionsConfig};
+pub use session::{BandwidthLimits, SessionLimits, SessionsConfig};
@@ -0,0 +1,245 @@
+//! Allow-list for permissioned networks.
+//!
+//! An [`AllowList`] restricts the peers that can connect, regardless of discovery. It is loaded
+//! from a file with one entry per line:
+//!
+//! ```text
+//! # peers, either as enode URL or peer id, optionally followed by a role
+//! enode://6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0@10.3.58.6:30303 static
+//! 6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0 trusted
+//!
+//! # ip ranges in CIDR notation
+//! 10.0.0.0/8
+//! ```
+//!
+//! A peer is allowed if either its id or its IP address is listed.
+
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use reth_network_peers::{NodeRecord, PeerId};
+
+use crate::PeerKind;
+
+/// A peer listed in the [`AllowList`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllowedPeer {
+    /// The role of the peer.
+    ///
+    /// [`PeerKind::Static`] and [`PeerKind::Trusted`] peers with a known address are connected to
+    /// and kept in the peer set.
+    pub kind: PeerKind,
+    /// The address of the peer, if listed as enode URL.
+    pub record: Option<NodeRecord>,
+}
+
+/// Restricts connections to a set of peer ids and IP ranges.
+///
+/// An empty allow-list does not restrict any connections, which is why allow-list files without
+/// any entries are rejected by [`AllowList::from_file`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AllowList {
+    /// The allowed peers, by id.
+    peers: HashMap<PeerId, AllowedPeer>,
+    /// The allowed IP networks.
+    networks: Vec<ipnet::IpNet>,
+}
+
+impl AllowList {
+    /// Creates a new allow-list from the given peers and networks.
+    pub fn new(
+        peers: impl IntoIterator<Item = (PeerId, AllowedPeer)>,
+        networks: impl IntoIterator<Item = ipnet::IpNet>,
+    ) -> Self {
+        Self { peers: peers.into_iter().collect(), networks: networks.into_iter().collect() }
+    }
+
+    /// Reads the allow-list from the given file.
+    ///
+    /// Returns an error if the file has no entries, so that an empty or truncated file never
+    /// lifts the restrictions.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, AllowListError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| AllowListError::Io { path: path.to_path_buf(), err })?;
+        let list: Self = contents.parse()?;
+        if !list.has_restrictions() {
+            return Err(AllowListError::Empty { path: path.to_path_buf() })
+        }
+        Ok(list)
+    }
+
+    /// Returns `true` if the allow-list restricts connections.
+    pub fn has_restrictions(&self) -> bool {
+        !self.peers.is_empty() || !self.networks.is_empty()
+    }
+
+    /// Returns `true` if a connection to the peer at the given IP address is allowed.
+    pub fn is_allowed(&self, peer_id: &PeerId, ip: &IpAddr) -> bool {
+        !self.has_restrictions() ||
+            self.peers.contains_key(peer_id) ||
+            self.networks.iter().any(|net| net.contains(ip))
+    }
+
+    /// Returns the listed peer with the given id.
+    pub fn get(&self, peer_id: &PeerId) -> Option<&AllowedPeer> {
+        self.peers.get(peer_id)
+    }
+
+    /// Returns an iterator over all listed peers.
+    pub fn peers(&self) -> impl Iterator<Item = (&PeerId, &AllowedPeer)> + '_ {
+        self.peers.iter()
+    }
+
+    /// Returns the allowed IP networks.
+    pub fn networks(&self) -> &[ipnet::IpNet] {
+        &self.networks
+    }
+}
+
+impl FromStr for AllowList {
+    type Err = AllowListError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut list = Self::default();
+        for (idx, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue
+            }
+            let invalid = |reason: String| AllowListError::InvalidEntry { line: idx + 1, reason };
+
+            let mut parts = line.split_whitespace();
+            let entry = parts.next().unwrap_or_default();
+            let role = parts.next();
+            if parts.next().is_some() {
+                return Err(invalid("unexpected trailing input".to_string()))
+            }
+
+            if entry.contains('/') && !entry.contains("://") {
+                if role.is_some() {
+                    return Err(invalid("ip ranges can not have a role".to_string()))
+                }
+                let net = entry.parse().map_err(|err| invalid(format!("{err}")))?;
+                list.networks.push(net);
+                continue
+            }
+
+            let (peer_id, record) = if entry.starts_with("enode://") {
+                let record =
+                    NodeRecord::from_str(entry).map_err(|err| invalid(format!("{err}")))?;
+                (record.id, Some(record))
+            } else {
+                let id = PeerId::from_str(entry).map_err(|err| invalid(format!("{err}")))?;
+                (id, None)
+            };
+            let kind = match role {
+                None | Some("basic") => PeerKind::Basic,
+                Some("trusted") => PeerKind::Trusted,
+                Some("static") if record.is_some() => PeerKind::Static,
+                Some("static") => {
+                    return Err(invalid("static peers must be listed as enode URL".to_string()))
+                }
+                Some(role) => return Err(invalid(format!("unknown role {role}"))),
+            };
+            list.peers.insert(peer_id, AllowedPeer { kind, record });
+        }
+        Ok(list)
+    }
+}
+
+/// Errors when loading an [`AllowList`].
+#[derive(Debug, thiserror::Error)]
+pub enum AllowListError {
+    /// Failed to read the allow-list file.
+    #[error("failed to read allow-list {}: {err}", path.display())]
+    Io {
+        /// The path of the file.
+        path: PathBuf,
+        /// The underlying error.
+        #[source]
+        err: std::io::Error,
+    },
+    /// The allow-list file has no entries.
+    #[error("allow-list {} has no entries", path.display())]
+    Empty {
+        /// The path of the file.
+        path: PathBuf,
+    },
+    /// The allow-list contains an invalid entry.
+    #[error("invalid allow-list entry on line {line}: {reason}")]
+    InvalidEntry {
+        /// The line number of the entry.
+        line: usize,
+        /// Why the entry is invalid.
+        reason: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0";
+
+    #[test]
+    fn parse_allow_list() {
+        let list: AllowList = format!(
+            "# consortium members\n\
+             enode://{ID}@10.3.58.6:30303 static\n\
+             \n\
+             192.168.0.0/16 # office\n\
+             2001:db8::/32\n"
+        )
+        .parse()
+        .unwrap();
+
+        let peer_id = PeerId::from_str(ID).unwrap();
+        let peer = list.get(&peer_id).unwrap();
+        assert_eq!(peer.kind, PeerKind::Static);
+        assert_eq!(peer.record.unwrap().address, "10.3.58.6".parse::<IpAddr>().unwrap());
+        assert_eq!(list.networks().len(), 2);
+
+        let other = PeerId::repeat_byte(1);
+        assert!(list.is_allowed(&peer_id, &"8.8.8.8".parse().unwrap()));
+        assert!(list.is_allowed(&other, &"192.168.1.1".parse().unwrap()));
+        assert!(list.is_allowed(&other, &"2001:db8::1".parse().unwrap()));
+        assert!(!list.is_allowed(&other, &"10.3.58.6".parse().unwrap()));
+    }
+
+    #[test]
+    fn empty_allow_list_allows_all() {
+        let list: AllowList = "# nothing yet\n".parse().unwrap();
+        assert!(!list.has_restrictions());
+        assert!(list.is_allowed(&PeerId::repeat_byte(1), &"8.8.8.8".parse().unwrap()));
+    }
+
+    #[test]
+    fn reject_empty_allow_list_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("allowlist");
+        std::fs::write(&path, "# nothing yet\n").unwrap();
+        assert!(matches!(AllowList::from_file(&path), Err(AllowListError::Empty { .. })));
+
+        std::fs::write(&path, format!("{ID}\n")).unwrap();
+        assert!(AllowList::from_file(&path).unwrap().has_restrictions());
+    }
+
+    #[test]
+    fn reject_invalid_entries() {
+        let err = format!("{ID} static").parse::<AllowList>().unwrap_err();
+        assert!(matches!(err, AllowListError::InvalidEntry { line: 1, .. }));
+
+        let err = format!("10.0.0.0/8\n{ID} admin").parse::<AllowList>().unwrap_err();
+        assert!(matches!(err, AllowListError::InvalidEntry { line: 2, .. }));
+
+        let err = "10.0.0.0/8 trusted".parse::<AllowList>().unwrap_err();
+        assert!(matches!(err, AllowListError::InvalidEntry { line: 1, .. }));
+
+        assert!("not-a-peer".parse::<AllowList>().is_err());
+    }
+}
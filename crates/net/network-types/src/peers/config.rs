@@ -3,7 +3,7 @@
 use std::{
     collections::HashSet,
     io::{self, ErrorKind},
-    path::Path,
+    path::{Path, PathBuf},
     time::Duration,
 };
 
@@ -172,6 +172,10 @@ pub struct PeersConfig {
     /// IPs within the specified CIDR ranges will be allowed.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub ip_filter: IpFilter,
+    /// Path to the [`AllowList`](crate::AllowList) of the only peers that are allowed to connect.
+    ///
+    /// The file is read when the network starts and can be reloaded at runtime.
+    pub allow_list_file: Option<PathBuf>,
 }
 
 impl Default for PeersConfig {
@@ -191,6 +195,7 @@ impl Default for PeersConfig {
             max_backoff_count: 5,
             incoming_ip_throttle_duration: INBOUND_IP_THROTTLE_DURATION,
             ip_filter: IpFilter::default(),
+            allow_list_file: None,
         }
     }
 }
@@ -314,6 +319,13 @@ impl PeersConfig {
         self
     }
 
+    /// Configure the file of the [`AllowList`](crate::AllowList) restricting the peers that can
+    /// connect.
+    pub fn with_allow_list_file(mut self, path: Option<PathBuf>) -> Self {
+        self.allow_list_file = path;
+        self
+    }
+
     /// Returns settings for testing
     #[cfg(any(test, feature = "test-utils"))]
     pub fn test() -> Self {
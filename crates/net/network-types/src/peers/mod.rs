@@ -1,11 +1,15 @@
 pub mod addr;
+pub mod allowlist;
 pub mod config;
 pub mod kind;
 pub mod reputation;
+pub mod score;
 pub mod state;
 
+pub use allowlist::{AllowList, AllowListError, AllowedPeer};
 pub use config::{ConnectionsConfig, PeersConfig};
 pub use reputation::{Reputation, ReputationChange, ReputationChangeKind, ReputationChangeWeights};
+pub use score::{PeerScore, ResponseSample};
 
 use alloy_eip2124::ForkId;
 use tracing::trace;
@@ -35,6 +39,8 @@ pub struct Peer {
     /// Counts number of times the peer was backed off due to a severe
     /// [`BackoffKind`](crate::BackoffKind).
     pub severe_backoff_counter: u8,
+    /// Rolling score of the responses the peer served.
+    pub score: PeerScore,
 }
 
 // === impl Peer ===
@@ -66,6 +72,7 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            score: Default::default(),
         }
     }
 
@@ -0,0 +1,196 @@
+//! Peer scoring based on response quality.
+//!
+//! Unlike the [`Reputation`](crate::Reputation), which only ever penalizes misbehaviour, the
+//! [`PeerScore`] also rewards peers that serve requests quickly and reliably.
+
+use std::time::{Duration, Instant};
+
+/// Weight of a new sample in the moving averages.
+const SAMPLE_WEIGHT: f64 = 0.2;
+
+/// Response latency that is scored as `0.5`.
+const REFERENCE_LATENCY: Duration = Duration::from_millis(500);
+
+/// Throughput in bytes per second that is scored as `0.5`.
+const REFERENCE_THROUGHPUT: f64 = 256.0 * 1024.0;
+
+/// Session uptime that is scored as `0.5`.
+const REFERENCE_UPTIME: Duration = Duration::from_secs(60 * 60);
+
+/// Score of a component without any samples.
+const NEUTRAL: f64 = 0.5;
+
+/// Weight of the latency component.
+const LATENCY_WEIGHT: f64 = 0.3;
+
+/// Weight of the throughput component.
+const THROUGHPUT_WEIGHT: f64 = 0.2;
+
+/// Weight of the valid responses component.
+const VALID_RESPONSES_WEIGHT: f64 = 0.4;
+
+/// Weight of the uptime component.
+const UPTIME_WEIGHT: f64 = 0.1;
+
+/// A response received from a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResponseSample {
+    /// Time between sending the request and receiving the response.
+    pub latency: Duration,
+    /// Size of the response.
+    pub bytes: u64,
+    /// Whether the response contained the requested data.
+    ///
+    /// Empty responses and responses that do not match the request are invalid.
+    pub valid: bool,
+}
+
+/// A rolling score of a peer in the range `0.0..=1.0`, higher is better.
+///
+/// The score combines exponential moving averages of the response latency, the throughput and the
+/// fraction of valid responses with the uptime of the current session. Components without samples
+/// are scored neutrally, so a peer that has not served any requests yet scores
+/// [`PeerScore::NEW`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PeerScore {
+    /// Moving average of the response latency in seconds.
+    latency: Option<f64>,
+    /// Moving average of the throughput in bytes per second.
+    throughput: Option<f64>,
+    /// Moving average of the fraction of valid responses.
+    valid_responses: Option<f64>,
+    /// When the current session was established.
+    connected_since: Option<Instant>,
+}
+
+impl PeerScore {
+    /// The score of a peer without any samples that just connected.
+    pub const NEW: f64 =
+        LATENCY_WEIGHT * NEUTRAL + THROUGHPUT_WEIGHT * NEUTRAL + VALID_RESPONSES_WEIGHT * NEUTRAL;
+
+    /// Records a response.
+    pub fn on_response(&mut self, sample: ResponseSample) {
+        let latency = sample.latency.as_secs_f64();
+        update_average(&mut self.latency, latency);
+        if latency > 0.0 {
+            update_average(&mut self.throughput, sample.bytes as f64 / latency);
+        }
+        update_average(&mut self.valid_responses, if sample.valid { 1.0 } else { 0.0 });
+    }
+
+    /// Records a request that failed or a response that was reported as invalid.
+    pub fn on_invalid_response(&mut self) {
+        update_average(&mut self.valid_responses, 0.0);
+    }
+
+    /// Records that a session to the peer was established.
+    pub const fn on_connected(&mut self, now: Instant) {
+        self.connected_since = Some(now);
+    }
+
+    /// Records that the session to the peer was closed.
+    pub const fn on_disconnected(&mut self) {
+        self.connected_since = None;
+    }
+
+    /// Returns the moving average of the response latency.
+    pub fn latency(&self) -> Option<Duration> {
+        self.latency.map(Duration::from_secs_f64)
+    }
+
+    /// Returns the moving average of the throughput in bytes per second.
+    pub const fn throughput(&self) -> Option<f64> {
+        self.throughput
+    }
+
+    /// Returns the moving average of the fraction of valid responses.
+    pub const fn valid_responses(&self) -> Option<f64> {
+        self.valid_responses
+    }
+
+    /// Returns the score at the given time.
+    pub fn value(&self, now: Instant) -> f64 {
+        let reference_latency = REFERENCE_LATENCY.as_secs_f64();
+        let latency = self
+            .latency
+            .map_or(NEUTRAL, |latency| reference_latency / (reference_latency + latency));
+        let throughput = self
+            .throughput
+            .map_or(NEUTRAL, |throughput| throughput / (throughput + REFERENCE_THROUGHPUT));
+        let valid_responses = self.valid_responses.unwrap_or(NEUTRAL);
+        let uptime = self.connected_since.map_or(0.0, |since| {
+            let uptime = now.saturating_duration_since(since).as_secs_f64();
+            uptime / (uptime + REFERENCE_UPTIME.as_secs_f64())
+        });
+
+        [
+            (LATENCY_WEIGHT, latency),
+            (THROUGHPUT_WEIGHT, throughput),
+            (VALID_RESPONSES_WEIGHT, valid_responses),
+            (UPTIME_WEIGHT, uptime),
+        ]
+        .into_iter()
+        .fold(0.0, |score, (weight, value)| weight.mul_add(value, score))
+    }
+}
+
+/// Folds the sample into the exponential moving average.
+fn update_average(average: &mut Option<f64>, sample: f64) {
+    *average = Some(average.map_or(sample, |avg| SAMPLE_WEIGHT.mul_add(sample - avg, avg)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_peer_is_neutral() {
+        let now = Instant::now();
+        let mut score = PeerScore::default();
+        assert_eq!(score.value(now), PeerScore::NEW);
+
+        // uptime is rewarded
+        score.on_connected(now);
+        assert!(score.value(now + REFERENCE_UPTIME) > PeerScore::NEW);
+        score.on_disconnected();
+        assert_eq!(score.value(now + REFERENCE_UPTIME), PeerScore::NEW);
+    }
+
+    #[test]
+    fn fast_peer_scores_better() {
+        let now = Instant::now();
+        let mut fast = PeerScore::default();
+        let mut slow = PeerScore::default();
+        for _ in 0..10 {
+            fast.on_response(ResponseSample {
+                latency: Duration::from_millis(50),
+                bytes: 512 * 1024,
+                valid: true,
+            });
+            slow.on_response(ResponseSample {
+                latency: Duration::from_secs(3),
+                bytes: 512 * 1024,
+                valid: true,
+            });
+        }
+        assert!(fast.value(now) > PeerScore::NEW);
+        assert!(fast.value(now) > slow.value(now));
+    }
+
+    #[test]
+    fn invalid_responses_lower_score() {
+        let now = Instant::now();
+        let mut score = PeerScore::default();
+        let sample = ResponseSample { latency: REFERENCE_LATENCY, bytes: 0, valid: false };
+        score.on_response(sample);
+        score.on_invalid_response();
+        assert_eq!(score.valid_responses(), Some(0.0));
+        assert!(score.value(now) < PeerScore::NEW);
+
+        // recovers with valid responses
+        for _ in 0..20 {
+            score.on_response(ResponseSample { valid: true, ..sample });
+        }
+        assert!(score.valid_responses().unwrap() > 0.9);
+    }
+}
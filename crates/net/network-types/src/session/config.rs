@@ -50,6 +50,10 @@ pub struct SessionsConfig {
     pub protocol_breach_request_timeout: Duration,
     /// The timeout after which a pending session attempt is considered failed.
     pub pending_session_timeout: Duration,
+    /// Bandwidth limits to enforce on established sessions.
+    ///
+    /// By default, no limits will be enforced.
+    pub bandwidth: BandwidthLimits,
 }
 
 impl Default for SessionsConfig {
@@ -69,6 +73,7 @@ impl Default for SessionsConfig {
             initial_internal_request_timeout: INITIAL_REQUEST_TIMEOUT,
             protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
             pending_session_timeout: PENDING_SESSION_TIMEOUT,
+            bandwidth: Default::default(),
         }
     }
 }
@@ -100,6 +105,12 @@ impl SessionsConfig {
         }
         self
     }
+
+    /// Sets the bandwidth limits to enforce on established sessions.
+    pub const fn with_bandwidth_limits(mut self, limits: BandwidthLimits) -> Self {
+        self.bandwidth = limits;
+        self
+    }
 }
 
 /// Limits for sessions.
@@ -144,6 +155,48 @@ impl SessionLimits {
     }
 }
 
+/// Bandwidth limits for established sessions, in bytes per second.
+///
+/// By default, no bandwidth limits will be enforced
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BandwidthLimits {
+    /// Maximum download rate over all sessions.
+    pub max_ingress: Option<u64>,
+    /// Maximum upload rate over all sessions.
+    pub max_egress: Option<u64>,
+    /// Maximum download rate of a single session.
+    pub max_ingress_per_peer: Option<u64>,
+    /// Maximum upload rate of a single session.
+    pub max_egress_per_peer: Option<u64>,
+}
+
+impl BandwidthLimits {
+    /// Sets the maximum download rate over all sessions.
+    pub const fn with_max_ingress(mut self, bytes_per_second: u64) -> Self {
+        self.max_ingress = Some(bytes_per_second);
+        self
+    }
+
+    /// Sets the maximum upload rate over all sessions.
+    pub const fn with_max_egress(mut self, bytes_per_second: u64) -> Self {
+        self.max_egress = Some(bytes_per_second);
+        self
+    }
+
+    /// Sets the maximum download rate of a single session.
+    pub const fn with_max_ingress_per_peer(mut self, bytes_per_second: u64) -> Self {
+        self.max_ingress_per_peer = Some(bytes_per_second);
+        self
+    }
+
+    /// Sets the maximum upload rate of a single session.
+    pub const fn with_max_egress_per_peer(mut self, bytes_per_second: u64) -> Self {
+        self.max_egress_per_peer = Some(bytes_per_second);
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
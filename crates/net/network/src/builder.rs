@@ -66,7 +66,10 @@ impl<Tx, Eth, N: NetworkPrimitives> NetworkBuilder<Tx, Eth, N> {
         let (tx, rx) = mpsc::channel(ETH_REQUEST_CHANNEL_CAPACITY);
         network.set_eth_request_handler(tx);
         let peers = network.handle().peers_handle().clone();
-        let request_handler = EthRequestHandler::new(client, peers, rx);
+        let mut request_handler = EthRequestHandler::new(client, peers, rx);
+        if let Some(limiter) = network.egress_limiter() {
+            request_handler = request_handler.with_egress_limiter(limiter.clone());
+        }
         NetworkBuilder { network, request_handler, transactions }
     }
 
@@ -14,7 +14,7 @@ use reth_dns_discovery::{
 use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
 use reth_network_api::{DiscoveredEvent, DiscoveryEvent};
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::PeerAddr;
+use reth_network_types::{AllowList, PeerAddr};
 use secp256k1::SecretKey;
 use std::{
     collections::VecDeque,
@@ -64,6 +64,8 @@ pub struct Discovery {
     queued_events: VecDeque<DiscoveryEvent>,
     /// List of listeners subscribed to discovery events.
     discovery_listeners: Vec<mpsc::UnboundedSender<DiscoveryEvent>>,
+    /// Discovered nodes that are not in the allow-list are not reported.
+    allow_list: Arc<AllowList>,
 }
 
 impl Discovery {
@@ -137,9 +139,16 @@ impl Discovery {
             _dns_disc_service,
             _dns_discovery,
             dns_discovery_updates,
+            allow_list: Default::default(),
         })
     }
 
+    /// Sets the [`AllowList`] that discovered nodes are checked against before they're reported
+    /// and dialed.
+    pub(crate) fn set_allow_list(&mut self, allow_list: Arc<AllowList>) {
+        self.allow_list = allow_list;
+    }
+
     /// Registers a listener for receiving [`DiscoveryEvent`] updates.
     pub(crate) fn add_listener(&mut self, tx: mpsc::UnboundedSender<DiscoveryEvent>) {
         self.discovery_listeners.push(tx);
@@ -222,6 +231,10 @@ impl Discovery {
             // useless peer for p2p
             return
         }
+        if !self.allow_list.is_allowed(&peer_id, &tcp_addr.ip()) {
+            trace!(target: "net::discovery", ?peer_id, ?tcp_addr, "ignoring node not in allow-list");
+            return
+        }
         let udp_addr = record.udp_addr();
         let addr = PeerAddr::new(tcp_addr, Some(udp_addr));
         _ =
@@ -336,6 +349,7 @@ impl Discovery {
             dns_discovery_updates: None,
             _dns_disc_service: None,
             discovery_listeners: Default::default(),
+            allow_list: Default::default(),
         }
     }
 }
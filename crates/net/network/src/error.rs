@@ -7,7 +7,7 @@ use reth_eth_wire::{
     errors::{EthHandshakeError, EthStreamError, P2PHandshakeError, P2PStreamError},
     DisconnectReason,
 };
-use reth_network_types::BackoffKind;
+use reth_network_types::{AllowListError, BackoffKind};
 use std::{fmt, io, io::ErrorKind, net::SocketAddr};
 
 /// Service kind.
@@ -63,6 +63,9 @@ pub enum NetworkError {
     /// See also [`DnsResolver`](reth_dns_discovery::DnsResolver::from_system_conf)
     #[error("failed to configure DNS resolver: {0}")]
     DnsResolver(#[from] ResolveError),
+    /// Error when loading the configured allow-list failed.
+    #[error(transparent)]
+    AllowList(#[from] AllowListError),
 }
 
 impl NetworkError {
@@ -9,9 +9,9 @@ use alloy_eips::BlockHashOrNumber;
 use alloy_rlp::Encodable;
 use futures::StreamExt;
 use reth_eth_wire::{
-    BlockBodies, BlockHeaders, EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, GetNodeData,
-    GetReceipts, GetReceipts70, HeadersDirection, NetworkPrimitives, NodeData, Receipts,
-    Receipts69, Receipts70,
+    bandwidth::BandwidthLimiter, BlockBodies, BlockHeaders, EthNetworkPrimitives, GetBlockBodies,
+    GetBlockHeaders, GetNodeData, GetReceipts, GetReceipts70, HeadersDirection, NetworkPrimitives,
+    NodeData, Receipts, Receipts69, Receipts70,
 };
 use reth_network_api::test_utils::PeersHandle;
 use reth_network_p2p::error::RequestResult;
@@ -48,6 +48,9 @@ pub const MAX_BODIES_SERVE: usize = 1024;
 /// Maximum size of replies to data retrievals: 2MB
 pub const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
 
+/// Maximum size of replies to data retrievals while the upload limit is exhausted: 256KB
+pub const THROTTLED_SOFT_RESPONSE_LIMIT: usize = SOFT_RESPONSE_LIMIT / 8;
+
 /// Manages eth related requests on top of the p2p network.
 ///
 /// This can be spawned to another task and is supposed to be run as background service.
@@ -64,6 +67,8 @@ pub struct EthRequestHandler<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     incoming_requests: ReceiverStream<IncomingEthRequest<N>>,
     /// Metrics for the eth request handler.
     metrics: EthRequestHandlerMetrics,
+    /// Limiter for sent bytes shared by all sessions, if an upload limit is configured.
+    egress_limiter: Option<BandwidthLimiter>,
 }
 
 // === impl EthRequestHandler ===
@@ -75,8 +80,15 @@ impl<C, N: NetworkPrimitives> EthRequestHandler<C, N> {
             peers,
             incoming_requests: ReceiverStream::new(incoming),
             metrics: Default::default(),
+            egress_limiter: None,
         }
     }
+
+    /// Reduces the size of responses while the given upload limiter is exhausted.
+    pub fn with_egress_limiter(mut self, limiter: BandwidthLimiter) -> Self {
+        self.egress_limiter = Some(limiter);
+        self
+    }
 }
 
 impl<C, N> EthRequestHandler<C, N>
@@ -84,6 +96,18 @@ where
     N: NetworkPrimitives,
     C: BlockReader,
 {
+    /// Returns the maximum size of the next response.
+    ///
+    /// Serving requests is the first thing to back off when the upload limit is exhausted, so
+    /// this is reduced to [`THROTTLED_SOFT_RESPONSE_LIMIT`] until the limit recovers.
+    fn soft_response_limit(&self) -> usize {
+        if self.egress_limiter.as_ref().is_some_and(BandwidthLimiter::is_exhausted) {
+            self.metrics.eth_requests_throttled_total.increment(1);
+            return THROTTLED_SOFT_RESPONSE_LIMIT
+        }
+        SOFT_RESPONSE_LIMIT
+    }
+
     /// Returns the list of requested headers
     fn get_headers_response(&self, request: GetBlockHeaders) -> Vec<C::Header> {
         let GetBlockHeaders { start_block, limit, skip, direction } = request;
@@ -102,6 +126,7 @@ where
 
         let skip = skip as u64;
         let mut total_bytes = 0;
+        let soft_limit = self.soft_response_limit();
 
         for _ in 0..limit {
             if let Some(header) = self.client.header_by_hash_or_number(block).unwrap_or_default() {
@@ -111,7 +136,7 @@ where
                 total_bytes += header.length();
                 headers.push(header);
 
-                if headers.len() >= MAX_HEADERS_SERVE || total_bytes > SOFT_RESPONSE_LIMIT {
+                if headers.len() >= MAX_HEADERS_SERVE || total_bytes > soft_limit {
                     break
                 }
 
@@ -169,6 +194,7 @@ where
         let mut bodies = Vec::new();
 
         let mut total_bytes = 0;
+        let soft_limit = self.soft_response_limit();
 
         for hash in request.0 {
             if let Some(block) = self.client.block_by_hash(hash).unwrap_or_default() {
@@ -176,7 +202,7 @@ where
                 total_bytes += body.length();
                 bodies.push(body);
 
-                if bodies.len() >= MAX_BODIES_SERVE || total_bytes > SOFT_RESPONSE_LIMIT {
+                if bodies.len() >= MAX_BODIES_SERVE || total_bytes > soft_limit {
                     break
                 }
             } else {
@@ -234,6 +260,7 @@ where
         let mut receipts = Vec::new();
         let mut total_bytes = 0usize;
         let mut last_block_incomplete = false;
+        let soft_limit = self.soft_response_limit();
 
         for (idx, hash) in block_hashes.into_iter().enumerate() {
             if idx >= MAX_RECEIPTS_SERVE {
@@ -257,7 +284,7 @@ where
 
             let block_size = block_receipts.length();
 
-            if total_bytes + block_size <= SOFT_RESPONSE_LIMIT {
+            if total_bytes + block_size <= soft_limit {
                 total_bytes += block_size;
                 receipts.push(block_receipts);
                 continue;
@@ -266,7 +293,7 @@ where
             let mut partial_block = Vec::new();
             for receipt in block_receipts {
                 let receipt_size = receipt.length();
-                if total_bytes + receipt_size > SOFT_RESPONSE_LIMIT {
+                if total_bytes + receipt_size > soft_limit {
                     break;
                 }
                 total_bytes += receipt_size;
@@ -289,6 +316,7 @@ where
     {
         let mut receipts = Vec::new();
         let mut total_bytes = 0;
+        let soft_limit = self.soft_response_limit();
 
         for hash in request.0 {
             if let Some(receipts_by_block) =
@@ -298,7 +326,7 @@ where
                 total_bytes += transformed_receipts.length();
                 receipts.push(transformed_receipts);
 
-                if receipts.len() >= MAX_RECEIPTS_SERVE || total_bytes > SOFT_RESPONSE_LIMIT {
+                if receipts.len() >= MAX_RECEIPTS_SERVE || total_bytes > soft_limit {
                     break
                 }
             } else {
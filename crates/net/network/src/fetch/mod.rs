@@ -6,6 +6,7 @@ pub use client::FetchClient;
 
 use crate::{message::BlockRequest, session::BlockRangeInfo};
 use alloy_primitives::B256;
+use alloy_rlp::Encodable;
 use futures::StreamExt;
 use reth_eth_wire::{
     Capabilities, EthNetworkPrimitives, GetBlockBodies, GetBlockHeaders, NetworkPrimitives,
@@ -17,7 +18,7 @@ use reth_network_p2p::{
     priority::Priority,
 };
 use reth_network_peers::PeerId;
-use reth_network_types::ReputationChangeKind;
+use reth_network_types::{ReputationChangeKind, ResponseSample};
 use std::{
     collections::{HashMap, VecDeque},
     ops::RangeInclusive,
@@ -26,10 +27,15 @@ use std::{
         Arc,
     },
     task::{Context, Poll},
+    time::Instant,
 };
 use tokio::sync::{mpsc, mpsc::UnboundedSender, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
+/// Peer scores that differ by less than this are considered equal when selecting a peer, so the
+/// timeout decides between peers of similar quality.
+const SCORE_BUCKET_SIZE: f64 = 0.05;
+
 type InflightHeadersRequest<H> = Request<HeadersRequest, PeerRequestResult<Vec<H>>>;
 type InflightBodiesRequest<B> = Request<(), PeerRequestResult<Vec<B>>>;
 
@@ -57,6 +63,8 @@ pub struct StateFetcher<N: NetworkPrimitives = EthNetworkPrimitives> {
     download_requests_rx: UnboundedReceiverStream<DownloadRequest<N>>,
     /// Sender for download requests, used to detach a [`FetchClient`]
     download_requests_tx: UnboundedSender<DownloadRequest<N>>,
+    /// Responses received since the last call to [`Self::drain_response_samples`].
+    response_samples: Vec<(PeerId, ResponseSample)>,
 }
 
 // === impl StateSyncer ===
@@ -73,10 +81,12 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             queued_requests: Default::default(),
             download_requests_rx: UnboundedReceiverStream::new(download_requests_rx),
             download_requests_tx,
+            response_samples: Default::default(),
         }
     }
 
     /// Invoked when connected to a new peer.
+    #[expect(clippy::too_many_arguments)]
     pub(crate) fn new_active_peer(
         &mut self,
         peer_id: PeerId,
@@ -85,6 +95,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         capabilities: Arc<Capabilities>,
         timeout: Arc<AtomicU64>,
         range_info: Option<BlockRangeInfo>,
+        score: f64,
     ) {
         self.peers.insert(
             peer_id,
@@ -96,10 +107,25 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 timeout,
                 last_response_likely_bad: false,
                 range_info,
+                score,
             },
         );
     }
 
+    /// Updates the [`PeerScore`](reth_network_types::PeerScore) value of the peer.
+    pub(crate) fn update_peer_score(&mut self, peer_id: &PeerId, score: f64) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.score = score;
+        }
+    }
+
+    /// Returns the responses received since the last call.
+    pub(crate) fn drain_response_samples(
+        &mut self,
+    ) -> impl Iterator<Item = (PeerId, ResponseSample)> + '_ {
+        self.response_samples.drain(..)
+    }
+
     /// Removes the peer from the peer list, after which it is no longer available for future
     /// requests.
     ///
@@ -138,9 +164,9 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
     }
 
     /// Returns the _next_ idle peer that's ready to accept a request,
-    /// prioritizing those with the lowest timeout/latency and those that recently responded with
-    /// adequate data. Additionally, if full blocks are required this prioritizes peers that have
-    /// full history available
+    /// prioritizing those with the highest score, then the lowest timeout/latency, and those that
+    /// recently responded with adequate data. Additionally, if full blocks are required this
+    /// prioritizes peers that have full history available
     fn next_best_peer(&self, requirement: BestPeerRequirements) -> Option<PeerId> {
         let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle());
 
@@ -159,10 +185,15 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 continue
             }
 
-            // replace best peer if this peer has better rtt and both have same range quality
-            if maybe_better.1.timeout() < best_peer.1.timeout() &&
-                !maybe_better.1.last_response_likely_bad
-            {
+            // replace best peer if this peer has a better score, or a similar score and better rtt,
+            // and both have same range quality
+            let score_bucket = maybe_better.1.score_bucket();
+            let is_better_score = match score_bucket.cmp(&best_peer.1.score_bucket()) {
+                std::cmp::Ordering::Greater => true,
+                std::cmp::Ordering::Equal => maybe_better.1.timeout() < best_peer.1.timeout(),
+                std::cmp::Ordering::Less => false,
+            };
+            if is_better_score && !maybe_better.1.last_response_likely_bad {
                 best_peer = maybe_better;
             }
         }
@@ -241,7 +272,8 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
 
         match req {
             DownloadRequest::GetBlockHeaders { request, response, .. } => {
-                let inflight = Request { request: request.clone(), response };
+                let inflight =
+                    Request { request: request.clone(), response, started: Instant::now() };
                 self.inflight_headers_requests.insert(peer_id, inflight);
                 let HeadersRequest { start, limit, direction } = request;
                 BlockRequest::GetBlockHeaders(GetBlockHeaders {
@@ -252,7 +284,7 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
                 })
             }
             DownloadRequest::GetBlockBodies { request, response, .. } => {
-                let inflight = Request { request: (), response };
+                let inflight = Request { request: (), response, started: Instant::now() };
                 self.inflight_bodies_requests.insert(peer_id, inflight);
                 BlockRequest::GetBlockBodies(GetBlockBodies(request))
             }
@@ -287,6 +319,16 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             resp.as_ref().is_some_and(|r| res.is_likely_bad_headers_response(&r.request));
 
         if let Some(resp) = resp {
+            let bytes = res.as_ref().map_or(0, |headers| headers.iter().map(|h| h.length()).sum());
+            self.response_samples.push((
+                peer_id,
+                ResponseSample {
+                    latency: resp.started.elapsed(),
+                    bytes: bytes as u64,
+                    valid: !is_error && !is_likely_bad_response,
+                },
+            ));
+
             // delegate the response
             let _ = resp.response.send(res.map(|h| (peer_id, h).into()));
         }
@@ -317,6 +359,15 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         let is_likely_bad_response = res.as_ref().map_or(true, |bodies| bodies.is_empty());
 
         if let Some(resp) = self.inflight_bodies_requests.remove(&peer_id) {
+            let bytes = res.as_ref().map_or(0, |bodies| bodies.iter().map(|b| b.length()).sum());
+            self.response_samples.push((
+                peer_id,
+                ResponseSample {
+                    latency: resp.started.elapsed(),
+                    bytes: bytes as u64,
+                    valid: !is_likely_bad_response,
+                },
+            ));
             let _ = resp.response.send(res.map(|b| (peer_id, b).into()));
         }
         if let Some(peer) = self.peers.get_mut(&peer_id) {
@@ -370,6 +421,8 @@ struct Peer {
     last_response_likely_bad: bool,
     /// Tracks the range info for the peer.
     range_info: Option<BlockRangeInfo>,
+    /// The peer's [`PeerScore`](reth_network_types::PeerScore) value, as of its last response.
+    score: f64,
 }
 
 impl Peer {
@@ -377,6 +430,11 @@ impl Peer {
         self.timeout.load(Ordering::Relaxed)
     }
 
+    /// Returns the score rounded to [`SCORE_BUCKET_SIZE`].
+    fn score_bucket(&self) -> i64 {
+        (self.score / SCORE_BUCKET_SIZE).round() as i64
+    }
+
     /// Returns the earliest block number available from the peer.
     fn earliest(&self) -> u64 {
         self.range_info.as_ref().map_or(0, |info| info.earliest())
@@ -487,6 +545,8 @@ struct Request<Req, Resp> {
     // TODO: this can be attached to the response in error case
     request: Req,
     response: oneshot::Sender<Resp>,
+    /// When the request was sent.
+    started: Instant,
 }
 
 /// Requests that can be sent to the Syncer from a [`FetchClient`]
@@ -585,6 +645,7 @@ mod tests {
     use crate::{peers::PeersManager, PeersConfig};
     use alloy_consensus::Header;
     use alloy_primitives::B512;
+    use reth_network_types::PeerScore;
     use std::future::poll_fn;
 
     #[tokio::test(flavor = "multi_thread")]
@@ -625,6 +686,7 @@ mod tests {
             Arc::clone(&capabilities),
             Arc::new(AtomicU64::new(1)),
             None,
+            PeerScore::NEW,
         );
         fetcher.new_active_peer(
             peer2,
@@ -633,6 +695,7 @@ mod tests {
             Arc::clone(&capabilities),
             Arc::new(AtomicU64::new(1)),
             None,
+            PeerScore::NEW,
         );
 
         let first_peer = fetcher.next_best_peer(BestPeerRequirements::None).unwrap();
@@ -668,6 +731,7 @@ mod tests {
             Arc::clone(&capabilities),
             Arc::new(AtomicU64::new(30)),
             None,
+            PeerScore::NEW,
         );
         fetcher.new_active_peer(
             peer2,
@@ -676,6 +740,7 @@ mod tests {
             Arc::clone(&capabilities),
             Arc::clone(&peer2_timeout),
             None,
+            PeerScore::NEW,
         );
         fetcher.new_active_peer(
             peer3,
@@ -684,6 +749,7 @@ mod tests {
             Arc::clone(&capabilities),
             Arc::new(AtomicU64::new(50)),
             None,
+            PeerScore::NEW,
         );
 
         // Must always get peer1 (lowest timeout)
@@ -694,6 +760,16 @@ mod tests {
         // Then we get peer 2 always (now lowest)
         assert_eq!(fetcher.next_best_peer(BestPeerRequirements::None), Some(peer2));
         assert_eq!(fetcher.next_best_peer(BestPeerRequirements::None), Some(peer2));
+
+        // a better score takes precedence over the timeout
+        fetcher.update_peer_score(&peer3, PeerScore::NEW + 0.1);
+        assert_eq!(fetcher.next_best_peer(BestPeerRequirements::None), Some(peer3));
+        fetcher.update_peer_score(&peer3, PeerScore::NEW - 0.1);
+        assert_eq!(fetcher.next_best_peer(BestPeerRequirements::None), Some(peer2));
+
+        // a slightly better score is considered equal, so the timeout decides
+        fetcher.update_peer_score(&peer3, PeerScore::NEW + 0.01);
+        assert_eq!(fetcher.next_best_peer(BestPeerRequirements::None), Some(peer2));
     }
 
     #[tokio::test]
@@ -743,6 +819,7 @@ mod tests {
                     direction: Default::default(),
                 },
                 response: tx,
+                started: Instant::now(),
             };
             let header = Header { number: 0, ..Default::default() };
             (req, header)
@@ -755,6 +832,7 @@ mod tests {
             Arc::new(Capabilities::from(vec![])),
             Default::default(),
             None,
+            PeerScore::NEW,
         );
 
         let (req, header) = request_pair();
@@ -794,6 +872,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(0, 100, B256::random())),
+            score: PeerScore::NEW,
         };
 
         let peer2 = Peer {
@@ -804,6 +883,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(20)),
             last_response_likely_bad: false,
             range_info: None,
+            score: PeerScore::NEW,
         };
 
         // With None requirement, is_better should always return false
@@ -822,6 +902,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(0, 100, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // Peer without full history (earliest = 50)
@@ -833,6 +914,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(50, 100, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // Peer without range info (treated as full history)
@@ -844,6 +926,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: None,
+            score: PeerScore::NEW,
         };
 
         // Peer with full history is better than peer without
@@ -872,6 +955,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(0, 100, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // Peer that doesn't cover the range (earliest too high)
@@ -883,6 +967,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(70, 100, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // Peer that covers the requested range is better than one that doesn't
@@ -906,6 +991,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(0, 50, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // Peer without full history that also covers the range
@@ -917,6 +1003,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(30, 50, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // When both cover the range, prefer none
@@ -938,6 +1025,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(0, 50, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // Peer without full history that also covers the range
@@ -949,6 +1037,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(30, 50, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // When both cover the range, prefer lower start value
@@ -970,6 +1059,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(0, 30, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // Peer without full history that also doesn't cover the range
@@ -981,6 +1071,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(10, 30, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // When neither covers the range, prefer full history
@@ -1002,6 +1093,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(30, 100, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // Peer without range info
@@ -1013,6 +1105,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: None,
+            score: PeerScore::NEW,
         };
 
         // Peer without range info is not better (we prefer peers with known ranges)
@@ -1038,6 +1131,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(30, 100, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // Peer without range info (treated as full history with unknown latest)
@@ -1049,6 +1143,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: None,
+            score: PeerScore::NEW,
         };
 
         // Peer with range that covers is better than peer without range info
@@ -1073,6 +1168,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(70, 100, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // Peer without range info (treated as full history)
@@ -1084,6 +1180,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: None,
+            score: PeerScore::NEW,
         };
 
         // Peer with range that doesn't cover is not better
@@ -1109,6 +1206,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(50, 100, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // Peer that's one block short at the start
@@ -1120,6 +1218,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(51, 100, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // Peer that's one block short at the end
@@ -1131,6 +1230,7 @@ mod tests {
             timeout: Arc::new(AtomicU64::new(10)),
             last_response_likely_bad: false,
             range_info: Some(BlockRangeInfo::new(50, 99, B256::random())),
+            score: PeerScore::NEW,
         };
 
         // Exact coverage is better than short coverage
@@ -39,7 +39,9 @@ use crate::{
 use futures::{Future, StreamExt};
 use parking_lot::Mutex;
 use reth_chainspec::EnrForkIdEntry;
-use reth_eth_wire::{DisconnectReason, EthNetworkPrimitives, NetworkPrimitives};
+use reth_eth_wire::{
+    bandwidth::BandwidthLimiter, DisconnectReason, EthNetworkPrimitives, NetworkPrimitives,
+};
 use reth_fs_util::{self as fs, FsPathError};
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
 use reth_network_api::{
@@ -48,14 +50,14 @@ use reth_network_api::{
     EthProtocolInfo, NetworkEvent, NetworkStatus, PeerInfo, PeerRequest,
 };
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::ReputationChangeKind;
+use reth_network_types::{AllowList, PeerScore, ReputationChangeKind};
 use reth_storage_api::BlockNumReader;
 use reth_tasks::shutdown::GracefulShutdown;
 use reth_tokio_util::EventSender;
 use secp256k1::SecretKey;
 use std::{
     net::SocketAddr,
-    path::Path,
+    path::{Path, PathBuf},
     pin::Pin,
     sync::{
         atomic::{AtomicU64, AtomicUsize, Ordering},
@@ -66,7 +68,7 @@ use std::{
 };
 use tokio::sync::mpsc::{self, error::TrySendError};
 use tokio_stream::wrappers::UnboundedReceiverStream;
-use tracing::{debug, error, trace, warn};
+use tracing::{debug, error, info, trace, warn};
 
 #[cfg_attr(doc, aquamarine::aquamarine)]
 // TODO: Inlined diagram due to a bug in aquamarine library, should become an include when it's
@@ -139,6 +141,8 @@ pub struct NetworkManager<N: NetworkPrimitives = EthNetworkPrimitives> {
     metrics: NetworkMetrics,
     /// Disconnect metrics for the Network
     disconnect_metrics: DisconnectMetrics,
+    /// The file of the [`AllowList`], if configured.
+    allow_list_file: Option<PathBuf>,
 }
 
 impl NetworkManager {
@@ -208,6 +212,12 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         self.swarm.sessions().secret_key()
     }
 
+    /// Returns the limiter for sent bytes shared by all sessions, if an upload limit is
+    /// configured.
+    pub const fn egress_limiter(&self) -> Option<&BandwidthLimiter> {
+        self.swarm.sessions().egress_limiter()
+    }
+
     #[inline]
     fn update_poll_metrics(&self, start: Instant, poll_durations: NetworkManagerPollDurations) {
         let metrics = &self.metrics;
@@ -254,6 +264,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             required_block_hashes,
         } = config;
 
+        let allow_list_file = peers_config.allow_list_file.clone();
+        let allow_list =
+            allow_list_file.as_ref().map(AllowList::from_file).transpose()?.unwrap_or_default();
         let peers_manager = PeersManager::new(peers_config);
         let peers_handle = peers_manager.handle();
 
@@ -315,7 +328,10 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             Arc::clone(&num_active_peers),
         );
 
-        let swarm = Swarm::new(incoming, sessions, state);
+        let mut swarm = Swarm::new(incoming, sessions, state);
+        if allow_list.has_restrictions() {
+            swarm.set_allow_list(allow_list);
+        }
 
         let (to_manager_tx, from_handle_rx) = mpsc::unbounded_channel();
 
@@ -354,6 +370,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             num_active_peers,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
+            allow_list_file,
         })
     }
 
@@ -650,6 +667,25 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         }
     }
 
+    /// Reloads the [`AllowList`] from the configured file.
+    fn reload_allow_list(&mut self) -> Result<(), reth_network_api::NetworkError> {
+        let Some(path) = &self.allow_list_file else {
+            return Err(reth_network_api::NetworkError::AllowList(
+                "no allow-list file configured".to_string(),
+            ))
+        };
+        let allow_list = AllowList::from_file(path)
+            .map_err(|err| reth_network_api::NetworkError::AllowList(err.to_string()))?;
+        info!(
+            target: "net",
+            peers = allow_list.peers().count(),
+            networks = allow_list.networks().len(),
+            "Reloaded allow-list"
+        );
+        self.swarm.set_allow_list(allow_list);
+        Ok(())
+    }
+
     /// Handler for received messages from a handle
     fn on_handle_message(&mut self, msg: NetworkHandleMessage<N>) {
         match msg {
@@ -711,6 +747,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             NetworkHandleMessage::GetReputationById(peer_id, tx) => {
                 let _ = tx.send(self.swarm.state_mut().peers().get_reputation(&peer_id));
             }
+            NetworkHandleMessage::ReloadAllowList(tx) => {
+                let _ = tx.send(self.reload_allow_list());
+            }
             NetworkHandleMessage::FetchClient(tx) => {
                 let _ = tx.send(self.fetch_client());
             }
@@ -997,6 +1036,11 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         }
     }
 
+    /// Returns the score of a connected peer.
+    fn peer_score(&self, peer_id: &PeerId) -> f64 {
+        self.swarm.state().peers().score(peer_id).unwrap_or(PeerScore::NEW)
+    }
+
     /// Returns [`PeerInfo`] for all connected peers
     fn get_peer_infos(&self) -> Vec<PeerInfo> {
         self.swarm
@@ -1004,11 +1048,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             .active_sessions()
             .iter()
             .filter_map(|(&peer_id, session)| {
-                self.swarm
-                    .state()
-                    .peers()
-                    .peer_by_id(peer_id)
-                    .map(|(record, kind)| session.peer_info(&record, kind))
+                self.swarm.state().peers().peer_by_id(peer_id).map(|(record, kind)| {
+                    session.peer_info(&record, kind, self.peer_score(&peer_id))
+                })
             })
             .collect()
     }
@@ -1022,7 +1064,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 .state()
                 .peers()
                 .peer_by_id(peer_id)
-                .map(|(record, kind)| session.peer_info(&record, kind))
+                .map(|(record, kind)| session.peer_info(&record, kind, self.peer_score(&peer_id)))
         })
     }
 
@@ -379,6 +379,11 @@ impl<N: NetworkPrimitives> Peers for NetworkHandle<N> {
         let _ = self.manager().send(NetworkHandleMessage::GetReputationById(peer_id, tx));
         Ok(rx.await?)
     }
+    async fn reload_allow_list(&self) -> Result<(), NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::ReloadAllowList(tx));
+        rx.await?
+    }
 }
 
 impl<N: NetworkPrimitives> PeersHandleProvider for NetworkHandle<N> {
@@ -557,6 +562,8 @@ pub(crate) enum NetworkHandleMessage<N: NetworkPrimitives = EthNetworkPrimitives
     GetPeerInfosByPeerKind(PeerKind, oneshot::Sender<Vec<PeerInfo>>),
     /// Gets the reputation for a specific peer via a oneshot sender.
     GetReputationById(PeerId, oneshot::Sender<Option<Reputation>>),
+    /// Reloads the allow-list from its file.
+    ReloadAllowList(oneshot::Sender<Result<(), NetworkError>>),
     /// Retrieves the `TransactionsHandle` via a oneshot sender.
     GetTransactionsHandle(oneshot::Sender<Option<TransactionsHandle<N>>>),
     /// Initiates a graceful shutdown of the network via a oneshot sender.
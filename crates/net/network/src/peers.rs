@@ -19,8 +19,9 @@ use reth_network_types::{
         config::PeerBackoffDurations,
         reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
     },
-    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
-    ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
+    AllowList, ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeerScore,
+    PeersConfig, ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
+    ResponseSample,
 };
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
@@ -92,6 +93,9 @@ pub struct PeersManager {
     incoming_ip_throttle_duration: Duration,
     /// IP address filter for restricting network connections to specific IP ranges.
     ip_filter: reth_net_banlist::IpFilter,
+    /// Peers whose [`PeerScore`] changed since the last call to
+    /// [`Self::drain_updated_scores`].
+    updated_scores: HashSet<PeerId>,
 }
 
 impl PeersManager {
@@ -111,6 +115,8 @@ impl PeersManager {
             max_backoff_count,
             incoming_ip_throttle_duration,
             ip_filter,
+            // the allow-list is loaded and applied by the network manager
+            allow_list_file: _,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
         let now = Instant::now();
@@ -167,6 +173,7 @@ impl PeersManager {
             net_connection_state: NetworkConnectionState::default(),
             incoming_ip_throttle_duration,
             ip_filter,
+            updated_scores: Default::default(),
         }
     }
 
@@ -208,6 +215,54 @@ impl PeersManager {
         })
     }
 
+    /// Returns the current [`PeerScore`] value of a connected peer.
+    pub(crate) fn score(&self, peer_id: &PeerId) -> Option<f64> {
+        self.peers
+            .get(peer_id)
+            .filter(|peer| peer.state.is_connected())
+            .map(|peer| peer.score.value(std::time::Instant::now()))
+    }
+
+    /// Returns the current score values of all connected peers whose score changed since the last
+    /// call.
+    pub(crate) fn drain_updated_scores(&mut self) -> impl Iterator<Item = (PeerId, f64)> + '_ {
+        let now = std::time::Instant::now();
+        let peers = &self.peers;
+        self.updated_scores.drain().filter_map(move |peer_id| {
+            let peer = peers.get(&peer_id)?;
+            peer.state.is_connected().then(|| (peer_id, peer.score.value(now)))
+        })
+    }
+
+    /// Records a response the peer served.
+    pub(crate) fn on_response(&mut self, peer_id: &PeerId, sample: ResponseSample) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.score.on_response(sample);
+            self.updated_scores.insert(*peer_id);
+        }
+    }
+
+    /// Returns the connected incoming peer with the lowest score below [`PeerScore::NEW`] that is
+    /// neither trusted nor static.
+    ///
+    /// Such a peer performs worse than a new peer is expected to and can be evicted in favour of
+    /// a new connection when all inbound slots are taken.
+    fn worst_evictable_incoming(&self, exclude: Option<&PeerId>) -> Option<PeerId> {
+        let now = std::time::Instant::now();
+        self.peers
+            .iter()
+            .filter(|(id, peer)| {
+                Some(*id) != exclude &&
+                    peer.state.is_incoming() &&
+                    !peer.is_trusted() &&
+                    !peer.is_static()
+            })
+            .map(|(id, peer)| (*id, peer.score.value(now)))
+            .filter(|(_, score)| *score < PeerScore::NEW)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(id, _)| id)
+    }
+
     /// Returns an iterator over all peer ids for peers with the given kind
     pub(crate) fn peers_by_kind(&self, kind: PeerKind) -> impl Iterator<Item = PeerId> + '_ {
         self.peers.iter().filter_map(move |(peer_id, peer)| (peer.kind == kind).then_some(*peer_id))
@@ -261,6 +316,15 @@ impl PeersManager {
 
         // check if we even have slots for a new incoming connection
         if !self.connection_info.has_in_capacity() {
+            // a poorly scored incoming peer can be evicted once the session is established
+            if self.connection_info.has_in_pending_capacity() &&
+                self.worst_evictable_incoming(None).is_some()
+            {
+                self.throttle_incoming_ip(addr);
+                self.connection_info.inc_pending_in();
+                return Ok(())
+            }
+
             if self.trusted_peer_ids.is_empty() {
                 // if we don't have any incoming slots and no trusted peers, we don't accept any new
                 // connections
@@ -366,6 +430,7 @@ impl PeersManager {
                 }
 
                 peer.state = PeerConnectionState::In;
+                peer.score.on_connected(std::time::Instant::now());
 
                 is_trusted = is_trusted || peer.is_trusted();
             }
@@ -374,6 +439,7 @@ impl PeersManager {
                 // disconnect, because we only know the outgoing port
                 let mut peer = Peer::with_state(PeerAddr::from_tcp(addr), PeerConnectionState::In);
                 peer.remove_after_disconnect = true;
+                peer.score.on_connected(std::time::Instant::now());
                 entry.insert(peer);
                 self.queued_actions.push_back(PeerAction::PeerAdded(peer_id));
             }
@@ -383,12 +449,25 @@ impl PeersManager {
         // increment new incoming connection
         self.connection_info.inc_in();
 
-        // disconnect the peer if we don't have capacity for more inbound connections
+        // disconnect the peer if we don't have capacity for more inbound connections, unless
+        // there's a poorly performing peer we can evict instead. The slot is freed once the
+        // evicted session is closed.
         if !is_trusted && !has_in_capacity {
-            self.queued_actions.push_back(PeerAction::Disconnect {
-                peer_id,
-                reason: Some(DisconnectReason::TooManyPeers),
-            });
+            if let Some(evicted) = self.worst_evictable_incoming(Some(&peer_id)) {
+                trace!(target: "net::peers", ?evicted, ?peer_id, "evicting poorly scored peer");
+                if let Some(peer) = self.peers.get_mut(&evicted) {
+                    peer.state.disconnect();
+                }
+                self.queued_actions.push_back(PeerAction::Disconnect {
+                    peer_id: evicted,
+                    reason: Some(DisconnectReason::UselessPeer),
+                });
+            } else {
+                self.queued_actions.push_back(PeerAction::Disconnect {
+                    peer_id,
+                    reason: Some(DisconnectReason::TooManyPeers),
+                });
+            }
         }
     }
 
@@ -472,6 +551,12 @@ impl PeersManager {
         trace!(target: "net::peers", ?peer_id, reputation=?rep, "applying reputation change");
 
         let outcome = if let Some(peer) = self.peers.get_mut(peer_id) {
+            // invalid responses also count against the peer's score
+            if matches!(rep, ReputationChangeKind::BadMessage | ReputationChangeKind::BadBlock) {
+                peer.score.on_invalid_response();
+                self.updated_scores.insert(*peer_id);
+            }
+
             // First check if we should reset the reputation
             if rep.is_reset() {
                 peer.reset_reputation()
@@ -554,6 +639,7 @@ impl PeersManager {
                     // session to that peer
                     peer.severe_backoff_counter = 0;
                     peer.state = PeerConnectionState::Idle;
+                    peer.score.on_disconnected();
 
                     // but we're backing off slightly to avoid dialing the peer again right away, to
                     // give the remote time to also properly register the closed session and clean
@@ -580,6 +666,7 @@ impl PeersManager {
             self.connection_info.decr_state(peer.state);
             self.connection_info.inc_out();
             peer.state = PeerConnectionState::Out;
+            peer.score.on_connected(std::time::Instant::now());
         }
     }
 
@@ -695,6 +782,7 @@ impl PeersManager {
 
                 self.connection_info.decr_state(peer.state);
                 peer.state = PeerConnectionState::Idle;
+                peer.score.on_disconnected();
 
                 if peer.severe_backoff_counter > self.max_backoff_count &&
                     !peer.is_trusted() &&
@@ -738,6 +826,28 @@ impl PeersManager {
         }
     }
 
+    /// Invoked if a pending session was rejected because the peer is not in the [`AllowList`].
+    ///
+    /// An outgoing connection means that the peer is in the set without being allowed, so it's
+    /// removed.
+    pub(crate) fn on_session_not_allowed(&mut self, peer_id: PeerId, direction: Direction) {
+        match direction {
+            Direction::Incoming => {
+                self.connection_info.decr_pending_in();
+            }
+            Direction::Outgoing(_) => {
+                if let Some(peer) = self.peers.get_mut(&peer_id) &&
+                    peer.state.is_pending_out()
+                {
+                    self.connection_info.decr_state(peer.state);
+                    peer.state = PeerConnectionState::Idle;
+                }
+                self.remove_peer_from_trusted_set(peer_id);
+                self.remove_peer(peer_id);
+            }
+        }
+    }
+
     /// Called as follow-up for a discovered peer.
     ///
     /// The [`ForkId`] is retrieved from an ENR record that the peer announces over the discovery
@@ -933,6 +1043,37 @@ impl PeersManager {
         self.trusted_peer_ids.remove(&peer_id);
     }
 
+    /// Applies the [`AllowList`] of the only peers that are allowed to connect.
+    ///
+    /// Peers that are no longer allowed are removed from the set and disconnected, even if they're
+    /// trusted. Listed static and trusted peers are added to the set.
+    pub(crate) fn apply_allow_list(&mut self, allow_list: &AllowList) {
+        let disallowed = self
+            .peers
+            .iter()
+            .filter(|(peer_id, peer)| !allow_list.is_allowed(peer_id, &peer.addr.tcp().ip()))
+            .map(|(peer_id, _)| *peer_id)
+            .collect::<Vec<_>>();
+        for peer_id in disallowed {
+            trace!(target: "net::peers", ?peer_id, "removing peer not in allow-list");
+            self.remove_peer_from_trusted_set(peer_id);
+            self.remove_peer(peer_id);
+        }
+
+        for (peer_id, allowed) in allow_list.peers() {
+            match (allowed.kind, allowed.record) {
+                (PeerKind::Basic, _) => {}
+                (kind, Some(record)) => self.add_peer_kind(
+                    *peer_id,
+                    Some(kind),
+                    PeerAddr::new(record.tcp_addr(), Some(record.udp_addr())),
+                    None,
+                ),
+                (_, None) => self.add_trusted_peer_id(*peer_id),
+            }
+        }
+    }
+
     /// Returns the idle peer with the highest reputation.
     ///
     /// Peers that are `trusted` or `static`, see [`PeerKind`], are prioritized as long as they're
@@ -1271,7 +1412,8 @@ mod tests {
     use reth_network_api::Direction;
     use reth_network_peers::{PeerId, TrustedPeer};
     use reth_network_types::{
-        peers::reputation::DEFAULT_REPUTATION, BackoffKind, Peer, ReputationChangeKind,
+        peers::reputation::DEFAULT_REPUTATION, AllowList, AllowedPeer, BackoffKind, Peer, PeerKind,
+        PeerScore, ReputationChangeKind, ResponseSample,
     };
     use std::{
         future::{poll_fn, Future},
@@ -1868,6 +2010,75 @@ mod tests {
         assert!(peers.on_incoming_pending_session(socket_addr.ip()).is_ok());
     }
 
+    #[tokio::test]
+    async fn test_evict_poorly_scored_incoming_at_capacity() {
+        let mut peers = PeersManager::new(PeersConfig::test().with_max_inbound(1));
+
+        let bad_peer = PeerId::random();
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 1)), 8008);
+        assert!(peers.on_incoming_pending_session(addr.ip()).is_ok());
+        peers.on_incoming_session_established(bad_peer, addr);
+        assert!(matches!(event!(peers), PeerAction::PeerAdded(id) if id == bad_peer));
+
+        for _ in 0..5 {
+            peers.on_response(
+                &bad_peer,
+                ResponseSample { latency: Duration::from_secs(5), bytes: 0, valid: false },
+            );
+        }
+        assert!(peers.score(&bad_peer).unwrap() < PeerScore::NEW);
+        assert_eq!(peers.drain_updated_scores().count(), 1);
+
+        // the poorly scored peer is evicted in favor of the new one
+        let new_peer = PeerId::random();
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        assert!(peers.on_incoming_pending_session(addr.ip()).is_ok());
+        peers.on_incoming_session_established(new_peer, addr);
+        assert!(matches!(event!(peers), PeerAction::PeerAdded(id) if id == new_peer));
+        assert!(matches!(
+            event!(peers),
+            PeerAction::Disconnect { peer_id, reason: Some(DisconnectReason::UselessPeer) }
+                if peer_id == bad_peer
+        ));
+
+        // a peer without samples is not evicted
+        peers.on_active_session_gracefully_closed(bad_peer);
+        assert!(matches!(event!(peers), PeerAction::PeerRemoved(id) if id == bad_peer));
+        assert_eq!(peers.connection_info.num_inbound, 1);
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 3)), 8008);
+        assert!(peers.on_incoming_pending_session(addr.ip()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_allow_list() {
+        let mut peers = PeersManager::default();
+        let allowed = PeerId::random();
+        let disallowed = PeerId::random();
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        peers.add_peer(allowed, PeerAddr::from_tcp(addr), None);
+        peers.add_peer(disallowed, PeerAddr::from_tcp(addr), None);
+        let _ = event!(peers);
+        let _ = event!(peers);
+
+        let trusted = PeerId::random();
+        let allow_list = AllowList::new(
+            [
+                (allowed, AllowedPeer { kind: PeerKind::Basic, record: None }),
+                (trusted, AllowedPeer { kind: PeerKind::Trusted, record: None }),
+            ],
+            [],
+        );
+        peers.apply_allow_list(&allow_list);
+
+        assert!(matches!(event!(peers), PeerAction::PeerRemoved(id) if id == disallowed));
+        assert!(peers.peers.contains_key(&allowed));
+        assert!(peers.trusted_peer_ids.contains(&trusted));
+
+        // outgoing sessions that were rejected remove the peer
+        peers.on_session_not_allowed(allowed, Direction::Outgoing(allowed));
+        assert!(matches!(event!(peers), PeerAction::PeerRemoved(id) if id == allowed));
+    }
+
     #[tokio::test]
     async fn test_closed_incoming() {
         let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
@@ -630,7 +630,14 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
 
             // Send messages by advancing the sink and queuing in buffered messages
             while this.conn.poll_ready_unpin(cx).is_ready() {
-                if let Some(msg) = this.queued_outgoing.pop_front() {
+                // responses to the peer's requests are sent last while the upload limit is
+                // exhausted, so our own requests and announcements aren't stuck behind them
+                let defer_responses = this
+                    .conn
+                    .inner()
+                    .bandwidth()
+                    .is_some_and(|bandwidth| bandwidth.is_egress_exhausted());
+                if let Some(msg) = this.queued_outgoing.pop_next(defer_responses) {
                     progress = true;
                     let res = match msg {
                         OutgoingMessage::Eth(msg) => this.conn.start_send_unpin(msg),
@@ -915,6 +922,19 @@ impl<N: NetworkPrimitives> QueuedOutgoingMessages<N> {
         self.messages.pop_front().inspect(|_| self.count.decrement(1))
     }
 
+    /// Pops the next message to send.
+    ///
+    /// If `defer_responses` is set, the first message that is not a response is preferred over
+    /// the front of the queue.
+    pub(crate) fn pop_next(&mut self, defer_responses: bool) -> Option<OutgoingMessage<N>> {
+        if defer_responses &&
+            let Some(idx) = self.messages.iter().position(|msg| !msg.is_response())
+        {
+            return self.messages.remove(idx).inspect(|_| self.count.decrement(1))
+        }
+        self.pop_front()
+    }
+
     pub(crate) fn shrink_to_fit(&mut self) {
         self.messages.shrink_to_fit();
     }
@@ -1324,6 +1344,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn defer_responses_while_throttled() {
+        let response = || -> OutgoingMessage<EthNetworkPrimitives> {
+            EthMessage::BlockHeaders(RequestPair { request_id: 1, message: Default::default() })
+                .into()
+        };
+        let request = || -> OutgoingMessage<EthNetworkPrimitives> {
+            EthMessage::GetBlockBodies(RequestPair {
+                request_id: 2,
+                message: GetBlockBodies(Vec::new()),
+            })
+            .into()
+        };
+
+        let mut queue = QueuedOutgoingMessages::new(Gauge::noop());
+        queue.push_back(response());
+        queue.push_back(request());
+
+        // the request skips the queued response
+        assert!(!queue.pop_next(true).unwrap().is_response());
+        assert!(queue.pop_next(true).unwrap().is_response());
+        assert!(queue.pop_next(true).is_none());
+
+        // otherwise the order is kept
+        queue.push_back(response());
+        queue.push_back(request());
+        assert!(queue.pop_next(false).unwrap().is_response());
+    }
+
     #[test]
     fn timeout_calculation_sanity_tests() {
         let rtt = Duration::from_secs(5);
@@ -7,10 +7,10 @@ use crate::{
 };
 use reth_ecies::ECIESError;
 use reth_eth_wire::{
-    errors::EthStreamError, Capabilities, DisconnectReason, EthVersion, NetworkPrimitives,
-    UnifiedStatus,
+    bandwidth::BandwidthMeter, errors::EthStreamError, Capabilities, DisconnectReason, EthVersion,
+    NetworkPrimitives, UnifiedStatus,
 };
-use reth_network_api::PeerInfo;
+use reth_network_api::{PeerInfo, ProtocolBandwidth};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::PeerKind;
 use std::{io, net::SocketAddr, sync::Arc, time::Instant};
@@ -75,6 +75,8 @@ pub struct ActiveSessionHandle<N: NetworkPrimitives> {
     pub(crate) local_addr: Option<SocketAddr>,
     /// The Status message the peer sent for the `eth` handshake
     pub(crate) status: Arc<UnifiedStatus>,
+    /// Bytes exchanged with the peer per capability.
+    pub(crate) bandwidth: BandwidthMeter,
 }
 
 // === impl ActiveSessionHandle ===
@@ -136,7 +138,7 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
     }
 
     /// Extracts the [`PeerInfo`] from the session handle.
-    pub(crate) fn peer_info(&self, record: &NodeRecord, kind: PeerKind) -> PeerInfo {
+    pub(crate) fn peer_info(&self, record: &NodeRecord, kind: PeerKind, score: f64) -> PeerInfo {
         PeerInfo {
             remote_id: self.remote_id,
             direction: self.direction,
@@ -150,6 +152,16 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
             status: self.status.clone(),
             session_established: self.established,
             kind,
+            score,
+            bandwidth: self
+                .bandwidth
+                .protocols()
+                .map(|(protocol, ingress_bytes, egress_bytes)| ProtocolBandwidth {
+                    protocol: protocol.to_string(),
+                    ingress_bytes,
+                    egress_bytes,
+                })
+                .collect(),
         }
     }
 }
@@ -18,7 +18,11 @@ use counter::SessionCounter;
 use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
-    errors::EthStreamError, handshake::EthRlpxHandshake, multiplex::RlpxProtocolMultiplexer,
+    bandwidth::{BandwidthLimiter, BandwidthMeter, StreamBandwidth},
+    capability::SharedCapabilities,
+    errors::EthStreamError,
+    handshake::EthRlpxHandshake,
+    multiplex::RlpxProtocolMultiplexer,
     BlockRangeUpdate, Capabilities, DisconnectReason, EthStream, EthVersion,
     HelloMessageWithProtocols, NetworkPrimitives, UnauthedP2PStream, UnifiedStatus,
     HANDSHAKE_TIMEOUT,
@@ -27,7 +31,7 @@ use reth_ethereum_forks::{ForkFilter, ForkId, ForkTransition, Head};
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_api::{PeerRequest, PeerRequestSender};
 use reth_network_peers::PeerId;
-use reth_network_types::SessionsConfig;
+use reth_network_types::{AllowList, BandwidthLimits, SessionsConfig};
 use reth_tasks::TaskSpawner;
 use rustc_hash::FxHashMap;
 use secp256k1::SecretKey;
@@ -56,6 +60,42 @@ pub use handle::{
 };
 pub use reth_network_api::{Direction, PeerInfo};
 
+/// The bandwidth limiters of the [`SessionManager`].
+#[derive(Debug)]
+struct SessionBandwidthLimiters {
+    /// Limits received bytes over all sessions.
+    ingress: Option<BandwidthLimiter>,
+    /// Limits sent bytes over all sessions.
+    egress: Option<BandwidthLimiter>,
+    /// Configured limits, used to create the limiters of each session.
+    limits: BandwidthLimits,
+}
+
+impl SessionBandwidthLimiters {
+    fn new(limits: BandwidthLimits) -> Self {
+        Self {
+            ingress: limits.max_ingress.and_then(BandwidthLimiter::new),
+            egress: limits.max_egress.and_then(BandwidthLimiter::new),
+            limits,
+        }
+    }
+
+    /// Returns the bandwidth accounting for a newly established session, limited by the global
+    /// limiters and new per-peer limiters.
+    fn stream(&self, shared_capabilities: &SharedCapabilities) -> StreamBandwidth {
+        let mut bandwidth = StreamBandwidth::new(BandwidthMeter::new(shared_capabilities));
+        let ingress = self.limits.max_ingress_per_peer.and_then(BandwidthLimiter::new);
+        for limiter in self.ingress.iter().cloned().chain(ingress) {
+            bandwidth = bandwidth.with_ingress_limiter(limiter);
+        }
+        let egress = self.limits.max_egress_per_peer.and_then(BandwidthLimiter::new);
+        for limiter in self.egress.iter().cloned().chain(egress) {
+            bandwidth = bandwidth.with_egress_limiter(limiter);
+        }
+        bandwidth
+    }
+}
+
 /// Internal identifier for active sessions.
 #[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Eq, Hash)]
 pub struct SessionId(usize);
@@ -120,6 +160,10 @@ pub struct SessionManager<N: NetworkPrimitives> {
     /// Shared local range information that gets propagated to active sessions.
     /// This represents the range of blocks that this node can serve to other peers.
     local_range_info: BlockRangeInfo,
+    /// Bandwidth limits enforced on established sessions.
+    bandwidth: SessionBandwidthLimiters,
+    /// Sessions with peers that are not in the allow-list are rejected after the handshake.
+    allow_list: Arc<AllowList>,
 }
 
 // === impl SessionManager ===
@@ -171,9 +215,22 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             metrics: Default::default(),
             handshake,
             local_range_info,
+            bandwidth: SessionBandwidthLimiters::new(config.bandwidth),
+            allow_list: Default::default(),
         }
     }
 
+    /// Sets the [`AllowList`] that the peers of newly established sessions are checked against.
+    pub(crate) fn set_allow_list(&mut self, allow_list: Arc<AllowList>) {
+        self.allow_list = allow_list;
+    }
+
+    /// Returns the limiter shared by all sessions for sent bytes, if an upload limit is
+    /// configured.
+    pub(crate) const fn egress_limiter(&self) -> Option<&BandwidthLimiter> {
+        self.bandwidth.egress.as_ref()
+    }
+
     /// Returns the currently tracked [`ForkId`].
     pub(crate) const fn fork_id(&self) -> ForkId {
         self.fork_filter.current()
@@ -493,7 +550,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                 local_addr,
                 peer_id,
                 capabilities,
-                conn,
+                mut conn,
                 status,
                 direction,
                 client_id,
@@ -525,6 +582,28 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     })
                 }
 
+                if !self.allow_list.is_allowed(&peer_id, &remote_addr.ip()) {
+                    trace!(
+                        target: "net::session",
+                        ?session_id,
+                        ?remote_addr,
+                        ?peer_id,
+                        ?direction,
+                        "peer not in allow-list"
+                    );
+
+                    // a non-fatal reason, so the remote only backs off and can connect once it's
+                    // allowed
+                    self.spawn(async move {
+                        let _ = conn
+                            .into_inner()
+                            .disconnect(DisconnectReason::DisconnectRequested)
+                            .await;
+                    });
+
+                    return Poll::Ready(SessionEvent::NotAllowed { peer_id, remote_addr, direction })
+                }
+
                 let (commands_to_session, commands_rx) = mpsc::channel(self.session_command_buffer);
 
                 let (to_session_tx, messages_rx) = mpsc::channel(self.session_command_buffer);
@@ -549,6 +628,11 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     interval
                 });
 
+                // account and limit the bytes exchanged over the connection
+                let bandwidth = self.bandwidth.stream(conn.inner().shared_capabilities());
+                let meter = bandwidth.meter().clone();
+                conn.inner_mut().set_bandwidth(bandwidth);
+
                 let session = ActiveSession {
                     next_id: 0,
                     remote_peer_id: peer_id,
@@ -592,6 +676,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     client_version: Arc::clone(&client_version),
                     remote_addr,
                     local_addr,
+                    bandwidth: meter,
                 };
 
                 self.active_sessions.insert(peer_id, handle);
@@ -757,6 +842,15 @@ pub enum SessionEvent<N: NetworkPrimitives> {
         /// The direction of the session, either `Inbound` or `Outgoing`
         direction: Direction,
     },
+    /// The peer is not in the allow-list and the session was rejected.
+    NotAllowed {
+        /// The remote node's public key
+        peer_id: PeerId,
+        /// The remote node's socket address
+        remote_addr: SocketAddr,
+        /// The direction of the session, either `Inbound` or `Outgoing`
+        direction: Direction,
+    },
     /// A session received a valid message via `RLPx`.
     ValidMessage {
         /// The remote node's public key
@@ -19,7 +19,7 @@ use reth_eth_wire::{
 use reth_ethereum_forks::ForkId;
 use reth_network_api::{DiscoveredEvent, DiscoveryEvent, PeerRequest, PeerRequestSender};
 use reth_network_peers::PeerId;
-use reth_network_types::{PeerAddr, PeerKind};
+use reth_network_types::{PeerAddr, PeerKind, PeerScore};
 use reth_primitives_traits::Block;
 use std::{
     collections::{HashMap, VecDeque},
@@ -164,6 +164,7 @@ impl<N: NetworkPrimitives> NetworkState<N> {
             Arc::clone(&capabilities),
             timeout,
             range_info,
+            self.peers_manager.score(&peer).unwrap_or(PeerScore::NEW),
         );
 
         self.active_peers.insert(
@@ -421,6 +422,10 @@ impl<N: NetworkPrimitives> NetworkState<N> {
             _ => None,
         };
 
+        for (peer, sample) in self.state_fetcher.drain_response_samples() {
+            self.peers_manager.on_response(&peer, sample);
+        }
+
         if let Some(outcome) = outcome {
             self.on_block_response_outcome(outcome);
         }
@@ -438,6 +443,11 @@ impl<N: NetworkPrimitives> NetworkState<N> {
                 self.on_discovery_event(discovery);
             }
 
+            // rank peers by their latest scores before dispatching new requests
+            for (peer_id, score) in self.peers_manager.drain_updated_scores() {
+                self.state_fetcher.update_peer_score(&peer_id, score);
+            }
+
             while let Poll::Ready(action) = self.state_fetcher.poll(cx) {
                 match action {
                     FetchAction::BlockRequest { peer_id, request } => {
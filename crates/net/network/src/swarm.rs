@@ -13,6 +13,7 @@ use reth_eth_wire::{
 };
 use reth_network_api::{PeerRequest, PeerRequestSender};
 use reth_network_peers::PeerId;
+use reth_network_types::AllowList;
 use std::{
     io,
     net::SocketAddr,
@@ -98,6 +99,16 @@ impl<N: NetworkPrimitives> Swarm<N> {
     pub(crate) const fn sessions_mut(&mut self) -> &mut SessionManager<N> {
         &mut self.sessions
     }
+
+    /// Restricts connections to the peers in the [`AllowList`].
+    ///
+    /// Connected peers that are not allowed are disconnected.
+    pub(crate) fn set_allow_list(&mut self, allow_list: AllowList) {
+        let allow_list = Arc::new(allow_list);
+        self.state.peers_mut().apply_allow_list(&allow_list);
+        self.state.discovery_mut().set_allow_list(Arc::clone(&allow_list));
+        self.sessions.set_allow_list(allow_list);
+    }
 }
 
 impl<N: NetworkPrimitives> Swarm<N> {
@@ -148,6 +159,11 @@ impl<N: NetworkPrimitives> Swarm<N> {
                 self.state.peers_mut().on_already_connected(direction);
                 None
             }
+            SessionEvent::NotAllowed { peer_id, remote_addr, direction } => {
+                trace!(target: "net", ?peer_id, ?remote_addr, ?direction, "peer not in allow-list");
+                self.state.peers_mut().on_session_not_allowed(peer_id, direction);
+                None
+            }
             SessionEvent::ValidMessage { peer_id, message } => {
                 Some(SwarmEvent::ValidMessage { peer_id, message })
             }
@@ -436,6 +436,86 @@ async fn test_trusted_peer_only() {
     assert_eq!(handle1.num_connected_peers(), 1);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_connect_with_allow_list() {
+    reth_tracing::init_test_tracing();
+    let net = Testnet::create(2).await;
+
+    let mut handles = net.handles();
+    let handle0 = handles.next().unwrap();
+    let handle1 = handles.next().unwrap();
+
+    drop(handles);
+    let _handle = net.spawn();
+
+    let dir = tempfile::tempdir().unwrap();
+    let allow_list = dir.path().join("allowlist");
+    std::fs::write(&allow_list, format!("{}\n", handle0.peer_id())).unwrap();
+
+    let secret_key = SecretKey::new(&mut rand_08::thread_rng());
+    let peers_config = PeersConfig::default()
+        .with_backoff_durations(PeerBackoffDurations::test())
+        .with_ban_duration(Duration::from_millis(200))
+        .with_allow_list_file(Some(allow_list.clone()));
+
+    let config = NetworkConfigBuilder::eth(secret_key)
+        .listener_port(0)
+        .disable_discovery()
+        .peer_config(peers_config)
+        .build(NoopProvider::default());
+
+    let network = NetworkManager::new(config).await.unwrap();
+
+    let handle = network.handle().clone();
+    tokio::task::spawn(network);
+
+    let events = handle.event_listener();
+    let mut event_stream = NetworkEventStream::new(events);
+
+    // incoming connections from peers that are not allowed are rejected
+    handle1.add_peer(*handle.peer_id(), handle.local_addr());
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert_eq!(handle.num_connected_peers(), 0);
+
+    // connections to allowed peers succeed
+    handle.add_peer(*handle0.peer_id(), handle0.local_addr());
+    let peer_id = event_stream.next_session_established().await.unwrap();
+    assert_eq!(peer_id, *handle0.peer_id());
+
+    // replace the allowed peer and reload
+    std::fs::write(&allow_list, format!("{}\n", handle1.peer_id())).unwrap();
+    handle.reload_allow_list().await.unwrap();
+
+    let (peer_id, _) = event_stream.next_session_closed().await.unwrap();
+    assert_eq!(peer_id, *handle0.peer_id());
+
+    handle.add_peer(*handle1.peer_id(), handle1.local_addr());
+    let peer_id = event_stream.next_session_established().await.unwrap();
+    assert_eq!(peer_id, *handle1.peer_id());
+    assert_eq!(handle.num_connected_peers(), 1);
+
+    // invalid allow-lists are not applied
+    std::fs::write(&allow_list, "not a peer\n").unwrap();
+    assert!(handle.reload_allow_list().await.is_err());
+    assert_eq!(handle.num_connected_peers(), 1);
+
+    // neither are empty ones, which would allow every peer
+    std::fs::write(&allow_list, "").unwrap();
+    assert!(handle.reload_allow_list().await.is_err());
+    handle0.add_peer(*handle.peer_id(), handle.local_addr());
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert_eq!(handle.num_connected_peers(), 1);
+
+    // a configured but empty allow-list fails startup
+    let peers_config = PeersConfig::default().with_allow_list_file(Some(allow_list));
+    let config = NetworkConfigBuilder::eth(SecretKey::new(&mut rand_08::thread_rng()))
+        .listener_port(0)
+        .disable_discovery()
+        .peer_config(peers_config)
+        .build(NoopProvider::default());
+    assert!(NetworkManager::new(config).await.is_err());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_network_state_change() {
     let net = Testnet::create(1).await;
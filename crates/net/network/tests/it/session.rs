@@ -485,3 +485,27 @@ async fn test_overlapping_version_sets_negotiation() {
 
     handle.terminate().await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_session_reports_bandwidth() {
+    reth_tracing::init_test_tracing();
+
+    let net = Testnet::create(2).await;
+
+    let mut handles = net.handles();
+    let handle0 = handles.next().unwrap();
+    let handle1 = handles.next().unwrap();
+    drop(handles);
+
+    let handle = net.spawn();
+
+    let mut events = NetworkEventStream::new(handle0.event_listener());
+    handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
+    let peer_id = events.next_session_established().await.unwrap();
+
+    let info = handle0.get_peer_by_id(peer_id).await.unwrap().unwrap();
+    let protocols = info.bandwidth.iter().map(|p| p.protocol.as_str()).collect::<Vec<_>>();
+    assert_eq!(protocols, vec!["p2p", "eth"]);
+
+    handle.terminate().await;
+}
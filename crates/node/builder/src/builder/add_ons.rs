@@ -1,9 +1,18 @@
 //! Node add-ons. Depend on core [`NodeComponents`](crate::NodeComponents).
 
-use reth_node_api::{FullNodeComponents, NodeAddOns};
+use reth_db_api::database::Database;
+use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypesWithDBAdapter};
+use reth_provider::DatabaseProvider;
+use reth_prune::segments::Segment;
 
 use crate::{exex::BoxedLaunchExEx, hooks::NodeHooks};
 
+/// The database provider the pruner of the node prunes its segments with.
+pub type PrunerProvider<Node> = DatabaseProvider<
+    <<Node as FullNodeTypes>::DB as Database>::TXMut,
+    NodeTypesWithDBAdapter<<Node as FullNodeTypes>::Types, <Node as FullNodeTypes>::DB>,
+>;
+
 /// Additional node extensions.
 ///
 /// At this point we consider all necessary components defined.
@@ -12,6 +21,8 @@ pub struct AddOns<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> {
     pub hooks: NodeHooks<Node, AddOns>,
     /// The `ExExs` (execution extensions) of the node.
     pub exexs: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    /// Additional segments run by the node's pruner after the configured segments.
+    pub prune_segments: Vec<Box<dyn Segment<PrunerProvider<Node>>>>,
     /// Additional captured addons.
     pub add_ons: AddOns,
 }
@@ -8,6 +8,7 @@ use crate::{
     node::FullNode,
     rpc::{RethRpcAddOns, RethRpcServerHandles, RpcContext},
     BlockReaderFor, DebugNode, DebugNodeLauncher, EngineNodeLauncher, LaunchNode, Node,
+    PrunerProvider,
 };
 use alloy_eips::eip4844::env_settings::EnvKzgSettings;
 use futures::Future;
@@ -32,6 +33,7 @@ use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
     ChainSpecProvider, FullProvider,
 };
+use reth_prune::segments::Segment;
 use reth_tasks::TaskExecutor;
 use reth_transaction_pool::{PoolConfig, PoolTransaction, TransactionPool};
 use secp256k1::SecretKey;
@@ -652,6 +654,19 @@ where
         }
     }
 
+    /// Installs an additional prune segment, e.g. for the tables of a node extension.
+    ///
+    /// See [`NodeBuilderWithComponents::install_prune_segment`].
+    pub fn install_prune_segment<S>(self, segment: S) -> Self
+    where
+        S: Segment<PrunerProvider<NodeAdapter<T, CB::Components>>> + 'static,
+    {
+        Self {
+            builder: self.builder.install_prune_segment(segment),
+            task_executor: self.task_executor,
+        }
+    }
+
     /// Launches the node with the given launcher.
     pub async fn launch_with<L>(self, launcher: L) -> eyre::Result<L::Node>
     where
@@ -10,12 +10,13 @@ use crate::{
     hooks::NodeHooks,
     launch::LaunchNode,
     rpc::{RethRpcAddOns, RethRpcServerHandles, RpcContext},
-    AddOns, ComponentsFor, FullNode,
+    AddOns, ComponentsFor, FullNode, PrunerProvider,
 };
 
 use reth_exex::ExExContext;
 use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypes};
 use reth_node_core::node_config::NodeConfig;
+use reth_prune::segments::Segment;
 use reth_tasks::TaskExecutor;
 use std::{fmt, fmt::Debug, future::Future};
 
@@ -47,7 +48,12 @@ impl<T: FullNodeTypes> NodeBuilderWithTypes<T> {
             config,
             adapter,
             components_builder,
-            add_ons: AddOns { hooks: NodeHooks::default(), exexs: Vec::new(), add_ons: () },
+            add_ons: AddOns {
+                hooks: NodeHooks::default(),
+                exexs: Vec::new(),
+                prune_segments: Vec::new(),
+                add_ons: (),
+            },
         }
     }
 }
@@ -173,7 +179,12 @@ where
             config,
             adapter,
             components_builder,
-            add_ons: AddOns { hooks: NodeHooks::default(), exexs: Vec::new(), add_ons },
+            add_ons: AddOns {
+                hooks: NodeHooks::default(),
+                exexs: Vec::new(),
+                prune_segments: Vec::new(),
+                add_ons,
+            },
         }
     }
 }
@@ -219,6 +230,24 @@ where
         self
     }
 
+    /// Installs an additional prune segment, e.g. for the tables of a node extension.
+    ///
+    /// The segment is run by the node's pruner after the configured segments, sharing their
+    /// delete limit and timeout, and its checkpoints are saved with the checkpoints of all other
+    /// segments.
+    ///
+    /// # Note
+    ///
+    /// The segment must be a [`PruneSegment::Custom`](reth_prune::PruneSegment::Custom)
+    /// segment with a unique ID.
+    pub fn install_prune_segment<S>(mut self, segment: S) -> Self
+    where
+        S: Segment<PrunerProvider<NodeAdapter<T, CB::Components>>> + 'static,
+    {
+        self.add_ons.prune_segments.push(Box::new(segment));
+        self
+    }
+
     /// Launches the node with the given closure.
     pub fn launch_with_fn<L, R>(self, launcher: L) -> R
     where
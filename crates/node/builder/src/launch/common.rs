@@ -68,7 +68,7 @@ use reth_provider::{
     providers::{NodeTypesForProvider, ProviderNodeTypes, RocksDBProvider, StaticFileProvider},
     BlockHashReader, BlockNumReader, DatabaseProviderFactory, ProviderError, ProviderFactory,
     ProviderResult, RocksDBProviderFactory, StageCheckpointReader, StaticFileProviderBuilder,
-    StaticFileProviderFactory,
+    StaticFileProviderFactory, StorageSettingsCache,
 };
 use reth_prune::{PruneModes, PrunerBuilder};
 use reth_rpc_builder::config::RethRpcServerConfig;
@@ -973,8 +973,11 @@ where
     pub fn check_pipeline_consistency(&self) -> ProviderResult<Option<B256>> {
         // We skip the era stage if it's not enabled
         let era_enabled = self.era_import_source().is_some();
-        let mut all_stages =
-            StageId::ALL.into_iter().filter(|id| era_enabled || id != &StageId::Era);
+        // We skip the log index stage if the log index is not enabled
+        let log_index_enabled = self.provider_factory().cached_storage_settings().log_index;
+        let mut all_stages = StageId::ALL.into_iter().filter(|id| {
+            (era_enabled || id != &StageId::Era) && (log_index_enabled || id != &StageId::IndexLogs)
+        });
 
         // Get the expected first stage based on config.
         let first_stage = all_stages.next().expect("there must be at least one stage");
@@ -1269,26 +1272,35 @@ mod tests {
                 pruning: PruningArgs {
                     full: true,
                     block_interval: None,
+                    disk_budget: None,
                     sender_recovery_full: false,
                     sender_recovery_distance: None,
                     sender_recovery_before: None,
+                    sender_recovery_age: None,
                     transaction_lookup_full: false,
                     transaction_lookup_distance: None,
                     transaction_lookup_before: None,
+                    transaction_lookup_age: None,
                     receipts_full: false,
                     receipts_pre_merge: false,
                     receipts_distance: None,
                     receipts_before: None,
+                    receipts_age: None,
                     account_history_full: false,
                     account_history_distance: None,
                     account_history_before: None,
+                    account_history_age: None,
                     storage_history_full: false,
                     storage_history_distance: None,
                     storage_history_before: None,
+                    storage_history_age: None,
+                    history_retain: Vec::new(),
                     bodies_pre_merge: false,
                     bodies_distance: None,
                     receipts_log_filter: None,
                     bodies_before: None,
+                    bodies_age: None,
+                    merkle_changesets_archive: false,
                 },
                 ..NodeConfig::test()
             };
@@ -17,7 +17,7 @@ use reth_engine_tree::{
     engine::{EngineApiRequest, EngineRequestHandler},
     tree::TreeConfig,
 };
-use reth_engine_util::EngineMessageStreamExt;
+use reth_engine_util::{reorg::EngineReorgConfig, EngineMessageStreamExt};
 use reth_exex::ExExManagerHandle;
 use reth_network::{types::BlockRangeUpdate, NetworkSyncUpdater, SyncState};
 use reth_network_api::BlockDownloaderProvider;
@@ -34,10 +34,11 @@ use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
     BlockNumReader, MetadataProvider,
 };
+use reth_prune::{DiskBudget, NodeStorageSize};
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info};
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{collections::HashSet, future::Future, pin::Pin, sync::Arc};
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::warn;
@@ -82,7 +83,7 @@ impl EngineNodeLauncher {
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: AddOns { hooks, exexs: installed_exex, add_ons },
+            add_ons: AddOns { hooks, exexs: installed_exex, prune_segments, add_ons },
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
@@ -181,7 +182,29 @@ impl EngineNodeLauncher {
             pruner_builder =
                 pruner_builder.finished_exex_height(exex_manager_handle.finished_height());
         }
-        let pruner = pruner_builder.build_with_provider_factory(ctx.provider_factory().clone());
+        if let Some(disk_budget) = ctx.prune_config().disk_budget {
+            pruner_builder = pruner_builder.disk_budget(DiskBudget::new(
+                disk_budget,
+                NodeStorageSize::new(
+                    ctx.provider_factory().db_ref().clone(),
+                    ctx.static_file_provider().directory().to_path_buf(),
+                    Some(ctx.data_dir().rocksdb()),
+                ),
+            ));
+        }
+        let mut custom_prune_segments = HashSet::new();
+        for segment in &prune_segments {
+            let segment = segment.segment();
+            if !segment.is_custom() {
+                eyre::bail!("installed prune segment {segment} is not a custom segment")
+            }
+            if !custom_prune_segments.insert(segment) {
+                eyre::bail!("prune segment {segment} is installed more than once")
+            }
+        }
+        let pruner = pruner_builder
+            .build_with_provider_factory(ctx.provider_factory().clone())
+            .with_segments(prune_segments);
         let pruner_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config(), "Pruner initialized");
 
@@ -209,17 +232,30 @@ impl EngineNodeLauncher {
 
         // Create the consensus engine stream with optional reorg
         let consensus_engine_stream = UnboundedReceiverStream::from(consensus_engine_rx)
+            .maybe_replay_messages(
+                node_config
+                    .debug
+                    .engine_api_replay
+                    .clone()
+                    .map(|replay| (replay.path, replay.speed)),
+            )?
             .maybe_skip_fcu(node_config.debug.skip_fcu)
             .maybe_skip_new_payload(node_config.debug.skip_new_payload)
             .maybe_reorg(
                 ctx.blockchain_db().clone(),
                 ctx.components().evm_config().clone(),
+                ctx.components().pool().clone(),
                 || validator_builder.build_tree_validator(&add_ons_ctx, engine_tree_config.clone()),
-                node_config.debug.reorg_frequency,
-                node_config.debug.reorg_depth,
+                node_config.debug.reorg_frequency.map(|frequency| EngineReorgConfig {
+                    frequency,
+                    depth: node_config.debug.reorg_depth.unwrap_or_default(),
+                    branch_length: node_config.debug.reorg_branch_length.unwrap_or(1),
+                    flips: node_config.debug.reorg_flips.unwrap_or_default(),
+                    record: node_config.debug.reorg_record.clone(),
+                }),
             )
             .await?
-            // Store messages _after_ skipping so that `replay-engine` command
+            // Store messages _after_ skipping so that `engine replay` command
             // would replay only the messages that were observed by the engine
             // during this run.
             .maybe_store_messages(node_config.debug.engine_api_store.clone());
@@ -45,6 +45,20 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
         let Self { head, extensions, components, config_container } = self;
         let head = BlockNumHash::new(head.number, head.hash);
 
+        // serve notifications to a remote consumer, if configured
+        #[cfg(unix)]
+        let extensions = extensions
+            .into_iter()
+            .chain(config_container.config.exex.remote_ipc.clone().map(|path| {
+                (
+                    "remote".to_string(),
+                    Box::new(move |ctx: ExExContext<Node>| async move {
+                        Ok(reth_exex::RemoteExEx::new(ctx, path).run())
+                    }) as Box<dyn BoxedLaunchExEx<Node>>,
+                )
+            }))
+            .collect::<Vec<_>>();
+
         if extensions.is_empty() {
             // nothing to launch
             return Ok(None)
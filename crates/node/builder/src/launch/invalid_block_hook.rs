@@ -55,6 +55,7 @@ where
 /// This function constructs the appropriate [`InvalidBlockHook`] based on the debug
 /// configuration in the node config. It supports:
 /// - Witness hooks for capturing block witness data
+/// - Prestate and opcode hooks for tracing the transactions of the block
 /// - Healthy node verification via RPC
 ///
 /// # Arguments
@@ -81,7 +82,9 @@ where
     E: reth_evm::ConfigureEvm<Primitives = N> + Clone + 'static,
 {
     use reth_engine_primitives::{InvalidBlockHooks, NoopInvalidBlockHook};
-    use reth_invalid_block_hooks::InvalidBlockWitnessHook;
+    use reth_invalid_block_hooks::{
+        InvalidBlockTraceHook, InvalidBlockTracer, InvalidBlockWitnessHook,
+    };
 
     let Some(ref hook) = config.debug.invalid_block_hook else {
         return Ok(Box::new(NoopInvalidBlockHook::default()))
@@ -104,9 +107,20 @@ where
                     output_directory,
                     healthy_node_rpc_client.clone(),
                 )),
-                InvalidBlockHookType::PreState | InvalidBlockHookType::Opcode => {
-                    eyre::bail!("invalid block hook {hook:?} is not implemented yet")
-                }
+                InvalidBlockHookType::PreState => Box::new(InvalidBlockTraceHook::new(
+                    provider.clone(),
+                    evm_config.clone(),
+                    InvalidBlockTracer::PreState,
+                    output_directory,
+                    healthy_node_rpc_client.clone(),
+                )),
+                InvalidBlockHookType::Opcode => Box::new(InvalidBlockTraceHook::new(
+                    provider.clone(),
+                    evm_config.clone(),
+                    InvalidBlockTracer::Opcode,
+                    output_directory,
+                    healthy_node_rpc_client.clone(),
+                )),
             } as Box<dyn InvalidBlockHook<_>>)
         })
         .collect::<Result<_, _>>()?;
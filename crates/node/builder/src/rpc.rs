@@ -24,6 +24,7 @@ use reth_node_core::{
     version::{version_metadata, CLIENT_CODE},
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadStore};
+use reth_provider::{DBProvider, DatabaseProviderFactory};
 use reth_rpc::{
     eth::{core::EthRpcConverterFor, DevSigner, EthApiTypes, FullEthApiServer},
     AdminApi,
@@ -998,7 +999,16 @@ where
             }),
         );
 
-        let eth_config = config.rpc.eth_config().max_batch_size(config.txpool.max_batch_size());
+        let mut eth_config = config
+            .rpc
+            .eth_config()
+            .max_batch_size(config.txpool.max_batch_size())
+            .witness_metrics(config.metrics.is_enabled());
+        if node.provider().database_provider_ro()?.prune_modes_ref().is_merkle_changesets_archive()
+        {
+            // The trie changesets of all blocks are kept, so proofs are cheap at any depth
+            eth_config = eth_config.eth_proof_window(u64::MAX);
+        }
         let ctx = EthApiCtx {
             components: &node,
             config: eth_config,
@@ -1339,14 +1349,20 @@ where
         let validator = self.payload_validator_builder.build(ctx).await?;
         let data_dir = ctx.config.datadir.clone().resolve_datadir(ctx.config.chain.chain());
         let invalid_block_hook = ctx.create_invalid_block_hook(&data_dir).await?;
-        Ok(BasicEngineValidator::new(
+        let persist_execution_cache = tree_config.persist_execution_cache();
+        let mut engine_validator = BasicEngineValidator::new(
             ctx.node.provider().clone(),
             std::sync::Arc::new(ctx.node.consensus().clone()),
             ctx.node.evm_config().clone(),
             validator,
             tree_config,
             invalid_block_hook,
-        ))
+        );
+        if persist_execution_cache {
+            engine_validator =
+                engine_validator.with_execution_cache_snapshot(data_dir.engine_cache_snapshot());
+        }
+        Ok(engine_validator)
     }
 }
 
@@ -106,6 +106,20 @@ where
 
     let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
 
+    let mut execution_stage = ExecutionStage::new(
+        evm_config.clone(),
+        Arc::clone(&consensus),
+        stage_config.execution.into(),
+        stage_config.execution_external_clean_threshold(),
+        exex_manager_handle,
+    );
+    if stage_config.execution.prewarm_threads > 0 {
+        execution_stage = execution_stage.with_parallel_execution(
+            provider_factory.clone(),
+            stage_config.execution.prewarm_threads,
+        );
+    }
+
     let pipeline = builder
         .with_tip_sender(tip_tx)
         .with_metrics_tx(metrics_tx)
@@ -116,18 +130,12 @@ where
                 Arc::clone(&consensus),
                 header_downloader,
                 body_downloader,
-                evm_config.clone(),
+                evm_config,
                 stage_config.clone(),
                 prune_config.segments,
                 era_import_source,
             )
-            .set(ExecutionStage::new(
-                evm_config,
-                consensus,
-                stage_config.execution.into(),
-                stage_config.execution_external_clean_threshold(),
-                exex_manager_handle,
-            )),
+            .set(execution_stage),
         )
         .build(provider_factory, static_file_producer);
 
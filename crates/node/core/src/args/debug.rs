@@ -66,12 +66,41 @@ pub struct DebugArgs {
     #[arg(long = "debug.reorg-depth", requires = "reorg_frequency", help_heading = "Debug")]
     pub reorg_depth: Option<usize>,
 
+    /// The number of blocks of the alternative branch built for chain reorgs, at most the reorg
+    /// depth + 1. Defaults to a single block.
+    #[arg(
+        long = "debug.reorg-branch-length",
+        requires = "reorg_frequency",
+        help_heading = "Debug"
+    )]
+    pub reorg_branch_length: Option<usize>,
+
+    /// The number of times the forkchoice is flipped between the canonical and the alternative
+    /// branch after a chain reorg.
+    #[arg(long = "debug.reorg-flips", requires = "reorg_frequency", help_heading = "Debug")]
+    pub reorg_flips: Option<usize>,
+
+    /// The file to record chain reorgs at.
+    /// If specified, every chain reorg is appended as a JSON line with the
+    /// reorged out blocks, the alternative branch and the expected head.
+    #[arg(
+        long = "debug.reorg-record",
+        requires = "reorg_frequency",
+        help_heading = "Debug",
+        value_name = "PATH"
+    )]
+    pub reorg_record: Option<PathBuf>,
+
     /// The path to store engine API messages at.
     /// If specified, all of the intercepted engine API messages
     /// will be written to specified location.
     #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
     pub engine_api_store: Option<PathBuf>,
 
+    /// The engine API messages to replay into the engine, set by `reth engine replay`.
+    #[arg(skip)]
+    pub engine_api_replay: Option<EngineApiReplay>,
+
     /// Determines which type of invalid block hook to install
     ///
     /// Example: `witness,prestate`
@@ -122,7 +151,11 @@ impl Default for DebugArgs {
             skip_new_payload: None,
             reorg_frequency: None,
             reorg_depth: None,
+            reorg_branch_length: None,
+            reorg_flips: None,
+            reorg_record: None,
             engine_api_store: None,
+            engine_api_replay: None,
             invalid_block_hook: Some(InvalidBlockSelection::default()),
             healthy_node_rpc_url: None,
             ethstats: None,
@@ -131,6 +164,16 @@ impl Default for DebugArgs {
     }
 }
 
+/// Engine API messages to replay into the engine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineApiReplay {
+    /// The path the engine API messages were stored at with `--debug.engine-api-store`.
+    pub path: PathBuf,
+    /// The replay speed relative to the recording, `0` replays messages as fast as the engine
+    /// responds.
+    pub speed: u64,
+}
+
 /// Describes the invalid block hooks that should be installed.
 ///
 /// # Example
@@ -37,6 +37,8 @@ pub struct DefaultEngineValues {
     storage_worker_count: Option<usize>,
     account_worker_count: Option<usize>,
     enable_proof_v2: bool,
+    execution_cache_persisted: bool,
+    block_access_lists_recorded: bool,
 }
 
 impl DefaultEngineValues {
@@ -172,6 +174,18 @@ impl DefaultEngineValues {
         self.enable_proof_v2 = v;
         self
     }
+
+    /// Set whether to persist the execution cache across restarts by default
+    pub const fn with_execution_cache_persisted(mut self, v: bool) -> Self {
+        self.execution_cache_persisted = v;
+        self
+    }
+
+    /// Set whether to record and store block access lists by default
+    pub const fn with_block_access_lists_recorded(mut self, v: bool) -> Self {
+        self.block_access_lists_recorded = v;
+        self
+    }
 }
 
 impl Default for DefaultEngineValues {
@@ -197,6 +211,8 @@ impl Default for DefaultEngineValues {
             storage_worker_count: None,
             account_worker_count: None,
             enable_proof_v2: false,
+            execution_cache_persisted: false,
+            block_access_lists_recorded: false,
         }
     }
 }
@@ -320,6 +336,22 @@ pub struct EngineArgs {
     /// Enable V2 storage proofs for state root calculations
     #[arg(long = "engine.enable-proof-v2", default_value_t = DefaultEngineValues::get_global().enable_proof_v2)]
     pub enable_proof_v2: bool,
+
+    /// Save the execution cache to disk on graceful shutdown and restore it on startup, so block
+    /// validation doesn't start with a cold cache after a restart.
+    ///
+    /// Only the cached accounts and storage slots are saved, not the sparse trie. The snapshot is
+    /// only restored if it was taken at the persisted head block.
+    #[arg(long = "engine.persist-execution-cache", default_value_t = DefaultEngineValues::get_global().execution_cache_persisted)]
+    pub execution_cache_persisted: bool,
+
+    /// Record the EIP-7928 block access list of every executed block and store it with the block,
+    /// so it can be served over RPC.
+    ///
+    /// Block access lists provided by payloads are always compared against the recorded ones, and
+    /// a mismatch is logged.
+    #[arg(long = "engine.record-block-access-lists", default_value_t = DefaultEngineValues::get_global().block_access_lists_recorded)]
+    pub block_access_lists_recorded: bool,
 }
 
 #[allow(deprecated)]
@@ -346,6 +378,8 @@ impl Default for EngineArgs {
             storage_worker_count,
             account_worker_count,
             enable_proof_v2,
+            execution_cache_persisted,
+            block_access_lists_recorded,
         } = DefaultEngineValues::get_global().clone();
         Self {
             persistence_threshold,
@@ -371,6 +405,8 @@ impl Default for EngineArgs {
             storage_worker_count,
             account_worker_count,
             enable_proof_v2,
+            execution_cache_persisted,
+            block_access_lists_recorded,
         }
     }
 }
@@ -406,7 +442,10 @@ impl EngineArgs {
             config = config.with_account_worker_count(count);
         }
 
-        config = config.with_enable_proof_v2(self.enable_proof_v2);
+        config = config
+            .with_enable_proof_v2(self.enable_proof_v2)
+            .with_persist_execution_cache(self.execution_cache_persisted)
+            .with_record_block_access_lists(self.block_access_lists_recorded);
 
         config
     }
@@ -458,6 +497,8 @@ mod tests {
             storage_worker_count: Some(16),
             account_worker_count: Some(8),
             enable_proof_v2: false,
+            execution_cache_persisted: true,
+            block_access_lists_recorded: true,
         };
 
         let parsed_args = CommandParser::<EngineArgs>::parse_from([
@@ -488,6 +529,8 @@ mod tests {
             "16",
             "--engine.account-worker-count",
             "8",
+            "--engine.persist-execution-cache",
+            "--engine.record-block-access-lists",
         ])
         .args;
 
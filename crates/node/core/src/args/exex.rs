@@ -0,0 +1,14 @@
+use clap::Args;
+use std::path::PathBuf;
+
+/// Parameters for configuring execution extensions.
+#[derive(Debug, Clone, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "ExEx")]
+pub struct ExExArgs {
+    /// Path of a Unix socket on which `ExEx` notifications are served to a remote consumer.
+    ///
+    /// The consumer subscribes with the head it has processed and acknowledges processed blocks,
+    /// which are taken into account for pruning the same way as for in-process `ExEx`es.
+    #[arg(long = "exex.remote-ipc", value_name = "PATH", verbatim_doc_comment)]
+    pub remote_ipc: Option<PathBuf>,
+}
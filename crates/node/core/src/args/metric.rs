@@ -33,3 +33,10 @@ pub struct MetricArgs {
     )]
     pub push_gateway_interval: Duration,
 }
+
+impl MetricArgs {
+    /// Returns `true` if the metrics are exported, either served or pushed to a gateway.
+    pub const fn is_enabled(&self) -> bool {
+        self.prometheus.is_some() || self.push_gateway_url.is_some()
+    }
+}
@@ -14,11 +14,11 @@ pub use rpc_state_cache::RpcStateCacheArgs;
 
 /// DebugArgs struct for debugging purposes
 mod debug;
-pub use debug::{DebugArgs, InvalidBlockHookType, InvalidBlockSelection};
+pub use debug::{DebugArgs, EngineApiReplay, InvalidBlockHookType, InvalidBlockSelection};
 
 /// DatabaseArgs struct for configuring the database
 mod database;
-pub use database::DatabaseArgs;
+pub use database::{ByteSize, DatabaseArgs};
 
 /// LogArgs struct for configuring the logger
 mod log;
@@ -76,6 +76,10 @@ pub use ress_args::RessArgs;
 mod era;
 pub use era::{DefaultEraHost, EraArgs, EraSourceArgs};
 
+/// `ExExArgs` for configuring execution extensions.
+mod exex;
+pub use exex::ExExArgs;
+
 /// `StaticFilesArgs` for configuring static files.
 mod static_files;
 pub use static_files::StaticFilesArgs;
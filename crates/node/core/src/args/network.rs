@@ -36,7 +36,7 @@ use reth_network::{
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
-    HelloMessageWithProtocols, NetworkConfigBuilder, NetworkPrimitives,
+    BandwidthLimits, HelloMessageWithProtocols, NetworkConfigBuilder, NetworkPrimitives,
 };
 use reth_network_peers::{mainnet_nodes, TrustedPeer};
 use secp256k1::SecretKey;
@@ -227,6 +227,48 @@ pub struct NetworkArgs {
     /// Example: --netrestrict "192.168.0.0/16,10.0.0.0/8"
     #[arg(long, value_name = "NETRESTRICT")]
     pub netrestrict: Option<String>,
+
+    /// Path to an allow-list file of the only peers that are allowed to connect.
+    ///
+    /// Each line is a peer id or enode URL, optionally followed by its role (basic, static or
+    /// trusted), or an IP range in CIDR notation. Discovery stays enabled, but only listed peers
+    /// are dialed and accepted. The file can be reloaded with `admin_reloadAllowlist`.
+    #[arg(long = "net.allowlist", value_name = "FILE")]
+    pub allowlist: Option<PathBuf>,
+
+    /// Maximum upload rate over all peer sessions, in bytes per second.
+    ///
+    /// When exhausted, responses to peer requests are reduced in size first.
+    #[arg(
+        long = "max-upload-rate",
+        value_name = "BYTES_PER_SEC",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub max_upload_rate: Option<u64>,
+
+    /// Maximum download rate over all peer sessions, in bytes per second.
+    #[arg(
+        long = "max-download-rate",
+        value_name = "BYTES_PER_SEC",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub max_download_rate: Option<u64>,
+
+    /// Maximum upload rate of a single peer session, in bytes per second.
+    #[arg(
+        long = "max-upload-rate-peer",
+        value_name = "BYTES_PER_SEC",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub max_upload_rate_per_peer: Option<u64>,
+
+    /// Maximum download rate of a single peer session, in bytes per second.
+    #[arg(
+        long = "max-download-rate-peer",
+        value_name = "BYTES_PER_SEC",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub max_download_rate_per_peer: Option<u64>,
 }
 
 impl NetworkArgs {
@@ -301,6 +343,24 @@ impl NetworkArgs {
         }
     }
 
+    /// Returns the bandwidth limits, where the configured rates take precedence over the given
+    /// limits.
+    pub const fn bandwidth_limits(&self, mut limits: BandwidthLimits) -> BandwidthLimits {
+        if let Some(rate) = self.max_upload_rate {
+            limits.max_egress = Some(rate);
+        }
+        if let Some(rate) = self.max_download_rate {
+            limits.max_ingress = Some(rate);
+        }
+        if let Some(rate) = self.max_upload_rate_per_peer {
+            limits.max_egress_per_peer = Some(rate);
+        }
+        if let Some(rate) = self.max_download_rate_per_peer {
+            limits.max_ingress_per_peer = Some(rate);
+        }
+        limits
+    }
+
     /// Build a [`NetworkConfigBuilder`] from a [`Config`] and a [`EthChainSpec`], in addition to
     /// the values in this option struct.
     ///
@@ -334,12 +394,19 @@ impl NetworkArgs {
             .with_max_inbound_opt(self.resolved_max_inbound_peers())
             .with_max_outbound_opt(self.resolved_max_outbound_peers())
             .with_ip_filter(ip_filter);
+        let allow_list_file =
+            self.allowlist.clone().or_else(|| peers_config.allow_list_file.clone());
+        let peers_config = peers_config.with_allow_list_file(allow_list_file);
 
         // Configure basic network stack
         NetworkConfigBuilder::<N>::new(secret_key)
             .external_ip_resolver(self.nat.clone())
             .sessions_config(
-                config.sessions.clone().with_upscaled_event_buffer(peers_config.max_peers()),
+                config
+                    .sessions
+                    .clone()
+                    .with_upscaled_event_buffer(peers_config.max_peers())
+                    .with_bandwidth_limits(self.bandwidth_limits(config.sessions.bandwidth)),
             )
             .peer_config(peers_config)
             .boot_nodes(chain_bootnodes.clone())
@@ -491,6 +558,11 @@ impl Default for NetworkArgs {
             required_block_hashes: vec![],
             network_id: None,
             netrestrict: None,
+            allowlist: None,
+            max_upload_rate: None,
+            max_download_rate: None,
+            max_upload_rate_per_peer: None,
+            max_download_rate_per_peer: None,
         }
     }
 }
@@ -758,6 +830,37 @@ mod tests {
         assert_eq!(args.max_inbound_peers, Some(15));
     }
 
+    #[test]
+    fn parse_bandwidth_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--max-upload-rate",
+            "1000000",
+            "--max-download-rate-peer",
+            "50000",
+        ])
+        .args;
+
+        let configured = BandwidthLimits::default().with_max_egress(1).with_max_ingress(2);
+        assert_eq!(
+            args.bandwidth_limits(configured),
+            BandwidthLimits::default()
+                .with_max_egress(1_000_000)
+                .with_max_ingress(2)
+                .with_max_ingress_per_peer(50_000)
+        );
+
+        // a zero rate would never allow any transfer
+        for flag in [
+            "--max-upload-rate",
+            "--max-download-rate",
+            "--max-upload-rate-peer",
+            "--max-download-rate-peer",
+        ] {
+            assert!(CommandParser::<NetworkArgs>::try_parse_from(["reth", flag, "0"]).is_err());
+        }
+    }
+
     #[test]
     fn parse_trusted_peer_args() {
         let args =
@@ -1001,6 +1104,17 @@ mod tests {
         assert_eq!(alloy_primitives::hex::encode(secret_key.secret_bytes()), hex);
     }
 
+    #[test]
+    fn parse_allowlist() {
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--net.allowlist",
+            "/etc/reth/allowlist",
+        ])
+        .args;
+        assert_eq!(args.allowlist, Some(PathBuf::from("/etc/reth/allowlist")));
+    }
+
     #[test]
     fn parse_netrestrict_single_network() {
         let args =
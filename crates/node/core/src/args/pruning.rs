@@ -1,6 +1,9 @@
 //! Pruning and full node arguments
 
-use crate::{args::error::ReceiptsLogError, primitives::EthereumHardfork};
+use crate::{
+    args::{error::ReceiptsLogError, ByteSize},
+    primitives::EthereumHardfork,
+};
 use alloy_primitives::{Address, BlockNumber};
 use clap::{builder::RangedU64ValueParser, Args};
 use reth_chainspec::EthereumHardforks;
@@ -9,7 +12,7 @@ use reth_prune_types::{
     PruneMode, PruneModes, ReceiptsLogPruneConfig, MERKLE_CHANGESETS_RETENTION_BLOCKS,
     MINIMUM_PRUNING_DISTANCE,
 };
-use std::{collections::BTreeMap, ops::Not};
+use std::{collections::BTreeMap, ops::Not, time::Duration};
 
 /// Parameters for pruning and full node
 #[derive(Debug, Clone, Args, PartialEq, Eq, Default)]
@@ -23,89 +26,136 @@ pub struct PruningArgs {
     #[arg(long = "prune.block-interval", alias = "block-interval", value_parser = RangedU64ValueParser::<u64>::new().range(1..))]
     pub block_interval: Option<u64>,
 
+    /// Maximum size of the node storage. While the storage is over the budget, configured segments
+    /// are pruned beyond their prune modes, in proportion to the excess. Format:
+    /// <`number`><`unit`>, e.g. 1536GB.
+    #[arg(long = "prune.disk-budget", value_name = "SIZE")]
+    pub disk_budget: Option<ByteSize>,
+
     // Sender Recovery
     /// Prunes all sender recovery data.
-    #[arg(long = "prune.sender-recovery.full", alias = "prune.senderrecovery.full", conflicts_with_all = &["sender_recovery_distance", "sender_recovery_before"])]
+    #[arg(long = "prune.sender-recovery.full", alias = "prune.senderrecovery.full", conflicts_with_all = &["sender_recovery_distance", "sender_recovery_before", "sender_recovery_age"])]
     pub sender_recovery_full: bool,
     /// Prune sender recovery data before the `head-N` block number. In other words, keep last N +
     /// 1 blocks.
-    #[arg(long = "prune.sender-recovery.distance", alias = "prune.senderrecovery.distance", value_name = "BLOCKS", conflicts_with_all = &["sender_recovery_full", "sender_recovery_before"])]
+    #[arg(long = "prune.sender-recovery.distance", alias = "prune.senderrecovery.distance", value_name = "BLOCKS", conflicts_with_all = &["sender_recovery_full", "sender_recovery_before", "sender_recovery_age"])]
     pub sender_recovery_distance: Option<u64>,
     /// Prune sender recovery data before the specified block number. The specified block number is
     /// not pruned.
-    #[arg(long = "prune.sender-recovery.before", alias = "prune.senderrecovery.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["sender_recovery_full", "sender_recovery_distance"])]
+    #[arg(long = "prune.sender-recovery.before", alias = "prune.senderrecovery.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["sender_recovery_full", "sender_recovery_distance", "sender_recovery_age"])]
     pub sender_recovery_before: Option<BlockNumber>,
+    /// Prune sender recovery data of blocks older than the specified duration, relative to the
+    /// timestamp of the `head` block. Format: <`number`><`unit`> where <`unit`> is one of s, m, h,
+    /// d or w, e.g. 30d.
+    #[arg(long = "prune.sender-recovery.age", alias = "prune.senderrecovery.age", value_name = "DURATION", value_parser = PruneMode::parse_age, conflicts_with_all = &["sender_recovery_full", "sender_recovery_distance", "sender_recovery_before"])]
+    pub sender_recovery_age: Option<Duration>,
 
     // Transaction Lookup
     /// Prunes all transaction lookup data.
-    #[arg(long = "prune.transaction-lookup.full", alias = "prune.transactionlookup.full", conflicts_with_all = &["transaction_lookup_distance", "transaction_lookup_before"])]
+    #[arg(long = "prune.transaction-lookup.full", alias = "prune.transactionlookup.full", conflicts_with_all = &["transaction_lookup_distance", "transaction_lookup_before", "transaction_lookup_age"])]
     pub transaction_lookup_full: bool,
     /// Prune transaction lookup data before the `head-N` block number. In other words, keep last N
     /// + 1 blocks.
-    #[arg(long = "prune.transaction-lookup.distance", alias = "prune.transactionlookup.distance", value_name = "BLOCKS", conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_before"])]
+    #[arg(long = "prune.transaction-lookup.distance", alias = "prune.transactionlookup.distance", value_name = "BLOCKS", conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_before", "transaction_lookup_age"])]
     pub transaction_lookup_distance: Option<u64>,
     /// Prune transaction lookup data before the specified block number. The specified block number
     /// is not pruned.
-    #[arg(long = "prune.transaction-lookup.before", alias = "prune.transactionlookup.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_distance"])]
+    #[arg(long = "prune.transaction-lookup.before", alias = "prune.transactionlookup.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_distance", "transaction_lookup_age"])]
     pub transaction_lookup_before: Option<BlockNumber>,
+    /// Prune transaction lookup data of blocks older than the specified duration, relative to the
+    /// timestamp of the `head` block. Format: <`number`><`unit`> where <`unit`> is one of s, m, h,
+    /// d or w, e.g. 30d.
+    #[arg(long = "prune.transaction-lookup.age", alias = "prune.transactionlookup.age", value_name = "DURATION", value_parser = PruneMode::parse_age, conflicts_with_all = &["transaction_lookup_full", "transaction_lookup_distance", "transaction_lookup_before"])]
+    pub transaction_lookup_age: Option<Duration>,
 
     // Receipts
     /// Prunes all receipt data.
-    #[arg(long = "prune.receipts.full", conflicts_with_all = &["receipts_pre_merge", "receipts_distance", "receipts_before"])]
+    #[arg(long = "prune.receipts.full", conflicts_with_all = &["receipts_pre_merge", "receipts_distance", "receipts_before", "receipts_age"])]
     pub receipts_full: bool,
     /// Prune receipts before the merge block.
-    #[arg(long = "prune.receipts.pre-merge", conflicts_with_all = &["receipts_full", "receipts_distance", "receipts_before"])]
+    #[arg(long = "prune.receipts.pre-merge", conflicts_with_all = &["receipts_full", "receipts_distance", "receipts_before", "receipts_age"])]
     pub receipts_pre_merge: bool,
     /// Prune receipts before the `head-N` block number. In other words, keep last N + 1 blocks.
-    #[arg(long = "prune.receipts.distance", value_name = "BLOCKS", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_before"])]
+    #[arg(long = "prune.receipts.distance", value_name = "BLOCKS", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_before", "receipts_age"])]
     pub receipts_distance: Option<u64>,
     /// Prune receipts before the specified block number. The specified block number is not pruned.
-    #[arg(long = "prune.receipts.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_distance"])]
+    #[arg(long = "prune.receipts.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_distance", "receipts_age"])]
     pub receipts_before: Option<BlockNumber>,
+    /// Prune receipts of blocks older than the specified duration, relative to the timestamp of the
+    /// `head` block. Format: <`number`><`unit`> where <`unit`> is one of s, m, h, d or w, e.g. 30d.
+    #[arg(long = "prune.receipts.age", value_name = "DURATION", value_parser = PruneMode::parse_age, conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_distance", "receipts_before"])]
+    pub receipts_age: Option<Duration>,
     // Receipts Log Filter
     /// Configure receipts log filter. Format:
     /// <`address`>:<`prune_mode`>... where <`prune_mode`> can be 'full', 'distance:<`blocks`>', or
     /// 'before:<`block_number`>'
-    #[arg(long = "prune.receiptslogfilter", value_name = "FILTER_CONFIG", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_distance",  "receipts_before"], value_parser = parse_receipts_log_filter)]
+    #[arg(long = "prune.receiptslogfilter", value_name = "FILTER_CONFIG", conflicts_with_all = &["receipts_full", "receipts_pre_merge", "receipts_distance", "receipts_before", "receipts_age"], value_parser = parse_receipts_log_filter)]
     pub receipts_log_filter: Option<ReceiptsLogPruneConfig>,
 
     // Account History
     /// Prunes all account history.
-    #[arg(long = "prune.account-history.full", alias = "prune.accounthistory.full", conflicts_with_all = &["account_history_distance", "account_history_before"])]
+    #[arg(long = "prune.account-history.full", alias = "prune.accounthistory.full", conflicts_with_all = &["account_history_distance", "account_history_before", "account_history_age"])]
     pub account_history_full: bool,
     /// Prune account before the `head-N` block number. In other words, keep last N + 1 blocks.
-    #[arg(long = "prune.account-history.distance", alias = "prune.accounthistory.distance", value_name = "BLOCKS", conflicts_with_all = &["account_history_full", "account_history_before"])]
+    #[arg(long = "prune.account-history.distance", alias = "prune.accounthistory.distance", value_name = "BLOCKS", conflicts_with_all = &["account_history_full", "account_history_before", "account_history_age"])]
     pub account_history_distance: Option<u64>,
     /// Prune account history before the specified block number. The specified block number is not
     /// pruned.
-    #[arg(long = "prune.account-history.before", alias = "prune.accounthistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["account_history_full", "account_history_distance"])]
+    #[arg(long = "prune.account-history.before", alias = "prune.accounthistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["account_history_full", "account_history_distance", "account_history_age"])]
     pub account_history_before: Option<BlockNumber>,
+    /// Prune account history of blocks older than the specified duration, relative to the timestamp
+    /// of the `head` block. Format: <`number`><`unit`> where <`unit`> is one of s, m, h, d or w,
+    /// e.g. 30d.
+    #[arg(long = "prune.account-history.age", alias = "prune.accounthistory.age", value_name = "DURATION", value_parser = PruneMode::parse_age, conflicts_with_all = &["account_history_full", "account_history_distance", "account_history_before"])]
+    pub account_history_age: Option<Duration>,
 
     // Storage History
     /// Prunes all storage history data.
-    #[arg(long = "prune.storage-history.full", alias = "prune.storagehistory.full", conflicts_with_all = &["storage_history_distance", "storage_history_before"])]
+    #[arg(long = "prune.storage-history.full", alias = "prune.storagehistory.full", conflicts_with_all = &["storage_history_distance", "storage_history_before", "storage_history_age"])]
     pub storage_history_full: bool,
     /// Prune storage history before the `head-N` block number. In other words, keep last N + 1
     /// blocks.
-    #[arg(long = "prune.storage-history.distance", alias = "prune.storagehistory.distance", value_name = "BLOCKS", conflicts_with_all = &["storage_history_full", "storage_history_before"])]
+    #[arg(long = "prune.storage-history.distance", alias = "prune.storagehistory.distance", value_name = "BLOCKS", conflicts_with_all = &["storage_history_full", "storage_history_before", "storage_history_age"])]
     pub storage_history_distance: Option<u64>,
     /// Prune storage history before the specified block number. The specified block number is not
     /// pruned.
-    #[arg(long = "prune.storage-history.before", alias = "prune.storagehistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["storage_history_full", "storage_history_distance"])]
+    #[arg(long = "prune.storage-history.before", alias = "prune.storagehistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["storage_history_full", "storage_history_distance", "storage_history_age"])]
     pub storage_history_before: Option<BlockNumber>,
+    /// Prune storage history of blocks older than the specified duration, relative to the timestamp
+    /// of the `head` block. Format: <`number`><`unit`> where <`unit`> is one of s, m, h, d or w,
+    /// e.g. 30d.
+    #[arg(long = "prune.storage-history.age", alias = "prune.storagehistory.age", value_name = "DURATION", value_parser = PruneMode::parse_age, conflicts_with_all = &["storage_history_full", "storage_history_distance", "storage_history_before"])]
+    pub storage_history_age: Option<Duration>,
+
+    // History Retention
+    /// Retain the full account and storage history of the specified addresses, even if account or
+    /// storage history is pruned. Comma-separated list of addresses.
+    #[arg(long = "prune.history.retain", value_name = "ADDRESSES", value_delimiter = ',')]
+    pub history_retain: Vec<Address>,
 
     // Bodies
     /// Prune bodies before the merge block.
-    #[arg(long = "prune.bodies.pre-merge", value_name = "BLOCKS", conflicts_with_all = &["bodies_distance", "bodies_before"])]
+    #[arg(long = "prune.bodies.pre-merge", value_name = "BLOCKS", conflicts_with_all = &["bodies_distance", "bodies_before", "bodies_age"])]
     pub bodies_pre_merge: bool,
     /// Prune bodies before the `head-N` block number. In other words, keep last N + 1
     /// blocks.
-    #[arg(long = "prune.bodies.distance", value_name = "BLOCKS", conflicts_with_all = &["bodies_pre_merge", "bodies_before"])]
+    #[arg(long = "prune.bodies.distance", value_name = "BLOCKS", conflicts_with_all = &["bodies_pre_merge", "bodies_before", "bodies_age"])]
     pub bodies_distance: Option<u64>,
     /// Prune storage history before the specified block number. The specified block number is not
     /// pruned.
-    #[arg(long = "prune.bodies.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["bodies_distance", "bodies_pre_merge"])]
+    #[arg(long = "prune.bodies.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["bodies_distance", "bodies_pre_merge", "bodies_age"])]
     pub bodies_before: Option<BlockNumber>,
+    /// Prune bodies of blocks older than the specified duration, relative to the timestamp of the
+    /// `head` block. Format: <`number`><`unit`> where <`unit`> is one of s, m, h, d or w, e.g. 30d.
+    #[arg(long = "prune.bodies.age", value_name = "DURATION", value_parser = PruneMode::parse_age, conflicts_with_all = &["bodies_pre_merge", "bodies_distance", "bodies_before"])]
+    pub bodies_age: Option<Duration>,
+
+    // Merkle Changesets
+    /// Keep the merkle changesets of all blocks since the archive mode was enabled, instead of
+    /// only the recent ones. This allows `eth_getProof` to generate proofs for any of these blocks,
+    /// regardless of `--rpc.eth-proof-window`.
+    #[arg(long = "prune.merkle-changesets.archive")]
+    pub merkle_changesets_archive: bool,
 }
 
 impl PruningArgs {
@@ -124,6 +174,7 @@ impl PruningArgs {
         if self.full {
             config = PruneConfig {
                 block_interval: config.block_interval,
+                disk_budget: config.disk_budget,
                 segments: PruneModes {
                     sender_recovery: Some(PruneMode::Full),
                     transaction_lookup: None,
@@ -134,8 +185,11 @@ impl PruningArgs {
                         .ethereum_fork_activation(EthereumHardfork::Paris)
                         .block_number()
                         .map(PruneMode::Before),
-                    merkle_changesets: PruneMode::Distance(MERKLE_CHANGESETS_RETENTION_BLOCKS),
+                    merkle_changesets: Some(PruneMode::Distance(
+                        MERKLE_CHANGESETS_RETENTION_BLOCKS,
+                    )),
                     receipts_log_filter: Default::default(),
+                    history_retention: Default::default(),
                 },
             }
         }
@@ -144,6 +198,9 @@ impl PruningArgs {
         if let Some(block_interval) = self.block_interval {
             config.block_interval = block_interval as usize;
         }
+        if let Some(disk_budget) = self.disk_budget {
+            config.disk_budget = Some(usize::from(disk_budget) as u64);
+        }
         if let Some(mode) = self.sender_recovery_prune_mode() {
             config.segments.sender_recovery = Some(mode);
         }
@@ -170,6 +227,12 @@ impl PruningArgs {
             // over the logs filter
             config.segments.receipts.take();
         }
+        if !self.history_retain.is_empty() {
+            config.segments.history_retention = self.history_retain.iter().copied().collect();
+        }
+        if self.merkle_changesets_archive {
+            config.segments.merkle_changesets = None;
+        }
 
         config.is_default().not().then_some(config)
     }
@@ -185,8 +248,10 @@ impl PruningArgs {
                 .map(PruneMode::Before)
         } else if let Some(distance) = self.bodies_distance {
             Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.bodies_before {
+            Some(PruneMode::Before(block_number))
         } else {
-            self.bodies_before.map(PruneMode::Before)
+            self.bodies_age.map(PruneMode::Age)
         }
     }
 
@@ -197,6 +262,8 @@ impl PruningArgs {
             Some(PruneMode::Distance(distance))
         } else if let Some(block_number) = self.sender_recovery_before {
             Some(PruneMode::Before(block_number))
+        } else if let Some(age) = self.sender_recovery_age {
+            Some(PruneMode::Age(age))
         } else {
             None
         }
@@ -209,6 +276,8 @@ impl PruningArgs {
             Some(PruneMode::Distance(distance))
         } else if let Some(block_number) = self.transaction_lookup_before {
             Some(PruneMode::Before(block_number))
+        } else if let Some(age) = self.transaction_lookup_age {
+            Some(PruneMode::Age(age))
         } else {
             None
         }
@@ -227,8 +296,10 @@ impl PruningArgs {
             Some(PruneMode::Full)
         } else if let Some(distance) = self.receipts_distance {
             Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.receipts_before {
+            Some(PruneMode::Before(block_number))
         } else {
-            self.receipts_before.map(PruneMode::Before)
+            self.receipts_age.map(PruneMode::Age)
         }
     }
 
@@ -239,6 +310,8 @@ impl PruningArgs {
             Some(PruneMode::Distance(distance))
         } else if let Some(block_number) = self.account_history_before {
             Some(PruneMode::Before(block_number))
+        } else if let Some(age) = self.account_history_age {
+            Some(PruneMode::Age(age))
         } else {
             None
         }
@@ -251,6 +324,8 @@ impl PruningArgs {
             Some(PruneMode::Distance(distance))
         } else if let Some(block_number) = self.storage_history_before {
             Some(PruneMode::Before(block_number))
+        } else if let Some(age) = self.storage_history_age {
+            Some(PruneMode::Age(age))
         } else {
             None
         }
@@ -329,6 +404,91 @@ mod tests {
         assert_eq!(args.receipts_log_filter, Some(config));
     }
 
+    #[test]
+    fn parse_history_retain() {
+        let args = CommandParser::<PruningArgs>::parse_from([
+            "reth",
+            "--prune.account-history.distance",
+            "10064",
+            "--prune.history.retain",
+            "0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002",
+        ])
+        .args;
+        let config = args.prune_config(&*reth_chainspec::MAINNET).unwrap();
+        assert_eq!(config.segments.account_history, Some(PruneMode::Distance(10064)));
+        assert!(config
+            .segments
+            .history_retention
+            .is_retained(&address!("0x0000000000000000000000000000000000000002")));
+        assert_eq!(config.segments.history_retention.len(), 2);
+    }
+
+    #[test]
+    fn parse_merkle_changesets_archive() {
+        let args = CommandParser::<PruningArgs>::parse_from([
+            "reth",
+            "--full",
+            "--prune.merkle-changesets.archive",
+        ])
+        .args;
+        let config = args.prune_config(&*reth_chainspec::MAINNET).unwrap();
+        assert!(config.segments.is_merkle_changesets_archive());
+        assert_eq!(
+            config.segments.account_history,
+            Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE))
+        );
+    }
+
+    #[test]
+    fn parse_disk_budget() {
+        let args =
+            CommandParser::<PruningArgs>::parse_from(["reth", "--prune.disk-budget", "2TB"]).args;
+        let config = args.prune_config(&*reth_chainspec::MAINNET).unwrap();
+        assert_eq!(config.disk_budget, Some(2 * 1024 * 1024 * 1024 * 1024));
+    }
+
+    #[test]
+    fn parse_prune_age() {
+        let args = CommandParser::<PruningArgs>::parse_from([
+            "reth",
+            "--prune.receipts.age",
+            "30d",
+            "--prune.bodies.age",
+            "2w",
+            "--prune.sender-recovery.age",
+            "12h",
+        ])
+        .args;
+        let config = args.prune_config(&*reth_chainspec::MAINNET).unwrap();
+        assert_eq!(
+            config.segments.receipts,
+            Some(PruneMode::Age(Duration::from_secs(30 * 24 * 60 * 60)))
+        );
+        assert_eq!(
+            config.segments.bodies_history,
+            Some(PruneMode::Age(Duration::from_secs(14 * 24 * 60 * 60)))
+        );
+        assert_eq!(
+            config.segments.sender_recovery,
+            Some(PruneMode::Age(Duration::from_secs(12 * 60 * 60)))
+        );
+
+        assert!(CommandParser::<PruningArgs>::try_parse_from([
+            "reth",
+            "--prune.account-history.age",
+            "30y"
+        ])
+        .is_err());
+        assert!(CommandParser::<PruningArgs>::try_parse_from([
+            "reth",
+            "--prune.storage-history.age",
+            "30d",
+            "--prune.storage-history.distance",
+            "10064"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn parse_receiptslogfilter() {
         let default_args = PruningArgs::default();
@@ -87,6 +87,7 @@ pub struct DefaultRpcServerArgs {
     rpc_max_simulate_blocks: u64,
     rpc_eth_proof_window: u64,
     rpc_proof_permits: usize,
+    rpc_witness_cache_size: u64,
     rpc_pending_block: PendingBlockKind,
     rpc_forwarder: Option<Url>,
     builder_disallow: Option<HashSet<Address>>,
@@ -322,6 +323,12 @@ impl DefaultRpcServerArgs {
         self
     }
 
+    /// Set the default execution witness cache size
+    pub const fn with_rpc_witness_cache_size(mut self, v: u64) -> Self {
+        self.rpc_witness_cache_size = v;
+        self
+    }
+
     /// Set the default pending block kind
     pub const fn with_rpc_pending_block(mut self, v: PendingBlockKind) -> Self {
         self.rpc_pending_block = v;
@@ -398,6 +405,7 @@ impl Default for DefaultRpcServerArgs {
             rpc_max_simulate_blocks: constants::DEFAULT_MAX_SIMULATE_BLOCKS,
             rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
+            rpc_witness_cache_size: 0,
             rpc_pending_block: PendingBlockKind::Full,
             rpc_forwarder: None,
             builder_disallow: None,
@@ -608,6 +616,15 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
 
+    /// Maximum memory in megabytes used to cache the execution witnesses generated by the
+    /// `debug_executionWitness` family of methods (0 = disabled).
+    #[arg(
+        long = "rpc.witness-cache-size",
+        value_name = "MB",
+        default_value_t = DefaultRpcServerArgs::get_global().rpc_witness_cache_size
+    )]
+    pub rpc_witness_cache_size: u64,
+
     /// Configures the pending block behavior for RPC responses.
     ///
     /// Options: full (include all transactions), empty (header only), none (disable pending
@@ -802,6 +819,7 @@ impl Default for RpcServerArgs {
             rpc_max_simulate_blocks,
             rpc_eth_proof_window,
             rpc_proof_permits,
+            rpc_witness_cache_size,
             rpc_pending_block,
             rpc_forwarder,
             builder_disallow,
@@ -846,6 +864,7 @@ impl Default for RpcServerArgs {
             rpc_max_simulate_blocks,
             rpc_eth_proof_window,
             rpc_proof_permits,
+            rpc_witness_cache_size,
             rpc_pending_block,
             rpc_forwarder,
             builder_disallow,
@@ -55,4 +55,8 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The log index stage within the pipeline.
+    ///
+    /// Manages the index of log addresses and topics.
+    LogIndex,
 }
@@ -379,6 +379,13 @@ impl<D> ChainPath<D> {
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
     }
+
+    /// Returns the path to the engine execution cache snapshot for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/engine/execution-cache.rlp`
+    pub fn engine_cache_snapshot(&self) -> PathBuf {
+        self.data_dir().join("engine/execution-cache.rlp")
+    }
 }
 
 impl<D> AsRef<Path> for ChainPath<D> {
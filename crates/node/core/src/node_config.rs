@@ -32,7 +32,7 @@ use std::{
 };
 use tracing::*;
 
-use crate::args::{EraArgs, MetricArgs};
+use crate::args::{EraArgs, ExExArgs, MetricArgs};
 pub use reth_engine_primitives::{
     DEFAULT_MEMORY_BLOCK_BUFFER_TARGET, DEFAULT_PERSISTENCE_THRESHOLD, DEFAULT_RESERVED_CPU_CORES,
 };
@@ -148,6 +148,9 @@ pub struct NodeConfig<ChainSpec> {
     /// All ERA import related arguments with --era prefix
     pub era: EraArgs,
 
+    /// All `ExEx` related arguments with --exex prefix
+    pub exex: ExExArgs,
+
     /// All static files related arguments
     pub static_files: StaticFilesArgs,
 }
@@ -180,6 +183,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             datadir: DatadirArgs::default(),
             engine: EngineArgs::default(),
             era: EraArgs::default(),
+            exex: ExExArgs::default(),
             static_files: StaticFilesArgs::default(),
         }
     }
@@ -254,6 +258,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             pruning,
             engine,
             era,
+            exex,
             static_files,
             ..
         } = self;
@@ -273,6 +278,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             pruning,
             engine,
             era,
+            exex,
             static_files,
         }
     }
@@ -543,6 +549,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             pruning: self.pruning,
             engine: self.engine,
             era: self.era,
+            exex: self.exex,
             static_files: self.static_files,
         }
     }
@@ -584,6 +591,7 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             datadir: self.datadir.clone(),
             engine: self.engine.clone(),
             era: self.era.clone(),
+            exex: self.exex.clone(),
             static_files: self.static_files,
         }
     }
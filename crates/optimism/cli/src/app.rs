@@ -98,8 +98,11 @@ where
                 runner.run_blocking_until_ctrl_c(command.execute::<OpNode>())
             }
             Commands::DumpGenesis(command) => runner.run_blocking_until_ctrl_c(command.execute()),
-            Commands::Db(command) => {
-                runner.run_blocking_command_until_exit(|ctx| command.execute::<OpNode>(ctx))
+            Commands::Db(command) => runner.run_blocking_command_until_exit(|ctx| {
+                command.execute::<OpNode, _>(ctx, components)
+            }),
+            Commands::ExEx(command) => {
+                runner.run_blocking_until_ctrl_c(command.execute::<OpNode>())
             }
             Commands::Stage(command) => {
                 runner.run_command_until_exit(|ctx| command.execute::<OpNode, _>(ctx, components))
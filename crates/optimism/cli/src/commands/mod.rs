@@ -5,7 +5,7 @@ use import_receipts::ImportReceiptsOpCommand;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
-    config_cmd, db, dump_genesis, init_cmd,
+    config_cmd, db, dump_genesis, exex, init_cmd,
     node::{self, NoArgs},
     p2p, prune, re_execute, stage,
 };
@@ -42,6 +42,9 @@ pub enum Commands<Spec: ChainSpecParser = OpChainSpecParser, Ext: clap::Args + f
     /// Database debugging utilities
     #[command(name = "db")]
     Db(db::Command<Spec>),
+    /// `ExEx` debugging utilities
+    #[command(name = "exex")]
+    ExEx(exex::Command<Spec>),
     /// Manipulate individual stages.
     #[command(name = "stage")]
     Stage(Box<stage::Command<Spec>>),
@@ -76,6 +79,7 @@ impl<
             Self::InitState(cmd) => cmd.chain_spec(),
             Self::DumpGenesis(cmd) => cmd.chain_spec(),
             Self::Db(cmd) => cmd.chain_spec(),
+            Self::ExEx(cmd) => cmd.chain_spec(),
             Self::Stage(cmd) => cmd.chain_spec(),
             Self::P2P(cmd) => cmd.chain_spec(),
             Self::Config(_) => None,
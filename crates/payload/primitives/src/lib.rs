@@ -16,6 +16,7 @@ extern crate alloc;
 use alloy_primitives::Bytes;
 use reth_chainspec::EthereumHardforks;
 use reth_primitives_traits::{NodePrimitives, SealedBlock};
+use serde::{Deserialize, Serialize};
 
 mod error;
 pub use error::{
@@ -381,7 +382,7 @@ where
 }
 
 /// The version of Engine API message.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
 pub enum EngineApiMessageVersion {
     /// Version 1
     V1 = 1,
@@ -1,4 +1,4 @@
-use crate::{segments::SegmentSet, Pruner};
+use crate::{segments::SegmentSet, DiskBudget, Pruner};
 use alloy_eips::eip2718::Encodable2718;
 use reth_config::PruneConfig;
 use reth_db_api::{table::Value, transaction::DbTxMut};
@@ -26,10 +26,15 @@ pub struct PrunerBuilder {
     timeout: Option<Duration>,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    /// Maximum size of the node storage.
+    disk_budget: Option<DiskBudget>,
 }
 
 impl PrunerBuilder {
     /// Creates a new [`PrunerBuilder`] from the given [`PruneConfig`].
+    ///
+    /// The [`PruneConfig::disk_budget`] is not applied, because the storage size reader depends on
+    /// the node. Use [`PrunerBuilder::disk_budget`] to set it.
     pub fn new(pruner_config: PruneConfig) -> Self {
         Self::default()
             .block_interval(pruner_config.block_interval)
@@ -72,6 +77,13 @@ impl PrunerBuilder {
         self
     }
 
+    /// Sets the maximum size of the node storage. While the storage is over the budget, segments
+    /// are pruned beyond their prune modes, in proportion to the excess.
+    pub fn disk_budget(mut self, disk_budget: DiskBudget) -> Self {
+        self.disk_budget = Some(disk_budget);
+        self
+    }
+
     /// Builds a [Pruner] from the current configuration with the given provider factory.
     pub fn build_with_provider_factory<PF>(self, provider_factory: PF) -> Pruner<PF::ProviderRW, PF>
     where
@@ -100,6 +112,7 @@ impl PrunerBuilder {
             self.timeout,
             self.finished_exex_height,
         )
+        .with_disk_budget(self.disk_budget)
     }
 
     /// Builds a [Pruner] from the current configuration with the given static file provider.
@@ -127,6 +140,7 @@ impl PrunerBuilder {
             self.timeout,
             self.finished_exex_height,
         )
+        .with_disk_budget(self.disk_budget)
     }
 }
 
@@ -138,6 +152,7 @@ impl Default for PrunerBuilder {
             delete_limit: usize::MAX,
             timeout: None,
             finished_exex_height: watch::channel(FinishedExExHeight::NoExExs).1,
+            disk_budget: None,
         }
     }
 }
@@ -0,0 +1,154 @@
+//! Disk budget enforced by the [`Pruner`](crate::Pruner).
+
+use reth_db_api::database_metrics::DatabaseMetrics;
+use reth_fs_util::{self as fs, FsPathError};
+use reth_provider::{ProviderError, ProviderResult};
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Size of the node storage, in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StorageSize {
+    /// Size of the data stored in the database.
+    pub database: u64,
+    /// Size of the static files.
+    pub static_files: u64,
+    /// Size of the `RocksDB` database.
+    pub rocksdb: u64,
+}
+
+impl StorageSize {
+    /// Returns the total size of the node storage.
+    pub const fn total(&self) -> u64 {
+        self.database + self.static_files + self.rocksdb
+    }
+}
+
+/// Reads the current size of the node storage.
+pub trait StorageSizeReader: Debug + Send + Sync {
+    /// Returns the current size of the node storage.
+    fn storage_size(&self) -> ProviderResult<StorageSize>;
+}
+
+/// Maximum size of the node storage.
+///
+/// While the storage is over the budget, the [`Pruner`](crate::Pruner) prunes the oldest blocks
+/// that every segment retains beyond its prune mode, in proportion to how far the storage is over
+/// the budget. Segments are never pruned below the minimum number of blocks they require.
+#[derive(Debug, Clone)]
+pub struct DiskBudget {
+    /// Maximum size of the node storage, in bytes.
+    max_size: u64,
+    /// Reader of the current storage size.
+    reader: Arc<dyn StorageSizeReader>,
+}
+
+impl DiskBudget {
+    /// Creates a new [`DiskBudget`] with the given maximum size in bytes, measured by the given
+    /// reader.
+    pub fn new(max_size: u64, reader: impl StorageSizeReader + 'static) -> Self {
+        Self { max_size, reader: Arc::new(reader) }
+    }
+
+    /// Returns the maximum size of the node storage, in bytes.
+    pub const fn max_size(&self) -> u64 {
+        self.max_size
+    }
+
+    /// Returns the current size of the node storage.
+    pub fn storage_size(&self) -> ProviderResult<StorageSize> {
+        self.reader.storage_size()
+    }
+}
+
+/// [`StorageSizeReader`] for the storage of a node.
+///
+/// The database size is the size of the pages used by its tables, as reported by the
+/// `db.table_size` database metrics. Free pages are not counted, because the database file does
+/// not shrink when data is pruned. Static files and `RocksDB` are measured by the size of the
+/// files in their directories.
+#[derive(Debug)]
+pub struct NodeStorageSize<DB> {
+    db: DB,
+    static_files_dir: PathBuf,
+    rocksdb_dir: Option<PathBuf>,
+}
+
+impl<DB> NodeStorageSize<DB> {
+    /// Creates a new [`NodeStorageSize`] for the given database and directories.
+    pub const fn new(db: DB, static_files_dir: PathBuf, rocksdb_dir: Option<PathBuf>) -> Self {
+        Self { db, static_files_dir, rocksdb_dir }
+    }
+}
+
+impl<DB: DatabaseMetrics + Debug + Send + Sync> StorageSizeReader for NodeStorageSize<DB> {
+    fn storage_size(&self) -> ProviderResult<StorageSize> {
+        let database = self
+            .db
+            .gauge_metrics()
+            .into_iter()
+            .filter(|(name, _, _)| *name == "db.table_size")
+            .map(|(_, size, _)| size as u64)
+            .sum();
+        let static_files = dir_size(&self.static_files_dir).map_err(ProviderError::other)?;
+        let rocksdb = self
+            .rocksdb_dir
+            .as_deref()
+            .filter(|dir| dir.exists())
+            .map(dir_size)
+            .transpose()
+            .map_err(ProviderError::other)?
+            .unwrap_or_default();
+
+        Ok(StorageSize { database, static_files, rocksdb })
+    }
+}
+
+/// Returns the total size of the files in the directory, recursively.
+fn dir_size(dir: &Path) -> Result<u64, FsPathError> {
+    let mut size = 0;
+    for entry in fs::read_dir(dir)? {
+        let path = entry.map_err(|err| FsPathError::read_dir(err, dir))?.path();
+        let metadata = fs::metadata(&path)?;
+        size += if metadata.is_dir() { dir_size(&path)? } else { metadata.len() };
+    }
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct TestDatabase;
+
+    impl DatabaseMetrics for TestDatabase {
+        fn gauge_metrics(&self) -> Vec<(&'static str, f64, Vec<metrics::Label>)> {
+            vec![
+                ("db.table_size", 100.0, vec![metrics::Label::new("table", "Headers")]),
+                ("db.table_size", 50.0, vec![metrics::Label::new("table", "Receipts")]),
+                ("db.freelist", 1000.0, vec![]),
+            ]
+        }
+    }
+
+    #[test]
+    fn node_storage_size() {
+        let static_files_dir = tempfile::tempdir().unwrap();
+        fs::write(static_files_dir.path().join("headers"), [0; 10]).unwrap();
+        fs::create_dir_all(static_files_dir.path().join("nested")).unwrap();
+        fs::write(static_files_dir.path().join("nested").join("receipts"), [0; 20]).unwrap();
+
+        let reader = NodeStorageSize::new(
+            TestDatabase,
+            static_files_dir.path().to_path_buf(),
+            Some(static_files_dir.path().join("missing")),
+        );
+        let size = reader.storage_size().unwrap();
+        assert_eq!(size, StorageSize { database: 150, static_files: 30, rocksdb: 0 });
+        assert_eq!(size.total(), 180);
+    }
+}
@@ -11,6 +11,7 @@
 
 mod builder;
 mod db_ext;
+mod disk_budget;
 mod error;
 mod limiter;
 mod metrics;
@@ -19,6 +20,7 @@ pub mod segments;
 
 use crate::metrics::Metrics;
 pub use builder::PrunerBuilder;
+pub use disk_budget::{DiskBudget, NodeStorageSize, StorageSize, StorageSizeReader};
 pub use error::PrunerError;
 pub use limiter::PruneLimiter;
 pub use pruner::{Pruner, PrunerResult, PrunerWithFactory, PrunerWithResult};
@@ -1,5 +1,5 @@
 use reth_metrics::{
-    metrics::{Gauge, Histogram},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
 use reth_prune_types::PruneSegment;
@@ -10,6 +10,12 @@ use std::collections::HashMap;
 pub(crate) struct Metrics {
     /// Pruning duration
     pub(crate) duration_seconds: Histogram,
+    /// Size of the node storage in bytes, measured against the disk budget
+    pub(crate) storage_size_bytes: Gauge,
+    /// Maximum size of the node storage in bytes
+    pub(crate) disk_budget_bytes: Gauge,
+    /// Number of pruner runs that started with the storage over the disk budget
+    pub(crate) disk_budget_exceeded: Counter,
     #[metric(skip)]
     prune_segments: HashMap<PruneSegment, PrunerSegmentMetrics>,
 }
@@ -34,4 +40,8 @@ pub(crate) struct PrunerSegmentMetrics {
     pub(crate) duration_seconds: Histogram,
     /// Highest pruned block per segment
     pub(crate) highest_pruned_block: Gauge,
+    /// Block up to which the segment was requested to be pruned in the last run
+    pub(crate) target_block: Gauge,
+    /// Number of runs where the prune mode of the segment was overridden by the disk budget
+    pub(crate) disk_budget_overrides: Counter,
 }
@@ -2,20 +2,20 @@
 
 use crate::{
     segments::{PruneInput, Segment},
-    Metrics, PruneLimiter, PrunerError, PrunerEvent,
+    DiskBudget, Metrics, PruneLimiter, PrunerError, PrunerEvent,
 };
 use alloy_primitives::BlockNumber;
 use reth_exex_types::FinishedExExHeight;
 use reth_provider::{
-    DBProvider, DatabaseProviderFactory, PruneCheckpointReader, PruneCheckpointWriter,
-    StageCheckpointReader,
+    resolve_prune_mode, DBProvider, DatabaseProviderFactory, HeaderProvider, PruneCheckpointReader,
+    PruneCheckpointWriter, StageCheckpointReader,
 };
-use reth_prune_types::{PruneProgress, PrunedSegmentInfo, PrunerOutput};
+use reth_prune_types::{PruneMode, PruneProgress, PruneSegment, PrunedSegmentInfo, PrunerOutput};
 use reth_stages_types::StageId;
 use reth_tokio_util::{EventSender, EventStream};
 use std::time::{Duration, Instant};
 use tokio::sync::watch;
-use tracing::debug;
+use tracing::{debug, info};
 
 /// Result of [`Pruner::run`] execution.
 pub type PrunerResult = Result<PrunerOutput, PrunerError>;
@@ -45,6 +45,9 @@ pub struct Pruner<Provider, PF> {
     timeout: Option<Duration>,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    /// Maximum size of the node storage. While exceeded, segments are pruned beyond their prune
+    /// modes, in proportion to the excess.
+    disk_budget: Option<DiskBudget>,
     #[doc(hidden)]
     metrics: Metrics,
     event_sender: EventSender<PrunerEvent>,
@@ -67,6 +70,7 @@ impl<Provider> Pruner<Provider, ()> {
             delete_limit,
             timeout,
             finished_exex_height,
+            disk_budget: None,
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
@@ -94,6 +98,7 @@ where
             delete_limit,
             timeout,
             finished_exex_height,
+            disk_budget: None,
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
@@ -102,13 +107,32 @@ where
 
 impl<Provider, S> Pruner<Provider, S>
 where
-    Provider: PruneCheckpointReader + PruneCheckpointWriter + StageCheckpointReader,
+    Provider:
+        PruneCheckpointReader + PruneCheckpointWriter + StageCheckpointReader + HeaderProvider,
 {
     /// Listen for events on the pruner.
     pub fn events(&self) -> EventStream<PrunerEvent> {
         self.event_sender.new_listener()
     }
 
+    /// Adds segments that are pruned after the segments the pruner was created with, e.g.
+    /// [`PruneSegment::Custom`](reth_prune_types::PruneSegment::Custom) segments of node
+    /// extensions. They share the delete limit and timeout of each run with all other segments.
+    pub fn with_segments(
+        mut self,
+        segments: impl IntoIterator<Item = Box<dyn Segment<Provider>>>,
+    ) -> Self {
+        self.segments.extend(segments);
+        self
+    }
+
+    /// Sets the maximum size of the node storage. While the storage is over the budget, segments
+    /// are pruned beyond their prune modes, in proportion to the excess.
+    pub fn with_disk_budget(mut self, disk_budget: Option<DiskBudget>) -> Self {
+        self.disk_budget = disk_budget;
+        self
+    }
+
     /// Run the pruner with the given provider. This will only prune data up to the highest finished
     /// `ExEx` height, if there are no `ExExes`.
     ///
@@ -189,19 +213,19 @@ where
             segments: Vec::with_capacity(self.segments.len()),
         };
 
+        let disk_budget_excess = self.disk_budget_excess()?;
+
         for segment in &self.segments {
             if limiter.is_limit_reached() {
                 break
             }
 
-            if let Some((to_block, prune_mode)) = segment
-                .mode()
-                .map(|mode| {
-                    mode.prune_target_block(tip_block_number, segment.segment(), segment.purpose())
-                })
-                .transpose()?
-                .flatten()
-            {
+            if let Some((to_block, prune_mode)) = self.segment_target(
+                provider,
+                segment.as_ref(),
+                tip_block_number,
+                disk_budget_excess,
+            )? {
                 // Check if segment has a required stage that must be finished first
                 if let Some(required_stage) = segment.required_stage() &&
                     !is_stage_finished(provider, required_stage)?
@@ -224,6 +248,11 @@ where
                     "Segment pruning started"
                 );
 
+                self.metrics
+                    .get_prune_segment_metrics(segment.segment())
+                    .target_block
+                    .set(to_block as f64);
+
                 let segment_start = Instant::now();
                 let previous_checkpoint = provider.get_prune_checkpoint(segment.segment())?;
                 let segment_output = segment.prune(
@@ -278,6 +307,128 @@ where
         Ok((stats, pruned, output))
     }
 
+    /// Returns the block up to which the segment is pruned at the given tip, and the prune mode it
+    /// is pruned with.
+    ///
+    /// [`PruneMode::Age`] is resolved against the header timestamps, and the prune mode is
+    /// overridden if the storage is over the disk budget by the given `disk_budget_excess`.
+    fn segment_target<P: PruneCheckpointReader + HeaderProvider>(
+        &self,
+        provider: &P,
+        segment: &dyn Segment<Provider>,
+        tip_block_number: BlockNumber,
+        disk_budget_excess: Option<f64>,
+    ) -> Result<Option<(BlockNumber, PruneMode)>, PrunerError> {
+        let mut mode = segment.mode();
+        if let Some(PruneMode::Age(age)) = mode {
+            let resolved_mode =
+                resolve_prune_mode(provider, PruneMode::Age(age), tip_block_number)?;
+            debug!(
+                target: "pruner",
+                segment = ?segment.segment(),
+                ?age,
+                ?resolved_mode,
+                "Resolved segment prune age"
+            );
+            mode = Some(resolved_mode);
+        }
+
+        if let Some(configured_mode) = mode &&
+            let Some(excess) = disk_budget_excess &&
+            segment.purpose().is_user() &&
+            let Some(min_blocks) = segment.disk_budget_min_blocks()
+        {
+            let pruned_block = provider
+                .get_prune_checkpoint(segment.segment())?
+                .and_then(|checkpoint| checkpoint.block_number);
+            let budget_mode = disk_budget_mode(min_blocks, tip_block_number, pruned_block, excess);
+
+            let target_block = |mode: PruneMode| {
+                mode.prune_target_block(tip_block_number, segment.segment(), segment.purpose())
+                    .map(|target| target.map(|(block, _)| block))
+            };
+            if target_block(budget_mode)? > target_block(configured_mode)? {
+                self.metrics
+                    .get_prune_segment_metrics(segment.segment())
+                    .disk_budget_overrides
+                    .increment(1);
+                info!(
+                    target: "pruner",
+                    segment = ?segment.segment(),
+                    ?configured_mode,
+                    ?budget_mode,
+                    "Overriding segment prune mode to get back under the disk budget"
+                );
+                mode = Some(budget_mode);
+            }
+        }
+
+        Ok(mode
+            .map(|mode| {
+                mode.prune_target_block(tip_block_number, segment.segment(), segment.purpose())
+            })
+            .transpose()?
+            .flatten())
+    }
+
+    /// Returns the block up to which every segment would be pruned at the given tip, and the prune
+    /// mode it would be pruned with, without pruning anything.
+    ///
+    /// The disk budget is taken into account the same way as when pruning. Any provider can be
+    /// used, so that the targets can be read without write access to the database.
+    pub fn prune_targets<P: PruneCheckpointReader + HeaderProvider>(
+        &self,
+        provider: &P,
+        tip_block_number: BlockNumber,
+    ) -> Result<Vec<(PruneSegment, BlockNumber, PruneMode)>, PrunerError> {
+        let disk_budget_excess = self.disk_budget_excess()?;
+
+        let mut targets = Vec::with_capacity(self.segments.len());
+        for segment in &self.segments {
+            if let Some((to_block, prune_mode)) = self.segment_target(
+                provider,
+                segment.as_ref(),
+                tip_block_number,
+                disk_budget_excess,
+            )? {
+                targets.push((segment.segment(), to_block, prune_mode));
+            }
+        }
+
+        Ok(targets)
+    }
+
+    /// Measures the node storage against the disk budget, if it's set.
+    ///
+    /// Returns the fraction of the storage that is over the budget, or `None` if the storage is
+    /// within the budget.
+    fn disk_budget_excess(&self) -> Result<Option<f64>, PrunerError> {
+        let Some(disk_budget) = &self.disk_budget else { return Ok(None) };
+
+        let storage_size = disk_budget.storage_size()?;
+        let max_size = disk_budget.max_size();
+        self.metrics.storage_size_bytes.set(storage_size.total() as f64);
+        self.metrics.disk_budget_bytes.set(max_size as f64);
+
+        if storage_size.total() <= max_size {
+            debug!(target: "pruner", ?storage_size, %max_size, "Storage is within the disk budget");
+            return Ok(None)
+        }
+
+        let excess = (storage_size.total() - max_size) as f64 / storage_size.total() as f64;
+        self.metrics.disk_budget_exceeded.increment(1);
+        info!(
+            target: "pruner",
+            ?storage_size,
+            total = %storage_size.total(),
+            %max_size,
+            %excess,
+            "Storage is over the disk budget, pruning segments beyond their configured modes"
+        );
+
+        Ok(Some(excess))
+    }
+
     /// Returns `true` if the pruning is needed at the provided tip block number.
     /// This is determined by the check against minimum pruning interval and last pruned block
     /// number.
@@ -334,7 +485,10 @@ where
 impl<PF> Pruner<PF::ProviderRW, PF>
 where
     PF: DatabaseProviderFactory<
-        ProviderRW: PruneCheckpointWriter + PruneCheckpointReader + StageCheckpointReader,
+        ProviderRW: PruneCheckpointWriter
+                        + PruneCheckpointReader
+                        + StageCheckpointReader
+                        + HeaderProvider,
     >,
 {
     /// Run the pruner. This will only prune data up to the highest finished ExEx height, if there
@@ -350,6 +504,24 @@ where
     }
 }
 
+/// Returns the prune mode of a segment that brings the node storage back under the disk budget,
+/// given the `excess` fraction of the storage that is over it.
+///
+/// The data of the segment is assumed to be spread evenly over the blocks it still retains, so the
+/// oldest `excess` fraction of them is pruned, but never more than the segment allows. Segments
+/// only hold a part of the storage, so it may take a few runs to get under the budget.
+fn disk_budget_mode(
+    min_blocks: u64,
+    tip_block_number: BlockNumber,
+    pruned_block: Option<BlockNumber>,
+    excess: f64,
+) -> PruneMode {
+    let retained_blocks =
+        (tip_block_number + 1).saturating_sub(pruned_block.map_or(0, |block| block + 1));
+    let keep_blocks = (retained_blocks as f64 * (1.0 - excess)) as u64;
+    PruneMode::Distance(keep_blocks.max(min_blocks))
+}
+
 /// Checks if the given stage has caught up with the `Finish` stage.
 ///
 /// Returns `true` if the stage checkpoint is >= the Finish stage checkpoint.
@@ -365,9 +537,105 @@ fn is_stage_finished<Provider: StageCheckpointReader>(
 
 #[cfg(test)]
 mod tests {
-    use crate::Pruner;
+    use crate::{
+        segments::{PruneInput, Segment},
+        DiskBudget, Pruner, PrunerError, StorageSize, StorageSizeReader,
+    };
+    use alloy_consensus::Header;
+    use alloy_primitives::BlockNumber;
     use reth_exex_types::FinishedExExHeight;
-    use reth_provider::test_utils::create_test_provider_factory;
+    use reth_primitives_traits::SealedHeader;
+    use reth_provider::{
+        test_utils::create_test_provider_factory, DatabaseProviderFactory, ProviderResult,
+        PruneCheckpointReader,
+    };
+    use reth_prune_types::{
+        PruneCheckpoint, PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutput,
+        SegmentOutputCheckpoint,
+    };
+    use reth_stages::test_utils::TestStageDB;
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    /// Segment that records the target blocks it was asked to prune to.
+    #[derive(Debug)]
+    struct RecordingSegment {
+        mode: PruneMode,
+        to_blocks: Arc<Mutex<Vec<BlockNumber>>>,
+    }
+
+    impl<Provider> Segment<Provider> for RecordingSegment {
+        fn segment(&self) -> PruneSegment {
+            PruneSegment::SenderRecovery
+        }
+
+        fn mode(&self) -> Option<PruneMode> {
+            Some(self.mode)
+        }
+
+        fn purpose(&self) -> PrunePurpose {
+            PrunePurpose::User
+        }
+
+        fn prune(&self, _: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+            self.to_blocks.lock().unwrap().push(input.to_block);
+            Ok(SegmentOutput {
+                progress: PruneProgress::Finished,
+                pruned: 0,
+                checkpoint: Some(SegmentOutputCheckpoint {
+                    block_number: Some(input.to_block),
+                    tx_number: None,
+                }),
+            })
+        }
+    }
+
+    /// Custom segment that prunes everything up to the target block in one go.
+    #[derive(Debug)]
+    struct CustomSegment {
+        id: u8,
+        disk_budget_min_blocks: Option<u64>,
+    }
+
+    impl<Provider> Segment<Provider> for CustomSegment {
+        fn segment(&self) -> PruneSegment {
+            PruneSegment::Custom(self.id)
+        }
+
+        fn mode(&self) -> Option<PruneMode> {
+            Some(PruneMode::Distance(100))
+        }
+
+        fn purpose(&self) -> PrunePurpose {
+            PrunePurpose::User
+        }
+
+        fn prune(&self, _: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+            Ok(SegmentOutput {
+                progress: PruneProgress::Finished,
+                pruned: 1,
+                checkpoint: Some(SegmentOutputCheckpoint {
+                    block_number: Some(input.to_block),
+                    tx_number: None,
+                }),
+            })
+        }
+
+        fn disk_budget_min_blocks(&self) -> Option<u64> {
+            self.disk_budget_min_blocks
+        }
+    }
+
+    #[derive(Debug)]
+    struct FixedStorageSize(Arc<Mutex<u64>>);
+
+    impl StorageSizeReader for FixedStorageSize {
+        fn storage_size(&self) -> ProviderResult<StorageSize> {
+            Ok(StorageSize { database: *self.0.lock().unwrap(), ..Default::default() })
+        }
+    }
 
     #[test]
     fn is_pruning_needed() {
@@ -408,4 +676,166 @@ mod tests {
         finished_exex_height_tx.send(FinishedExExHeight::Height(third_block_number)).unwrap();
         assert!(pruner.is_pruning_needed(third_block_number));
     }
+
+    #[test]
+    fn disk_budget() {
+        let provider_factory = create_test_provider_factory();
+        let to_blocks = Arc::new(Mutex::new(Vec::new()));
+        let storage_size = Arc::new(Mutex::new(0));
+
+        let segment =
+            RecordingSegment { mode: PruneMode::Distance(100), to_blocks: to_blocks.clone() };
+        let mut pruner = Pruner::new_with_factory(
+            provider_factory,
+            vec![Box::new(segment) as Box<dyn Segment<_>>],
+            5,
+            0,
+            None,
+            tokio::sync::watch::channel(FinishedExExHeight::NoExExs).1,
+        )
+        .with_disk_budget(Some(DiskBudget::new(1000, FixedStorageSize(storage_size.clone()))));
+
+        // Within the budget, the configured prune mode is used
+        *storage_size.lock().unwrap() = 1000;
+        pruner.run(1000).unwrap();
+        assert_eq!(to_blocks.lock().unwrap().last(), Some(&900));
+
+        // 20% over the budget, so the oldest 20% of the 105 retained blocks (901..=1005) are pruned
+        *storage_size.lock().unwrap() = 1250;
+        pruner.run(1005).unwrap();
+        assert_eq!(to_blocks.lock().unwrap().last(), Some(&921));
+
+        // Still 50% over the budget, so half of the 89 retained blocks (922..=1010) are pruned
+        *storage_size.lock().unwrap() = 2000;
+        pruner.run(1010).unwrap();
+        assert_eq!(to_blocks.lock().unwrap().last(), Some(&966));
+
+        // Slightly over the budget, but the configured prune mode is more aggressive
+        *storage_size.lock().unwrap() = 1001;
+        pruner.run(1100).unwrap();
+        assert_eq!(to_blocks.lock().unwrap().last(), Some(&1000));
+
+        // Back within the budget
+        *storage_size.lock().unwrap() = 500;
+        pruner.run(1105).unwrap();
+        assert_eq!(to_blocks.lock().unwrap().last(), Some(&1005));
+    }
+
+    #[test]
+    fn custom_segment() {
+        let provider_factory = create_test_provider_factory();
+
+        let mut pruner = Pruner::new_with_factory(
+            provider_factory.clone(),
+            vec![],
+            5,
+            0,
+            None,
+            tokio::sync::watch::channel(FinishedExExHeight::NoExExs).1,
+        )
+        .with_segments([
+            Box::new(CustomSegment { id: 1, disk_budget_min_blocks: None }) as Box<dyn Segment<_>>
+        ]);
+
+        let output = pruner.run(1000).unwrap();
+        assert_eq!(output.segments.len(), 1);
+        assert_eq!(output.segments[0].0, PruneSegment::Custom(1));
+
+        // The checkpoint of the custom segment is saved and listed with all other checkpoints
+        let provider = provider_factory.database_provider_ro().unwrap();
+        assert_eq!(
+            provider.get_prune_checkpoints().unwrap(),
+            vec![(
+                PruneSegment::Custom(1),
+                PruneCheckpoint {
+                    block_number: Some(900),
+                    tx_number: None,
+                    prune_mode: PruneMode::Distance(100),
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn disk_budget_custom_segments() {
+        let provider_factory = create_test_provider_factory();
+
+        let mut pruner = Pruner::new_with_factory(
+            provider_factory.clone(),
+            vec![],
+            5,
+            0,
+            None,
+            tokio::sync::watch::channel(FinishedExExHeight::NoExExs).1,
+        )
+        .with_segments([
+            Box::new(CustomSegment { id: 1, disk_budget_min_blocks: None }) as Box<dyn Segment<_>>,
+            Box::new(CustomSegment { id: 2, disk_budget_min_blocks: Some(80) }),
+        ])
+        .with_disk_budget(Some(DiskBudget::new(
+            1000,
+            FixedStorageSize(Arc::new(Mutex::new(2000))),
+        )));
+
+        // 50% over the budget, so half of the 110 blocks retained after the first run
+        // (901..=1010) would be pruned
+        pruner.run(1000).unwrap();
+        pruner.run(1010).unwrap();
+
+        let provider = provider_factory.database_provider_ro().unwrap();
+        let pruned_block = |id| {
+            provider.get_prune_checkpoint(PruneSegment::Custom(id)).unwrap().unwrap().block_number
+        };
+        // The prune mode of a custom segment is only overridden if it declares the minimum number
+        // of blocks it needs
+        assert_eq!(pruned_block(1), Some(910));
+        assert_eq!(pruned_block(2), Some(930));
+
+        // The targets of the next run are the same as the ones it would prune to
+        let provider_rw = provider_factory.database_provider_rw().unwrap();
+        assert_eq!(
+            pruner.prune_targets(&provider_rw, 1020).unwrap(),
+            vec![
+                (PruneSegment::Custom(1), 920, PruneMode::Distance(100)),
+                (PruneSegment::Custom(2), 940, PruneMode::Distance(80)),
+            ]
+        );
+    }
+
+    #[test]
+    fn prune_age() {
+        let db = TestStageDB::default();
+        let headers = (0..=100)
+            .map(|number| {
+                SealedHeader::seal_slow(Header {
+                    number,
+                    timestamp: number * 12,
+                    ..Default::default()
+                })
+            })
+            .collect::<Vec<_>>();
+        db.insert_headers(headers.iter()).unwrap();
+
+        let to_blocks = Arc::new(Mutex::new(Vec::new()));
+        let segment = RecordingSegment {
+            mode: PruneMode::Age(Duration::from_secs(120)),
+            to_blocks: to_blocks.clone(),
+        };
+        let mut pruner = Pruner::new_with_factory(
+            db.factory.clone(),
+            vec![Box::new(segment) as Box<dyn Segment<_>>],
+            5,
+            0,
+            None,
+            tokio::sync::watch::channel(FinishedExExHeight::NoExExs).1,
+        );
+
+        // Tip timestamp is 1200, so blocks older than 1080 (block 90) are pruned
+        pruner.run(100).unwrap();
+        assert_eq!(to_blocks.lock().unwrap().last(), Some(&89));
+
+        // The age is resolved against the tip of every run
+        pruner.run(50).unwrap();
+        assert_eq!(to_blocks.lock().unwrap().last(), Some(&39));
+    }
 }
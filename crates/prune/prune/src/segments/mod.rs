@@ -93,6 +93,16 @@ pub trait Segment<Provider>: Debug + Send + Sync {
     fn required_stage(&self) -> Option<StageId> {
         None
     }
+
+    /// Returns the minimum number of blocks the segment keeps when the pruner overrides its prune
+    /// mode to get back under the disk budget.
+    ///
+    /// If this returns `None`, the prune mode of the segment is never overridden. That's the case
+    /// for [`PruneSegment::Custom`] segments by default, as only they know which data they need.
+    fn disk_budget_min_blocks(&self) -> Option<u64> {
+        let segment = self.segment();
+        (!segment.is_custom()).then(|| segment.min_blocks(self.purpose()))
+    }
 }
 
 /// Segment pruning input, see [`Segment::prune`].
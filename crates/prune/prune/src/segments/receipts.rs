@@ -5,20 +5,25 @@
 
 use crate::{
     db_ext::DbTxPruneExt,
-    segments::{self, PruneInput},
+    segments::{self, user::history::prune_history_indices, PruneInput},
     PrunerError,
 };
-use reth_db_api::{table::Value, tables, transaction::DbTxMut};
+use alloy_primitives::{Address, BlockNumber, B256};
+use reth_db_api::{models::ShardedKey, table::Value, tables, transaction::DbTxMut};
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
-    errors::provider::ProviderResult, BlockReader, DBProvider, EitherWriter,
-    NodePrimitivesProvider, PruneCheckpointWriter, StaticFileProviderFactory, StorageSettingsCache,
-    TransactionsProvider,
+    errors::provider::ProviderResult, for_each_log_key, BlockReader, DBProvider, EitherWriter,
+    NodePrimitivesProvider, PruneCheckpointWriter, ReceiptProvider, StaticFileProviderFactory,
+    StorageSettingsCache, TransactionsProvider,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment, SegmentOutput, SegmentOutputCheckpoint};
 use reth_static_file_types::StaticFileSegment;
+use std::collections::BTreeSet;
 use tracing::{debug, trace};
 
+/// Number of receipts to read from static files at once when collecting the log index keys.
+const LOG_INDEX_RECEIPTS_CHUNK_SIZE: u64 = 100_000;
+
 pub(crate) fn prune<Provider>(
     provider: &Provider,
     input: PruneInput,
@@ -29,11 +34,43 @@ where
         + BlockReader
         + StorageSettingsCache
         + StaticFileProviderFactory
-        + NodePrimitivesProvider<Primitives: NodePrimitives<Receipt: Value>>,
+        + NodePrimitivesProvider<Primitives: NodePrimitives<SignedTx: Value, Receipt: Value>>,
 {
+    let log_index = provider.cached_storage_settings().log_index;
+    let mut log_addresses = BTreeSet::new();
+    let mut log_topics = BTreeSet::new();
+
     if EitherWriter::receipts_destination(provider).is_static_file() {
         debug!(target: "pruner", "Pruning receipts from static files.");
-        return segments::prune_static_files(provider, input, StaticFileSegment::Receipts)
+
+        // The log index keys are read from the receipts, so they have to be collected before the
+        // static files are deleted.
+        if log_index && let Some(tx_range) = input.get_next_tx_num_range(provider)? {
+            let static_file_provider = provider.static_file_provider();
+            for chunk_start in tx_range.clone().step_by(LOG_INDEX_RECEIPTS_CHUNK_SIZE as usize) {
+                let chunk_end =
+                    (chunk_start + LOG_INDEX_RECEIPTS_CHUNK_SIZE - 1).min(*tx_range.end());
+                let receipts =
+                    static_file_provider.receipts_by_tx_range(chunk_start..=chunk_end)?;
+                for_each_log_key(
+                    &receipts,
+                    |address| {
+                        log_addresses.insert(address);
+                    },
+                    |topic| {
+                        log_topics.insert(topic);
+                    },
+                );
+            }
+        }
+
+        let to_block = input.to_block;
+        let mut output =
+            segments::prune_static_files(provider, input, StaticFileSegment::Receipts)?;
+        if log_index {
+            output.pruned += prune_log_index(provider, log_addresses, log_topics, to_block)?;
+        }
+        return Ok(output)
     }
     debug!(target: "pruner", "Pruning receipts from database.");
 
@@ -50,13 +87,26 @@ where
     let mut limiter = input.limiter;
 
     let mut last_pruned_transaction = tx_range_end;
-    let (pruned, done) = provider.tx_ref().prune_table_with_range::<tables::Receipts<
+    let (mut pruned, done) = provider.tx_ref().prune_table_with_range::<tables::Receipts<
         <Provider::Primitives as NodePrimitives>::Receipt,
     >>(
         tx_range,
         &mut limiter,
         |_| false,
-        |row| last_pruned_transaction = row.0,
+        |row| {
+            last_pruned_transaction = row.0;
+            if log_index {
+                for_each_log_key(
+                    [&row.1],
+                    |address| {
+                        log_addresses.insert(address);
+                    },
+                    |topic| {
+                        log_topics.insert(topic);
+                    },
+                );
+            }
+        },
     )?;
     trace!(target: "pruner", %pruned, %done, "Pruned receipts");
 
@@ -67,6 +117,10 @@ where
         // so we could finish pruning its receipts on the next run.
         .checked_sub(if done { 0 } else { 1 });
 
+    if log_index && let Some(last_pruned_block) = last_pruned_block {
+        pruned += prune_log_index(provider, log_addresses, log_topics, last_pruned_block)?;
+    }
+
     let progress = limiter.progress(done);
 
     Ok(SegmentOutput {
@@ -79,6 +133,30 @@ where
     })
 }
 
+/// Prunes the log index of the given addresses and topics up to and including `to_block`.
+///
+/// Returns the number of deleted shards.
+fn prune_log_index<Provider: DBProvider<Tx: DbTxMut>>(
+    provider: &Provider,
+    addresses: BTreeSet<Address>,
+    topics: BTreeSet<B256>,
+    to_block: BlockNumber,
+) -> Result<usize, PrunerError> {
+    let address_outcomes = prune_history_indices::<Provider, tables::LogAddressIndex, _>(
+        provider,
+        addresses.into_iter().map(|address| ShardedKey::new(address, to_block)),
+        |a, b| a.key == b.key,
+    )?;
+    let topic_outcomes = prune_history_indices::<Provider, tables::LogTopicIndex, _>(
+        provider,
+        topics.into_iter().map(|topic| ShardedKey::new(topic, to_block)),
+        |a, b| a.key == b.key,
+    )?;
+    trace!(target: "pruner", ?address_outcomes, ?topic_outcomes, "Pruned log index");
+
+    Ok(address_outcomes.deleted + topic_outcomes.deleted)
+}
+
 pub(crate) fn save_checkpoint(
     provider: impl PruneCheckpointWriter,
     checkpoint: PruneCheckpoint,
@@ -95,14 +173,16 @@ pub(crate) fn save_checkpoint(
 #[cfg(test)]
 mod tests {
     use crate::segments::{PruneInput, PruneLimiter, SegmentOutput};
-    use alloy_primitives::{BlockNumber, TxNumber, B256};
+    use alloy_primitives::{Address, BlockNumber, TxNumber, B256};
     use assert_matches::assert_matches;
     use itertools::{
         FoldWhile::{Continue, Done},
         Itertools,
     };
-    use reth_db_api::tables;
-    use reth_provider::{DBProvider, DatabaseProviderFactory, PruneCheckpointReader};
+    use reth_db_api::{models::ShardedKey, tables, BlockNumberList};
+    use reth_provider::{
+        DBProvider, DatabaseProviderFactory, LogIndexWriter, PruneCheckpointReader,
+    };
     use reth_prune_types::{
         PruneCheckpoint, PruneInterruptReason, PruneMode, PruneProgress, PruneSegment,
     };
@@ -244,4 +324,26 @@ mod tests {
         test_prune(6, (PruneProgress::Finished, 2));
         test_prune(10, (PruneProgress::Finished, 8));
     }
+
+    #[test]
+    fn prune_log_index() {
+        let db = TestStageDB::default();
+        let address = Address::with_last_byte(1);
+        let topic = B256::with_last_byte(2);
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        provider.insert_log_index([(address, vec![1, 5, 10])], [(topic, vec![3, 4])]).unwrap();
+
+        // The address shard keeps the blocks above the pruned ones, the topic shard is deleted
+        let pruned =
+            super::prune_log_index(&provider, [address].into(), [topic].into(), 5).unwrap();
+        provider.commit().unwrap();
+        assert_eq!(pruned, 1);
+
+        assert_eq!(
+            db.table::<tables::LogAddressIndex>().unwrap(),
+            vec![(ShardedKey::new(address, u64::MAX), BlockNumberList::new_pre_sorted([10]))]
+        );
+        assert!(db.table::<tables::LogTopicIndex>().unwrap().is_empty());
+    }
 }
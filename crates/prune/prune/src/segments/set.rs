@@ -69,17 +69,25 @@ where
             bodies_history,
             merkle_changesets,
             receipts_log_filter,
+            history_retention,
         } = prune_modes;
 
         Self::default()
             // Bodies - run first since file deletion is fast
             .segment_opt(bodies_history.map(Bodies::new))
             // Merkle changesets
-            .segment(MerkleChangeSets::new(merkle_changesets))
+            .segment_opt(merkle_changesets.map(MerkleChangeSets::new))
             // Account history
-            .segment_opt(account_history.map(AccountHistory::new))
+            .segment_opt(
+                account_history.map(|mode| {
+                    AccountHistory::new(mode).with_retention(history_retention.clone())
+                }),
+            )
             // Storage history
-            .segment_opt(storage_history.map(StorageHistory::new))
+            .segment_opt(
+                storage_history
+                    .map(|mode| StorageHistory::new(mode).with_retention(history_retention)),
+            )
             // User receipts
             .segment_opt(receipts.map(UserReceipts::new))
             // Receipts by logs
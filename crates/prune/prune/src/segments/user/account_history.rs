@@ -1,15 +1,20 @@
 use crate::{
     db_ext::DbTxPruneExt,
-    segments::{user::history::prune_history_indices, PruneInput, Segment},
+    segments::{
+        user::history::{prune_history_indices, sync_retained_history},
+        PruneInput, Segment,
+    },
     PrunerError,
 };
 use itertools::Itertools;
 use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut};
 use reth_provider::DBProvider;
 use reth_prune_types::{
-    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+    HistoryRetentionConfig, PruneMode, PrunePurpose, PruneSegment, SegmentOutput,
+    SegmentOutputCheckpoint,
 };
 use rustc_hash::FxHashMap;
+use std::collections::BTreeSet;
 use tracing::{instrument, trace};
 
 /// Number of account history tables to prune in one step.
@@ -21,11 +26,19 @@ const ACCOUNT_HISTORY_TABLES_TO_PRUNE: usize = 2;
 #[derive(Debug)]
 pub struct AccountHistory {
     mode: PruneMode,
+    /// Addresses whose history is never pruned.
+    retention: HistoryRetentionConfig,
 }
 
 impl AccountHistory {
     pub const fn new(mode: PruneMode) -> Self {
-        Self { mode }
+        Self { mode, retention: HistoryRetentionConfig(BTreeSet::new()) }
+    }
+
+    /// Retains the history of the given addresses.
+    pub fn with_retention(mut self, retention: HistoryRetentionConfig) -> Self {
+        self.retention = retention;
+        self
     }
 }
 
@@ -68,6 +81,9 @@ where
             ))
         }
 
+        // record newly retained addresses before any of their history is pruned
+        sync_retained_history(provider, &self.retention)?;
+
         let mut last_changeset_pruned_block = None;
         // Deleted account changeset keys (account addresses) with the highest block number deleted
         // for that key.
@@ -82,7 +98,7 @@ where
             provider.tx_ref().prune_table_with_range::<tables::AccountChangeSets>(
                 range,
                 &mut limiter,
-                |_| false,
+                |(_, account)| self.retention.is_retained(&account.address),
                 |(block_number, account)| {
                     highest_deleted_accounts.insert(account.address, block_number);
                     last_changeset_pruned_block = Some(block_number);
@@ -127,15 +143,18 @@ where
 #[cfg(test)]
 mod tests {
     use crate::segments::{
-        user::account_history::ACCOUNT_HISTORY_TABLES_TO_PRUNE, AccountHistory, PruneInput,
-        PruneLimiter, Segment, SegmentOutput,
+        user::{account_history::ACCOUNT_HISTORY_TABLES_TO_PRUNE, history::sync_retained_history},
+        AccountHistory, PruneInput, PruneLimiter, Segment, SegmentOutput,
     };
-    use alloy_primitives::{BlockNumber, B256};
+    use alloy_primitives::{Address, BlockNumber, B256};
     use assert_matches::assert_matches;
-    use reth_db_api::{tables, BlockNumberList};
-    use reth_provider::{DBProvider, DatabaseProviderFactory, PruneCheckpointReader};
+    use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut, BlockNumberList};
+    use reth_provider::{
+        DBProvider, DatabaseProviderFactory, PruneCheckpointReader, PruneCheckpointWriter,
+    };
     use reth_prune_types::{
-        PruneCheckpoint, PruneInterruptReason, PruneMode, PruneProgress, PruneSegment,
+        HistoryRetentionConfig, PruneCheckpoint, PruneInterruptReason, PruneMode, PruneProgress,
+        PruneSegment,
     };
     use reth_stages::test_utils::{StorageKind, TestStageDB};
     use reth_testing_utils::generators::{
@@ -303,4 +322,91 @@ mod tests {
         test_prune(998, 2, (PruneProgress::Finished, 998));
         test_prune(1400, 3, (PruneProgress::Finished, 804));
     }
+
+    #[test]
+    fn prune_retained() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            1..=100,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let accounts = random_eoa_accounts(&mut rng, 2).into_iter().collect::<BTreeMap<_, _>>();
+        let retained = *accounts.keys().next().unwrap();
+
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
+            0..0,
+            0..0,
+        );
+        db.insert_changesets(changesets.clone(), None).expect("insert changesets");
+        db.insert_history(changesets, None).expect("insert history");
+
+        let original_changesets = db.table::<tables::AccountChangeSets>().unwrap();
+        let original_shards = db.table::<tables::AccountsHistory>().unwrap();
+
+        let to_block = 50;
+        let input =
+            PruneInput { previous_checkpoint: None, to_block, limiter: PruneLimiter::default() };
+        let segment = AccountHistory::new(PruneMode::Before(to_block))
+            .with_retention(HistoryRetentionConfig::from_iter([retained]));
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert!(result.progress.is_finished());
+        provider.commit().expect("commit");
+
+        // Changesets and shards of the retained account are untouched
+        let expected_changesets = original_changesets
+            .into_iter()
+            .filter(|(block_number, change)| change.address == retained || *block_number > to_block)
+            .collect::<Vec<_>>();
+        assert_eq!(db.table::<tables::AccountChangeSets>().unwrap(), expected_changesets);
+
+        let retained_shards = |shards: Vec<(ShardedKey<Address>, BlockNumberList)>| {
+            shards.into_iter().filter(|(key, _)| key.key == retained).collect::<Vec<_>>()
+        };
+        let actual_shards = db.table::<tables::AccountsHistory>().unwrap();
+        assert_eq!(retained_shards(actual_shards.clone()), retained_shards(original_shards));
+        assert!(actual_shards
+            .iter()
+            .filter(|(key, _)| key.key != retained)
+            .all(|(_, blocks)| blocks.iter().all(|block| block > to_block)));
+
+        // the address is retained from genesis, as nothing was pruned before
+        assert_eq!(db.table::<tables::RetainedHistory>().unwrap(), vec![(retained, 0)]);
+    }
+
+    #[test]
+    fn retained_history_is_not_retroactive() {
+        let db = TestStageDB::default();
+        let retained = Address::with_last_byte(1);
+        let no_longer_retained = Address::with_last_byte(2);
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        for (segment, block_number) in
+            [(PruneSegment::AccountHistory, 10), (PruneSegment::StorageHistory, 20)]
+        {
+            let checkpoint = PruneCheckpoint {
+                block_number: Some(block_number),
+                tx_number: None,
+                prune_mode: PruneMode::Before(block_number + 1),
+            };
+            provider.save_prune_checkpoint(segment, checkpoint).unwrap();
+        }
+        provider.tx_ref().put::<tables::RetainedHistory>(no_longer_retained, 0).unwrap();
+
+        sync_retained_history(&provider, &HistoryRetentionConfig::from_iter([retained])).unwrap();
+        provider.commit().expect("commit");
+
+        // the history of the newly retained address is only available above the highest prune
+        // checkpoint, and the address that is no longer retained is removed
+        assert_eq!(db.table::<tables::RetainedHistory>().unwrap(), vec![(retained, 21)]);
+    }
 }
@@ -1,12 +1,16 @@
 use crate::{
+    db_ext::DbTxPruneExt,
     segments::{self, PruneInput, Segment},
     PrunerError,
 };
-use reth_provider::{BlockReader, StaticFileProviderFactory};
+use reth_db_api::{tables, transaction::DbTxMut};
+use reth_provider::{BlockReader, DBProvider, StaticFileProviderFactory};
 use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment, SegmentOutput};
 use reth_static_file_types::StaticFileSegment;
+use tracing::trace;
 
-/// Segment responsible for pruning transactions in static files.
+/// Segment responsible for pruning transactions in static files, and the block access lists
+/// stored alongside them.
 ///
 /// This segment is controlled by the `bodies_history` configuration.
 #[derive(Debug)]
@@ -23,7 +27,7 @@ impl Bodies {
 
 impl<Provider> Segment<Provider> for Bodies
 where
-    Provider: StaticFileProviderFactory + BlockReader,
+    Provider: DBProvider<Tx: DbTxMut> + StaticFileProviderFactory + BlockReader,
 {
     fn segment(&self) -> PruneSegment {
         PruneSegment::Bodies
@@ -37,20 +41,40 @@ where
         PrunePurpose::User
     }
 
-    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
-        segments::prune_static_files(provider, input, StaticFileSegment::Transactions)
+    fn prune(
+        &self,
+        provider: &Provider,
+        mut input: PruneInput,
+    ) -> Result<SegmentOutput, PrunerError> {
+        let (block_access_lists_pruned, done) =
+            provider.tx_ref().prune_table_with_range::<tables::BlockAccessLists>(
+                ..=input.to_block,
+                &mut input.limiter,
+                |_| false,
+                |_| {},
+            )?;
+        trace!(target: "pruner", %block_access_lists_pruned, %done, "Pruned block access lists");
+        let progress = input.limiter.progress(done);
+
+        let mut output =
+            segments::prune_static_files(provider, input, StaticFileSegment::Transactions)?;
+        output.pruned += block_access_lists_pruned;
+        // Block access lists left over because of the limit are pruned on the next run
+        output.progress = progress;
+        Ok(output)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Pruner;
-    use alloy_primitives::BlockNumber;
+    use crate::{PruneLimiter, Pruner};
+    use alloy_primitives::{BlockNumber, Bytes};
+    use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
     use reth_exex_types::FinishedExExHeight;
     use reth_provider::{
         test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
-        ProviderFactory, StaticFileWriter,
+        DatabaseProviderFactory, ProviderFactory, StaticFileWriter,
     };
     use reth_prune_types::{PruneMode, PruneProgress, PruneSegment};
     use reth_static_file_types::{
@@ -187,6 +211,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn prune_block_access_lists() {
+        let factory = create_test_provider_factory();
+        let provider = factory.database_provider_rw().unwrap();
+        for block in 0..10 {
+            provider
+                .tx_ref()
+                .put::<tables::BlockAccessLists>(block, Bytes::from(vec![block as u8]))
+                .unwrap();
+        }
+
+        let bodies = Bodies::new(PruneMode::Before(6));
+        let prune = |limit| {
+            let input = PruneInput {
+                previous_checkpoint: None,
+                to_block: 5,
+                limiter: PruneLimiter::default().set_deleted_entries_limit(limit),
+            };
+            bodies.prune(&provider, input).unwrap()
+        };
+
+        // The limit is reached before all block access lists up to the target block are pruned
+        let output = prune(4);
+        assert_eq!(output.pruned, 4);
+        assert!(!output.progress.is_finished());
+
+        let output = prune(4);
+        assert_eq!(output.pruned, 2);
+        assert!(output.progress.is_finished());
+
+        let blocks = provider
+            .tx_ref()
+            .cursor_read::<tables::BlockAccessLists>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|row| row.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(blocks, (6..10).collect::<Vec<_>>());
+    }
+
     #[test]
     fn min_block_updated_on_sync() {
         // Regression test: update_index must update min_block to prevent stale values
@@ -3,10 +3,12 @@ use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
     models::ShardedKey,
     table::Table,
-    transaction::DbTxMut,
+    tables,
+    transaction::{DbTx, DbTxMut},
     BlockNumberList, DatabaseError, RawKey, RawTable, RawValue,
 };
 use reth_provider::DBProvider;
+use reth_prune_types::{HistoryRetentionConfig, PruneSegment};
 
 enum PruneShardOutcome {
     Deleted,
@@ -21,6 +23,51 @@ pub(crate) struct PrunedIndices {
     pub(crate) unchanged: usize,
 }
 
+/// Syncs [`tables::RetainedHistory`] with the retention config.
+///
+/// Newly retained addresses are recorded with the block from which their history is still
+/// available, which is above the highest account and storage history prune checkpoint. Addresses
+/// that are no longer retained are removed, as their history is pruned again.
+///
+/// Must be called before pruning any account or storage history.
+pub(crate) fn sync_retained_history<Provider>(
+    provider: &Provider,
+    retention: &HistoryRetentionConfig,
+) -> Result<(), DatabaseError>
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    let tx = provider.tx_ref();
+
+    let mut stale = Vec::new();
+    for entry in tx.cursor_read::<tables::RetainedHistory>()?.walk(None)? {
+        let (address, _) = entry?;
+        if !retention.is_retained(&address) {
+            stale.push(address);
+        }
+    }
+    for address in stale {
+        tx.delete::<tables::RetainedHistory>(address, None)?;
+    }
+
+    let mut retained_from = 0;
+    for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+        if let Some(pruned) = tx
+            .get::<tables::PruneCheckpoints>(segment)?
+            .and_then(|checkpoint| checkpoint.block_number)
+        {
+            retained_from = retained_from.max(pruned + 1);
+        }
+    }
+    for address in retention.iter() {
+        if tx.get::<tables::RetainedHistory>(*address)?.is_none() {
+            tx.put::<tables::RetainedHistory>(*address, retained_from)?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Prune history indices according to the provided list of highest sharded keys.
 ///
 /// Returns total number of deleted, updated and unchanged entities.
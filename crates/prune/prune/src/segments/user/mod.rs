@@ -1,6 +1,6 @@
 mod account_history;
 mod bodies;
-mod history;
+pub(crate) mod history;
 mod merkle_change_sets;
 mod receipts;
 mod receipts_by_logs;
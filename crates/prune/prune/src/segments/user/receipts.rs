@@ -30,7 +30,7 @@ where
         + BlockReader
         + StorageSettingsCache
         + StaticFileProviderFactory
-        + NodePrimitivesProvider<Primitives: NodePrimitives<Receipt: Value>>,
+        + NodePrimitivesProvider<Primitives: NodePrimitives<SignedTx: Value, Receipt: Value>>,
 {
     fn segment(&self) -> PruneSegment {
         PruneSegment::Receipts
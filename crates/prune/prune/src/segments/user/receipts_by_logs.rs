@@ -7,7 +7,8 @@ use alloy_consensus::TxReceipt;
 use reth_db_api::{table::Value, tables, transaction::DbTxMut};
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::{
-    BlockReader, DBProvider, NodePrimitivesProvider, PruneCheckpointWriter, TransactionsProvider,
+    block_timestamp, BlockReader, DBProvider, NodePrimitivesProvider, PruneCheckpointWriter,
+    TransactionsProvider,
 };
 use reth_prune_types::{
     PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment, ReceiptsLogPruneConfig, SegmentOutput,
@@ -71,7 +72,9 @@ where
 
         // Figure out what receipts have already been pruned, so we can have an accurate
         // `address_filter`
-        let address_filter = self.config.group_by_block(input.to_block, last_pruned_block)?;
+        let config =
+            self.config.resolve_age(input.to_block, |block| block_timestamp(provider, block))?;
+        let address_filter = config.group_by_block(input.to_block, last_pruned_block)?;
 
         // Splits all transactions in different block ranges. Each block range will have its own
         // filter address list and will check it while going through the table
@@ -205,8 +208,7 @@ where
         //
         // Only applies if we were able to prune everything intended for this run, otherwise the
         // checkpoint is the `last_pruned_block`.
-        let prune_mode_block = self
-            .config
+        let prune_mode_block = config
             .lowest_block_with_distance(input.to_block, initial_last_pruned_block)?
             .unwrap_or(to_block);
 
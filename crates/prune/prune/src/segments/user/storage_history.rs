@@ -1,6 +1,9 @@
 use crate::{
     db_ext::DbTxPruneExt,
-    segments::{user::history::prune_history_indices, PruneInput, Segment, SegmentOutput},
+    segments::{
+        user::history::{prune_history_indices, sync_retained_history},
+        PruneInput, Segment, SegmentOutput,
+    },
     PrunerError,
 };
 use itertools::Itertools;
@@ -10,8 +13,11 @@ use reth_db_api::{
     transaction::DbTxMut,
 };
 use reth_provider::DBProvider;
-use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment, SegmentOutputCheckpoint};
+use reth_prune_types::{
+    HistoryRetentionConfig, PruneMode, PrunePurpose, PruneSegment, SegmentOutputCheckpoint,
+};
 use rustc_hash::FxHashMap;
+use std::collections::BTreeSet;
 use tracing::{instrument, trace};
 
 /// Number of storage history tables to prune in one step
@@ -23,11 +29,19 @@ const STORAGE_HISTORY_TABLES_TO_PRUNE: usize = 2;
 #[derive(Debug)]
 pub struct StorageHistory {
     mode: PruneMode,
+    /// Addresses whose history is never pruned.
+    retention: HistoryRetentionConfig,
 }
 
 impl StorageHistory {
     pub const fn new(mode: PruneMode) -> Self {
-        Self { mode }
+        Self { mode, retention: HistoryRetentionConfig(BTreeSet::new()) }
+    }
+
+    /// Retains the history of the given addresses.
+    pub fn with_retention(mut self, retention: HistoryRetentionConfig) -> Self {
+        self.retention = retention;
+        self
     }
 }
 
@@ -70,6 +84,9 @@ where
             ))
         }
 
+        // record newly retained addresses before any of their history is pruned
+        sync_retained_history(provider, &self.retention)?;
+
         let mut last_changeset_pruned_block = None;
         // Deleted storage changeset keys (account addresses and storage slots) with the highest
         // block number deleted for that key.
@@ -84,7 +101,7 @@ where
             provider.tx_ref().prune_table_with_range::<tables::StorageChangeSets>(
                 BlockNumberAddress::range(range),
                 &mut limiter,
-                |_| false,
+                |(BlockNumberAddress((_, address)), _)| self.retention.is_retained(address),
                 |(BlockNumberAddress((block_number, address)), entry)| {
                     highest_deleted_storages.insert((address, entry.key), block_number);
                     last_changeset_pruned_block = Some(block_number);
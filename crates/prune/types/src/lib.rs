@@ -18,13 +18,16 @@ mod pruner;
 mod segment;
 mod target;
 
-use alloc::{collections::BTreeMap, vec::Vec};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
 use alloy_primitives::{Address, BlockNumber};
 use core::ops::Deref;
 
 pub use checkpoint::PruneCheckpoint;
 pub use event::PrunerEvent;
-pub use mode::PruneMode;
+pub use mode::{PruneAgeError, PruneMode};
 pub use pruner::{
     PruneInterruptReason, PruneProgress, PrunedSegmentInfo, PrunerOutput, SegmentOutput,
     SegmentOutputCheckpoint,
@@ -46,6 +49,32 @@ impl ReceiptsLogPruneConfig {
         self.0.is_empty()
     }
 
+    /// Resolves all [`PruneMode::Age`] modes of the configuration with
+    /// [`PruneMode::resolve_age`].
+    ///
+    /// Like distances, prune targets by age move with the tip, so they are resolved into
+    /// [`PruneMode::Distance`] to be taken into account by [`Self::lowest_block_with_distance`].
+    /// The distance is never less than the minimum required for [`PruneSegment::ContractLogs`].
+    pub fn resolve_age<E>(
+        &self,
+        tip: BlockNumber,
+        mut timestamp: impl FnMut(BlockNumber) -> Result<u64, E>,
+    ) -> Result<Self, E> {
+        let min_distance = PruneSegment::ContractLogs.min_blocks(PrunePurpose::User);
+        self.iter()
+            .map(|(address, mode)| {
+                let mode = match mode.resolve_age(tip, &mut timestamp)? {
+                    PruneMode::Before(block) if mode.is_age() => {
+                        PruneMode::Distance((tip + 1 - block).max(min_distance))
+                    }
+                    mode => mode,
+                };
+                Ok((*address, mode))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
     /// Given the `tip` block number, consolidates the structure so it can easily be queried for
     /// filtering across a range of blocks.
     ///
@@ -119,6 +148,41 @@ impl Deref for ReceiptsLogPruneConfig {
     }
 }
 
+/// Configuration for retaining the account and storage history of the specified addresses.
+///
+/// Changesets and history indices of these addresses are kept by the
+/// [`PruneSegment::AccountHistory`] and [`PruneSegment::StorageHistory`] segments, regardless of
+/// their prune modes. Only history that was not pruned before the address was added is retained.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
+pub struct HistoryRetentionConfig(pub BTreeSet<Address>);
+
+impl HistoryRetentionConfig {
+    /// Checks if the configuration is empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the history of the given address is retained.
+    pub fn is_retained(&self, address: &Address) -> bool {
+        self.0.contains(address)
+    }
+}
+
+impl Deref for HistoryRetentionConfig {
+    type Target = BTreeSet<Address>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl FromIterator<Address> for HistoryRetentionConfig {
+    fn from_iter<I: IntoIterator<Item = Address>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -330,4 +394,28 @@ mod tests {
         // - Finally the lowest block is 100000
         assert_eq!(config.lowest_block_with_distance(tip, pruned_block).unwrap(), Some(100000));
     }
+
+    #[test]
+    fn test_resolve_age() {
+        let day = core::time::Duration::from_secs(24 * 60 * 60);
+        let config = ReceiptsLogPruneConfig(BTreeMap::from([
+            (Address::new([1; 20]), PruneMode::Age(2 * day)),
+            (Address::new([2; 20]), PruneMode::Age(day)),
+            (Address::new([3; 20]), PruneMode::Before(500)),
+        ]));
+        let tip = 20000;
+
+        // Blocks are 12 seconds apart, so two days are 14400 blocks
+        let resolved = config.resolve_age(tip, |block| Ok::<_, ()>(block * 12)).unwrap();
+        assert_eq!(
+            resolved.0,
+            BTreeMap::from([
+                (Address::new([1; 20]), PruneMode::Distance(14401)),
+                // One day is less than the minimum pruning distance
+                (Address::new([2; 20]), PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                (Address::new([3; 20]), PruneMode::Before(500)),
+            ])
+        );
+        assert_eq!(resolved.lowest_block_with_distance(tip, None).unwrap(), Some(5599));
+    }
 }
@@ -1,5 +1,12 @@
 use crate::{segment::PrunePurpose, PruneSegment, PruneSegmentError};
+use alloc::string::{String, ToString};
 use alloy_primitives::BlockNumber;
+use core::time::Duration;
+use thiserror::Error;
+
+/// Units of the [`PruneMode::Age`] duration in descending order, with their length in seconds.
+const AGE_UNITS: [(&str, u64); 5] =
+    [("w", 7 * 24 * 60 * 60), ("d", 24 * 60 * 60), ("h", 60 * 60), ("m", 60), ("s", 1)];
 
 /// Prune mode.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -15,6 +22,14 @@ pub enum PruneMode {
     Distance(u64),
     /// Prune blocks before the specified block number. The specified block number is not pruned.
     Before(BlockNumber),
+    /// Prune blocks with a timestamp older than the specified duration, relative to the timestamp
+    /// of the `head` block.
+    ///
+    /// The block number this corresponds to depends on the header timestamps, so it has to be
+    /// resolved into [`PruneMode::Before`] with [`PruneMode::resolve_age`] against the current tip
+    /// before it's checked. Unresolved, the mode is treated as having nothing to prune.
+    #[cfg_attr(any(test, feature = "serde"), serde(with = "age"))]
+    Age(Duration),
 }
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -33,6 +48,35 @@ impl PruneMode {
         Self::Before(block_number + 1)
     }
 
+    /// Resolves [`PruneMode::Age`] into [`PruneMode::Before`] the lowest block with a timestamp not
+    /// older than the age, relative to the timestamp of the `tip` block. Other modes are returned
+    /// as is.
+    ///
+    /// `timestamp` returns the timestamp of the block with the given number.
+    pub fn resolve_age<E>(
+        self,
+        tip: BlockNumber,
+        mut timestamp: impl FnMut(BlockNumber) -> Result<u64, E>,
+    ) -> Result<Self, E> {
+        let Self::Age(age) = self else { return Ok(self) };
+
+        let cutoff = timestamp(tip)?.saturating_sub(age.as_secs());
+
+        // Header timestamps are strictly increasing, so binary search for the first block that is
+        // not older than the cutoff.
+        let (mut low, mut high) = (0, tip);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if timestamp(mid)? < cutoff {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        Ok(Self::Before(low))
+    }
+
     /// Returns block up to which variant pruning needs to be done, inclusive, according to the
     /// provided tip.
     pub fn prune_target_block(
@@ -52,6 +96,8 @@ impl PruneMode {
             Self::Before(n) => {
                 (tip - n >= segment.min_blocks(purpose)).then(|| ((*n).saturating_sub(1), *self))
             }
+            // Needs to be resolved into `PruneMode::Before` first
+            Self::Age(_) => None,
             _ => return Err(PruneSegmentError::Configuration(segment)),
         };
         Ok(result)
@@ -68,6 +114,8 @@ impl PruneMode {
                 block < tip - *distance
             }
             Self::Before(n) => *n > block,
+            // Can't be known without the block timestamp, so the block is kept
+            Self::Age(_) => false,
         }
     }
 
@@ -80,6 +128,75 @@ impl PruneMode {
     pub const fn is_distance(&self) -> bool {
         matches!(self, Self::Distance(_))
     }
+
+    /// Returns true if the prune mode is [`PruneMode::Age`].
+    pub const fn is_age(&self) -> bool {
+        matches!(self, Self::Age(_))
+    }
+
+    /// Parses the duration of [`PruneMode::Age`] from a number followed by a unit, e.g. `"30d"`.
+    ///
+    /// Supported units are `s`, `m`, `h`, `d` and `w`.
+    pub fn parse_age(value: &str) -> Result<Duration, PruneAgeError> {
+        let value = value.trim();
+        let (number, unit) =
+            value.split_at(value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len()));
+
+        let number =
+            number.parse::<u64>().map_err(|_| PruneAgeError::InvalidNumber(value.to_string()))?;
+        let (_, unit_secs) = AGE_UNITS
+            .iter()
+            .find(|(name, _)| *name == unit.trim())
+            .ok_or_else(|| PruneAgeError::InvalidUnit(value.to_string()))?;
+
+        number
+            .checked_mul(*unit_secs)
+            .map(Duration::from_secs)
+            .ok_or_else(|| PruneAgeError::TooLarge(value.to_string()))
+    }
+}
+
+/// Error returned by [`PruneMode::parse_age`].
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+pub enum PruneAgeError {
+    /// The age doesn't start with a number.
+    #[error("invalid prune age `{0}`")]
+    InvalidNumber(String),
+    /// The unit of the age is unknown.
+    #[error("invalid prune age unit in `{0}`, expected one of s, m, h, d, w")]
+    InvalidUnit(String),
+    /// The age overflows.
+    #[error("prune age `{0}` is too large")]
+    TooLarge(String),
+}
+
+/// (De)serializes the duration of [`PruneMode::Age`] as a number followed by a unit, e.g. `"30d"`.
+///
+/// Supported units are `s`, `m`, `h`, `d` and `w`. Subsecond precision is not preserved.
+#[cfg(any(test, feature = "serde"))]
+mod age {
+    use super::{PruneMode, AGE_UNITS};
+    use alloc::{format, string::String};
+    use core::time::Duration;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        age: &Duration,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let secs = age.as_secs();
+        let (unit, unit_secs) = AGE_UNITS
+            .iter()
+            .find(|(_, unit_secs)| secs % unit_secs == 0 && secs != 0)
+            .unwrap_or(&("s", 1));
+        serializer.serialize_str(&format!("{}{unit}", secs / unit_secs))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Duration, D::Error> {
+        PruneMode::parse_age(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
 }
 
 #[cfg(test)]
@@ -88,6 +205,7 @@ mod tests {
         PruneMode, PrunePurpose, PruneSegment, PruneSegmentError, MINIMUM_PRUNING_DISTANCE,
     };
     use assert_matches::assert_matches;
+    use core::time::Duration;
     use serde::Deserialize;
 
     #[test]
@@ -172,12 +290,14 @@ mod tests {
             b: Option<PruneMode>,
             c: Option<PruneMode>,
             d: Option<PruneMode>,
+            e: Option<PruneMode>,
         }
 
         let toml_str = r#"
         a = "full"
         b = { distance = 10 }
         c = { before = 20 }
+        e = { age = "30d" }
     "#;
 
         assert_matches!(
@@ -186,8 +306,61 @@ mod tests {
                 a: Some(PruneMode::Full),
                 b: Some(PruneMode::Distance(10)),
                 c: Some(PruneMode::Before(20)),
-                d: None
-            })
+                d: None,
+                e: Some(PruneMode::Age(age)),
+            }) if age == Duration::from_secs(30 * 24 * 60 * 60)
+        );
+    }
+
+    #[test]
+    fn prune_mode_age_serde() {
+        for (age, expected) in [
+            (Duration::from_secs(2 * 7 * 24 * 60 * 60), r#"{"age":"2w"}"#),
+            (Duration::from_secs(36 * 60 * 60), r#"{"age":"36h"}"#),
+            (Duration::from_secs(90), r#"{"age":"90s"}"#),
+            (Duration::ZERO, r#"{"age":"0s"}"#),
+        ] {
+            let json = serde_json::to_string(&PruneMode::Age(age)).unwrap();
+            assert_eq!(json, expected);
+            assert_eq!(serde_json::from_str::<PruneMode>(&json).unwrap(), PruneMode::Age(age));
+        }
+
+        assert!(serde_json::from_str::<PruneMode>(r#"{"age":"30"}"#).is_err());
+        assert!(serde_json::from_str::<PruneMode>(r#"{"age":"d"}"#).is_err());
+        assert!(serde_json::from_str::<PruneMode>(r#"{"age":"30y"}"#).is_err());
+    }
+
+    #[test]
+    fn prune_mode_age() {
+        // Unresolved, there's nothing to prune
+        let mode = PruneMode::Age(Duration::from_secs(60));
+        assert_eq!(
+            mode.prune_target_block(20000, PruneSegment::Receipts, PrunePurpose::User),
+            Ok(None)
+        );
+        assert!(!mode.should_prune(0, 20000));
+
+        // Blocks are 12 seconds apart
+        let resolve = |tip, age| {
+            PruneMode::Age(Duration::from_secs(age))
+                .resolve_age(tip, |block| Ok::<_, ()>(block * 12))
+                .unwrap()
+        };
+
+        // Tip timestamp is 1200, so blocks older than 1080 (block 90) are pruned
+        assert_eq!(resolve(100, 120), PruneMode::Before(90));
+        // Cutoff between two block timestamps
+        assert_eq!(resolve(100, 125), PruneMode::Before(90));
+        assert_eq!(resolve(100, 0), PruneMode::Before(100));
+        // Age is longer than the chain
+        assert_eq!(resolve(100, 10_000), PruneMode::Before(0));
+        // Relative to a tip below the highest block
+        assert_eq!(resolve(50, 120), PruneMode::Before(40));
+
+        // Other modes are not resolved
+        assert_eq!(
+            PruneMode::Distance(10).resolve_age(100, |_| Err(())),
+            Ok(PruneMode::Distance(10))
         );
     }
 }
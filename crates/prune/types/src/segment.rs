@@ -41,6 +41,13 @@ pub enum PruneSegment {
     MerkleChangeSets,
     /// Prune segment responsible for bodies (transactions in static files).
     Bodies,
+    /// Prune segment defined outside of reth, e.g. for the tables of a node extension, identified
+    /// by its ID.
+    ///
+    /// Custom segments are excluded from [`PruneSegment::variants`].
+    #[strum(disabled)]
+    #[display("Custom({_0})")]
+    Custom(u8),
 }
 
 #[cfg(test)]
@@ -55,7 +62,7 @@ impl PruneSegment {
     /// Returns an iterator over all variants of [`PruneSegment`].
     ///
     /// Excludes deprecated variants that are no longer used, but can still be found in the
-    /// database.
+    /// database, and [`Self::Custom`] segments.
     pub fn variants() -> impl Iterator<Item = Self> {
         Self::iter()
     }
@@ -63,7 +70,8 @@ impl PruneSegment {
     /// Returns minimum number of blocks to keep in the database for this segment.
     pub const fn min_blocks(&self, purpose: PrunePurpose) -> u64 {
         match self {
-            Self::SenderRecovery | Self::TransactionLookup => 0,
+            // Custom segments are responsible for keeping the data they need themselves
+            Self::SenderRecovery | Self::TransactionLookup | Self::Custom(_) => 0,
             Self::Receipts if purpose.is_static_file() => 0,
             Self::ContractLogs |
             Self::AccountHistory |
@@ -86,6 +94,11 @@ impl PruneSegment {
     pub const fn is_storage_history(&self) -> bool {
         matches!(self, Self::StorageHistory)
     }
+
+    /// Returns true if this is a [`Self::Custom`] segment.
+    pub const fn is_custom(&self) -> bool {
+        matches!(self, Self::Custom(_))
+    }
 }
 
 /// Prune purpose.
@@ -131,5 +144,6 @@ mod tests {
             assert!(!segments.contains(&PruneSegment::Headers));
             assert!(!segments.contains(&PruneSegment::Transactions));
         }
+        assert!(!segments.iter().any(PruneSegment::is_custom));
     }
 }
@@ -2,7 +2,9 @@ use alloy_primitives::BlockNumber;
 use derive_more::Display;
 use thiserror::Error;
 
-use crate::{PruneCheckpoint, PruneMode, PruneSegment, ReceiptsLogPruneConfig};
+use crate::{
+    HistoryRetentionConfig, PruneCheckpoint, PruneMode, PruneSegment, ReceiptsLogPruneConfig,
+};
 
 /// Minimum distance from the tip necessary for the node to work correctly:
 /// 1. Minimum 2 epochs (32 blocks per epoch) required to handle any reorg according to the
@@ -41,8 +43,8 @@ pub enum HistoryType {
 pub const MERKLE_CHANGESETS_RETENTION_BLOCKS: u64 = 128;
 
 /// Default pruning mode for merkle changesets
-const fn default_merkle_changesets_mode() -> PruneMode {
-    PruneMode::Distance(MERKLE_CHANGESETS_RETENTION_BLOCKS)
+const fn default_merkle_changesets_mode() -> Option<PruneMode> {
+    Some(PruneMode::Distance(MERKLE_CHANGESETS_RETENTION_BLOCKS))
 }
 
 /// Pruning configuration for every segment of the data that can be pruned.
@@ -84,7 +86,7 @@ pub struct PruneModes {
         )
     )]
     pub storage_history: Option<PruneMode>,
-    /// Bodies History pruning configuration.
+    /// Bodies History pruning configuration. Also prunes the stored block access lists.
     #[cfg_attr(
         any(test, feature = "serde"),
         serde(
@@ -95,8 +97,14 @@ pub struct PruneModes {
     pub bodies_history: Option<PruneMode>,
     /// Merkle Changesets pruning configuration for `AccountsTrieChangeSets` and
     /// `StoragesTrieChangeSets`.
-    #[cfg_attr(any(test, feature = "serde"), serde(default = "default_merkle_changesets_mode"))]
-    pub merkle_changesets: PruneMode,
+    ///
+    /// [`None`] is the archive mode, configured as `"archive"`, which keeps the changesets of all
+    /// blocks, so that state proofs can be generated for any historical block.
+    #[cfg_attr(
+        any(test, feature = "serde"),
+        serde(default = "default_merkle_changesets_mode", with = "merkle_changesets_mode")
+    )]
+    pub merkle_changesets: Option<PruneMode>,
     /// Receipts pruning configuration by retaining only those receipts that contain logs emitted
     /// by the specified addresses, discarding others. This setting is overridden by `receipts`.
     ///
@@ -107,6 +115,13 @@ pub struct PruneModes {
         serde(skip_serializing_if = "ReceiptsLogPruneConfig::is_empty")
     )]
     pub receipts_log_filter: ReceiptsLogPruneConfig,
+    /// Addresses whose account and storage history is retained, even if `account_history` or
+    /// `storage_history` pruning is configured.
+    #[cfg_attr(
+        any(test, feature = "serde"),
+        serde(skip_serializing_if = "HistoryRetentionConfig::is_empty")
+    )]
+    pub history_retention: HistoryRetentionConfig,
 }
 
 impl Default for PruneModes {
@@ -120,6 +135,7 @@ impl Default for PruneModes {
             bodies_history: None,
             merkle_changesets: default_merkle_changesets_mode(),
             receipts_log_filter: ReceiptsLogPruneConfig::default(),
+            history_retention: HistoryRetentionConfig::default(),
         }
     }
 }
@@ -134,11 +150,34 @@ impl PruneModes {
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
             bodies_history: Some(PruneMode::Full),
-            merkle_changesets: PruneMode::Full,
+            merkle_changesets: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
+            history_retention: Default::default(),
         }
     }
 
+    /// Resolves all [`PruneMode::Age`] modes with [`PruneMode::resolve_age`].
+    pub fn resolve_age<E>(
+        &self,
+        tip: BlockNumber,
+        mut timestamp: impl FnMut(BlockNumber) -> Result<u64, E>,
+    ) -> Result<Self, E> {
+        let mut resolve = |mode: Option<PruneMode>| {
+            mode.map(|mode| mode.resolve_age(tip, &mut timestamp)).transpose()
+        };
+        Ok(Self {
+            sender_recovery: resolve(self.sender_recovery)?,
+            transaction_lookup: resolve(self.transaction_lookup)?,
+            receipts: resolve(self.receipts)?,
+            account_history: resolve(self.account_history)?,
+            storage_history: resolve(self.storage_history)?,
+            bodies_history: resolve(self.bodies_history)?,
+            merkle_changesets: resolve(self.merkle_changesets)?,
+            receipts_log_filter: self.receipts_log_filter.resolve_age(tip, &mut timestamp)?,
+            history_retention: self.history_retention.clone(),
+        })
+    }
+
     /// Returns whether there is any kind of receipt pruning configuration.
     pub fn has_receipts_pruning(&self) -> bool {
         self.receipts.is_some() || !self.receipts_log_filter.is_empty()
@@ -151,15 +190,20 @@ impl PruneModes {
     /// Currently migrates:
     /// - `merkle_changesets`: `Distance(n)` where `n < 128` or `n == 10064` -> `Distance(128)`
     pub const fn migrate(&mut self) -> bool {
-        if let PruneMode::Distance(d) = self.merkle_changesets &&
+        if let Some(PruneMode::Distance(d)) = self.merkle_changesets &&
             (d < MERKLE_CHANGESETS_RETENTION_BLOCKS || d == MINIMUM_PRUNING_DISTANCE)
         {
-            self.merkle_changesets = PruneMode::Distance(MERKLE_CHANGESETS_RETENTION_BLOCKS);
+            self.merkle_changesets = default_merkle_changesets_mode();
             return true;
         }
         false
     }
 
+    /// Returns whether the merkle changesets of all blocks are kept.
+    pub const fn is_merkle_changesets_archive(&self) -> bool {
+        self.merkle_changesets.is_none()
+    }
+
     /// Returns an error if we can't unwind to the targeted block because the target block is
     /// outside the range.
     ///
@@ -259,6 +303,45 @@ fn serde_deserialize_validate<'a, 'de, const MIN_BLOCKS: u64, D: serde::Deserial
     }
 }
 
+/// (De)serializes the merkle changesets prune mode, with [`None`] represented as `"archive"`.
+#[cfg(any(test, feature = "serde"))]
+mod merkle_changesets_mode {
+    use crate::PruneMode;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Archive {
+        Archive,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Mode {
+        Archive(Archive),
+        Prune(PruneMode),
+    }
+
+    pub(super) fn serialize<S: Serializer>(
+        mode: &Option<PruneMode>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match mode {
+            Some(mode) => mode.serialize(serializer),
+            None => Archive::Archive.serialize(serializer),
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<PruneMode>, D::Error> {
+        Ok(match Mode::deserialize(deserializer)? {
+            Mode::Archive(_) => None,
+            Mode::Prune(mode) => Some(mode),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -285,6 +368,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_merkle_changesets_archive() {
+        let archive = PruneModes { merkle_changesets: None, ..Default::default() };
+        assert!(archive.is_merkle_changesets_archive());
+
+        let json = serde_json::to_string(&archive).unwrap();
+        assert!(json.contains(r#""merkle_changesets":"archive""#));
+        assert_eq!(serde_json::from_str::<PruneModes>(&json).unwrap(), archive);
+
+        let modes = serde_json::from_str::<PruneModes>(r#"{"merkle_changesets":"full"}"#).unwrap();
+        assert_eq!(modes.merkle_changesets, Some(PruneMode::Full));
+
+        let modes = serde_json::from_str::<PruneModes>("{}").unwrap();
+        assert_eq!(modes.merkle_changesets, default_merkle_changesets_mode());
+        assert!(!modes.is_merkle_changesets_archive());
+    }
+
     #[test]
     fn test_unwind_target_unpruned() {
         // Test case 1: No pruning configured - should always succeed
@@ -19,7 +19,7 @@ use reth_primitives_traits::{Block as _, Header, RecoveredBlock};
 use reth_ress_protocol::RessProtocolProvider;
 use reth_revm::{database::StateProviderDatabase, db::State, witness::ExecutionWitnessRecord};
 use reth_tasks::TaskSpawner;
-use reth_trie::{MultiProofTargets, Nibbles, TrieInput};
+use reth_trie::{metrics::WitnessMetrics, MultiProofTargets, Nibbles, TrieInput, WitnessStats};
 use schnellru::{ByLength, LruMap};
 use std::{sync::Arc, time::Instant};
 use tokio::sync::{oneshot, Semaphore};
@@ -42,6 +42,7 @@ pub struct RethRessProtocolProvider<P, E> {
     max_witness_window: u64,
     witness_semaphore: Arc<Semaphore>,
     witness_cache: Arc<Mutex<LruMap<B256, Arc<Vec<Bytes>>>>>,
+    witness_metrics: Option<WitnessMetrics>,
     pending_state: PendingState<EthPrimitives>,
 }
 
@@ -51,6 +52,9 @@ where
     E: ConfigureEvm<Primitives = EthPrimitives> + 'static,
 {
     /// Create new ress protocol provider.
+    ///
+    /// If `witness_metrics` is set, the statistics of generated witnesses are recorded, which
+    /// walks all of their trie nodes.
     pub fn new(
         provider: P,
         evm_config: E,
@@ -58,6 +62,7 @@ where
         max_witness_window: u64,
         witness_max_parallel: usize,
         cache_size: u32,
+        witness_metrics: bool,
         pending_state: PendingState<EthPrimitives>,
     ) -> eyre::Result<Self> {
         Ok(Self {
@@ -67,6 +72,7 @@ where
             max_witness_window,
             witness_semaphore: Arc::new(Semaphore::new(witness_max_parallel)),
             witness_cache: Arc::new(Mutex::new(LruMap::new(ByLength::new(cache_size)))),
+            witness_metrics: witness_metrics.then(|| WitnessMetrics::new("ress")),
             pending_state,
         })
     }
@@ -92,6 +98,20 @@ where
         Ok(maybe_block)
     }
 
+    /// Retrieve the header of a valid or invalid block by block hash.
+    pub fn header_by_hash(&self, block_hash: B256) -> ProviderResult<Option<Header>> {
+        let maybe_header = if let Some(block) = self.pending_state.recovered_block(&block_hash) {
+            Some(block.header().clone())
+        } else if let Some(header) = self.provider.header(block_hash)? {
+            Some(header)
+        } else {
+            self.pending_state
+                .invalid_recovered_block(&block_hash)
+                .map(|block| block.header().clone())
+        };
+        Ok(maybe_header)
+    }
+
     /// Generate state witness
     pub fn generate_witness(&self, block_hash: B256) -> ProviderResult<Vec<Bytes>> {
         if let Some(witness) = self.witness_cache.lock().get(&block_hash).cloned() {
@@ -182,6 +202,12 @@ where
             witness_state_provider.witness(trie_input, hashed_state)?
         };
 
+        if let Some(metrics) = &self.witness_metrics &&
+            let Some(parent) = self.header_by_hash(block.parent_hash())?
+        {
+            metrics.record(&WitnessStats::new(parent.state_root, &witness, &[]));
+        }
+
         // Insert witness into the cache.
         let cached_witness = Arc::new(witness.clone());
         self.witness_cache.lock().insert(block_hash, cached_witness);
@@ -197,7 +223,7 @@ where
 {
     fn header(&self, block_hash: B256) -> ProviderResult<Option<Header>> {
         trace!(target: "reth::ress_provider", %block_hash, "Serving header");
-        Ok(self.block_by_hash(block_hash)?.map(|b| b.header().clone()))
+        self.header_by_hash(block_hash)
     }
 
     fn block_body(&self, block_hash: B256) -> ProviderResult<Option<BlockBody>> {
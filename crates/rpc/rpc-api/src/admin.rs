@@ -46,6 +46,12 @@ pub trait AdminApi {
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
 
+    /// Reloads the allow-list of the only peers that are allowed to connect from its file.
+    ///
+    /// Connected peers that are no longer allowed are disconnected.
+    #[method(name = "reloadAllowlist")]
+    async fn reload_allow_list(&self) -> RpcResult<bool>;
+
     /// Clears all transactions from the transaction pool.
     /// Returns the number of transactions that were removed from the pool.
     #[method(name = "clearTxpool")]
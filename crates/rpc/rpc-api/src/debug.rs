@@ -1,3 +1,4 @@
+use alloy_eip7928::BlockAccessList;
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
 use alloy_json_rpc::RpcObject;
@@ -8,7 +9,7 @@ use alloy_rpc_types_trace::geth::{
     BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_trie_common::{updates::TrieUpdates, HashedPostState};
+use reth_trie_common::{updates::TrieUpdates, HashedPostState, WitnessStats};
 
 /// Debug rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
@@ -156,6 +157,27 @@ pub trait DebugApi<TxReq: RpcObject> {
         hash: B256,
     ) -> RpcResult<ExecutionWitness>;
 
+    /// The `debug_executionWitnessStats` method returns statistics of the execution witness of a
+    /// block, as generated by `debug_executionWitness`: the number and size of the trie nodes by
+    /// trie and depth, the bytecodes, and the number of nodes shared between multiple proofs.
+    ///
+    /// The first argument is the block number or tag.
+    #[method(name = "executionWitnessStats")]
+    async fn debug_execution_witness_stats(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> RpcResult<WitnessStats>;
+
+    /// Returns the EIP-7928 block access list of the given block.
+    ///
+    /// Block access lists stored by the node are returned as is, otherwise the block is re-executed
+    /// to record it.
+    #[method(name = "getBlockAccessList")]
+    async fn debug_get_block_access_list(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<BlockAccessList>>;
+
     /// Sets the logging backtrace location. When a backtrace location is set and a log message is
     /// emitted at that location, the stack of the goroutine executing the log statement will
     /// be printed to stderr.
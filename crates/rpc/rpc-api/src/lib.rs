@@ -31,6 +31,7 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use reth::{AccountChange, HistoryAccount, HistoryPage, StorageChange};
 pub use testing::{TestingBuildBlockRequestV1, TESTING_BUILD_BLOCK_V1};
 
 /// re-export of all server traits
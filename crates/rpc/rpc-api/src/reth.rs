@@ -1,11 +1,66 @@
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{Address, BlockNumber, B256, U256};
+use alloy_serde::JsonStorageKey;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 // Required for the subscription attribute below
 use reth_chain_state as _;
 
+/// Account fields as returned by `reth_getAccountHistory`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryAccount {
+    /// Account nonce.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// Account balance.
+    pub balance: U256,
+    /// Hash of the account's bytecode.
+    pub code_hash: B256,
+}
+
+/// A change of an account in a block.
+///
+/// `None` means that the account did not exist before or after the block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountChange {
+    /// The block that changed the account.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// The account before the block.
+    pub old: Option<HistoryAccount>,
+    /// The account after the block.
+    pub new: Option<HistoryAccount>,
+}
+
+/// A change of a storage slot in a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageChange {
+    /// The block that changed the slot.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// The value before the block.
+    pub old: U256,
+    /// The value after the block.
+    pub new: U256,
+}
+
+/// A page of history changes, ordered by block number.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryPage<T> {
+    /// The changes in this page.
+    pub changes: Vec<T>,
+    /// The block to use as `fromBlock` to request the next page, if there are more changes in the
+    /// requested range.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub next_block: Option<BlockNumber>,
+}
+
 /// Reth API namespace for reth-specific methods
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
@@ -17,6 +72,31 @@ pub trait RethApi {
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
 
+    /// Returns the changes of an account within the given block range, in ascending order.
+    ///
+    /// At most `limit` changes are returned; `nextBlock` is set if there are more.
+    #[method(name = "getAccountHistory")]
+    async fn reth_get_account_history(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        limit: Option<usize>,
+    ) -> RpcResult<HistoryPage<AccountChange>>;
+
+    /// Returns the changes of a storage slot within the given block range, in ascending order.
+    ///
+    /// At most `limit` changes are returned; `nextBlock` is set if there are more.
+    #[method(name = "getStorageHistory")]
+    async fn reth_get_storage_history(
+        &self,
+        address: Address,
+        slot: JsonStorageKey,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        limit: Option<usize>,
+    ) -> RpcResult<HistoryPage<StorageChange>>;
+
     /// Subscribe to json `ChainNotifications`
     #[subscription(
         name = "subscribeChainNotifications",
@@ -107,6 +107,7 @@ impl RethRpcServerConfig for RpcServerArgs {
             .pending_block_kind(self.rpc_pending_block)
             .raw_tx_forwarder(self.rpc_forwarder.clone())
             .rpc_evm_memory_limit(self.rpc_evm_memory_limit)
+            .witness_cache_size((self.rpc_witness_cache_size * 1024 * 1024) as usize)
     }
 
     fn flashbots_config(&self) -> ValidationApiConfig {
@@ -54,8 +54,8 @@ use reth_rpc_eth_types::{receipt::EthReceiptConverter, EthConfig, EthSubscriptio
 use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
 pub use reth_rpc_server_types::RethRpcModule;
 use reth_storage_api::{
-    AccountReader, BlockReader, ChangeSetReader, FullRpcProvider, NodePrimitivesProvider,
-    StateProviderFactory,
+    AccountReader, BlockAccessListProvider, BlockReader, ChangeSetReader, FullRpcProvider,
+    HistoryReader, LogIndexProvider, NodePrimitivesProvider, StateProviderFactory,
 };
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
 use reth_tokio_util::EventSender;
@@ -312,7 +312,11 @@ where
     Provider: FullRpcProvider<Block = N::Block, Receipt = N::Receipt, Header = N::BlockHeader>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + HistoryReader
+        + BlockAccessListProvider
+        + LogIndexProvider
+        + Sync,
     Pool: TransactionPool + Clone + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EvmConfig: ConfigureEvm<Primitives = N> + 'static,
@@ -361,7 +365,7 @@ where
         EthApi: FullEthApiServer<Provider = Provider, Pool = Pool>,
     {
         let Self { provider, pool, network, executor, consensus, evm_config, .. } = self;
-        RpcRegistryInner::new(
+        let mut registry = RpcRegistryInner::new(
             provider,
             pool,
             network,
@@ -371,7 +375,9 @@ where
             evm_config,
             eth,
             engine_events,
-        )
+        );
+        registry.eth.filter = registry.eth.filter.clone().with_log_index(registry.provider.clone());
+        registry
     }
 
     /// Configures all [`RpcModule`]s specific to the given [`TransportRpcModuleConfig`] which can
@@ -655,6 +661,8 @@ where
             Transaction = N::SignedTx,
         > + AccountReader
         + ChangeSetReader
+        + HistoryReader
+        + BlockAccessListProvider
         + CanonStateSubscriptions,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: EthApiServer<
@@ -770,7 +778,9 @@ where
             Transaction = N::SignedTx,
             Receipt = N::Receipt,
         > + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + HistoryReader
+        + BlockAccessListProvider,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: EthApiTypes,
     EvmConfig: ConfigureEvm<Primitives = N>,
@@ -808,6 +818,7 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn debug_api(&self) -> DebugApi<EthApi>
     where
+        Provider: Sync,
         EthApi: FullEthApiTypes,
     {
         DebugApi::new(
@@ -815,7 +826,10 @@ where
             self.blocking_pool_guard.clone(),
             self.tasks(),
             self.engine_events.new_listener(),
+            self.eth_config.witness_cache_size,
+            self.eth_config.witness_metrics,
         )
+        .with_block_access_lists(self.provider.clone())
     }
 
     /// Instantiates `NetApi`
@@ -844,7 +858,9 @@ where
     Provider: FullRpcProvider<Block = N::Block>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + HistoryReader
+        + BlockAccessListProvider,
     Pool: TransactionPool + Clone + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: FullEthApiServer,
@@ -938,7 +954,10 @@ where
                             self.blocking_pool_guard.clone(),
                             &*self.executor,
                             self.engine_events.new_listener(),
+                            self.eth_config.witness_cache_size,
+                            self.eth_config.witness_metrics,
                         )
+                        .with_block_access_lists(self.provider.clone())
                         .into_rpc()
                         .into(),
                         RethRpcModule::Eth => {
@@ -107,6 +107,11 @@ pub struct EthConfig {
     pub send_raw_transaction_sync_timeout: Duration,
     /// Maximum memory the EVM can allocate per RPC request.
     pub rpc_evm_memory_limit: u64,
+    /// Maximum memory in bytes used to cache generated execution witnesses, `0` disables the
+    /// cache.
+    pub witness_cache_size: usize,
+    /// Whether to record the statistics of generated execution witnesses as metrics.
+    pub witness_metrics: bool,
 }
 
 impl EthConfig {
@@ -140,6 +145,8 @@ impl Default for EthConfig {
             raw_tx_forwarder: ForwardConfig::default(),
             send_raw_transaction_sync_timeout: RPC_DEFAULT_SEND_RAW_TX_SYNC_TIMEOUT_SECS,
             rpc_evm_memory_limit: (1 << 32) - 1,
+            witness_cache_size: 0,
+            witness_metrics: false,
         }
     }
 }
@@ -242,6 +249,18 @@ impl EthConfig {
         self.rpc_evm_memory_limit = memory_limit;
         self
     }
+
+    /// Configures the maximum memory in bytes used to cache generated execution witnesses.
+    pub const fn witness_cache_size(mut self, size: usize) -> Self {
+        self.witness_cache_size = size;
+        self
+    }
+
+    /// Configures whether to record the statistics of generated execution witnesses as metrics.
+    pub const fn witness_metrics(mut self, enabled: bool) -> Self {
+        self.witness_metrics = enabled;
+        self
+    }
 }
 
 /// Config for the filter
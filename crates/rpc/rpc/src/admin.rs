@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use alloy_genesis::ChainConfig;
 use alloy_rpc_types_admin::{
@@ -91,7 +91,22 @@ where
                 protocols: PeerProtocolInfo {
                     eth: Some(EthPeerInfo::Info(EthInfo { version: peer.status.version as u64 })),
                     snap: None,
-                    other: Default::default(),
+                    other: BTreeMap::from([
+                        (
+                            "bandwidth".to_string(),
+                            peer.bandwidth
+                                .iter()
+                                .map(|protocol| {
+                                    serde_json::json!({
+                                        "protocol": protocol.protocol,
+                                        "ingressBytes": protocol.ingress_bytes,
+                                        "egressBytes": protocol.egress_bytes,
+                                    })
+                                })
+                                .collect(),
+                        ),
+                        ("score".to_string(), peer.score.into()),
+                    ]),
                 },
             })
         }
@@ -186,6 +201,12 @@ where
         Err("admin_peerEvents is not implemented yet".into())
     }
 
+    /// Handler for `admin_reloadAllowlist`
+    async fn reload_allow_list(&self) -> RpcResult<bool> {
+        self.network.reload_allow_list().await.to_rpc_result()?;
+        Ok(true)
+    }
+
     /// Handler for `admin_clearTxpool`
     async fn clear_txpool(&self) -> RpcResult<u64> {
         let all_hashes = self.pool.all_transaction_hashes();
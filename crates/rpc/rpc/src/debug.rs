@@ -1,8 +1,9 @@
 use alloy_consensus::{transaction::TxHashRef, BlockHeader};
+use alloy_eip7928::BlockAccessList;
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
-use alloy_evm::env::BlockEnvironment;
+use alloy_evm::{block::StateChangeSource, env::BlockEnvironment};
 use alloy_genesis::ChainConfig;
-use alloy_primitives::{hex::decode, uint, Address, Bytes, B256};
+use alloy_primitives::{hex::decode, map::B256Map, uint, Address, Bytes, B256};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types::BlockTransactionsKind;
 use alloy_rpc_types_debug::ExecutionWitness;
@@ -13,15 +14,17 @@ use alloy_rpc_types_trace::geth::{
 use async_trait::async_trait;
 use futures::Stream;
 use jsonrpsee::core::RpcResult;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_engine_primitives::ConsensusEngineEvent;
 use reth_errors::RethError;
-use reth_evm::{execute::Executor, ConfigureEvm, EvmEnvFor};
+use reth_evm::{
+    block_access_list::BlockAccessListBuilder, execute::Executor, ConfigureEvm, EvmEnvFor,
+};
 use reth_primitives_traits::{
     Block as BlockTrait, BlockBody, BlockTy, ReceiptWithBloom, RecoveredBlock,
 };
-use reth_revm::{db::State, witness::ExecutionWitnessRecord};
+use reth_revm::{db::State, state::EvmState, witness::ExecutionWitnessRecord};
 use reth_rpc_api::DebugApiServer;
 use reth_rpc_convert::RpcTxReq;
 use reth_rpc_eth_api::{
@@ -31,11 +34,13 @@ use reth_rpc_eth_api::{
 use reth_rpc_eth_types::EthApiError;
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use reth_storage_api::{
-    BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderBlock, ReceiptProviderIdExt,
-    StateProofProvider, StateProviderFactory, StateRootProvider, TransactionVariant,
+    BlockAccessListProvider, BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderBlock,
+    ReceiptProviderIdExt, StateProofProvider, StateProviderFactory, StateRootProvider,
+    TransactionVariant,
 };
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner};
-use reth_trie_common::{updates::TrieUpdates, HashedPostState};
+use reth_trie::metrics::WitnessMetrics;
+use reth_trie_common::{updates::TrieUpdates, HashedPostState, WitnessStats};
 use revm::DatabaseCommit;
 use revm_inspectors::tracing::{DebugInspector, TransactionContext};
 use serde::{Deserialize, Serialize};
@@ -48,6 +53,8 @@ use tokio_stream::StreamExt;
 /// This type provides the functionality for handling `debug` related requests.
 pub struct DebugApi<Eth: RpcNodeCore> {
     inner: Arc<DebugApiInner<Eth>>,
+    /// Provider of the stored block access lists, if configured.
+    block_access_lists: Option<Arc<dyn BlockAccessListProvider + Sync>>,
 }
 
 impl<Eth> DebugApi<Eth>
@@ -55,17 +62,25 @@ where
     Eth: RpcNodeCore,
 {
     /// Create a new instance of the [`DebugApi`]
+    ///
+    /// Generated execution witnesses are cached up to `witness_cache_size` bytes, `0` disables
+    /// the cache. If `witness_metrics` is set, the statistics of generated witnesses are recorded,
+    /// which walks all of their trie nodes.
     pub fn new(
         eth_api: Eth,
         blocking_task_guard: BlockingTaskGuard,
         executor: impl TaskSpawner,
         mut stream: impl Stream<Item = ConsensusEngineEvent<Eth::Primitives>> + Send + Unpin + 'static,
+        witness_cache_size: usize,
+        witness_metrics: bool,
     ) -> Self {
         let bad_block_store = BadBlockStore::default();
         let inner = Arc::new(DebugApiInner {
             eth_api,
             blocking_task_guard,
             bad_block_store: bad_block_store.clone(),
+            witness_cache: WitnessCache::new(witness_cache_size),
+            witness_metrics: witness_metrics.then(|| WitnessMetrics::new("rpc")),
         });
 
         // Spawn a task caching bad blocks
@@ -80,7 +95,18 @@ where
             }
         }));
 
-        Self { inner }
+        Self { inner, block_access_lists: None }
+    }
+
+    /// Configures the provider of the stored block access lists.
+    ///
+    /// Without it, `debug_getBlockAccessList` re-executes every requested block.
+    pub fn with_block_access_lists(
+        mut self,
+        provider: impl BlockAccessListProvider + Sync + 'static,
+    ) -> Self {
+        self.block_access_lists = Some(Arc::new(provider));
+        self
     }
 
     /// Access the underlying `Eth` API.
@@ -527,12 +553,42 @@ where
         self.debug_execution_witness_for_block(block).await
     }
 
+    /// Returns the statistics of the execution witness of the given block, see
+    /// [`Self::debug_execution_witness`].
+    pub async fn debug_execution_witness_stats(
+        &self,
+        block_id: BlockNumberOrTag,
+    ) -> Result<WitnessStats, Eth::Error> {
+        let this = self.clone();
+        let block = this
+            .eth_api()
+            .recovered_block(block_id.into())
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_id.into()))?;
+        let parent_hash = block.parent_hash();
+
+        let witness = self.debug_execution_witness_for_block(block).await?;
+        let parent = self
+            .provider()
+            .header(parent_hash)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(parent_hash.into()))?;
+
+        Ok(WitnessStats::new(parent.state_root(), &witness.state, &witness.codes))
+    }
+
     /// Generates an execution witness, using the given recovered block.
     pub async fn debug_execution_witness_for_block(
         &self,
         block: Arc<RecoveredBlock<ProviderBlock<Eth::Provider>>>,
     ) -> Result<ExecutionWitness, Eth::Error> {
+        let block_hash = block.hash();
+        if let Some(witness) = self.inner.witness_cache.get(&block_hash) {
+            return Ok(witness)
+        }
+
         let block_number = block.header().number();
+        let parent_hash = block.parent_hash();
 
         let (mut exec_witness, lowest_block_number) = self
             .eth_api()
@@ -584,9 +640,63 @@ where
             })
             .collect();
 
+        if let Some(metrics) = &self.inner.witness_metrics &&
+            let Some(parent) = self.provider().header(parent_hash).map_err(EthApiError::from)?
+        {
+            metrics.record(&WitnessStats::new(
+                parent.state_root(),
+                &exec_witness.state,
+                &exec_witness.codes,
+            ));
+        }
+        self.inner.witness_cache.insert(block_hash, &exec_witness);
+
         Ok(exec_witness)
     }
 
+    /// Returns the EIP-7928 block access list of the given block.
+    ///
+    /// The block is re-executed to record the block access list if it wasn't stored, or no
+    /// provider of the stored block access lists is configured.
+    pub async fn debug_get_block_access_list(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<BlockAccessList>, Eth::Error> {
+        let Some(block) = self.eth_api().recovered_block(block_id).await? else { return Ok(None) };
+
+        if let Some(provider) = &self.block_access_lists &&
+            let Some(bal) = provider
+                .block_access_list(block.header().number())
+                .map_err(Eth::Error::from_eth_err)?
+        {
+            let bal = BlockAccessList::decode(&mut bal.as_ref())
+                .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+            return Ok(Some(bal))
+        }
+
+        let bal = self
+            .eth_api()
+            .spawn_with_state_at_block(block.parent_hash(), move |eth_api, mut db| {
+                let builder = Arc::new(Mutex::new(BlockAccessListBuilder::default()));
+                let hook = builder.clone();
+                let output = eth_api
+                    .evm_config()
+                    .executor(&mut db)
+                    .execute_with_state_hook(
+                        &block,
+                        move |source: StateChangeSource, state: &EvmState| {
+                            hook.lock().on_state(source, state);
+                        },
+                    )
+                    .map_err(|err| EthApiError::Internal(err.into()))?;
+
+                Ok(core::mem::take(&mut *builder.lock()).finish(&output.state))
+            })
+            .await?;
+
+        Ok(Some(bal))
+    }
+
     /// Returns the code associated with a given hash at the specified block ID. If no code is
     /// found, it returns None. If no block ID is provided, it defaults to the latest block.
     pub async fn debug_code_by_hash(
@@ -827,6 +937,24 @@ where
         Self::debug_execution_witness_by_block_hash(self, hash).await.map_err(Into::into)
     }
 
+    /// Handler for `debug_executionWitnessStats`
+    async fn debug_execution_witness_stats(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> RpcResult<WitnessStats> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_execution_witness_stats(self, block).await.map_err(Into::into)
+    }
+
+    /// Handler for `debug_getBlockAccessList`
+    async fn debug_get_block_access_list(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<BlockAccessList>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_get_block_access_list(self, block_id).await.map_err(Into::into)
+    }
+
     async fn debug_backtrace_at(&self, _location: &str) -> RpcResult<()> {
         Ok(())
     }
@@ -1097,7 +1225,7 @@ impl<Eth: RpcNodeCore> std::fmt::Debug for DebugApi<Eth> {
 
 impl<Eth: RpcNodeCore> Clone for DebugApi<Eth> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self { inner: Arc::clone(&self.inner), block_access_lists: self.block_access_lists.clone() }
     }
 }
 
@@ -1108,6 +1236,10 @@ struct DebugApiInner<Eth: RpcNodeCore> {
     blocking_task_guard: BlockingTaskGuard,
     /// Cache for bad blocks.
     bad_block_store: BadBlockStore<BlockTy<Eth::Primitives>>,
+    /// Cache for generated execution witnesses.
+    witness_cache: WitnessCache,
+    /// Metrics for generated execution witnesses, if enabled.
+    witness_metrics: Option<WitnessMetrics>,
 }
 
 /// A bounded, deduplicating store of recently observed bad blocks.
@@ -1152,3 +1284,62 @@ impl<B: BlockTrait> Default for BadBlockStore<B> {
         Self::new(64)
     }
 }
+
+/// A cache of generated execution witnesses by block hash, bounded by their total size in bytes.
+///
+/// The oldest witnesses are evicted first.
+#[derive(Debug, Default)]
+struct WitnessCache {
+    inner: RwLock<WitnessCacheInner>,
+    max_size: usize,
+}
+
+#[derive(Debug, Default)]
+struct WitnessCacheInner {
+    witnesses: B256Map<(ExecutionWitness, usize)>,
+    order: VecDeque<B256>,
+    size: usize,
+}
+
+impl WitnessCache {
+    /// Creates a new cache holding up to `max_size` bytes of witnesses.
+    fn new(max_size: usize) -> Self {
+        Self { inner: Default::default(), max_size }
+    }
+
+    /// Returns the cached witness of the given block.
+    fn get(&self, block_hash: &B256) -> Option<ExecutionWitness> {
+        if self.max_size == 0 {
+            return None
+        }
+        self.inner.read().witnesses.get(block_hash).map(|(witness, _)| witness.clone())
+    }
+
+    /// Inserts the witness of the given block, evicting the oldest witnesses if the cache is full.
+    fn insert(&self, block_hash: B256, witness: &ExecutionWitness) {
+        let size = [&witness.state, &witness.codes, &witness.keys, &witness.headers]
+            .into_iter()
+            .flatten()
+            .map(Bytes::len)
+            .sum::<usize>();
+        if size > self.max_size {
+            return
+        }
+
+        let mut inner = self.inner.write();
+        if inner.witnesses.contains_key(&block_hash) {
+            return
+        }
+        inner.witnesses.insert(block_hash, (witness.clone(), size));
+        inner.order.push_back(block_hash);
+        inner.size += size;
+
+        while inner.size > self.max_size &&
+            let Some(oldest) = inner.order.pop_front()
+        {
+            if let Some((_, size)) = inner.witnesses.remove(&oldest) {
+                inner.size -= size;
+            }
+        }
+    }
+}
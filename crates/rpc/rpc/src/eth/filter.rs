@@ -28,8 +28,8 @@ use reth_rpc_eth_types::{
 };
 use reth_rpc_server_types::{result::rpc_error_with_code, ToRpcResult};
 use reth_storage_api::{
-    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, ProviderBlock,
-    ProviderReceipt, ReceiptProvider,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, LogIndexBlocks,
+    LogIndexProvider, ProviderBlock, ProviderReceipt, ReceiptProvider,
 };
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
@@ -94,6 +94,8 @@ const DEFAULT_PARALLEL_CONCURRENCY: usize = 4;
 pub struct EthFilter<Eth: EthApiTypes> {
     /// All nested fields bundled together
     inner: Arc<EthFilterInner<Eth>>,
+    /// Optional log index, which narrows down the blocks that are searched for logs.
+    log_index: Option<Arc<dyn LogIndexProvider + Sync>>,
 }
 
 impl<Eth> Clone for EthFilter<Eth>
@@ -101,7 +103,7 @@ where
     Eth: EthApiTypes,
 {
     fn clone(&self) -> Self {
-        Self { inner: self.inner.clone() }
+        Self { inner: self.inner.clone(), log_index: self.log_index.clone() }
     }
 }
 
@@ -149,7 +151,7 @@ where
             query_limits: QueryLimits { max_blocks_per_filter, max_logs_per_response },
         };
 
-        let eth_filter = Self { inner: Arc::new(inner) };
+        let eth_filter = Self { inner: Arc::new(inner), log_index: None };
 
         let this = eth_filter.clone();
         eth_filter.inner.task_spawner.spawn_critical(
@@ -162,6 +164,13 @@ where
         eth_filter
     }
 
+    /// Configures the log index, which is used to look up the candidate blocks of log queries
+    /// instead of checking the bloom of every header in the range.
+    pub fn with_log_index(mut self, log_index: impl LogIndexProvider + Sync + 'static) -> Self {
+        self.log_index = Some(Arc::new(log_index));
+        self
+    }
+
     /// Returns all currently active filters
     pub fn active_filters(&self) -> &ActiveFilters<RpcTransaction<Eth::NetworkTypes>> {
         &self.inner.active_filters
@@ -286,6 +295,7 @@ where
                         from_block_number,
                         to_block_number,
                         self.inner.query_limits,
+                        self.log_index.clone(),
                     )
                     .await?;
                 Ok(FilterChanges::Logs(logs))
@@ -321,7 +331,7 @@ where
         filter: Filter,
         limits: QueryLimits,
     ) -> Result<Vec<Log>, EthFilterError> {
-        self.inner.clone().logs_for_filter(filter, limits).await
+        self.inner.clone().logs_for_filter(filter, limits, self.log_index.clone()).await
     }
 }
 
@@ -464,6 +474,7 @@ where
         self: Arc<Self>,
         filter: Filter,
         limits: QueryLimits,
+        log_index: Option<Arc<dyn LogIndexProvider + Sync>>,
     ) -> Result<Vec<Log>, EthFilterError> {
         match filter.block_option {
             FilterBlockOption::AtBlockHash(block_hash) => {
@@ -565,8 +576,14 @@ where
                 let (from_block_number, to_block_number) =
                     logs_utils::get_filter_block_range(from, to, start_block, info)?;
 
-                self.get_logs_in_block_range(filter, from_block_number, to_block_number, limits)
-                    .await
+                self.get_logs_in_block_range(
+                    filter,
+                    from_block_number,
+                    to_block_number,
+                    limits,
+                    log_index,
+                )
+                .await
             }
         }
     }
@@ -606,6 +623,7 @@ where
         from_block: u64,
         to_block: u64,
         limits: QueryLimits,
+        log_index: Option<Arc<dyn LogIndexProvider + Sync>>,
     ) -> Result<Vec<Log>, EthFilterError> {
         trace!(target: "rpc::eth::filter", from=from_block, to=to_block, ?filter, "finding logs in range");
 
@@ -623,8 +641,15 @@ where
         let (tx, rx) = oneshot::channel();
         let this = self.clone();
         self.task_spawner.spawn_blocking(Box::pin(async move {
-            let res =
-                this.get_logs_in_block_range_inner(&filter, from_block, to_block, limits).await;
+            let res = this
+                .get_logs_in_block_range_inner(
+                    &filter,
+                    from_block,
+                    to_block,
+                    limits,
+                    log_index.as_deref(),
+                )
+                .await;
             let _ = tx.send(res);
         }));
 
@@ -645,6 +670,7 @@ where
         from_block: u64,
         to_block: u64,
         limits: QueryLimits,
+        log_index: Option<&(dyn LogIndexProvider + Sync)>,
     ) -> Result<Vec<Log>, EthFilterError> {
         let mut all_logs = Vec::new();
         let mut matching_headers = Vec::new();
@@ -652,9 +678,29 @@ where
         // get current chain tip to determine processing mode
         let chain_tip = self.provider().best_block_number()?;
 
+        // if the log index is enabled, it narrows down the candidate blocks, only the blocks above
+        // the last indexed block need to be checked against the header bloom
+        let mut bloom_from_block = from_block;
+        let addresses = filter.address.iter().copied().collect::<Vec<_>>();
+        let topics =
+            filter.topics.iter().map(|topic| topic.iter().copied().collect()).collect::<Vec<_>>();
+        if let Some(log_index) = log_index &&
+            let Some(LogIndexBlocks { blocks, last_indexed_block }) =
+                log_index.log_index_blocks(&addresses, &topics, from_block..=to_block)?
+        {
+            for block_number in blocks {
+                if let Some(header) = self.provider().sealed_header(block_number)? &&
+                    filter.matches_bloom(header.logs_bloom())
+                {
+                    matching_headers.push(header);
+                }
+            }
+            bloom_from_block = from_block.max(last_indexed_block + 1);
+        }
+
         // first collect all headers that match the bloom filter for cached mode decision
         for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+            BlockRangeInclusiveIter::new(bloom_from_block..=to_block, self.max_headers_range)
         {
             let headers = self.provider().headers_range(from..=to)?;
 
@@ -1883,7 +1929,7 @@ mod tests {
         let logs = eth_filter
             .inner
             .clone()
-            .get_logs_in_block_range(filter, 100, 103, QueryLimits::default())
+            .get_logs_in_block_range(filter, 100, 103, QueryLimits::default(), None)
             .await
             .expect("should succeed");
 
@@ -1897,4 +1943,98 @@ mod tests {
         assert_eq!(logs[0].block_hash, Some(expected_hashes[0])); // block 100
         assert_eq!(logs[1].block_hash, Some(expected_hashes[2])); // block 102
     }
+
+    /// Log index that returns fixed candidate blocks for any filter.
+    struct FixedLogIndex(LogIndexBlocks);
+
+    impl LogIndexProvider for FixedLogIndex {
+        fn log_index_blocks(
+            &self,
+            _addresses: &[alloy_primitives::Address],
+            _topics: &[Vec<alloy_primitives::B256>],
+            _range: RangeInclusive<u64>,
+        ) -> reth_provider::ProviderResult<Option<LogIndexBlocks>> {
+            Ok(Some(self.0.clone()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_log_index_candidate_blocks() {
+        use reth_db_api::models::StoredBlockBodyIndices;
+
+        let provider = MockEthProvider::default();
+
+        // All blocks match the bloom and have a receipt with a log
+        let tx = reth_ethereum_primitives::TransactionSigned::new_unhashed(
+            alloy_consensus::TxLegacy::default().into(),
+            alloy_primitives::Signature::test_signature(),
+        );
+        let receipt = reth_ethereum_primitives::Receipt {
+            tx_type: TxType::Legacy,
+            cumulative_gas_used: 21_000,
+            logs: vec![alloy_primitives::Log {
+                address: alloy_primitives::Address::ZERO,
+                data: alloy_primitives::LogData::new_unchecked(
+                    vec![],
+                    alloy_primitives::Bytes::new(),
+                ),
+            }],
+            success: true,
+        };
+        let mut parent_hash = alloy_primitives::B256::default();
+        for number in 100u64..=103 {
+            let header = alloy_consensus::Header {
+                number,
+                parent_hash,
+                logs_bloom: alloy_primitives::Bloom::from([1u8; 256]),
+                ..Default::default()
+            };
+            parent_hash = header.hash_slow();
+            provider.add_block(
+                parent_hash,
+                reth_ethereum_primitives::Block {
+                    header,
+                    body: reth_ethereum_primitives::BlockBody {
+                        transactions: vec![tx.clone()],
+                        ..Default::default()
+                    },
+                },
+            );
+            provider.add_receipts(number, vec![receipt.clone()]);
+            provider.add_block_body_indices(
+                number,
+                StoredBlockBodyIndices { first_tx_num: number - 100, tx_count: 1 },
+            );
+        }
+
+        // The index covers blocks up to 102 and only block 101 is a candidate, block 103 is
+        // checked against the bloom
+        let eth_filter = EthFilter::new(
+            build_test_eth_api(provider),
+            EthFilterConfig::default(),
+            Box::new(TokioTaskExecutor::default()),
+        )
+        .with_log_index(FixedLogIndex(LogIndexBlocks {
+            blocks: vec![101],
+            last_indexed_block: 102,
+        }));
+
+        let logs = eth_filter
+            .inner
+            .clone()
+            .get_logs_in_block_range(
+                Filter::default(),
+                100,
+                103,
+                QueryLimits::default(),
+                eth_filter.log_index.clone(),
+            )
+            .await
+            .expect("should succeed");
+
+        assert_eq!(
+            logs.iter().map(|log| log.block_number).collect::<Vec<_>>(),
+            vec![Some(101), Some(103)]
+        );
+    }
 }
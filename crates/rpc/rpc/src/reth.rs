@@ -1,21 +1,25 @@
-use std::{collections::HashMap, future::Future, sync::Arc};
+use std::{collections::HashMap, future::Future, ops::RangeInclusive, sync::Arc};
 
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{Address, BlockNumber, U256};
+use alloy_serde::JsonStorageKey;
 use async_trait::async_trait;
 use futures::StreamExt;
 use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink};
 use jsonrpsee_types::ErrorObject;
 use reth_chain_state::{CanonStateNotificationStream, CanonStateSubscriptions};
 use reth_errors::RethResult;
-use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::RethApiServer;
+use reth_primitives_traits::{Account, NodePrimitives};
+use reth_rpc_api::{AccountChange, HistoryAccount, HistoryPage, RethApiServer, StorageChange};
 use reth_rpc_eth_types::{EthApiError, EthResult};
 use reth_rpc_server_types::result::internal_rpc_err;
-use reth_storage_api::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_storage_api::{BlockReaderIdExt, ChangeSetReader, HistoryReader, StateProviderFactory};
 use reth_tasks::TaskSpawner;
 use tokio::sync::oneshot;
 
+/// The maximum number of changes returned by a single history request.
+const MAX_HISTORY_PAGE_SIZE: usize = 1000;
+
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
@@ -40,7 +44,7 @@ impl<Provider> RethApi<Provider> {
 
 impl<Provider> RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt + ChangeSetReader + HistoryReader + StateProviderFactory + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -88,6 +92,75 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the changes of an account within the block range.
+    pub async fn account_history(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        limit: Option<usize>,
+    ) -> EthResult<HistoryPage<AccountChange>> {
+        self.on_blocking_task(|this| async move {
+            let (range, limit) = this.history_params(from_block, to_block, limit)?;
+            let changes = this.provider().account_history(address, range, limit + 1)?;
+            Ok(history_page(changes, limit, |block_number, old, new| AccountChange {
+                block_number,
+                old: old.map(history_account),
+                new: new.map(history_account),
+            }))
+        })
+        .await
+    }
+
+    /// Returns the changes of a storage slot within the block range.
+    pub async fn storage_history(
+        &self,
+        address: Address,
+        slot: JsonStorageKey,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        limit: Option<usize>,
+    ) -> EthResult<HistoryPage<StorageChange>> {
+        self.on_blocking_task(|this| async move {
+            let (range, limit) = this.history_params(from_block, to_block, limit)?;
+            let changes =
+                this.provider().storage_history(address, slot.as_b256(), range, limit + 1)?;
+            Ok(history_page(changes, limit, |block_number, old, new| StorageChange {
+                block_number,
+                old,
+                new,
+            }))
+        })
+        .await
+    }
+
+    /// Resolves the block range and page size of a history request.
+    fn history_params(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        limit: Option<usize>,
+    ) -> EthResult<(RangeInclusive<BlockNumber>, usize)> {
+        let Some(from) = self.provider().convert_block_number(from_block)? else {
+            return Err(EthApiError::HeaderNotFound(from_block.into()))
+        };
+        let Some(to) = self.provider().convert_block_number(to_block)? else {
+            return Err(EthApiError::HeaderNotFound(to_block.into()))
+        };
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+
+        let limit = limit.unwrap_or(MAX_HISTORY_PAGE_SIZE);
+        if limit == 0 || limit > MAX_HISTORY_PAGE_SIZE {
+            return Err(EthApiError::InvalidParams(format!(
+                "limit must be between 1 and {MAX_HISTORY_PAGE_SIZE}"
+            )))
+        }
+
+        Ok((from..=to, limit))
+    }
 }
 
 #[async_trait]
@@ -95,6 +168,7 @@ impl<Provider> RethApiServer for RethApi<Provider>
 where
     Provider: BlockReaderIdExt
         + ChangeSetReader
+        + HistoryReader
         + StateProviderFactory
         + CanonStateSubscriptions
         + 'static,
@@ -107,6 +181,29 @@ where
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
 
+    /// Handler for `reth_getAccountHistory`
+    async fn reth_get_account_history(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        limit: Option<usize>,
+    ) -> RpcResult<HistoryPage<AccountChange>> {
+        Ok(Self::account_history(self, address, from_block, to_block, limit).await?)
+    }
+
+    /// Handler for `reth_getStorageHistory`
+    async fn reth_get_storage_history(
+        &self,
+        address: Address,
+        slot: JsonStorageKey,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        limit: Option<usize>,
+    ) -> RpcResult<HistoryPage<StorageChange>> {
+        Ok(Self::storage_history(self, address, slot, from_block, to_block, limit).await?)
+    }
+
     /// Handler for `reth_subscribeChainNotifications`
     async fn reth_subscribe_chain_notifications(
         &self,
@@ -122,6 +219,35 @@ where
     }
 }
 
+/// Converts the `(block, old, new)` entries of a history lookup into a page.
+///
+/// The lookup is expected to request one entry more than `limit`, which becomes the start of the
+/// next page.
+fn history_page<T, R>(
+    mut changes: Vec<(BlockNumber, T, T)>,
+    limit: usize,
+    f: impl Fn(BlockNumber, T, T) -> R,
+) -> HistoryPage<R> {
+    let next_block = changes.get(limit).map(|(block_number, ..)| *block_number);
+    changes.truncate(limit);
+    HistoryPage {
+        changes: changes
+            .into_iter()
+            .map(|(block_number, old, new)| f(block_number, old, new))
+            .collect(),
+        next_block,
+    }
+}
+
+/// Converts an account into its history representation.
+fn history_account(account: Account) -> HistoryAccount {
+    HistoryAccount {
+        nonce: account.nonce,
+        balance: account.balance,
+        code_hash: account.get_bytecode_hash(),
+    }
+}
+
 /// Pipes all stream items to the subscription sink.
 async fn pipe_from_stream<N: NodePrimitives>(
     sink: SubscriptionSink,
@@ -39,8 +39,8 @@
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, EraImportSource, EraStage, ExecutionStage, FinishStage,
-        HeaderStage, IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleChangeSets,
-        MerkleStage, PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage,
+        HeaderStage, IndexAccountHistoryStage, IndexLogsStage, IndexStorageHistoryStage,
+        MerkleChangeSets, MerkleStage, PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage,
         StorageHashingStage, TransactionLookupStage,
     },
     StageSet, StageSetBuilder,
@@ -80,6 +80,7 @@ use tokio::sync::watch;
 /// - [`TransactionLookupStage`]
 /// - [`IndexStorageHistoryStage`]
 /// - [`IndexAccountHistoryStage`]
+/// - [`IndexLogsStage`]
 /// - [`PruneStage`] (execute)
 /// - [`FinishStage`]
 #[derive(Debug)]
@@ -339,7 +340,10 @@ where
             .add_stage_opt(self.prune_modes.sender_recovery.map(|prune_mode| {
                 PruneSenderRecoveryStage::new(prune_mode, self.stages_config.prune.commit_threshold)
             }))
-            .add_set(HashingStages { stages_config: self.stages_config.clone() })
+            .add_set(HashingStages {
+                stages_config: self.stages_config.clone(),
+                prune_modes: self.prune_modes.clone(),
+            })
             .add_set(HistoryIndexingStages {
                 stages_config: self.stages_config.clone(),
                 prune_modes: self.prune_modes.clone(),
@@ -407,6 +411,8 @@ where
 pub struct HashingStages {
     /// Configuration for each stage in the pipeline
     stages_config: StageConfig,
+    /// Prune configuration for every segment that can be pruned
+    prune_modes: PruneModes,
 }
 
 impl<Provider> StageSet<Provider> for HashingStages
@@ -431,7 +437,11 @@ where
                 self.stages_config.merkle.rebuild_threshold,
                 self.stages_config.merkle.incremental_threshold,
             ))
-            .add_stage(MerkleChangeSets::new())
+            .add_stage(if self.prune_modes.is_merkle_changesets_archive() {
+                MerkleChangeSets::archive()
+            } else {
+                MerkleChangeSets::new()
+            })
     }
 }
 
@@ -450,6 +460,7 @@ where
     TransactionLookupStage: Stage<Provider>,
     IndexStorageHistoryStage: Stage<Provider>,
     IndexAccountHistoryStage: Stage<Provider>,
+    IndexLogsStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
         StageSetBuilder::default()
@@ -468,5 +479,10 @@ where
                 self.stages_config.etl.clone(),
                 self.prune_modes.account_history,
             ))
+            .add_stage(IndexLogsStage::new(
+                self.stages_config.index_logs,
+                self.stages_config.etl.clone(),
+                self.prune_modes.receipts,
+            ))
     }
 }
@@ -7,7 +7,7 @@ use reth_db_api::{table::Value, transaction::DbTxMut};
 use reth_era::{common::file_ops::StreamReader, era1::file::Era1Reader};
 use reth_era_downloader::{read_dir, EraClient, EraMeta, EraStream, EraStreamConfig};
 use reth_era_utils as era;
-use reth_etl::Collector;
+use reth_etl::{Collector, DiskBudget};
 use reth_primitives_traits::{FullBlockBody, FullBlockHeader, NodePrimitives};
 use reth_provider::{
     BlockReader, BlockWriter, DBProvider, StageCheckpointWriter, StaticFileProviderFactory,
@@ -121,7 +121,9 @@ impl<Header, Body, F> EraStage<Header, Body, F> {
             source,
             item: None,
             stream: None,
-            hash_collector: Collector::new(etl_config.file_size, etl_config.dir),
+            hash_collector: Collector::new(etl_config.file_size, etl_config.dir)
+                .with_compression(etl_config.compression)
+                .with_disk_budget(etl_config.disk_budget.map(DiskBudget::new)),
         }
     }
 }
@@ -5,14 +5,20 @@ use num_traits::Zero;
 use reth_config::config::ExecutionConfig;
 use reth_consensus::FullConsensus;
 use reth_db::{static_file::HeaderMask, tables};
-use reth_evm::{execute::Executor, metrics::ExecutorMetrics, ConfigureEvm};
+use reth_evm::{
+    execute::{BlockExecutionError, Executor},
+    metrics::ExecutorMetrics,
+    ConfigureEvm, Database,
+};
 use reth_execution_types::Chain;
 use reth_exex::{ExExManagerHandle, ExExNotification, ExExNotificationSource};
-use reth_primitives_traits::{format_gas_throughput, BlockBody, NodePrimitives};
+use reth_primitives_traits::{
+    format_gas_throughput, BlockBody, BlockTy, NodePrimitives, ReceiptTy, RecoveredBlock,
+};
 use reth_provider::{
     providers::{StaticFileProvider, StaticFileWriter},
-    BlockHashReader, BlockReader, DBProvider, EitherWriter, ExecutionOutcome, HeaderProvider,
-    LatestStateProviderRef, OriginalValuesKnown, ProviderError, StateWriter,
+    resolve_prune_modes, BlockHashReader, BlockReader, DBProvider, EitherWriter, ExecutionOutcome,
+    HeaderProvider, LatestStateProviderRef, OriginalValuesKnown, ProviderError, StateWriter,
     StaticFileProviderFactory, StatsReader, StorageSettingsCache, TransactionVariant,
 };
 use reth_revm::database::StateProviderDatabase;
@@ -31,7 +37,7 @@ use std::{
 };
 use tracing::*;
 
-use super::missing_static_data_error;
+use super::{missing_static_data_error, ExecutionPrefetchProvider, ParallelExecution};
 
 /// The execution stage executes all transactions and
 /// update history indexes.
@@ -89,6 +95,8 @@ where
     exex_manager_handle: ExExManagerHandle<E::Primitives>,
     /// Executor metrics.
     metrics: ExecutorMetrics,
+    /// Parallel execution settings, if blocks are prefetched and prewarmed on other threads.
+    parallel: Option<ParallelExecution<E::Primitives>>,
 }
 
 impl<E> ExecutionStage<E>
@@ -112,9 +120,29 @@ where
             post_unwind_commit_input: None,
             exex_manager_handle,
             metrics: ExecutorMetrics::default(),
+            parallel: None,
         }
     }
 
+    /// Enables parallel execution with the given number of prewarm threads.
+    ///
+    /// Upcoming blocks are fetched and their transactions are speculatively executed ahead of
+    /// execution to load the state they access, and the state changes are written while the
+    /// following blocks are executed. The blocks are still executed in order, so the result is the
+    /// same as with serial execution.
+    ///
+    /// The provider must read the same database the stage is executed on. All state changes must be
+    /// committed before the stage is executed, as the prefetched state is read outside of the
+    /// stage's provider.
+    pub fn with_parallel_execution(
+        mut self,
+        provider: impl ExecutionPrefetchProvider<E::Primitives>,
+        prewarm_threads: usize,
+    ) -> Self {
+        self.parallel = Some(ParallelExecution::new(Arc::new(provider), prewarm_threads));
+        self
+    }
+
     /// Create an execution stage with the provided executor.
     ///
     /// The commit threshold will be set to [`MERKLE_STAGE_DEFAULT_INCREMENTAL_THRESHOLD`].
@@ -295,11 +323,7 @@ where
 
         self.ensure_consistency(provider, input.checkpoint().block_number, None)?;
 
-        let db = StateProviderDatabase(LatestStateProviderRef::new(provider));
-        let mut executor = self.evm_config.batch_executor(db);
-
         // Progress tracking
-        let mut stage_progress = start_block;
         let mut stage_checkpoint = execution_checkpoint(
             &static_file_provider,
             start_block,
@@ -307,119 +331,98 @@ where
             input.checkpoint(),
         )?;
 
-        let mut fetch_block_duration = Duration::default();
-        let mut execution_duration = Duration::default();
-
-        let mut last_block = start_block;
-        let mut last_execution_duration = Duration::default();
-        let mut last_cumulative_gas = 0;
-        let mut last_log_instant = Instant::now();
-        let log_duration = Duration::from_secs(10);
+        let can_prune_changesets = self.can_prune_changesets(provider, start_block, max_block)?;
+        let prune_modes = resolve_prune_modes(provider, provider.prune_modes_ref(), max_block)?;
 
         debug!(target: "sync::stages::execution", start = start_block, end = max_block, "Executing range");
 
-        // Execute block range
-        let mut cumulative_gas = 0;
-        let batch_start = Instant::now();
-
-        let mut blocks = Vec::new();
-        let mut results = Vec::new();
-        for block_number in start_block..=max_block {
-            // Fetch the block
-            let fetch_block_start = Instant::now();
-
-            // we need the block's transactions but we don't need the transaction hashes
-            let block = provider
-                .recovered_block(block_number.into(), TransactionVariant::NoHash)?
-                .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
-
-            fetch_block_duration += fetch_block_start.elapsed();
-
-            cumulative_gas += block.header().gas_used();
-
-            // Configure the executor to use the current state.
-            trace!(target: "sync::stages::execution", number = block_number, txs = block.body().transactions().len(), "Executing block");
-
-            // Execute the block
-            let execute_start = Instant::now();
-
-            let result = self.metrics.metered_one(&block, |input| {
-                executor.execute_one(input).map_err(|error| StageError::Block {
-                    block: Box::new(block.block_with_parent()),
-                    error: BlockErrorKind::Execution(error),
-                })
-            })?;
-
-            if let Err(err) = self.consensus.validate_block_post_execution(&block, &result) {
-                return Err(StageError::Block {
-                    block: Box::new(block.block_with_parent()),
-                    error: BlockErrorKind::Validation(err),
-                })
+        // Blocks and state for the post execute commit hook, where an `ExExNotification` will be
+        // sent.
+        let mut executed: Option<(
+            Vec<RecoveredBlock<BlockTy<E::Primitives>>>,
+            ExecutionOutcome<ReceiptTy<E::Primitives>>,
+        )> = None;
+        let mut db_write_duration = Duration::default();
+        let write = |mut state: ExecutionOutcome<_>, blocks: Vec<_>| -> Result<(), StageError> {
+            // Note: Since we only keep the blocks if there are any ExExes, we don't need to perform
+            // the `has_exexs` check here as well
+            if !blocks.is_empty() {
+                match &mut executed {
+                    Some((executed_blocks, executed_state)) => {
+                        executed_blocks.extend(blocks);
+                        executed_state.extend(state.clone());
+                    }
+                    None => executed = Some((blocks, state.clone())),
+                }
             }
-            results.push(result);
-
-            execution_duration += execute_start.elapsed();
 
-            // Log execution throughput
-            if last_log_instant.elapsed() >= log_duration {
-                info!(
-                    target: "sync::stages::execution",
-                    start = last_block,
-                    end = block_number,
-                    throughput = format_gas_throughput(cumulative_gas - last_cumulative_gas, execution_duration - last_execution_duration),
-                    "Executed block range"
-                );
+            let time = Instant::now();
 
-                last_block = block_number + 1;
-                last_execution_duration = execution_duration;
-                last_cumulative_gas = cumulative_gas;
-                last_log_instant = Instant::now();
+            if can_prune_changesets {
+                // Iterate over all reverts and clear them if pruning is configured.
+                for (block_number, reverts) in
+                    (state.first_block()..).zip(state.bundle.reverts.iter_mut())
+                {
+                    // If both account history and storage history pruning is configured, clear
+                    // reverts for this block.
+                    if prune_modes
+                        .account_history
+                        .is_some_and(|m| m.should_prune(block_number, max_block)) &&
+                        prune_modes
+                            .storage_history
+                            .is_some_and(|m| m.should_prune(block_number, max_block))
+                    {
+                        reverts.clear();
+                    }
+                }
             }
 
-            stage_progress = block_number;
-            stage_checkpoint.progress.processed += block.header().gas_used();
-
-            // If we have ExExes we need to save the block in memory for later
-            if self.exex_manager_handle.has_exexs() {
-                blocks.push(block);
-            }
+            // write output
+            provider.write_state(&state, OriginalValuesKnown::Yes)?;
 
-            // Check if we should commit now
-            if self.thresholds.is_end_of_batch(
-                block_number - start_block,
-                executor.size_hint() as u64,
-                cumulative_gas,
-                batch_start.elapsed(),
-            ) {
-                break
-            }
-        }
+            db_write_duration += time.elapsed();
+            Ok(())
+        };
 
-        // prepare execution output for writing
-        let time = Instant::now();
-        let mut state = ExecutionOutcome::from_blocks(
-            start_block,
-            executor.into_state().take_bundle(),
-            results,
-        );
-        let write_preparation_duration = time.elapsed();
+        let range_executor = RangeExecutor {
+            consensus: self.consensus.as_ref(),
+            thresholds: &self.thresholds,
+            metrics: &self.metrics,
+            keep_blocks: self.exex_manager_handle.has_exexs(),
+        };
+        let range = if let Some(parallel) = &self.parallel {
+            parallel.execute(&self.evm_config, &range_executor, start_block, max_block, write)?
+        } else {
+            let db = StateProviderDatabase(LatestStateProviderRef::new(provider));
+            range_executor.execute(
+                self.evm_config.batch_executor(db),
+                start_block,
+                max_block,
+                |block_number| {
+                    // we need the block's transactions but we don't need the transaction hashes
+                    let block = provider
+                        .recovered_block(block_number.into(), TransactionVariant::NoHash)?
+                        .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+                    Ok(Arc::new(block))
+                },
+                usize::MAX,
+                write,
+            )?
+        };
+        stage_checkpoint.progress.processed += range.gas_used;
 
         // log the gas per second for the range we just executed
         debug!(
             target: "sync::stages::execution",
             start = start_block,
-            end = stage_progress,
-            throughput = format_gas_throughput(cumulative_gas, execution_duration),
+            end = range.last_block,
+            throughput = format_gas_throughput(range.gas_used, range.execution_duration),
             "Finished executing block range"
         );
 
-        // Prepare the input for post execute commit hook, where an `ExExNotification` will be sent.
-        //
-        // Note: Since we only write to `blocks` if there are any ExExes, we don't need to perform
-        // the `has_exexs` check here as well
-        if !blocks.is_empty() {
+        if let Some((blocks, state)) = executed {
             let previous_input =
-                self.post_execute_commit_input.replace(Chain::new(blocks, state.clone(), None));
+                self.post_execute_commit_input.replace(Chain::new(blocks, state, None));
 
             if previous_input.is_some() {
                 // Not processing the previous post execute commit input is a critical error, as it
@@ -430,49 +433,18 @@ where
             }
         }
 
-        let time = Instant::now();
-
-        if self.can_prune_changesets(provider, start_block, max_block)? {
-            let prune_modes = provider.prune_modes_ref();
-
-            // Iterate over all reverts and clear them if pruning is configured.
-            for block_number in start_block..=max_block {
-                let Some(reverts) =
-                    state.bundle.reverts.get_mut((block_number - start_block) as usize)
-                else {
-                    break
-                };
-
-                // If both account history and storage history pruning is configured, clear reverts
-                // for this block.
-                if prune_modes
-                    .account_history
-                    .is_some_and(|m| m.should_prune(block_number, max_block)) &&
-                    prune_modes
-                        .storage_history
-                        .is_some_and(|m| m.should_prune(block_number, max_block))
-                {
-                    reverts.clear();
-                }
-            }
-        }
-
-        // write output
-        provider.write_state(&state, OriginalValuesKnown::Yes)?;
-
-        let db_write_duration = time.elapsed();
         debug!(
             target: "sync::stages::execution",
-            block_fetch = ?fetch_block_duration,
-            execution = ?execution_duration,
-            write_preparation = ?write_preparation_duration,
+            block_fetch = ?range.fetch_block_duration,
+            execution = ?range.execution_duration,
+            write_preparation = ?range.write_preparation_duration,
             write = ?db_write_duration,
             "Execution time"
         );
 
-        let done = stage_progress == max_block;
+        let done = range.last_block == max_block;
         Ok(ExecOutput {
-            checkpoint: StageCheckpoint::new(stage_progress)
+            checkpoint: StageCheckpoint::new(range.last_block)
                 .with_execution_stage_checkpoint(stage_checkpoint),
             done,
         })
@@ -564,6 +536,158 @@ where
     }
 }
 
+/// Executes block ranges for the [`ExecutionStage`].
+pub(crate) struct RangeExecutor<'a, N: NodePrimitives> {
+    consensus: &'a dyn FullConsensus<N>,
+    thresholds: &'a ExecutionStageThresholds,
+    metrics: &'a ExecutorMetrics,
+    /// Whether the executed blocks are kept for the `ExEx` notification.
+    keep_blocks: bool,
+}
+
+/// Summary of a range executed by the [`RangeExecutor`].
+#[derive(Debug, Default)]
+pub(crate) struct ExecutedRange {
+    /// The last executed block.
+    last_block: BlockNumber,
+    /// The gas used by all executed blocks.
+    gas_used: u64,
+    fetch_block_duration: Duration,
+    execution_duration: Duration,
+    write_preparation_duration: Duration,
+}
+
+impl<N: NodePrimitives> RangeExecutor<'_, N> {
+    /// Executes the blocks starting at `start_block` until `max_block` or the end of the batch.
+    ///
+    /// The execution outcome is passed to `write` with the executed blocks at the end, and
+    /// whenever the executor holds at least `chunk_changes` state changes before that.
+    pub(crate) fn execute<DB: Database>(
+        &self,
+        mut executor: impl Executor<DB, Primitives = N, Error = BlockExecutionError>,
+        start_block: BlockNumber,
+        max_block: BlockNumber,
+        mut next_block: impl FnMut(BlockNumber) -> Result<Arc<RecoveredBlock<N::Block>>, StageError>,
+        chunk_changes: usize,
+        mut write: impl FnMut(
+            ExecutionOutcome<N::Receipt>,
+            Vec<RecoveredBlock<N::Block>>,
+        ) -> Result<(), StageError>,
+    ) -> Result<ExecutedRange, StageError> {
+        let mut range = ExecutedRange { last_block: start_block, ..Default::default() };
+
+        let mut last_block = start_block;
+        let mut last_execution_duration = Duration::default();
+        let mut last_cumulative_gas = 0;
+        let mut last_log_instant = Instant::now();
+        let log_duration = Duration::from_secs(10);
+
+        let batch_start = Instant::now();
+
+        // The first block and the state changes of the chunk that wasn't written yet
+        let mut chunk_start = start_block;
+        let mut written_changes = 0;
+
+        let mut blocks = Vec::new();
+        let mut results = Vec::new();
+        for block_number in start_block..=max_block {
+            // Fetch the block
+            let fetch_block_start = Instant::now();
+            let block = next_block(block_number)?;
+            range.fetch_block_duration += fetch_block_start.elapsed();
+
+            range.gas_used += block.header().gas_used();
+
+            // Configure the executor to use the current state.
+            trace!(target: "sync::stages::execution", number = block_number, txs = block.body().transactions().len(), "Executing block");
+
+            // Execute the block
+            let execute_start = Instant::now();
+
+            let result = self.metrics.metered_one(&block, |input| {
+                executor.execute_one(input).map_err(|error| StageError::Block {
+                    block: Box::new(block.block_with_parent()),
+                    error: BlockErrorKind::Execution(error),
+                })
+            })?;
+
+            if let Err(err) = self.consensus.validate_block_post_execution(&block, &result) {
+                return Err(StageError::Block {
+                    block: Box::new(block.block_with_parent()),
+                    error: BlockErrorKind::Validation(err),
+                })
+            }
+            results.push(result);
+
+            range.execution_duration += execute_start.elapsed();
+
+            // Log execution throughput
+            if last_log_instant.elapsed() >= log_duration {
+                info!(
+                    target: "sync::stages::execution",
+                    start = last_block,
+                    end = block_number,
+                    throughput = format_gas_throughput(range.gas_used - last_cumulative_gas, range.execution_duration - last_execution_duration),
+                    "Executed block range"
+                );
+
+                last_block = block_number + 1;
+                last_execution_duration = range.execution_duration;
+                last_cumulative_gas = range.gas_used;
+                last_log_instant = Instant::now();
+            }
+
+            range.last_block = block_number;
+
+            // If we have ExExes we need to save the block in memory for later
+            if self.keep_blocks {
+                blocks.push(Arc::unwrap_or_clone(block));
+            }
+
+            // Check if we should commit now
+            if block_number == max_block ||
+                self.thresholds.is_end_of_batch(
+                    block_number - start_block,
+                    (written_changes + executor.size_hint()) as u64,
+                    range.gas_used,
+                    batch_start.elapsed(),
+                )
+            {
+                break
+            }
+
+            // Hand over the chunk to be written while the execution continues
+            if executor.size_hint() >= chunk_changes {
+                written_changes += executor.size_hint();
+
+                let time = Instant::now();
+                let state = ExecutionOutcome::from_blocks(
+                    chunk_start,
+                    executor.take_bundle(),
+                    std::mem::take(&mut results),
+                );
+                range.write_preparation_duration += time.elapsed();
+
+                write(state, std::mem::take(&mut blocks))?;
+                chunk_start = block_number + 1;
+            }
+        }
+
+        // prepare execution output for writing
+        let time = Instant::now();
+        let state = ExecutionOutcome::from_blocks(
+            chunk_start,
+            executor.into_state().take_bundle(),
+            results,
+        );
+        range.write_preparation_duration += time.elapsed();
+
+        write(state, blocks)?;
+
+        Ok(range)
+    }
+}
+
 fn execution_checkpoint<N>(
     provider: &StaticFileProvider<N>,
     start_block: BlockNumber,
@@ -672,6 +796,7 @@ where
 mod tests {
     use super::*;
     use crate::{stages::MERKLE_STAGE_DEFAULT_REBUILD_THRESHOLD, test_utils::TestStageDB};
+    use alloy_consensus::{Header, EMPTY_ROOT_HASH};
     use alloy_primitives::{address, hex_literal::hex, keccak256, Address, B256, U256};
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
@@ -996,6 +1121,104 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parallel_execution_matches_serial() {
+        let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
+        let genesis = SealedBlock::<Block>::decode(&mut genesis_rlp).unwrap();
+        let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
+        let mut blocks = vec![genesis, SealedBlock::<Block>::decode(&mut block_rlp).unwrap()];
+        // Empty blocks on top, which only change the state with the block reward
+        for _ in 0..2 {
+            let parent = blocks.last().unwrap();
+            let header = Header {
+                parent_hash: parent.hash(),
+                number: parent.number + 1,
+                gas_used: 0,
+                transactions_root: EMPTY_ROOT_HASH,
+                receipts_root: EMPTY_ROOT_HASH,
+                logs_bloom: Default::default(),
+                ..parent.header().clone()
+            };
+            blocks.push(SealedBlock::seal_slow(Block { header, body: Default::default() }));
+        }
+
+        let acc1 = address!("0x1000000000000000000000000000000000000000");
+        let acc2 = address!("0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+        let code = hex!("5a465a905090036002900360015500");
+        let code_hash = keccak256(code);
+
+        let execute = |parallel: bool| {
+            let db = TestStageDB::default();
+            let provider = db.factory.provider_rw().unwrap();
+            for block in &blocks {
+                provider.insert_block(&block.clone().try_recover().unwrap()).unwrap();
+            }
+            provider
+                .static_file_provider()
+                .latest_writer(StaticFileSegment::Headers)
+                .unwrap()
+                .commit()
+                .unwrap();
+            {
+                let static_file_provider = provider.static_file_provider();
+                let mut receipts_writer =
+                    static_file_provider.latest_writer(StaticFileSegment::Receipts).unwrap();
+                receipts_writer.increment_block(0).unwrap();
+                receipts_writer.commit().unwrap();
+            }
+            provider
+                .tx_ref()
+                .put::<tables::PlainAccountState>(
+                    acc1,
+                    Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+                )
+                .unwrap();
+            provider
+                .tx_ref()
+                .put::<tables::PlainAccountState>(
+                    acc2,
+                    Account {
+                        nonce: 0,
+                        balance: U256::from(0x3635c9adc5dea00000u128),
+                        bytecode_hash: None,
+                    },
+                )
+                .unwrap();
+            provider
+                .tx_ref()
+                .put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into()))
+                .unwrap();
+            provider.commit().unwrap();
+
+            let mut stage = stage();
+            if parallel {
+                stage = stage.with_parallel_execution(db.factory.clone(), 2);
+                // Write every block separately
+                stage.parallel.as_mut().unwrap().chunk_changes = 1;
+            }
+
+            let provider = db.factory.database_provider_rw().unwrap();
+            let output =
+                stage.execute(&provider, ExecInput { target: Some(3), checkpoint: None }).unwrap();
+            provider.commit().unwrap();
+
+            let receipts = db.factory.receipts_by_block_range(1..=3).unwrap();
+            (
+                output,
+                receipts,
+                db.table::<tables::PlainAccountState>().unwrap(),
+                db.table::<tables::PlainStorageState>().unwrap(),
+                db.table::<tables::AccountChangeSets>().unwrap(),
+                db.table::<tables::StorageChangeSets>().unwrap(),
+            )
+        };
+
+        let serial = execute(false);
+        assert!(serial.0.done);
+        assert_eq!(serial.1.len(), 3);
+        assert_eq!(execute(true), serial);
+    }
+
     #[tokio::test]
     async fn sanity_execute_unwind() {
         let factory = create_test_provider_factory();
@@ -7,7 +7,7 @@ use reth_db_api::{
     transaction::{DbTx, DbTxMut},
     RawKey, RawTable, RawValue,
 };
-use reth_etl::Collector;
+use reth_etl::{Collector, DiskBudget};
 use reth_primitives_traits::Account;
 use reth_provider::{AccountExtReader, DBProvider, HashingWriter, StatsReader};
 use reth_stages_api::{
@@ -161,7 +161,9 @@ where
 
             let mut accounts_cursor = tx.cursor_read::<RawTable<tables::PlainAccountState>>()?;
             let mut collector =
-                Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+                Collector::new(self.etl_config.file_size, self.etl_config.dir.clone())
+                    .with_compression(self.etl_config.compression)
+                    .with_disk_budget(self.etl_config.disk_budget.map(DiskBudget::new));
             let mut channels = Vec::with_capacity(MAXIMUM_CHANNELS);
 
             // channels used to return result of account hashing
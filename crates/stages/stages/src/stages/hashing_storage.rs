@@ -8,7 +8,7 @@ use reth_db_api::{
     tables,
     transaction::{DbTx, DbTxMut},
 };
-use reth_etl::Collector;
+use reth_etl::{Collector, DiskBudget};
 use reth_primitives_traits::StorageEntry;
 use reth_provider::{DBProvider, HashingWriter, StatsReader, StorageReader};
 use reth_stages_api::{
@@ -94,7 +94,9 @@ where
 
             let mut storage_cursor = tx.cursor_read::<tables::PlainStorageState>()?;
             let mut collector =
-                Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+                Collector::new(self.etl_config.file_size, self.etl_config.dir.clone())
+                    .with_compression(self.etl_config.compression)
+                    .with_disk_budget(self.etl_config.disk_budget.map(DiskBudget::new));
             let mut channels = Vec::with_capacity(MAXIMUM_CHANNELS);
 
             for chunk in &storage_cursor.walk(None)?.chunks(WORKER_CHUNK_SIZE) {
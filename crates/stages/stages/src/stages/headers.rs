@@ -9,7 +9,7 @@ use reth_db_api::{
     transaction::{DbTx, DbTxMut},
     DbTxUnwindExt, RawKey, RawTable, RawValue,
 };
-use reth_etl::Collector;
+use reth_etl::{Collector, DiskBudget};
 use reth_network_p2p::headers::{
     downloader::{HeaderDownloader, HeaderSyncGap, SyncTarget},
     error::HeadersDownloaderError,
@@ -75,13 +75,18 @@ where
         tip: watch::Receiver<B256>,
         etl_config: EtlConfig,
     ) -> Self {
+        let disk_budget = etl_config.disk_budget.map(DiskBudget::new);
         Self {
             provider: database,
             downloader,
             tip,
             sync_gap: None,
-            hash_collector: Collector::new(etl_config.file_size / 2, etl_config.dir.clone()),
-            header_collector: Collector::new(etl_config.file_size / 2, etl_config.dir),
+            hash_collector: Collector::new(etl_config.file_size / 2, etl_config.dir.clone())
+                .with_compression(etl_config.compression)
+                .with_disk_budget(disk_budget.clone()),
+            header_collector: Collector::new(etl_config.file_size / 2, etl_config.dir)
+                .with_compression(etl_config.compression)
+                .with_disk_budget(disk_budget),
             is_etl_ready: false,
         }
     }
@@ -5,7 +5,8 @@ use alloy_primitives::Address;
 use reth_config::config::{EtlConfig, IndexHistoryConfig};
 use reth_db_api::{models::ShardedKey, table::Decode, tables, transaction::DbTxMut};
 use reth_provider::{
-    DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter, StorageSettingsCache,
+    resolve_prune_mode, DBProvider, HeaderProvider, HistoryWriter, PruneCheckpointReader,
+    PruneCheckpointWriter, StorageSettingsCache,
 };
 use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
 use reth_stages_api::{
@@ -48,6 +49,7 @@ impl Default for IndexAccountHistoryStage {
 impl<Provider> Stage<Provider> for IndexAccountHistoryStage
 where
     Provider: DBProvider<Tx: DbTxMut>
+        + HeaderProvider
         + HistoryWriter
         + PruneCheckpointReader
         + PruneCheckpointWriter
@@ -68,6 +70,8 @@ where
     ) -> Result<ExecOutput, StageError> {
         if let Some((target_prunable_block, prune_mode)) = self
             .prune_mode
+            .map(|mode| resolve_prune_mode(provider, mode, input.target()))
+            .transpose()?
             .map(|mode| {
                 mode.prune_target_block(
                     input.target(),
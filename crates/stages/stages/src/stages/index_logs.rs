@@ -0,0 +1,264 @@
+use super::{collect_log_indices, load_history_indices};
+use alloy_primitives::{Address, B256};
+use reth_config::config::{EtlConfig, IndexHistoryConfig};
+use reth_db_api::{models::ShardedKey, table::Decode, tables, transaction::DbTxMut};
+use reth_primitives_traits::Receipt;
+use reth_provider::{
+    resolve_prune_mode, DBProvider, HeaderProvider, LogIndexWriter, PruneCheckpointReader,
+    ReceiptProvider, StorageSettingsCache,
+};
+use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::fmt::Debug;
+use tracing::info;
+
+/// Stage is indexing the log addresses and log topics of the receipts generated in
+/// [`ExecutionStage`][crate::stages::ExecutionStage]. For more information on index sharding take
+/// a look at [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`].
+///
+/// The stage only does work if the log index is enabled in the storage settings, otherwise its
+/// checkpoint is left untouched, so the index is built from there once it gets enabled.
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Receipts pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [`IndexLogsStage`].
+    pub const fn new(
+        config: IndexHistoryConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { commit_threshold: config.commit_threshold, prune_mode, etl_config }
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, prune_mode: None, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexLogsStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + HeaderProvider
+        + LogIndexWriter
+        + PruneCheckpointReader
+        + ReceiptProvider<Receipt: Receipt>
+        + StorageSettingsCache,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogs
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if !provider.cached_storage_settings().log_index {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        // Receipts below the prune target are removed by the pruner, there is nothing to index.
+        if let Some((target_prunable_block, _)) = self
+            .prune_mode
+            .map(|mode| resolve_prune_mode(provider, mode, input.target()))
+            .transpose()?
+            .map(|mode| {
+                mode.prune_target_block(input.target(), PruneSegment::Receipts, PrunePurpose::User)
+            })
+            .transpose()?
+            .flatten() &&
+            target_prunable_block > input.checkpoint().block_number
+        {
+            input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let mut range = input.next_block_range();
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync we might have logs coming from genesis. We clear the tables since it's
+        // faster to rebuild from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::LogAddressIndex>()?;
+            provider.tx_ref().clear::<tables::LogTopicIndex>()?;
+            range = 0..=*input.next_block_range().end();
+        }
+
+        info!(target: "sync::stages::index_logs::exec", ?first_sync, "Collecting indices");
+        let (address_collector, topic_collector) =
+            collect_log_indices(provider, range.clone(), &self.etl_config)?;
+
+        info!(target: "sync::stages::index_logs::exec", "Loading indices into database");
+        load_history_indices::<_, tables::LogAddressIndex, _>(
+            provider,
+            address_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+        load_history_indices::<_, tables::LogTopicIndex, _>(
+            provider,
+            topic_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<B256>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: true })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_log_index(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use alloy_primitives::{address, b256, BlockNumber, Bytes, Log};
+    use reth_db_api::{
+        models::{StorageSettings, StoredBlockBodyIndices},
+        BlockNumberList,
+    };
+    use reth_ethereum_primitives::Receipt;
+    use reth_provider::DatabaseProviderFactory;
+    use std::collections::BTreeMap;
+
+    const ADDRESS: Address = address!("0x0000000000000000000000000000000000000001");
+    const TOPIC: B256 = b256!("0x0000000000000000000000000000000000000000000000000000000000000001");
+
+    const MAX_BLOCK: BlockNumber = 10;
+
+    fn cast<K: Ord>(table: Vec<(ShardedKey<K>, BlockNumberList)>) -> BTreeMap<K, Vec<u64>> {
+        table.into_iter().map(|(k, v)| (k.key, v.iter().collect())).collect()
+    }
+
+    /// Sets up one transaction per block, with a log of [`ADDRESS`] and [`TOPIC`] in every even
+    /// block.
+    fn setup(db: &TestStageDB) {
+        db.factory.set_storage_settings_cache(StorageSettings::legacy().with_log_index(true));
+        db.commit(|tx| {
+            for block in 0..=MAX_BLOCK {
+                tx.put::<tables::BlockBodyIndices>(
+                    block,
+                    StoredBlockBodyIndices { first_tx_num: block, tx_count: 1 },
+                )?;
+                tx.put::<tables::TransactionBlocks>(block, block)?;
+
+                let logs = if block % 2 == 0 {
+                    vec![Log::new_unchecked(ADDRESS, vec![TOPIC], Bytes::new())]
+                } else {
+                    Vec::new()
+                };
+                tx.put::<tables::Receipts>(block, Receipt { logs, ..Default::default() })?;
+            }
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    fn run(db: &TestStageDB, run_to: u64, input_checkpoint: Option<BlockNumber>) -> ExecOutput {
+        let input = ExecInput {
+            target: Some(run_to),
+            checkpoint: input_checkpoint.map(StageCheckpoint::new),
+        };
+        let mut stage = IndexLogsStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = stage.execute(&provider, input).unwrap();
+        provider.commit().unwrap();
+        out
+    }
+
+    fn unwind(db: &TestStageDB, unwind_from: u64, unwind_to: u64) {
+        let input = UnwindInput {
+            checkpoint: StageCheckpoint::new(unwind_from),
+            unwind_to,
+            ..Default::default()
+        };
+        let mut stage = IndexLogsStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(unwind_to) });
+        provider.commit().unwrap();
+    }
+
+    #[tokio::test]
+    async fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        setup(&db);
+
+        // index the first half
+        let out = run(&db, 5, None);
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(5), done: true });
+        assert_eq!(
+            cast(db.table::<tables::LogAddressIndex>().unwrap()),
+            BTreeMap::from([(ADDRESS, vec![0, 2, 4])])
+        );
+        assert_eq!(
+            cast(db.table::<tables::LogTopicIndex>().unwrap()),
+            BTreeMap::from([(TOPIC, vec![0, 2, 4])])
+        );
+
+        // append the second half to the existing shards
+        let out = run(&db, MAX_BLOCK, Some(5));
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(MAX_BLOCK), done: true });
+        assert_eq!(
+            cast(db.table::<tables::LogAddressIndex>().unwrap()),
+            BTreeMap::from([(ADDRESS, vec![0, 2, 4, 6, 8, 10])])
+        );
+
+        // unwind
+        unwind(&db, MAX_BLOCK, 4);
+        assert_eq!(
+            cast(db.table::<tables::LogAddressIndex>().unwrap()),
+            BTreeMap::from([(ADDRESS, vec![0, 2, 4])])
+        );
+        assert_eq!(
+            cast(db.table::<tables::LogTopicIndex>().unwrap()),
+            BTreeMap::from([(TOPIC, vec![0, 2, 4])])
+        );
+    }
+
+    #[tokio::test]
+    async fn execute_disabled() {
+        let db = TestStageDB::default();
+        setup(&db);
+        db.factory.set_storage_settings_cache(StorageSettings::legacy());
+
+        let out = run(&db, MAX_BLOCK, None);
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(0), done: true });
+        assert!(db.table_is_empty::<tables::LogAddressIndex>().unwrap());
+        assert!(db.table_is_empty::<tables::LogTopicIndex>().unwrap());
+    }
+}
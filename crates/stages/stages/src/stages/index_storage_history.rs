@@ -7,7 +7,10 @@ use reth_db_api::{
     tables,
     transaction::DbTxMut,
 };
-use reth_provider::{DBProvider, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter};
+use reth_provider::{
+    resolve_prune_mode, DBProvider, HeaderProvider, HistoryWriter, PruneCheckpointReader,
+    PruneCheckpointWriter,
+};
 use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
 use reth_stages_api::{ExecInput, ExecOutput, Stage, StageError, UnwindInput, UnwindOutput};
 use std::fmt::Debug;
@@ -46,8 +49,11 @@ impl Default for IndexStorageHistoryStage {
 
 impl<Provider> Stage<Provider> for IndexStorageHistoryStage
 where
-    Provider:
-        DBProvider<Tx: DbTxMut> + PruneCheckpointWriter + HistoryWriter + PruneCheckpointReader,
+    Provider: DBProvider<Tx: DbTxMut>
+        + HeaderProvider
+        + PruneCheckpointWriter
+        + HistoryWriter
+        + PruneCheckpointReader,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -62,6 +68,8 @@ where
     ) -> Result<ExecOutput, StageError> {
         if let Some((target_prunable_block, prune_mode)) = self
             .prune_mode
+            .map(|mode| resolve_prune_mode(provider, mode, input.target()))
+            .transpose()?
             .map(|mode| {
                 mode.prune_target_block(
                     input.target(),
@@ -25,23 +25,35 @@ use tracing::{debug, error};
 /// The `MerkleChangeSets` stage.
 ///
 /// This stage processes and maintains trie changesets from the finalized block to the latest block.
+///
+/// In archive mode, the already computed changesets are never discarded, and new changesets are
+/// always computed as a continuation of them, so that the changesets cover every block since the
+/// archive mode was enabled.
 #[derive(Debug, Clone)]
 pub struct MerkleChangeSets {
     /// The number of blocks to retain changesets for, used as a fallback when the finalized block
     /// is not found. Defaults to [`MERKLE_CHANGESETS_RETENTION_BLOCKS`] (2 epochs in beacon
     /// chain).
     retention_blocks: u64,
+    /// Whether the changesets of all blocks are kept.
+    archive: bool,
 }
 
 impl MerkleChangeSets {
     /// Creates a new `MerkleChangeSets` stage with the default retention blocks.
     pub const fn new() -> Self {
-        Self { retention_blocks: MERKLE_CHANGESETS_RETENTION_BLOCKS }
+        Self { retention_blocks: MERKLE_CHANGESETS_RETENTION_BLOCKS, archive: false }
     }
 
     /// Creates a new `MerkleChangeSets` stage with a custom finalized block height.
     pub const fn with_retention_blocks(retention_blocks: u64) -> Self {
-        Self { retention_blocks }
+        Self { retention_blocks, archive: false }
+    }
+
+    /// Creates a new `MerkleChangeSets` stage in archive mode, which keeps the changesets of all
+    /// blocks.
+    pub const fn archive() -> Self {
+        Self { retention_blocks: MERKLE_CHANGESETS_RETENTION_BLOCKS, archive: true }
     }
 
     /// Returns the range of blocks which are already computed. Will return an empty range if none
@@ -354,8 +366,15 @@ where
         //      |-------target-------|
         //      |-------actual-------|
         //
+        //
+        // In archive mode, the computed range is always continued, even if that means filling a gap
+        // up to the target range, so that no previously computed data is discarded.
         if target_range.start >= computed_range.start {
-            target_range.start = target_range.start.max(computed_range.end);
+            target_range.start = if self.archive && !computed_range.is_empty() {
+                computed_range.end
+            } else {
+                target_range.start.max(computed_range.end)
+            };
         }
 
         // If target range is empty (target_start >= target_end), stage is already successfully
@@ -417,14 +436,16 @@ where
         //
         // We don't do this check if the target block is not greater than the retention threshold
         // (which happens near genesis), as in that case would could still have all possible
-        // changesets even if the total count doesn't meet the threshold.
+        // changesets even if the total count doesn't meet the threshold. In archive mode, the
+        // changesets can't be regenerated, so they're never cleared.
         debug!(
             target: "sync::stages::merkle_changesets",
             ?computed_range,
             retention_blocks=?self.retention_blocks,
             "Checking if computed range is over retention threshold",
         );
-        if input.unwind_to > self.retention_blocks &&
+        if !self.archive &&
+            input.unwind_to > self.retention_blocks &&
             computed_range.end - computed_range.start < self.retention_blocks
         {
             debug!(
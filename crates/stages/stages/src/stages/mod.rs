@@ -14,10 +14,14 @@ mod headers;
 mod index_account_history;
 /// Index history of storage changes
 mod index_storage_history;
+/// Index of log addresses and topics
+mod index_logs;
 /// Stage for computing state root.
 mod merkle;
 /// Stage for computing merkle changesets.
 mod merkle_changesets;
+/// Parallel prefetching and prewarming for the execution stage.
+mod parallel_execution;
 mod prune;
 /// The sender recovery stage.
 mod sender_recovery;
@@ -32,9 +36,11 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_logs::*;
 pub use index_storage_history::*;
 pub use merkle::*;
 pub use merkle_changesets::*;
+pub use parallel_execution::*;
 pub use prune::*;
 pub use sender_recovery::*;
 pub use tx_lookup::*;
@@ -0,0 +1,326 @@
+//! Parallel execution for the [`ExecutionStage`](super::ExecutionStage).
+//!
+//! Blocks are still executed in order by a single executor, but the work around the execution is
+//! moved off its thread:
+//!
+//! - A prefetch thread reads the upcoming blocks with their senders from the database.
+//! - Prewarm workers speculatively execute the transactions of the upcoming blocks, each one
+//!   independently against the state at the start of the batch, and record the state they read in
+//!   a cache shared with the executor.
+//! - The execution outcome is handed over in chunks, so that writing the state changes of a chunk
+//!   overlaps with executing the next one.
+//!
+//! The executor reads the state through a read-only transaction opened at the start of the batch
+//! instead of the stage's provider, and the prewarm cache only holds values read from that same
+//! state. The executor caches everything it has read or changed in the batch, so it only reads
+//! values from the database that are unchanged since the start of the batch, which makes the
+//! result identical to serial execution. This requires all state changes to be committed before
+//! the stage is executed, which is the case in the pipeline, as it commits after every stage.
+
+use super::execution::{ExecutedRange, RangeExecutor};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Address, BlockNumber, StorageKey, StorageValue, B256};
+use dashmap::DashMap;
+use reth_evm::{ConfigureEvm, Evm};
+use reth_primitives_traits::{Account, Bytecode, NodePrimitives, RecoveredBlock};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockReader, DBProvider, DatabaseProviderFactory,
+    ExecutionOutcome, LatestStateProvider, ProviderError, ProviderFactory, ProviderResult,
+    StateProviderBox, TransactionVariant,
+};
+use reth_revm::database::{EvmStateProvider, StateProviderDatabase};
+use reth_stages_api::StageError;
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+};
+use tracing::*;
+
+/// The number of blocks that are fetched and prewarmed ahead of the block being executed.
+const PREFETCH_BLOCKS: usize = 32;
+
+/// The default number of state changes after which the execution outcome is handed over to be
+/// written.
+const WRITE_CHUNK_CHANGES: usize = 250_000;
+
+/// Read-only access to the committed state of the database, used to prefetch blocks and state on
+/// other threads than the one the [`ExecutionStage`](super::ExecutionStage) is executed on.
+pub trait ExecutionPrefetchProvider<N: NodePrimitives>: Debug + Send + Sync + 'static {
+    /// Returns a state provider for the latest committed state.
+    fn latest_state(&self) -> ProviderResult<StateProviderBox>;
+
+    /// Returns the block with the given number with its senders, without transaction hashes.
+    fn block_with_senders(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<RecoveredBlock<N::Block>>>;
+}
+
+impl<N: ProviderNodeTypes> ExecutionPrefetchProvider<N::Primitives> for ProviderFactory<N> {
+    fn latest_state(&self) -> ProviderResult<StateProviderBox> {
+        // The transaction is kept open for the whole batch, which can exceed the maximum duration
+        // of read transactions.
+        let provider = self.database_provider_ro()?.disable_long_read_transaction_safety();
+        Ok(Box::new(LatestStateProvider::new(provider)))
+    }
+
+    fn block_with_senders(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<RecoveredBlock<<N::Primitives as NodePrimitives>::Block>>> {
+        self.recovered_block(number.into(), TransactionVariant::NoHash)
+    }
+}
+
+/// Parallel execution settings of the [`ExecutionStage`](super::ExecutionStage).
+#[derive(Debug, Clone)]
+pub(crate) struct ParallelExecution<N: NodePrimitives> {
+    /// Provider for the committed state.
+    pub(crate) provider: Arc<dyn ExecutionPrefetchProvider<N>>,
+    /// The number of prewarm workers.
+    pub(crate) prewarm_threads: usize,
+    /// The number of state changes after which the execution outcome is handed over to be
+    /// written.
+    pub(crate) chunk_changes: usize,
+}
+
+impl<N: NodePrimitives> ParallelExecution<N> {
+    /// Creates new parallel execution settings.
+    pub(crate) fn new(
+        provider: Arc<dyn ExecutionPrefetchProvider<N>>,
+        prewarm_threads: usize,
+    ) -> Self {
+        Self { provider, prewarm_threads, chunk_changes: WRITE_CHUNK_CHANGES }
+    }
+
+    /// Executes the blocks starting at `start_block` on a separate thread, while the blocks are
+    /// prefetched and prewarmed ahead of execution.
+    ///
+    /// `write` is called on the current thread for each executed chunk.
+    pub(crate) fn execute<E>(
+        &self,
+        evm_config: &E,
+        range_executor: &RangeExecutor<'_, N>,
+        start_block: BlockNumber,
+        max_block: BlockNumber,
+        mut write: impl FnMut(
+            ExecutionOutcome<N::Receipt>,
+            Vec<RecoveredBlock<N::Block>>,
+        ) -> Result<(), StageError>,
+    ) -> Result<ExecutedRange, StageError>
+    where
+        E: ConfigureEvm<Primitives = N>,
+    {
+        let cache = PrewarmCache::default();
+        // The block that is currently executed, prewarm workers skip the blocks before it
+        let executing = AtomicU64::new(start_block);
+        // Signals the prefetch thread and prewarm workers to stop
+        let finished = AtomicBool::new(false);
+
+        let db = StateProviderDatabase::new(PrewarmedStateProvider {
+            inner: self.provider.latest_state()?,
+            cache: &cache,
+            consume: true,
+        });
+        let executor = evm_config.batch_executor(db);
+
+        let (block_tx, block_rx) = mpsc::sync_channel(PREFETCH_BLOCKS);
+        let (chunk_tx, chunk_rx) = mpsc::channel();
+
+        thread::scope(|scope| {
+            let prewarm_txs = (0..self.prewarm_threads)
+                .map(|worker| {
+                    let (prewarm_tx, prewarm_rx) = mpsc::channel();
+                    let (cache, executing, finished) = (&cache, &executing, &finished);
+                    scope.spawn(move || {
+                        self.prewarm(evm_config, prewarm_rx, worker, cache, executing, finished)
+                    });
+                    prewarm_tx
+                })
+                .collect::<Vec<_>>();
+
+            let finished = &finished;
+            scope.spawn(move || {
+                for number in start_block..=max_block {
+                    if finished.load(Ordering::Relaxed) {
+                        break
+                    }
+
+                    let block = self
+                        .provider
+                        .block_with_senders(number)
+                        .and_then(|block| block.ok_or(ProviderError::HeaderNotFound(number.into())))
+                        .map(Arc::new);
+                    if let Ok(block) = &block {
+                        for prewarm_tx in &prewarm_txs {
+                            let _ = prewarm_tx.send(block.clone());
+                        }
+                    }
+
+                    // Stop if the block couldn't be fetched or the execution has finished
+                    let failed = block.is_err();
+                    if block_tx.send(block).is_err() || failed {
+                        break
+                    }
+                }
+            });
+
+            let executing = &executing;
+            let execution = scope.spawn(move || {
+                let result = range_executor.execute(
+                    executor,
+                    start_block,
+                    max_block,
+                    |number| {
+                        if finished.load(Ordering::Relaxed) {
+                            return Err(StageError::ChannelClosed)
+                        }
+                        executing.store(number, Ordering::Relaxed);
+                        block_rx.recv().map_err(|_| StageError::ChannelClosed)?.map_err(Into::into)
+                    },
+                    self.chunk_changes,
+                    |state, blocks| {
+                        chunk_tx.send((state, blocks)).map_err(|_| StageError::ChannelClosed)
+                    },
+                );
+
+                // Stop prefetching and prewarming the blocks that won't be executed
+                finished.store(true, Ordering::Relaxed);
+                result
+            });
+
+            // Write the executed chunks while the following blocks are executed
+            for (state, blocks) in chunk_rx {
+                if let Err(err) = write(state, blocks) {
+                    finished.store(true, Ordering::Relaxed);
+                    return Err(err)
+                }
+            }
+
+            execution.join().map_err(|_| StageError::Fatal("execution thread panicked".into()))?
+        })
+    }
+
+    /// Speculatively executes the transactions of the received blocks that are assigned to the
+    /// worker, until the execution has finished.
+    fn prewarm<E>(
+        &self,
+        evm_config: &E,
+        blocks: mpsc::Receiver<Arc<RecoveredBlock<N::Block>>>,
+        worker: usize,
+        cache: &PrewarmCache,
+        executing: &AtomicU64,
+        finished: &AtomicBool,
+    ) where
+        E: ConfigureEvm<Primitives = N>,
+    {
+        let inner = match self.provider.latest_state() {
+            Ok(inner) => inner,
+            Err(err) => {
+                debug!(target: "sync::stages::execution", %err, "Failed to open state provider for prewarming");
+                return
+            }
+        };
+        let mut db =
+            StateProviderDatabase::new(PrewarmedStateProvider { inner, cache, consume: false });
+
+        for block in blocks {
+            if finished.load(Ordering::Relaxed) {
+                break
+            }
+            // Skip the blocks the executor has already reached
+            if block.header().number() <= executing.load(Ordering::Relaxed) {
+                continue
+            }
+
+            let Ok(mut evm_env) = evm_config.evm_env(block.header()) else { continue };
+            // The transactions are executed independently of each other, so their nonces don't
+            // necessarily match the state.
+            evm_env.cfg_env.disable_nonce_check = true;
+            let mut evm = evm_config.evm_with_env(&mut db, evm_env);
+
+            for tx in block.transactions_recovered().skip(worker).step_by(self.prewarm_threads) {
+                if finished.load(Ordering::Relaxed) {
+                    break
+                }
+
+                // Failures are expected, as the transaction doesn't see the changes of the
+                // transactions and blocks before it.
+                let _ = evm.transact(evm_config.tx_env(tx));
+            }
+        }
+    }
+}
+
+/// State read by the prewarm workers, shared with the executor.
+///
+/// All values are read from the state at the start of the batch.
+#[derive(Debug, Default)]
+struct PrewarmCache {
+    accounts: DashMap<Address, Option<Account>>,
+    storage: DashMap<(Address, StorageKey), Option<StorageValue>>,
+    bytecodes: DashMap<B256, Option<Bytecode>>,
+}
+
+/// An [`EvmStateProvider`] that reads through the [`PrewarmCache`].
+struct PrewarmedStateProvider<'a, S> {
+    inner: S,
+    cache: &'a PrewarmCache,
+    /// Whether values are removed from the cache when read instead of inserted into it.
+    ///
+    /// The executor caches all state it has read itself and never reads the same value twice, so
+    /// it consumes the prewarmed values to keep the cache small.
+    consume: bool,
+}
+
+impl<S> PrewarmedStateProvider<'_, S> {
+    fn read<K: Eq + Hash, V: Clone>(
+        &self,
+        map: &DashMap<K, V>,
+        key: K,
+        read: impl FnOnce() -> ProviderResult<V>,
+    ) -> ProviderResult<V> {
+        if self.consume {
+            if let Some((_, value)) = map.remove(&key) {
+                return Ok(value)
+            }
+            return read()
+        }
+
+        if let Some(value) = map.get(&key) {
+            return Ok(value.clone())
+        }
+        let value = read()?;
+        map.insert(key, value.clone());
+        Ok(value)
+    }
+}
+
+impl<S: EvmStateProvider> EvmStateProvider for PrewarmedStateProvider<'_, S> {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        self.read(&self.cache.accounts, *address, || self.inner.basic_account(address))
+    }
+
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.inner.block_hash(number)
+    }
+
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        self.read(&self.cache.bytecodes, *code_hash, || self.inner.bytecode_by_hash(code_hash))
+    }
+
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        self.read(&self.cache.storage, (account, storage_key), || {
+            self.inner.storage(account, storage_key)
+        })
+    }
+}
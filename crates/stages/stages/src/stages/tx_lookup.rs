@@ -7,12 +7,12 @@ use reth_db_api::{
     tables,
     transaction::DbTxMut,
 };
-use reth_etl::Collector;
+use reth_etl::{Collector, DiskBudget};
 use reth_primitives_traits::{NodePrimitives, SignedTransaction};
 use reth_provider::{
-    BlockReader, DBProvider, EitherWriter, PruneCheckpointReader, PruneCheckpointWriter,
-    RocksDBProviderFactory, StaticFileProviderFactory, StatsReader, StorageSettingsCache,
-    TransactionsProvider, TransactionsProviderExt,
+    resolve_prune_mode, BlockReader, DBProvider, EitherWriter, PruneCheckpointReader,
+    PruneCheckpointWriter, RocksDBProviderFactory, StaticFileProviderFactory, StatsReader,
+    StorageSettingsCache, TransactionsProvider, TransactionsProviderExt,
 };
 use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
 use reth_stages_api::{
@@ -81,6 +81,8 @@ where
     ) -> Result<ExecOutput, StageError> {
         if let Some((target_prunable_block, prune_mode)) = self
             .prune_mode
+            .map(|mode| resolve_prune_mode(provider, mode, input.target()))
+            .transpose()?
             .map(|mode| {
                 mode.prune_target_block(
                     input.target(),
@@ -118,7 +120,9 @@ where
 
         // 500MB temporary files
         let mut hash_collector: Collector<TxHash, TxNumber> =
-            Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+            Collector::new(self.etl_config.file_size, self.etl_config.dir.clone())
+                .with_compression(self.etl_config.compression)
+                .with_disk_budget(self.etl_config.disk_budget.map(DiskBudget::new));
 
         info!(
             target: "sync::stages::transaction_lookup",
@@ -1,27 +1,35 @@
 //! Utils for `stages`.
-use alloy_primitives::{Address, BlockNumber, TxNumber};
+use alloy_primitives::{Address, BlockNumber, TxNumber, B256};
 use reth_config::config::EtlConfig;
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
     models::{sharded_key::NUM_OF_INDICES_IN_SHARD, AccountBeforeTx, ShardedKey},
-    table::{Decompress, Table},
+    table::{Decompress, Key, Table},
     transaction::{DbTx, DbTxMut},
     BlockNumberList, DatabaseError,
 };
-use reth_etl::Collector;
+use reth_etl::{Collector, DiskBudget};
+use reth_primitives_traits::Receipt;
 use reth_provider::{
     providers::StaticFileProvider, to_range, BlockReader, DBProvider, ProviderError,
-    StaticFileProviderFactory,
+    ReceiptProvider, StaticFileProviderFactory,
 };
 use reth_stages_api::StageError;
 use reth_static_file_types::StaticFileSegment;
-use reth_storage_api::ChangeSetReader;
-use std::{collections::HashMap, hash::Hash, ops::RangeBounds};
+use reth_storage_api::{for_each_log_key, ChangeSetReader};
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{RangeBounds, RangeInclusive},
+};
 use tracing::info;
 
 /// Number of blocks before pushing indices from cache to [`Collector`]
 const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
 
+/// Number of blocks to read receipts for at once when collecting log indices.
+const RECEIPTS_CHUNK_SIZE: u64 = 1_000;
+
 /// Collects all history (`H`) indices for a range of changesets (`CS`) and stores them in a
 /// [`Collector`].
 ///
@@ -54,7 +62,9 @@ where
 {
     let mut changeset_cursor = provider.tx_ref().cursor_read::<CS>()?;
 
-    let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone())
+        .with_compression(etl_config.compression)
+        .with_disk_budget(etl_config.disk_budget.map(DiskBudget::new));
     let mut cache: HashMap<P, Vec<u64>> = HashMap::default();
 
     let mut collect = |cache: &HashMap<P, Vec<u64>>| {
@@ -121,7 +131,9 @@ pub(crate) fn collect_account_history_indices<Provider>(
 where
     Provider: DBProvider + ChangeSetReader + StaticFileProviderFactory,
 {
-    let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone())
+        .with_compression(etl_config.compression)
+        .with_disk_budget(etl_config.disk_budget.map(DiskBudget::new));
     let mut cache: HashMap<Address, Vec<u64>> = HashMap::default();
 
     let mut insert_fn = |address: Address, indices: Vec<u64>| {
@@ -171,6 +183,92 @@ where
     Ok(collector)
 }
 
+/// Collects the log address and log topic indices for a range of blocks and stores them in a
+/// [`Collector`] each.
+///
+/// Works like [`collect_history_indices`], with the partial keys being the emitter addresses and
+/// the topics of the logs in the block receipts. Each block is recorded at most once per key.
+pub(crate) fn collect_log_indices<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+    etl_config: &EtlConfig,
+) -> Result<
+    (Collector<ShardedKey<Address>, BlockNumberList>, Collector<ShardedKey<B256>, BlockNumberList>),
+    StageError,
+>
+where
+    Provider: ReceiptProvider<Receipt: Receipt>,
+{
+    let disk_budget = etl_config.disk_budget.map(DiskBudget::new);
+    let mut address_collector = Collector::new(etl_config.file_size, etl_config.dir.clone())
+        .with_compression(etl_config.compression)
+        .with_disk_budget(disk_budget.clone());
+    let mut topic_collector = Collector::new(etl_config.file_size, etl_config.dir.clone())
+        .with_compression(etl_config.compression)
+        .with_disk_budget(disk_budget);
+    let mut address_cache: HashMap<Address, Vec<u64>> = HashMap::default();
+    let mut topic_cache: HashMap<B256, Vec<u64>> = HashMap::default();
+
+    fn insert<K: Eq + Hash>(cache: &mut HashMap<K, Vec<u64>>, key: K, block_number: BlockNumber) {
+        let indices = cache.entry(key).or_default();
+        if indices.last() != Some(&block_number) {
+            indices.push(block_number);
+        }
+    }
+
+    fn collect<K: Copy>(
+        cache: &mut HashMap<K, Vec<u64>>,
+        collector: &mut Collector<ShardedKey<K>, BlockNumberList>,
+    ) -> Result<(), StageError>
+    where
+        ShardedKey<K>: Key,
+    {
+        for (key, indices) in cache.drain() {
+            let last = indices.last().expect("qed");
+            collector.insert(
+                ShardedKey::new(key, *last),
+                BlockNumberList::new_pre_sorted(indices.into_iter()),
+            )?;
+        }
+        Ok(())
+    }
+
+    // observability
+    let total_blocks = range.end().saturating_sub(*range.start()) + 1;
+    let interval = (total_blocks / 1000).max(1);
+
+    let mut flush_counter = 0;
+    for chunk_start in range.clone().step_by(RECEIPTS_CHUNK_SIZE as usize) {
+        let chunk = chunk_start..=(chunk_start + RECEIPTS_CHUNK_SIZE - 1).min(*range.end());
+        let receipts = provider.receipts_by_block_range(chunk.clone())?;
+
+        for (block_number, receipts) in chunk.zip(receipts) {
+            for_each_log_key(
+                &receipts,
+                |address| insert(&mut address_cache, address, block_number),
+                |topic| insert(&mut topic_cache, topic, block_number),
+            );
+
+            let idx = block_number - range.start();
+            if idx > 0 && idx.is_multiple_of(interval) && total_blocks > 1000 {
+                info!(target: "sync::stages::index_history", progress = %format!("{:.4}%", (idx as f64 / total_blocks as f64) * 100.0), "Collecting indices");
+            }
+
+            // Make sure we only flush the cache every DEFAULT_CACHE_THRESHOLD blocks.
+            flush_counter += 1;
+            if flush_counter > DEFAULT_CACHE_THRESHOLD {
+                collect(&mut address_cache, &mut address_collector)?;
+                collect(&mut topic_cache, &mut topic_collector)?;
+                flush_counter = 0;
+            }
+        }
+    }
+    collect(&mut address_cache, &mut address_collector)?;
+    collect(&mut topic_cache, &mut topic_collector)?;
+
+    Ok((address_collector, topic_collector))
+}
+
 /// Given a [`Collector`] created by [`collect_history_indices`] it iterates all entries, loading
 /// the indices into the database in shards.
 ///
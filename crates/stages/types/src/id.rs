@@ -25,6 +25,7 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    IndexLogs,
     MerkleChangeSets,
     Prune,
     Finish,
@@ -40,7 +41,7 @@ static ENCODED_STAGE_IDS: OnceLock<HashMap<StageId, Vec<u8>>> = OnceLock::new();
 
 impl StageId {
     /// All supported Stages
-    pub const ALL: [Self; 16] = [
+    pub const ALL: [Self; 17] = [
         Self::Era,
         Self::Headers,
         Self::Bodies,
@@ -54,13 +55,14 @@ impl StageId {
         Self::TransactionLookup,
         Self::IndexStorageHistory,
         Self::IndexAccountHistory,
+        Self::IndexLogs,
         Self::MerkleChangeSets,
         Self::Prune,
         Self::Finish,
     ];
 
     /// Stages that require state.
-    pub const STATE_REQUIRED: [Self; 9] = [
+    pub const STATE_REQUIRED: [Self; 10] = [
         Self::Execution,
         Self::PruneSenderRecovery,
         Self::MerkleUnwind,
@@ -69,6 +71,7 @@ impl StageId {
         Self::MerkleExecute,
         Self::IndexStorageHistory,
         Self::IndexAccountHistory,
+        Self::IndexLogs,
         Self::Prune,
     ];
 
@@ -90,6 +93,7 @@ impl StageId {
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
             Self::IndexStorageHistory => "IndexStorageHistory",
+            Self::IndexLogs => "IndexLogs",
             Self::MerkleChangeSets => "MerkleChangeSets",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
@@ -155,6 +159,7 @@ mod tests {
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
+        assert_eq!(StageId::IndexLogs.to_string(), "IndexLogs");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
@@ -25,6 +25,9 @@ pub use checkpoints::{
 mod execution;
 pub use execution::*;
 
+mod report;
+pub use report::{StageRunReport, StorageReport};
+
 /// Direction and target block for pipeline operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PipelineTarget {
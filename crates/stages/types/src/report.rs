@@ -0,0 +1,108 @@
+use alloc::string::String;
+use core::time::Duration;
+
+/// Report of a single stage run, as written by `reth stage run --report`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StageRunReport {
+    /// The stage that was run.
+    pub stage: String,
+    /// The block the stage was run from.
+    pub from: u64,
+    /// The block the stage was run to.
+    pub to: u64,
+    /// Duration of the stage execution in seconds, excluding the unwind.
+    pub elapsed: f64,
+    /// Gas used by the executed blocks.
+    pub gas_used: u64,
+    /// Size of the storage before the stage was run.
+    pub storage_before: StorageReport,
+    /// Size of the storage after the stage was run.
+    pub storage_after: StorageReport,
+}
+
+impl StageRunReport {
+    /// Returns the duration of the stage execution.
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_secs_f64(self.elapsed)
+    }
+
+    /// Returns the number of blocks executed per second.
+    pub fn blocks_per_second(&self) -> f64 {
+        (self.to - self.from) as f64 / self.elapsed
+    }
+
+    /// Returns the gas processed per second.
+    pub fn gas_per_second(&self) -> f64 {
+        self.gas_used as f64 / self.elapsed
+    }
+
+    /// Returns the growth of the storage in bytes.
+    pub const fn bytes_written(&self) -> u64 {
+        self.storage_after.total().saturating_sub(self.storage_before.total())
+    }
+}
+
+/// Size of the node storage, with the page statistics of the database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageReport {
+    /// Size in bytes of the database pages used by the tables.
+    pub database: u64,
+    /// Size in bytes of the static files.
+    pub static_files: u64,
+    /// Size in bytes of the `RocksDB` database.
+    pub rocksdb: u64,
+    /// Number of leaf pages of the database tables.
+    pub leaf_pages: u64,
+    /// Number of branch pages of the database tables.
+    pub branch_pages: u64,
+    /// Number of overflow pages of the database tables.
+    pub overflow_pages: u64,
+    /// Number of free pages of the database.
+    pub freelist_pages: u64,
+}
+
+impl StorageReport {
+    /// Returns the total size of the storage in bytes.
+    pub const fn total(&self) -> u64 {
+        self.database + self.static_files + self.rocksdb
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stage_run_report() {
+        let report = StageRunReport {
+            stage: "execution".into(),
+            from: 0,
+            to: 100,
+            elapsed: 2.0,
+            gas_used: 3_000_000,
+            storage_before: StorageReport {
+                database: 100,
+                static_files: 50,
+                rocksdb: 10,
+                ..Default::default()
+            },
+            storage_after: StorageReport {
+                database: 300,
+                static_files: 70,
+                rocksdb: 10,
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(report.elapsed(), Duration::from_secs(2));
+        assert_eq!(report.blocks_per_second(), 50.0);
+        assert_eq!(report.gas_per_second(), 1_500_000.0);
+        assert_eq!(report.bytes_written(), 220);
+
+        // Pruning can shrink the storage
+        let report = StageRunReport { storage_after: StorageReport::default(), ..report };
+        assert_eq!(report.bytes_written(), 0);
+    }
+}
@@ -493,6 +493,28 @@ impl Compact for bool {
     }
 }
 
+impl Compact for core::time::Duration {
+    /// `Duration` is encoded with a fixed width: 8 bytes for the seconds and 4 bytes for the
+    /// subsecond nanoseconds.
+    #[inline]
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_u64(self.as_secs());
+        buf.put_u32(self.subsec_nanos());
+        12
+    }
+
+    /// `Duration` doesn't care about the len passed, since it's not actually compacted.
+    #[inline]
+    fn from_compact(mut buf: &[u8], _: usize) -> (Self, &[u8]) {
+        let secs = buf.get_u64();
+        let nanos = buf.get_u32();
+        (Self::new(secs, nanos), buf)
+    }
+}
+
 fn encode_varuint<B>(mut n: usize, buf: &mut B)
 where
     B: bytes::BufMut + AsMut<[u8]>,
@@ -589,6 +611,22 @@ mod tests {
         assert_eq!(bool::from_compact(&buf, 0), (false, buf.as_slice()));
     }
 
+    #[test]
+    fn compact_duration() {
+        let duration = core::time::Duration::new(30 * 24 * 60 * 60, 500);
+        let mut buf = vec![];
+        assert_eq!(duration.to_compact(&mut buf), 12);
+
+        // Add some noise data.
+        buf.push(1);
+
+        // Duration shouldn't care about the len passed, since it's not actually compacted.
+        assert_eq!(
+            core::time::Duration::from_compact(&buf, 1000),
+            (duration, vec![1u8].as_slice())
+        );
+    }
+
     #[test]
     fn compact_option() {
         let opt = Some(B256::ZERO);
@@ -31,6 +31,9 @@ pub struct StorageSettings {
     /// Whether this node should read and write account changesets from static files.
     #[serde(default)]
     pub account_changesets_in_static_files: bool,
+    /// Whether this node maintains the `LogAddressIndex` and `LogTopicIndex` tables.
+    #[serde(default)]
+    pub log_index: bool,
 }
 
 impl StorageSettings {
@@ -47,6 +50,7 @@ impl StorageSettings {
             transaction_hash_numbers_in_rocksdb: false,
             account_history_in_rocksdb: false,
             account_changesets_in_static_files: false,
+            log_index: false,
         }
     }
 
@@ -85,4 +89,10 @@ impl StorageSettings {
         self.account_changesets_in_static_files = value;
         self
     }
+
+    /// Sets the `log_index` flag to the provided value.
+    pub const fn with_log_index(mut self, value: bool) -> Self {
+        self.log_index = value;
+        self
+    }
 }
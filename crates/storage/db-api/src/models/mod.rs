@@ -160,11 +160,12 @@ impl Decode for StoredNibblesSubKey {
 }
 
 impl Encode for PruneSegment {
-    type Encoded = [u8; 1];
+    // Built-in segments are encoded as a single byte, custom segments additionally contain their ID
+    type Encoded = Vec<u8>;
 
     fn encode(self) -> Self::Encoded {
-        let mut buf = [0u8];
-        self.to_compact(&mut buf.as_mut());
+        let mut buf = Vec::with_capacity(2);
+        self.to_compact(&mut buf);
         buf
     }
 }
@@ -375,4 +376,25 @@ mod tests {
         validate_bitflag_backwards_compat!(StoredBlockWithdrawals, UnusedBits::Zero);
         validate_bitflag_backwards_compat!(StorageHashingCheckpoint, UnusedBits::NotZero);
     }
+
+    #[test]
+    fn test_prune_segment_key() {
+        use super::*;
+        use reth_prune_types::PruneSegment;
+
+        // Built-in segments keep their single byte encoding
+        for segment in PruneSegment::variants() {
+            let encoded = segment.encode();
+            assert_eq!(encoded.len(), 1);
+            assert_eq!(PruneSegment::decode(&encoded).unwrap(), segment);
+        }
+
+        // Custom segments are sorted after all built-in segments
+        let bodies = PruneSegment::Bodies.encode();
+        for id in [0, 1, u8::MAX] {
+            let encoded = PruneSegment::Custom(id).encode();
+            assert!(encoded > bodies);
+            assert_eq!(PruneSegment::decode(&encoded).unwrap(), PruneSegment::Custom(id));
+        }
+    }
 }
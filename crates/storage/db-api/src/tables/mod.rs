@@ -27,7 +27,7 @@ use crate::{
     table::{Decode, DupSort, Encode, Table, TableInfo},
 };
 use alloy_consensus::Header;
-use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256};
+use alloy_primitives::{Address, BlockHash, BlockNumber, Bytes, TxHash, TxNumber, B256};
 use reth_ethereum_primitives::{Receipt, TransactionSigned};
 use reth_primitives_traits::{Account, Bytecode, StorageEntry};
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
@@ -350,6 +350,14 @@ tables! {
         type Value = StoredBlockWithdrawals;
     }
 
+    /// Stores the RLP encoded EIP-7928 block access lists of executed blocks.
+    ///
+    /// Pruned together with the block bodies.
+    table BlockAccessLists {
+        type Key = BlockNumber;
+        type Value = Bytes;
+    }
+
     /// Canonical only Stores the transaction body for canonical transactions.
     table Transactions<T = TransactionSigned> {
         type Key = TxNumber;
@@ -442,6 +450,24 @@ tables! {
         type Value = BlockNumberList;
     }
 
+    /// Stores pointers to the blocks with logs emitted by an address.
+    ///
+    /// Sharded the same way as [`AccountsHistory`]. Only maintained if the log index is enabled in
+    /// the storage settings.
+    table LogAddressIndex {
+        type Key = ShardedKey<Address>;
+        type Value = BlockNumberList;
+    }
+
+    /// Stores pointers to the blocks with logs that contain a topic, at any position.
+    ///
+    /// Sharded the same way as [`AccountsHistory`]. Only maintained if the log index is enabled in
+    /// the storage settings.
+    table LogTopicIndex {
+        type Key = ShardedKey<B256>;
+        type Value = BlockNumberList;
+    }
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -532,6 +558,16 @@ tables! {
         type Value = PruneCheckpoint;
     }
 
+    /// Stores the lowest block from which the account and storage history of an address that is
+    /// retained by the pruner is available.
+    ///
+    /// An address is added once the pruner first retains it, so history that was pruned before is
+    /// not considered available.
+    table RetainedHistory {
+        type Key = Address;
+        type Value = BlockNumber;
+    }
+
     /// Stores the history of client versions that have accessed the database with write privileges by unix timestamp in seconds.
     table VersionHistory {
         type Key = u64;
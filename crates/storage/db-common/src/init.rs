@@ -7,7 +7,7 @@ use reth_chainspec::EthChainSpec;
 use reth_codecs::Compact;
 use reth_config::config::EtlConfig;
 use reth_db_api::{tables, transaction::DbTxMut, DatabaseError};
-use reth_etl::Collector;
+use reth_etl::{Collector, DiskBudget};
 use reth_execution_errors::StateRootError;
 use reth_primitives_traits::{
     Account, Bytecode, GotExpected, NodePrimitives, SealedHeader, StorageEntry,
@@ -558,7 +558,9 @@ fn parse_accounts(
     etl_config: EtlConfig,
 ) -> Result<Collector<Address, GenesisAccount>, eyre::Error> {
     let mut line = String::new();
-    let mut collector = Collector::new(etl_config.file_size, etl_config.dir);
+    let mut collector = Collector::new(etl_config.file_size, etl_config.dir)
+        .with_compression(etl_config.compression)
+        .with_disk_budget(etl_config.disk_budget.map(DiskBudget::new));
 
     loop {
         let n = reader.read_line(&mut line)?;
@@ -13,7 +13,9 @@ use crate::{
 };
 use alloy_consensus::transaction::TransactionMeta;
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag};
-use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256};
+use alloy_primitives::{
+    Address, BlockHash, BlockNumber, Bytes, StorageKey, TxHash, TxNumber, B256,
+};
 use alloy_rpc_types_engine::ForkchoiceState;
 use reth_chain_state::{
     BlockState, CanonicalInMemoryState, ForkChoiceNotifications, ForkChoiceSubscriptions,
@@ -27,7 +29,11 @@ use reth_primitives_traits::{Account, RecoveredBlock, SealedHeader, StorageEntry
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
-use reth_storage_api::{BlockBodyIndicesProvider, NodePrimitivesProvider, StorageChangeSetReader};
+use reth_storage_api::{
+    AccountHistoryEntry, BlockAccessListProvider, BlockBodyIndicesProvider, HistoryReader,
+    LogIndexBlocks, LogIndexProvider, NodePrimitivesProvider, StorageChangeSetReader,
+    StorageHistoryEntry,
+};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{updates::TrieUpdatesSorted, HashedPostState, KeccakKeyHasher};
 use revm_database::BundleState;
@@ -466,6 +472,44 @@ impl<N: ProviderNodeTypes> BlockBodyIndicesProvider for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> BlockAccessListProvider for BlockchainProvider<N> {
+    fn block_access_list(&self, number: BlockNumber) -> ProviderResult<Option<Bytes>> {
+        self.consistent_provider()?.block_access_list(number)
+    }
+}
+
+impl<N: ProviderNodeTypes> HistoryReader for BlockchainProvider<N> {
+    fn account_history(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<AccountHistoryEntry>> {
+        self.consistent_provider()?.account_history(address, range, limit)
+    }
+
+    fn storage_history(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<StorageHistoryEntry>> {
+        self.consistent_provider()?.storage_history(address, storage_key, range, limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> LogIndexProvider for BlockchainProvider<N> {
+    fn log_index_blocks(
+        &self,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<LogIndexBlocks>> {
+        self.consistent_provider()?.log_index_blocks(addresses, topics, range)
+    }
+}
+
 impl<N: ProviderNodeTypes> StageCheckpointReader for BlockchainProvider<N> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         self.consistent_provider()?.get_stage_checkpoint(id)
@@ -801,8 +845,8 @@ mod tests {
     use reth_storage_api::{
         BlockBodyIndicesProvider, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader,
         BlockReaderIdExt, BlockSource, ChangeSetReader, DBProvider, DatabaseProviderFactory,
-        HeaderProvider, ReceiptProvider, ReceiptProviderIdExt, StateProviderFactory, StateWriter,
-        TransactionVariant, TransactionsProvider,
+        HeaderProvider, HistoryReader, ReceiptProvider, ReceiptProviderIdExt, StateProviderFactory,
+        StateWriter, TransactionVariant, TransactionsProvider,
     };
     use reth_testing_utils::generators::{
         self, random_block, random_block_range, random_changeset_range, random_eoa_accounts,
@@ -1745,6 +1789,145 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_history_reader() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let (database_blocks, in_memory_blocks) =
+            random_blocks(&mut rng, TEST_BLOCKS_COUNT, 1, None, None, 0..1);
+
+        let first_database_block = database_blocks.first().map(|block| block.number).unwrap();
+        let first_in_memory_block = in_memory_blocks.first().map(|block| block.number).unwrap();
+
+        let accounts = random_eoa_accounts(&mut rng, 2);
+
+        let (database_changesets, database_state) = random_changeset_range(
+            &mut rng,
+            &database_blocks,
+            accounts.into_iter().map(|(address, account)| (address, (account, Vec::new()))),
+            0..0,
+            0..0,
+        );
+        let (in_memory_changesets, in_memory_state) = random_changeset_range(
+            &mut rng,
+            &in_memory_blocks,
+            database_state
+                .iter()
+                .map(|(address, (account, storage))| (*address, (*account, storage.clone()))),
+            0..0,
+            0..0,
+        );
+        let in_memory_changesets = in_memory_changesets.into_iter().next().unwrap();
+
+        // An account that changed in the in-memory block, and its account after that block
+        let address = in_memory_changesets.first().map(|(address, _, _)| *address).unwrap();
+        let latest_account = in_memory_state[&address].0;
+
+        // Accounts before each change, in ascending block order
+        let before = (first_database_block..)
+            .zip(database_changesets.iter())
+            .chain(std::iter::once((first_in_memory_block, &in_memory_changesets)))
+            .filter_map(|(block_number, changeset)| {
+                changeset
+                    .iter()
+                    .find(|(changed, _, _)| *changed == address)
+                    .map(|(_, account, _)| (block_number, *account))
+            })
+            .collect::<Vec<_>>();
+        let expected = before
+            .iter()
+            .enumerate()
+            .map(|(i, (block_number, account))| {
+                let after = before.get(i + 1).map_or(latest_account, |(_, after)| *after);
+                (*block_number, Some(*account), Some(after))
+            })
+            .collect::<Vec<_>>();
+
+        let factory = create_test_provider_factory();
+
+        let provider_rw = factory.provider_rw()?;
+        provider_rw.append_blocks_with_state(
+            database_blocks
+                .into_iter()
+                .map(|b| b.try_recover().expect("failed to seal block with senders"))
+                .collect(),
+            &ExecutionOutcome {
+                bundle: BundleState::new(
+                    database_state.into_iter().map(|(address, (account, _))| {
+                        (address, None, Some(account.into()), Default::default())
+                    }),
+                    database_changesets
+                        .iter()
+                        .map(|block_changesets| {
+                            block_changesets.iter().map(|(address, account, _)| {
+                                (*address, Some(Some((*account).into())), [])
+                            })
+                        })
+                        .collect::<Vec<_>>(),
+                    Vec::new(),
+                ),
+                first_block: first_database_block,
+                ..Default::default()
+            },
+            Default::default(),
+        )?;
+        provider_rw.commit()?;
+
+        let provider = BlockchainProvider::new(factory)?;
+
+        let chain = NewCanonicalChain::Commit {
+            new: vec![in_memory_blocks
+                .first()
+                .map(|block| {
+                    let senders = block.senders().expect("failed to recover senders");
+                    ExecutedBlock {
+                        recovered_block: Arc::new(RecoveredBlock::new_sealed(
+                            block.clone(),
+                            senders,
+                        )),
+                        execution_output: Arc::new(ExecutionOutcome {
+                            bundle: BundleState::new(
+                                in_memory_state.into_iter().map(|(address, (account, _))| {
+                                    (address, None, Some(account.into()), Default::default())
+                                }),
+                                [in_memory_changesets.iter().map(|(address, account, _)| {
+                                    (*address, Some(Some((*account).into())), Vec::new())
+                                })],
+                                [],
+                            ),
+                            first_block: first_in_memory_block,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }
+                })
+                .unwrap()],
+        };
+        provider.canonical_in_memory_state.update_chain(chain);
+
+        // Changes are returned across the database and in-memory blocks
+        assert_eq!(
+            provider.account_history(address, first_database_block..=first_in_memory_block, 100)?,
+            expected
+        );
+        // The limit is respected
+        assert_eq!(
+            provider.account_history(address, first_database_block..=first_in_memory_block, 1)?,
+            expected[..1]
+        );
+        // Only the in-memory block is in range
+        assert_eq!(
+            provider.account_history(
+                address,
+                first_in_memory_block..=first_in_memory_block,
+                100
+            )?,
+            expected[expected.len() - 1..]
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_state_provider_factory() -> eyre::Result<()> {
         let mut rng = generators::rng();
@@ -14,7 +14,7 @@ use alloy_eips::{
 };
 use alloy_primitives::{
     map::{hash_map, HashMap},
-    Address, BlockHash, BlockNumber, TxHash, TxNumber, B256,
+    Address, BlockHash, BlockNumber, Bytes, StorageKey, TxHash, TxNumber, B256,
 };
 use reth_chain_state::{BlockState, CanonicalInMemoryState, MemoryOverlayStateProviderRef};
 use reth_chainspec::ChainInfo;
@@ -26,8 +26,10 @@ use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, DatabaseProviderFactory, NodePrimitivesProvider, StateProvider,
-    StateProviderBox, StorageChangeSetReader, TryIntoHistoricalStateProvider,
+    AccountHistoryEntry, BlockAccessListProvider, BlockBodyIndicesProvider,
+    DatabaseProviderFactory, HistoryReader, LogIndexBlocks, LogIndexProvider,
+    NodePrimitivesProvider, StateProvider, StateProviderBox, StorageChangeSetReader,
+    StorageHistoryEntry, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::updates::TrieUpdatesSorted;
@@ -1185,6 +1187,137 @@ impl<N: ProviderNodeTypes> BlockBodyIndicesProvider for ConsistentProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> BlockAccessListProvider for ConsistentProvider<N> {
+    fn block_access_list(&self, number: BlockNumber) -> ProviderResult<Option<Bytes>> {
+        self.get_in_memory_or_storage_by_block(
+            number.into(),
+            |db_provider| db_provider.block_access_list(number),
+            |block_state| Ok(block_state.block_ref().block_access_list.clone()),
+        )
+    }
+}
+
+impl<N: ProviderNodeTypes> ConsistentProvider<N> {
+    /// Returns the persisted part of the range, if any, and the in-memory blocks within the range
+    /// in ascending order.
+    fn split_history_range(
+        &self,
+        range: &RangeInclusive<BlockNumber>,
+    ) -> (Option<RangeInclusive<BlockNumber>>, Vec<Arc<BlockState<N::Primitives>>>) {
+        let Some(head_block) = &self.head_block else { return (Some(range.clone()), Vec::new()) };
+
+        // the anchor is the end of the db range
+        let database_end = head_block.anchor().number.min(*range.end());
+        let database_range =
+            (*range.start() <= database_end).then(|| *range.start()..=database_end);
+
+        let mut in_memory = head_block
+            .chain()
+            .filter(|block_state| range.contains(&block_state.number()))
+            .collect::<Vec<_>>();
+        in_memory.reverse();
+
+        (database_range, in_memory)
+    }
+}
+
+impl<N: ProviderNodeTypes> HistoryReader for ConsistentProvider<N> {
+    fn account_history(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<AccountHistoryEntry>> {
+        let (database_range, in_memory) = self.split_history_range(&range);
+        let mut changes = match database_range {
+            Some(range) => self.storage_provider.account_history(address, range, limit)?,
+            None => Vec::new(),
+        };
+
+        for block_state in in_memory {
+            if changes.len() >= limit {
+                break
+            }
+
+            let bundle = &block_state.block_ref().execution_output.bundle;
+            let Some((_, before)) = bundle
+                .reverts
+                .clone()
+                .to_plain_state_reverts()
+                .accounts
+                .into_iter()
+                .flatten()
+                .find(|(changed, _)| *changed == address)
+            else {
+                continue
+            };
+            let after = bundle.account(&address).and_then(|account| account.info.clone());
+
+            changes.push((block_state.number(), before.map(Into::into), after.map(Into::into)));
+        }
+
+        Ok(changes)
+    }
+
+    fn storage_history(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<StorageHistoryEntry>> {
+        let (database_range, in_memory) = self.split_history_range(&range);
+        let mut changes = match database_range {
+            Some(range) => {
+                self.storage_provider.storage_history(address, storage_key, range, limit)?
+            }
+            None => Vec::new(),
+        };
+
+        for block_state in in_memory {
+            if changes.len() >= limit {
+                break
+            }
+
+            let bundle = &block_state.block_ref().execution_output.bundle;
+            let Some((_, before)) = bundle
+                .reverts
+                .clone()
+                .to_plain_state_reverts()
+                .storage
+                .into_iter()
+                .flatten()
+                .filter(|revert: &PlainStorageRevert| revert.address == address)
+                .flat_map(|revert| revert.storage_revert)
+                .find(|(key, _)| B256::from(*key) == storage_key)
+            else {
+                continue
+            };
+            let after = bundle
+                .account(&address)
+                .and_then(|account| account.storage_slot(storage_key.into()))
+                .unwrap_or_default();
+
+            changes.push((block_state.number(), before.to_previous_value(), after));
+        }
+
+        Ok(changes)
+    }
+}
+
+impl<N: ProviderNodeTypes> LogIndexProvider for ConsistentProvider<N> {
+    fn log_index_blocks(
+        &self,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<LogIndexBlocks>> {
+        // Only persisted blocks are indexed, in-memory blocks are always above the last indexed
+        // block.
+        self.storage_provider.log_index_blocks(addresses, topics, range)
+    }
+}
+
 impl<N: ProviderNodeTypes> StageCheckpointReader for ConsistentProvider<N> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         self.storage_provider.get_stage_checkpoint(id)
@@ -13,7 +13,9 @@ use crate::{
 };
 use alloy_consensus::transaction::TransactionMeta;
 use alloy_eips::BlockHashOrNumber;
-use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256};
+use alloy_primitives::{
+    Address, BlockHash, BlockNumber, Bytes, StorageKey, TxHash, TxNumber, B256,
+};
 use core::fmt;
 use parking_lot::RwLock;
 use reth_chainspec::ChainInfo;
@@ -28,8 +30,9 @@ use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, NodePrimitivesProvider, StorageSettings, StorageSettingsCache,
-    TryIntoHistoricalStateProvider,
+    AccountHistoryEntry, BlockAccessListProvider, BlockBodyIndicesProvider, HistoryReader,
+    LogIndexBlocks, LogIndexProvider, NodePrimitivesProvider, StorageHistoryEntry, StorageSettings,
+    StorageSettingsCache, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::HashedPostState;
@@ -564,6 +567,44 @@ impl<N: ProviderNodeTypes> BlockBodyIndicesProvider for ProviderFactory<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> BlockAccessListProvider for ProviderFactory<N> {
+    fn block_access_list(&self, number: BlockNumber) -> ProviderResult<Option<Bytes>> {
+        self.provider()?.block_access_list(number)
+    }
+}
+
+impl<N: ProviderNodeTypes> HistoryReader for ProviderFactory<N> {
+    fn account_history(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<AccountHistoryEntry>> {
+        self.provider()?.account_history(address, range, limit)
+    }
+
+    fn storage_history(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<StorageHistoryEntry>> {
+        self.provider()?.storage_history(address, storage_key, range, limit)
+    }
+}
+
+impl<N: ProviderNodeTypes> LogIndexProvider for ProviderFactory<N> {
+    fn log_index_blocks(
+        &self,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<LogIndexBlocks>> {
+        self.provider()?.log_index_blocks(addresses, topics, range)
+    }
+}
+
 impl<N: ProviderNodeTypes> StageCheckpointReader for ProviderFactory<N> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         self.provider()?.get_stage_checkpoint(id)
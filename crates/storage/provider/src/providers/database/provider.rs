@@ -30,7 +30,7 @@ use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::{
     keccak256,
     map::{hash_map, B256Map, HashMap, HashSet},
-    Address, BlockHash, BlockNumber, TxHash, TxNumber, B256,
+    Address, BlockHash, BlockNumber, Bytes, Log, StorageKey, TxHash, TxNumber, B256,
 };
 use itertools::Itertools;
 use parking_lot::RwLock;
@@ -60,8 +60,10 @@ use reth_prune_types::{
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BlockBodyReader, MetadataProvider, MetadataWriter,
-    NodePrimitivesProvider, StateProvider, StorageChangeSetReader, StorageSettingsCache,
+    for_each_log_key, resolve_prune_modes, AccountHistoryEntry, BlockAccessListProvider,
+    BlockBodyIndicesProvider, BlockBodyReader, HistoryReader, LogIndexBlocks, LogIndexProvider,
+    LogIndexWriter, MetadataProvider, MetadataWriter, NodePrimitivesProvider, StateProvider,
+    StorageChangeSetReader, StorageHistoryEntry, StorageSettingsCache,
     TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
@@ -83,7 +85,7 @@ use std::{
     cmp::Ordering,
     collections::{BTreeMap, BTreeSet},
     fmt::Debug,
-    ops::{Deref, DerefMut, Range, RangeBounds, RangeFrom, RangeInclusive},
+    ops::{Bound, Deref, DerefMut, Range, RangeBounds, RangeFrom, RangeInclusive},
     sync::Arc,
 };
 use tracing::{debug, trace};
@@ -242,10 +244,36 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
                 prune_checkpoint_block_number + 1,
             );
         }
+        if let Some(block_number) = self.lowest_available_trie_changesets_block_number()? {
+            state_provider =
+                state_provider.with_lowest_available_trie_changesets_block_number(block_number);
+        }
 
         Ok(Box::new(state_provider))
     }
 
+    /// Returns the lowest block number at which the trie changesets are available, or [`None`] if
+    /// they aren't computed up to the database tip.
+    ///
+    /// The trie changesets of a block contain the trie nodes before the block was applied, so they
+    /// allow to revert the trie to the state at the start of any block since the lowest one.
+    fn lowest_available_trie_changesets_block_number(&self) -> ProviderResult<Option<BlockNumber>> {
+        let changesets_tip = self
+            .get_stage_checkpoint(StageId::MerkleChangeSets)?
+            .map(|checkpoint| checkpoint.block_number);
+        if changesets_tip != Some(self.best_block_number()?) {
+            return Ok(None)
+        }
+
+        // The prune checkpoint is the highest pruned block, so the changesets are available
+        // starting from the next block. If there is no checkpoint, nothing was pruned.
+        Ok(Some(
+            self.get_prune_checkpoint(PruneSegment::MerkleChangeSets)?
+                .and_then(|checkpoint| checkpoint.block_number)
+                .map_or(0, |block_number| block_number + 1),
+        ))
+    }
+
     #[cfg(feature = "test-utils")]
     /// Sets the prune modes for provider.
     pub fn set_prune_modes(&mut self, prune_modes: PruneModes) {
@@ -354,7 +382,7 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         // Insert the blocks
         for block in blocks {
             let trie_data = block.trie_data();
-            let ExecutedBlock { recovered_block, execution_output, .. } = block;
+            let ExecutedBlock { recovered_block, execution_output, block_access_list, .. } = block;
             let block_number = recovered_block.number();
             self.insert_block(&recovered_block)?;
 
@@ -362,11 +390,17 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
             // Must be written after blocks because of the receipt lookup.
             self.write_state(&execution_output, OriginalValuesKnown::No)?;
 
+            self.index_block_logs(&execution_output)?;
+
             // insert hashes and intermediate merkle nodes
             self.write_hashed_state(&trie_data.hashed_state)?;
 
             self.write_trie_changesets(block_number, &trie_data.trie_updates, None)?;
             self.write_trie_updates_sorted(&trie_data.trie_updates)?;
+
+            if let Some(block_access_list) = block_access_list {
+                self.tx.put::<tables::BlockAccessLists>(block_number, block_access_list)?;
+            }
         }
 
         // update history indices
@@ -380,6 +414,48 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         Ok(())
     }
 
+    /// Inserts the logs of the given execution outcome into the log index and advances the
+    /// [`StageId::IndexLogs`] checkpoint.
+    ///
+    /// Does nothing if the log index is disabled or doesn't cover all blocks below the outcome. In
+    /// the latter case, the index is caught up by the pipeline.
+    fn index_block_logs<R: TxReceipt<Log = Log>>(
+        &self,
+        execution_outcome: &ExecutionOutcome<R>,
+    ) -> ProviderResult<()> {
+        if !self.cached_storage_settings().log_index || execution_outcome.is_empty() {
+            return Ok(())
+        }
+
+        let range = execution_outcome.first_block()..=execution_outcome.last_block();
+        let last_indexed_block =
+            self.get_stage_checkpoint(StageId::IndexLogs)?.unwrap_or_default().block_number;
+        if last_indexed_block + 1 != *range.start() {
+            return Ok(())
+        }
+
+        let mut addresses = BTreeMap::new();
+        let mut topics = BTreeMap::new();
+        collect_log_keys(
+            range.clone().zip(execution_outcome.receipts()),
+            &mut addresses,
+            &mut topics,
+        );
+        self.insert_log_index(addresses, topics)?;
+
+        self.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(*range.end()))
+    }
+
+    /// Unwinds the log index above the given block, if it covers any of these blocks.
+    fn unwind_log_index_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        let last_indexed_block =
+            self.get_stage_checkpoint(StageId::IndexLogs)?.unwrap_or_default().block_number;
+        if last_indexed_block > block {
+            self.unwind_log_index(block + 1..=last_indexed_block)?;
+        }
+        Ok(())
+    }
+
     /// Unwinds trie state starting at and including the given block.
     ///
     /// This includes calculating the resulted state root and comparing it with the parent block
@@ -483,11 +559,40 @@ impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for Databa
                 prune_checkpoint_block_number + 1,
             );
         }
+        if let Some(block_number) = self.lowest_available_trie_changesets_block_number()? {
+            state_provider =
+                state_provider.with_lowest_available_trie_changesets_block_number(block_number);
+        }
 
         Ok(Box::new(state_provider))
     }
 }
 
+/// Collects the emitter addresses and topics of the logs in the given block receipts, together
+/// with the blocks they occur in.
+///
+/// Blocks must be yielded in ascending order. Each block is recorded at most once per key.
+fn collect_log_keys<'a, R: TxReceipt<Log = Log> + 'a>(
+    block_receipts: impl IntoIterator<Item = (BlockNumber, &'a Vec<R>)>,
+    addresses: &mut BTreeMap<Address, Vec<BlockNumber>>,
+    topics: &mut BTreeMap<B256, Vec<BlockNumber>>,
+) {
+    fn insert<K: Ord>(index: &mut BTreeMap<K, Vec<BlockNumber>>, key: K, block: BlockNumber) {
+        let blocks = index.entry(key).or_default();
+        if blocks.last() != Some(&block) {
+            blocks.push(block);
+        }
+    }
+
+    for (block_number, receipts) in block_receipts {
+        for_each_log_key(
+            receipts,
+            |address| insert(addresses, address, block_number),
+            |topic| insert(topics, topic, block_number),
+        );
+    }
+}
+
 /// For a given key, unwind all history shards that contain block numbers at or above the given
 /// block number.
 ///
@@ -1590,6 +1695,235 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> BlockBodyIndicesProvider
     }
 }
 
+impl<TX: DbTx, N: NodeTypes> BlockAccessListProvider for DatabaseProvider<TX, N> {
+    fn block_access_list(&self, number: BlockNumber) -> ProviderResult<Option<Bytes>> {
+        Ok(self.tx.get::<tables::BlockAccessLists>(number)?)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Returns an error if the history of the address at the given block was pruned.
+    ///
+    /// The history of an address retained by the pruner is available from the block at which it
+    /// got retained, regardless of the prune checkpoint.
+    fn ensure_history_available(
+        &self,
+        segment: PruneSegment,
+        address: &Address,
+        block_number: BlockNumber,
+    ) -> ProviderResult<()> {
+        if self
+            .tx
+            .get::<tables::RetainedHistory>(*address)?
+            .is_some_and(|retained_from| block_number >= retained_from)
+        {
+            return Ok(())
+        }
+
+        if let Some(pruned) =
+            self.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number) &&
+            block_number <= pruned
+        {
+            return Err(ProviderError::StateAtBlockPruned(block_number))
+        }
+        Ok(())
+    }
+
+    /// Walks the history index shards of a key from the start of the range and returns the blocks
+    /// the key changed in.
+    ///
+    /// The walk stops after the first block beyond the range or the limit, which is included so
+    /// that the value after the last change in the range can be looked up in its changeset.
+    fn history_blocks<T, K>(
+        &self,
+        key: K,
+        key_filter: impl Fn(&K) -> bool,
+        range: &RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<BlockNumber>>
+    where
+        T: Table<Key = K, Value = BlockNumberList>,
+    {
+        let mut blocks = Vec::new();
+        for entry in self.tx.cursor_read::<T>()?.walk(Some(key))? {
+            let (key, shard) = entry?;
+            if !key_filter(&key) {
+                break
+            }
+
+            for block_number in shard.iter().skip_while(|block_number| block_number < range.start())
+            {
+                blocks.push(block_number);
+                if block_number > *range.end() || blocks.len() > limit {
+                    return Ok(blocks)
+                }
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// Returns the blocks in the range at which any of the keys is indexed in the given log index
+    /// table, as the union of the shards that overlap the range.
+    fn log_index_lookup<T, K>(
+        &self,
+        keys: &[K],
+        range: &RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BlockNumberList>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: Copy + PartialEq,
+    {
+        let mut blocks = BlockNumberList::empty();
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        for &key in keys {
+            for entry in cursor.walk(Some(ShardedKey::new(key, *range.start())))? {
+                let (sharded_key, shard) = entry?;
+                if sharded_key.key != key {
+                    break
+                }
+
+                blocks.0 |= shard.0;
+                if sharded_key.highest_block_number >= *range.end() {
+                    break
+                }
+            }
+        }
+
+        blocks.0.remove_range(..*range.start());
+        blocks.0.remove_range((Bound::Excluded(*range.end()), Bound::Unbounded));
+        Ok(blocks)
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> HistoryReader for DatabaseProvider<TX, N> {
+    fn account_history(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<AccountHistoryEntry>> {
+        if self.cached_storage_settings().account_history_in_rocksdb {
+            return Err(ProviderError::UnsupportedProvider)
+        }
+        self.ensure_history_available(PruneSegment::AccountHistory, &address, *range.start())?;
+
+        let blocks = self.history_blocks::<tables::AccountsHistory, _>(
+            ShardedKey::new(address, *range.start()),
+            |key| key.key == address,
+            &range,
+            limit,
+        )?;
+
+        let mut before = blocks
+            .iter()
+            .map(|&block_number| {
+                self.get_account_before_block(block_number, address)?
+                    .ok_or(ProviderError::AccountChangesetNotFound { block_number, address })
+                    .map(|account_before| account_before.info)
+            })
+            .collect::<ProviderResult<Vec<_>>>()?;
+        // The account after the last change is the current one
+        before.push(self.basic_account(&address)?);
+
+        Ok(blocks
+            .into_iter()
+            .zip(before.windows(2))
+            .take_while(|(block_number, _)| range.contains(block_number))
+            .take(limit)
+            .map(|(block_number, accounts)| (block_number, accounts[0], accounts[1]))
+            .collect())
+    }
+
+    fn storage_history(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<StorageHistoryEntry>> {
+        if self.cached_storage_settings().storages_history_in_rocksdb {
+            return Err(ProviderError::UnsupportedProvider)
+        }
+        self.ensure_history_available(PruneSegment::StorageHistory, &address, *range.start())?;
+
+        let blocks = self.history_blocks::<tables::StoragesHistory, _>(
+            StorageShardedKey::new(address, storage_key, *range.start()),
+            |key| key.address == address && key.sharded_key.key == storage_key,
+            &range,
+            limit,
+        )?;
+
+        let mut changesets = self.tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+        let mut before = blocks
+            .iter()
+            .map(|&block_number| {
+                changesets
+                    .seek_by_key_subkey((block_number, address).into(), storage_key)?
+                    .filter(|entry| entry.key == storage_key)
+                    .ok_or_else(|| ProviderError::StorageChangesetNotFound {
+                        block_number,
+                        address,
+                        storage_key: Box::new(storage_key),
+                    })
+                    .map(|entry| entry.value)
+            })
+            .collect::<ProviderResult<Vec<_>>>()?;
+        // The value after the last change is the current one
+        before.push(
+            self.tx
+                .cursor_dup_read::<tables::PlainStorageState>()?
+                .seek_by_key_subkey(address, storage_key)?
+                .filter(|entry| entry.key == storage_key)
+                .map(|entry| entry.value)
+                .unwrap_or_default(),
+        );
+
+        Ok(blocks
+            .into_iter()
+            .zip(before.windows(2))
+            .take_while(|(block_number, _)| range.contains(block_number))
+            .take(limit)
+            .map(|(block_number, values)| (block_number, values[0], values[1]))
+            .collect())
+    }
+}
+
+impl<TX: DbTx + 'static, N: NodeTypes> LogIndexProvider for DatabaseProvider<TX, N> {
+    fn log_index_blocks(
+        &self,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<LogIndexBlocks>> {
+        if !self.cached_storage_settings().log_index ||
+            (addresses.is_empty() && topics.iter().all(Vec::is_empty))
+        {
+            return Ok(None)
+        }
+
+        let last_indexed_block =
+            self.get_stage_checkpoint(StageId::IndexLogs)?.unwrap_or_default().block_number;
+        let range = *range.start()..=(*range.end()).min(last_indexed_block);
+
+        // Each non-empty set matches the union of its keys' blocks, and all sets have to match.
+        let mut blocks = (!addresses.is_empty())
+            .then(|| self.log_index_lookup::<tables::LogAddressIndex, _>(addresses, &range))
+            .transpose()?;
+        for topic in topics.iter().filter(|topic| !topic.is_empty()) {
+            let topic_blocks = self.log_index_lookup::<tables::LogTopicIndex, _>(topic, &range)?;
+            match &mut blocks {
+                Some(blocks) => blocks.0 &= topic_blocks.0,
+                None => blocks = Some(topic_blocks),
+            }
+        }
+
+        Ok(Some(LogIndexBlocks {
+            blocks: blocks.map(|blocks| blocks.iter().collect()).unwrap_or_default(),
+            last_indexed_block,
+        }))
+    }
+}
+
 impl<TX: DbTx, N: NodeTypes> StageCheckpointReader for DatabaseProvider<TX, N> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         Ok(if let Some(encoded) = id.get_pre_encoded() {
@@ -1641,6 +1975,18 @@ impl<TX: DbTxMut, N: NodeTypes> StageCheckpointWriter for DatabaseProvider<TX, N
         let mut cursor = self.tx.cursor_write::<tables::StageCheckpoints>()?;
         for stage_id in StageId::ALL {
             let (_, checkpoint) = cursor.seek_exact(stage_id.to_string())?.unwrap_or_default();
+
+            // The log index is optional and only advanced when the logs are actually indexed, so
+            // it can only be moved backwards here.
+            let block_number = if stage_id == StageId::IndexLogs {
+                if !drop_stage_checkpoint {
+                    continue
+                }
+                block_number.min(checkpoint.block_number)
+            } else {
+                block_number
+            };
+
             cursor.upsert(
                 stage_id.to_string(),
                 &StageCheckpoint {
@@ -1757,8 +2103,10 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
 
         let mut receipts_writer = EitherWriter::new_receipts(self, first_block)?;
 
-        let has_contract_log_filter = !self.prune_modes.receipts_log_filter.is_empty();
-        let contract_log_pruner = self.prune_modes.receipts_log_filter.group_by_block(tip, None)?;
+        // Prune modes by age are resolved against the headers, which are written before the state
+        let prune_modes = resolve_prune_modes(self, &self.prune_modes, tip)?;
+        let has_contract_log_filter = !prune_modes.receipts_log_filter.is_empty();
+        let contract_log_pruner = prune_modes.receipts_log_filter.group_by_block(tip, None)?;
 
         // All receipts from the last 128 blocks are required for blockchain tree, even with
         // [`PruneSegment::ContractLogs`].
@@ -1789,9 +2137,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
 
             // Skip writing receipts if pruning configuration requires us to.
             if prunable_receipts &&
-                self.prune_modes
-                    .receipts
-                    .is_some_and(|mode| mode.should_prune(block_number, tip))
+                prune_modes.receipts.is_some_and(|mode| mode.should_prune(block_number, tip))
             {
                 continue
             }
@@ -2892,6 +3238,72 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
     }
 }
 
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Unwinds the shards of the given keys in a log index table, starting at and including the
+    /// given block.
+    fn unwind_log_index_shards<T, K>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        block_number: BlockNumber,
+    ) -> ProviderResult<()>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: Copy + PartialEq,
+    {
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        for key in keys {
+            let partial_shard = unwind_history_shards::<_, T, _>(
+                &mut cursor,
+                ShardedKey::last(key),
+                block_number,
+                |sharded_key| sharded_key.key == key,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(key),
+                    &BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> LogIndexWriter
+    for DatabaseProvider<TX, N>
+{
+    fn insert_log_index(
+        &self,
+        addresses: impl IntoIterator<Item = (Address, impl IntoIterator<Item = u64>)>,
+        topics: impl IntoIterator<Item = (B256, impl IntoIterator<Item = u64>)>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::LogAddressIndex>(addresses, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicIndex>(topics, ShardedKey::new)
+    }
+
+    fn unwind_log_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize> {
+        let receipts = self.receipts_by_block_range(range.clone())?;
+
+        let mut addresses = BTreeMap::new();
+        let mut topics = BTreeMap::new();
+        collect_log_keys(range.clone().zip(&receipts), &mut addresses, &mut topics);
+
+        self.unwind_log_index_shards::<tables::LogAddressIndex, _>(
+            addresses.into_keys(),
+            *range.start(),
+        )?;
+        self.unwind_log_index_shards::<tables::LogTopicIndex, _>(
+            topics.into_keys(),
+            *range.start(),
+        )?;
+
+        Ok(receipts.iter().map(Vec::len).sum())
+    }
+}
+
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecutionWriter
     for DatabaseProvider<TX, N>
 {
@@ -2901,6 +3313,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
     ) -> ProviderResult<Chain<Self::Primitives>> {
         let range = block + 1..=self.last_block_number()?;
 
+        // the log index is read from the receipts, so it has to be unwound before the state
+        self.unwind_log_index_above(block)?;
+
         self.unwind_trie_state_from(block + 1)?;
 
         // get execution res
@@ -2919,6 +3334,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockExecu
     }
 
     fn remove_block_and_execution_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        // the log index is read from the receipts, so it has to be unwound before the state
+        self.unwind_log_index_above(block)?;
+
         self.unwind_trie_state_from(block + 1)?;
 
         // remove execution res
@@ -3130,6 +3548,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
             .ok_or(ProviderError::BlockBodyIndicesNotFound(block))?;
 
         self.remove::<tables::BlockBodyIndices>(block + 1..)?;
+        self.remove::<tables::BlockAccessLists>(block + 1..)?;
         self.remove::<tables::TransactionBlocks>(unwind_tx_from..)?;
 
         let static_file_tx_num =
@@ -3177,6 +3596,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
         self.write_state(execution_outcome, OriginalValuesKnown::No)?;
         durations_recorder.record_relative(metrics::Action::InsertState);
 
+        self.index_block_logs(execution_outcome)?;
+
         // insert hashes and intermediate merkle nodes
         self.write_hashed_state(&hashed_state)?;
         durations_recorder.record_relative(metrics::Action::InsertHashes);
@@ -3203,14 +3624,25 @@ impl<TX: DbTx + 'static, N: NodeTypes> PruneCheckpointReader for DatabaseProvide
     }
 
     fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
-        Ok(PruneSegment::variants()
+        let mut checkpoints = PruneSegment::variants()
             .filter_map(|segment| {
                 self.tx
                     .get::<tables::PruneCheckpoints>(segment)
                     .transpose()
                     .map(|chk| chk.map(|chk| (segment, chk)))
             })
-            .collect::<Result<_, _>>()?)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Custom segments are sorted after all built-in segments
+        let mut cursor = self.tx.cursor_read::<tables::PruneCheckpoints>()?;
+        for entry in cursor.walk(Some(PruneSegment::Custom(0)))? {
+            let (segment, checkpoint) = entry?;
+            if segment.is_custom() {
+                checkpoints.push((segment, checkpoint));
+            }
+        }
+
+        Ok(checkpoints)
     }
 }
 
@@ -4975,4 +5407,65 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_index_block_logs() {
+        let factory = create_test_provider_factory();
+        factory.set_storage_settings_cache(StorageSettings::legacy().with_log_index(true));
+
+        let address = Address::with_last_byte(1);
+        let topic = B256::with_last_byte(2);
+        let receipt = |topics| Receipt {
+            logs: vec![Log::new_unchecked(address, topics, Default::default())],
+            ..Default::default()
+        };
+        let outcome = |first_block, receipts| ExecutionOutcome {
+            first_block,
+            receipts,
+            ..Default::default()
+        };
+
+        // Blocks 1 and 2 are indexed, the log of block 2 has no topics
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .index_block_logs(&outcome(1, vec![vec![receipt(vec![topic])], vec![receipt(vec![])]]))
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::IndexLogs).unwrap(),
+            Some(StageCheckpoint::new(2))
+        );
+        assert_eq!(
+            provider.log_index_blocks(&[address], &[], 0..=10).unwrap(),
+            Some(LogIndexBlocks { blocks: vec![1, 2], last_indexed_block: 2 })
+        );
+        assert_eq!(
+            provider.log_index_blocks(&[address], &[vec![topic]], 0..=10).unwrap(),
+            Some(LogIndexBlocks { blocks: vec![1], last_indexed_block: 2 })
+        );
+        assert_eq!(
+            provider.log_index_blocks(&[address], &[], 2..=10).unwrap(),
+            Some(LogIndexBlocks { blocks: vec![2], last_indexed_block: 2 })
+        );
+        assert_eq!(provider.log_index_blocks(&[], &[vec![]], 0..=10).unwrap(), None);
+        drop(provider);
+
+        // Block 4 doesn't follow the last indexed block, so it's skipped without an error and left
+        // to the pipeline
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.index_block_logs(&outcome(4, vec![vec![receipt(vec![topic])]])).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::IndexLogs).unwrap(),
+            Some(StageCheckpoint::new(2))
+        );
+        assert_eq!(
+            provider.log_index_blocks(&[address], &[vec![topic]], 0..=10).unwrap(),
+            Some(LogIndexBlocks { blocks: vec![1], last_indexed_block: 2 })
+        );
+    }
 }
@@ -14,7 +14,7 @@ use reth_db_api::{
 };
 use reth_primitives_traits::{Account, Bytecode};
 use reth_storage_api::{
-    BlockNumReader, BytecodeReader, DBProvider, StateProofProvider, StorageRootProvider,
+    BlockNumReader, BytecodeReader, DBProvider, StateProofProvider, StorageRootProvider, TrieReader,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
@@ -127,18 +127,36 @@ impl<'b, Provider: DBProvider + ChangeSetReader + BlockNumReader>
         Self { provider, block_number, lowest_available_blocks }
     }
 
+    /// Returns the lowest block from which the history of the address is available, if it is
+    /// retained by the pruner and the provider block is not below it.
+    ///
+    /// Retained history is available below the prune checkpoints, but only from the block at which
+    /// the pruner started retaining the address.
+    fn retained_history_from(&self, address: Address) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self
+            .tx()
+            .get::<tables::RetainedHistory>(address)?
+            .filter(|retained_from| self.block_number >= *retained_from))
+    }
+
     /// Lookup an account in the `AccountsHistory` table
     pub fn account_history_lookup(&self, address: Address) -> ProviderResult<HistoryInfo> {
-        if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
+        let lowest_available_block_number = if let Some(retained_from) =
+            self.retained_history_from(address)?
+        {
+            Some(retained_from)
+        } else if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
             return Err(ProviderError::StateAtBlockPruned(self.block_number))
-        }
+        } else {
+            self.lowest_available_blocks.account_history_block_number
+        };
 
         // history key to search IntegerList of block number changesets.
         let history_key = ShardedKey::new(address, self.block_number);
         self.history_info::<tables::AccountsHistory, _>(
             history_key,
             |key| key.key == address,
-            self.lowest_available_blocks.account_history_block_number,
+            lowest_available_block_number,
         )
     }
 
@@ -148,16 +166,22 @@ impl<'b, Provider: DBProvider + ChangeSetReader + BlockNumReader>
         address: Address,
         storage_key: StorageKey,
     ) -> ProviderResult<HistoryInfo> {
-        if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
+        let lowest_available_block_number = if let Some(retained_from) =
+            self.retained_history_from(address)?
+        {
+            Some(retained_from)
+        } else if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
             return Err(ProviderError::StateAtBlockPruned(self.block_number))
-        }
+        } else {
+            self.lowest_available_blocks.storage_history_block_number
+        };
 
         // history key to search IntegerList of block number changesets.
         let history_key = StorageShardedKey::new(address, storage_key, self.block_number);
         self.history_info::<tables::StoragesHistory, _>(
             history_key,
             |key| key.address == address && key.sharded_key.key == storage_key,
-            self.lowest_available_blocks.storage_history_block_number,
+            lowest_available_block_number,
         )
     }
 
@@ -189,7 +213,9 @@ impl<'b, Provider: DBProvider + ChangeSetReader + BlockNumReader>
 
     /// Retrieve revert hashed storage for this history provider and target address.
     fn revert_storage(&self, address: Address) -> ProviderResult<HashedStorage> {
-        if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
+        if self.retained_history_from(address)?.is_none() &&
+            !self.lowest_available_blocks.is_storage_history_available(self.block_number)
+        {
             return Err(ProviderError::StateAtBlockPruned(self.block_number))
         }
 
@@ -272,6 +298,15 @@ impl<'b, Provider: DBProvider + ChangeSetReader + BlockNumReader>
         self.lowest_available_blocks.storage_history_block_number = Some(block_number);
         self
     }
+
+    /// Set the lowest block number at which the trie changesets are available.
+    pub const fn with_lowest_available_trie_changesets_block_number(
+        mut self,
+        block_number: BlockNumber,
+    ) -> Self {
+        self.lowest_available_blocks.trie_changesets_block_number = Some(block_number);
+        self
+    }
 }
 
 impl<Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'_, Provider> {
@@ -280,6 +315,27 @@ impl<Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'_, Provi
     }
 }
 
+impl<Provider: DBProvider + ChangeSetReader + BlockNumReader + TrieReader>
+    HistoricalStateProviderRef<'_, Provider>
+{
+    /// Prepends the reverts from the database state to the state at this historical block to the
+    /// trie input.
+    ///
+    /// If the trie changesets are available, the trie nodes are reverted together with the hashed
+    /// state, so the trie doesn't have to be recomputed for the reverted keys. Otherwise, the
+    /// reverted keys are added to the prefix sets.
+    fn prepend_reverts(&self, input: &mut TrieInput) -> ProviderResult<()> {
+        let revert_state = self.revert_state()?;
+        if self.lowest_available_blocks.is_trie_changesets_available(self.block_number) {
+            let revert_trie = self.provider.trie_reverts(self.block_number)?;
+            input.prepend_cached(revert_trie.into(), revert_state.into());
+        } else {
+            input.prepend(revert_state.into());
+        }
+        Ok(())
+    }
+}
+
 impl<Provider: DBProvider + BlockNumReader + ChangeSetReader> AccountReader
     for HistoricalStateProviderRef<'_, Provider>
 {
@@ -397,7 +453,7 @@ impl<Provider: DBProvider + ChangeSetReader + BlockNumReader> StorageRootProvide
     }
 }
 
-impl<Provider: DBProvider + ChangeSetReader + BlockNumReader> StateProofProvider
+impl<Provider: DBProvider + ChangeSetReader + BlockNumReader + TrieReader> StateProofProvider
     for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get account and storage proofs.
@@ -407,7 +463,7 @@ impl<Provider: DBProvider + ChangeSetReader + BlockNumReader> StateProofProvider
         address: Address,
         slots: &[B256],
     ) -> ProviderResult<AccountProof> {
-        input.prepend(self.revert_state()?.into());
+        self.prepend_reverts(&mut input)?;
         let proof = <Proof<_, _> as DatabaseProof>::from_tx(self.tx());
         proof.overlay_account_proof(input, address, slots).map_err(ProviderError::from)
     }
@@ -417,13 +473,13 @@ impl<Provider: DBProvider + ChangeSetReader + BlockNumReader> StateProofProvider
         mut input: TrieInput,
         targets: MultiProofTargets,
     ) -> ProviderResult<MultiProof> {
-        input.prepend(self.revert_state()?.into());
+        self.prepend_reverts(&mut input)?;
         let proof = <Proof<_, _> as DatabaseProof>::from_tx(self.tx());
         proof.overlay_multiproof(input, targets).map_err(ProviderError::from)
     }
 
     fn witness(&self, mut input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
-        input.prepend(self.revert_state()?.into());
+        self.prepend_reverts(&mut input)?;
         TrieWitness::overlay_witness(self.tx(), input, target)
             .map_err(ProviderError::from)
             .map(|hm| hm.into_values().collect())
@@ -436,8 +492,8 @@ impl<Provider> HashedPostStateProvider for HistoricalStateProviderRef<'_, Provid
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + BlockHashReader + ChangeSetReader> StateProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<Provider: DBProvider + BlockNumReader + BlockHashReader + ChangeSetReader + TrieReader>
+    StateProvider for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get storage.
     fn storage(
@@ -515,6 +571,15 @@ impl<Provider: DBProvider + ChangeSetReader + BlockNumReader> HistoricalStatePro
         self
     }
 
+    /// Set the lowest block number at which the trie changesets are available.
+    pub const fn with_lowest_available_trie_changesets_block_number(
+        mut self,
+        block_number: BlockNumber,
+    ) -> Self {
+        self.lowest_available_blocks.trie_changesets_block_number = Some(block_number);
+        self
+    }
+
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     const fn as_ref(&self) -> HistoricalStateProviderRef<'_, Provider> {
@@ -527,7 +592,7 @@ impl<Provider: DBProvider + ChangeSetReader + BlockNumReader> HistoricalStatePro
 }
 
 // Delegates all provider impls to [HistoricalStateProviderRef]
-reth_storage_api::macros::delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader + ChangeSetReader]);
+reth_storage_api::macros::delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader + ChangeSetReader + TrieReader]);
 
 /// Lowest blocks at which different parts of the state are available.
 /// They may be [Some] if pruning is enabled.
@@ -541,6 +606,12 @@ pub struct LowestAvailableBlocks {
     /// [`reth_prune_types::PruneSegment::StorageHistory`] was pruned.
     /// [`Option::None`] means all history is available.
     pub storage_history_block_number: Option<BlockNumber>,
+    /// Lowest block number at which the trie changesets are available, i.e. the trie can be
+    /// reverted to the state at this block without recomputing it. They may not be available if
+    /// [`reth_prune_types::PruneSegment::MerkleChangeSets`] was pruned, or if they haven't been
+    /// computed up to the database tip yet.
+    /// [`Option::None`] means the trie changesets are not available.
+    pub trie_changesets_block_number: Option<BlockNumber>,
 }
 
 impl LowestAvailableBlocks {
@@ -555,6 +626,13 @@ impl LowestAvailableBlocks {
     pub fn is_storage_history_available(&self, at: BlockNumber) -> bool {
         self.storage_history_block_number.map(|block_number| block_number <= at).unwrap_or(true)
     }
+
+    /// Check if the trie changesets are available at the provided block number, i.e. lowest
+    /// available block number for the trie changesets is less than or equal to the provided block
+    /// number.
+    pub fn is_trie_changesets_available(&self, at: BlockNumber) -> bool {
+        self.trie_changesets_block_number.is_some_and(|block_number| block_number <= at)
+    }
 }
 
 /// Checks if a previous shard lookup is needed to determine if we're before the first write.
@@ -820,6 +898,7 @@ mod tests {
             LowestAvailableBlocks {
                 account_history_block_number: Some(3),
                 storage_history_block_number: Some(3),
+                ..Default::default()
             },
         );
         assert!(matches!(
@@ -839,6 +918,7 @@ mod tests {
             LowestAvailableBlocks {
                 account_history_block_number: Some(2),
                 storage_history_block_number: Some(2),
+                ..Default::default()
             },
         );
         assert!(matches!(
@@ -858,6 +938,7 @@ mod tests {
             LowestAvailableBlocks {
                 account_history_block_number: Some(1),
                 storage_history_block_number: Some(1),
+                ..Default::default()
             },
         );
         assert!(matches!(
@@ -870,6 +951,68 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn trie_changesets_availability() {
+        let lowest_available_blocks = LowestAvailableBlocks::default();
+        assert!(lowest_available_blocks.is_account_history_available(0));
+        assert!(!lowest_available_blocks.is_trie_changesets_available(0));
+
+        let lowest_available_blocks =
+            LowestAvailableBlocks { trie_changesets_block_number: Some(5), ..Default::default() };
+        assert!(!lowest_available_blocks.is_trie_changesets_available(4));
+        assert!(lowest_available_blocks.is_trie_changesets_available(5));
+        assert!(lowest_available_blocks.is_trie_changesets_available(6));
+    }
+
+    #[test]
+    fn history_provider_retained_address() {
+        let factory = create_test_provider_factory();
+        let db = factory.database_provider_rw().unwrap();
+        // the address got retained once the history up to block 1 was already pruned
+        db.tx_ref().put::<tables::RetainedHistory>(ADDRESS, 2).unwrap();
+
+        let lowest_available_blocks = LowestAvailableBlocks {
+            account_history_block_number: Some(3),
+            storage_history_block_number: Some(3),
+            ..Default::default()
+        };
+
+        // state at provider block is pruned, except for the retained address
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+            &db,
+            2,
+            lowest_available_blocks,
+        );
+        assert!(matches!(provider.account_history_lookup(ADDRESS), Ok(HistoryInfo::NotYetWritten)));
+        assert!(matches!(
+            provider.storage_history_lookup(ADDRESS, STORAGE),
+            Ok(HistoryInfo::NotYetWritten)
+        ));
+        assert!(matches!(
+            provider.account_history_lookup(HIGHER_ADDRESS),
+            Err(ProviderError::StateAtBlockPruned(number)) if number == provider.block_number
+        ));
+        assert!(matches!(
+            provider.storage_history_lookup(HIGHER_ADDRESS, STORAGE),
+            Err(ProviderError::StateAtBlockPruned(number)) if number == provider.block_number
+        ));
+
+        // history of the retained address below the block it got retained at is pruned
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+            &db,
+            1,
+            lowest_available_blocks,
+        );
+        assert!(matches!(
+            provider.account_history_lookup(ADDRESS),
+            Err(ProviderError::StateAtBlockPruned(number)) if number == provider.block_number
+        ));
+        assert!(matches!(
+            provider.storage_history_lookup(ADDRESS, STORAGE),
+            Err(ProviderError::StateAtBlockPruned(number)) if number == provider.block_number
+        ));
+    }
+
     #[test]
     fn test_history_info_from_lookup() {
         // Before first write, no pruning → not yet written
@@ -175,8 +175,9 @@ where
         // Extract the lower bound from prune checkpoint if available.
         //
         // If not available we assume pruning has never ran and so there is no lower bound. This
-        // should not generally happen, since MerkleChangeSets always have pruning enabled, but when
-        // starting a new node from scratch (e.g. in a test case or benchmark) it can surface.
+        // should not generally happen, since the MerkleChangeSets stage saves the checkpoint even
+        // in archive mode, but when starting a new node from scratch (e.g. in a test case or
+        // benchmark) it can surface.
         //
         // The prune checkpoint's block_number is the highest pruned block, so data is available
         // starting from the next block
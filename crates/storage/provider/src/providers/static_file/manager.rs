@@ -710,6 +710,29 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         }
     }
 
+    /// Returns a [`StaticFileProviderRW`] that backfills the static file of the segment containing
+    /// `block`, which must be below the lowest static file of the segment.
+    ///
+    /// This is used to restore static files deleted by pruning. See
+    /// [`StaticFileProviderRW::new_backfill`] for more.
+    pub fn backfill_writer(
+        &self,
+        block: BlockNumber,
+        segment: StaticFileSegment,
+    ) -> ProviderResult<StaticFileProviderRW<N>> {
+        if self.access.is_read_only() {
+            return Err(ProviderError::ReadOnlyStaticFileAccess)
+        }
+
+        trace!(target: "provider::static_file", ?block, ?segment, "Getting backfill writer.");
+        StaticFileProviderRW::new_backfill(
+            segment,
+            block,
+            Arc::downgrade(&self.0),
+            self.metrics.clone(),
+        )
+    }
+
     /// Given a segment and block, it deletes the jar and all files from the respective block range.
     ///
     /// CAUTION: destructive. Deletes files on disk.
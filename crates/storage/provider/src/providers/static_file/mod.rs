@@ -584,6 +584,67 @@ mod tests {
         Ok(count)
     }
 
+    #[test]
+    fn test_backfill_writer() {
+        let (static_dir, _) = create_test_static_files_dir();
+        let blocks_per_file = 10;
+        let segment = StaticFileSegment::Transactions;
+
+        let sf_rw: StaticFileProvider<EthPrimitives> =
+            StaticFileProviderBuilder::read_write(&static_dir)
+                .with_blocks_per_file(blocks_per_file)
+                .build()
+                .expect("Failed to build static file provider");
+
+        // One transaction per block, with the transaction number as the nonce
+        let transaction = |tx_num: TxNumber| -> TransactionSigned {
+            TxLegacy { nonce: tx_num, ..Default::default() }
+                .into_signed(Signature::test_signature())
+                .into()
+        };
+
+        let mut writer = sf_rw.latest_writer(segment).unwrap();
+        for block in 0..blocks_per_file * 3 {
+            writer.increment_block(block).unwrap();
+            writer.append_transaction(block, &transaction(block)).unwrap();
+        }
+        writer.commit().unwrap();
+        drop(writer);
+
+        // Delete the first two static files, as pruning does
+        sf_rw.delete_segment_below_block(segment, blocks_per_file * 2).unwrap();
+        assert_eq!(sf_rw.get_lowest_range_start(segment), Some(blocks_per_file * 2));
+
+        // Existing static files can't be backfilled
+        assert!(matches!(
+            sf_rw.backfill_writer(blocks_per_file * 2, segment),
+            Err(ProviderError::FinalizedStaticFile(_, _))
+        ));
+
+        let mut writer = sf_rw.backfill_writer(blocks_per_file, segment).unwrap();
+        for block in blocks_per_file..blocks_per_file * 2 {
+            writer.increment_block(block).unwrap();
+            writer.append_transaction(block, &transaction(block)).unwrap();
+        }
+        // The backfilled static file can't be extended into the next one
+        assert!(matches!(
+            writer.increment_block(blocks_per_file * 2),
+            Err(ProviderError::FinalizedStaticFile(_, _))
+        ));
+        writer.commit().unwrap();
+
+        // The index is only updated once it's initialized again
+        assert_eq!(sf_rw.get_lowest_range_start(segment), Some(blocks_per_file * 2));
+        sf_rw.initialize_index().unwrap();
+        assert_eq!(sf_rw.get_lowest_range_start(segment), Some(blocks_per_file));
+        assert_eq!(sf_rw.get_highest_static_file_block(segment), Some(blocks_per_file * 3 - 1));
+
+        for tx_num in blocks_per_file..blocks_per_file * 3 {
+            let tx = sf_rw.transaction_by_id(tx_num).unwrap().unwrap();
+            assert_eq!(tx.nonce(), tx_num);
+        }
+    }
+
     #[test]
     fn test_dynamic_size() -> eyre::Result<()> {
         let (static_dir, _) = create_test_static_files_dir();
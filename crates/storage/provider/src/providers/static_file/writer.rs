@@ -182,6 +182,8 @@ pub struct StaticFileProviderRW<N> {
     metrics: Option<Arc<StaticFileProviderMetrics>>,
     /// On commit, contains the pruning strategy to apply for the segment.
     prune_on_commit: Option<PruneStrategy>,
+    /// Whether the writer backfills a static file below the lowest one of the segment.
+    backfill: bool,
 }
 
 impl<N: NodePrimitives> StaticFileProviderRW<N> {
@@ -203,6 +205,7 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
             reader,
             metrics,
             prune_on_commit: None,
+            backfill: false,
         };
 
         writer.ensure_end_range_consistency()?;
@@ -210,6 +213,44 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         Ok(writer)
     }
 
+    /// Creates a new [`StaticFileProviderRW`] that backfills the static file of a
+    /// [`StaticFileSegment`] containing `block`, below the lowest static file of the segment.
+    ///
+    /// The static file must not exist, which is the case for static files deleted by pruning. The
+    /// writer can't write past the end of the static file, and doesn't update the reader index, so
+    /// [`StaticFileProvider::initialize_index`] must be called once the static file is committed.
+    pub fn new_backfill(
+        segment: StaticFileSegment,
+        block: BlockNumber,
+        reader: Weak<StaticFileProviderInner<N>>,
+        metrics: Option<Arc<StaticFileProviderMetrics>>,
+    ) -> ProviderResult<Self> {
+        let static_file_provider = Self::upgrade_provider_to_strong_reference(&reader);
+
+        let block_range = static_file_provider.find_fixed_range(segment, block);
+        let data_path = static_file_provider.directory().join(segment.filename(&block_range));
+        if data_path.exists() ||
+            static_file_provider
+                .get_lowest_range_start(segment)
+                .is_none_or(|lowest_block| block_range.end() >= lowest_block)
+        {
+            return Err(ProviderError::FinalizedStaticFile(segment, block))
+        }
+
+        let writer = NippyJarWriter::new(create_jar(segment, &data_path, block_range))
+            .map_err(ProviderError::other)?;
+
+        Ok(Self {
+            writer,
+            data_path,
+            buf: Vec::with_capacity(100),
+            reader,
+            metrics,
+            prune_on_commit: None,
+            backfill: true,
+        })
+    }
+
     fn open(
         segment: StaticFileSegment,
         block: u64,
@@ -352,7 +393,9 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
                 "Committed writer to disk"
             );
 
-            self.update_index()?;
+            if !self.backfill {
+                self.update_index()?;
+            }
         }
 
         Ok(())
@@ -462,6 +505,11 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         if let Some(last_block) = self.writer.user_header().block_end() {
             // We have finished the previous static file and must freeze it
             if last_block == self.writer.user_header().expected_block_end() {
+                // The static file after a backfilled one already exists
+                if self.backfill {
+                    return Err(ProviderError::FinalizedStaticFile(segment, expected_block_number))
+                }
+
                 // Commits offsets and new user_header to disk
                 self.commit()?;
 
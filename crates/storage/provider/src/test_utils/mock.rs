@@ -32,9 +32,10 @@ use reth_primitives_traits::{
 use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BytecodeReader, DBProvider, DatabaseProviderFactory,
-    HashedPostStateProvider, NodePrimitivesProvider, StageCheckpointReader, StateProofProvider,
-    StorageRootProvider, TrieReader,
+    AccountHistoryEntry, BlockAccessListProvider, BlockBodyIndicesProvider, BytecodeReader,
+    DBProvider, DatabaseProviderFactory, HashedPostStateProvider, HistoryReader, LogIndexBlocks,
+    LogIndexProvider, NodePrimitivesProvider, StageCheckpointReader, StateProofProvider,
+    StorageHistoryEntry, StorageRootProvider, TrieReader,
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
 use reth_trie::{
@@ -727,6 +728,46 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> AccountReader for MockEthProvide
     }
 }
 
+impl<T: NodePrimitives, ChainSpec: Send + Sync> BlockAccessListProvider
+    for MockEthProvider<T, ChainSpec>
+{
+    fn block_access_list(&self, _number: BlockNumber) -> ProviderResult<Option<Bytes>> {
+        Ok(None)
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> HistoryReader for MockEthProvider<T, ChainSpec> {
+    fn account_history(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<AccountHistoryEntry>> {
+        Ok(Vec::new())
+    }
+
+    fn storage_history(
+        &self,
+        _address: Address,
+        _storage_key: StorageKey,
+        _range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<StorageHistoryEntry>> {
+        Ok(Vec::new())
+    }
+}
+
+impl<T: NodePrimitives, ChainSpec: Send + Sync> LogIndexProvider for MockEthProvider<T, ChainSpec> {
+    fn log_index_blocks(
+        &self,
+        _addresses: &[Address],
+        _topics: &[Vec<B256>],
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<LogIndexBlocks>> {
+        Ok(None)
+    }
+}
+
 impl<T: NodePrimitives, ChainSpec: Send + Sync> StageCheckpointReader
     for MockEthProvider<T, ChainSpec>
 {
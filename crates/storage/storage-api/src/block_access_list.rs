@@ -0,0 +1,11 @@
+use alloy_primitives::{BlockNumber, Bytes};
+use reth_storage_errors::provider::ProviderResult;
+
+/// Client trait for fetching the EIP-7928 block access lists of executed blocks.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait BlockAccessListProvider: Send {
+    /// Returns the RLP encoded block access list of the block with the given number.
+    ///
+    /// Returns `None` if no block access list was recorded for the block.
+    fn block_access_list(&self, number: BlockNumber) -> ProviderResult<Option<Bytes>>;
+}
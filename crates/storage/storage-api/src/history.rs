@@ -56,3 +56,22 @@ pub trait HistoryWriter: Send {
     /// Read account/storage changesets and update account/storage history indices.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
+
+/// Log index writer.
+#[auto_impl(&, Box)]
+pub trait LogIndexWriter: Send {
+    /// Insert log address and log topic indices to database.
+    fn insert_log_index(
+        &self,
+        addresses: impl IntoIterator<Item = (Address, impl IntoIterator<Item = u64>)>,
+        topics: impl IntoIterator<Item = (B256, impl IntoIterator<Item = u64>)>,
+    ) -> ProviderResult<()>;
+
+    /// Unwind and clear log address and log topic indices in a given block range.
+    ///
+    /// The indexed keys are read from the receipts of the range, so this needs to be called
+    /// before the receipts are removed.
+    ///
+    /// Returns number of receipts walked.
+    fn unwind_log_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize>;
+}
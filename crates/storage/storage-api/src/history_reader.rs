@@ -0,0 +1,37 @@
+use alloc::vec::Vec;
+use alloy_primitives::{Address, BlockNumber, StorageKey, StorageValue};
+use auto_impl::auto_impl;
+use core::ops::RangeInclusive;
+use reth_primitives_traits::Account;
+use reth_storage_errors::provider::ProviderResult;
+
+/// A change of an account in a block, as the account before and after the block.
+pub type AccountHistoryEntry = (BlockNumber, Option<Account>, Option<Account>);
+
+/// A change of a storage slot in a block, as the value before and after the block.
+pub type StorageHistoryEntry = (BlockNumber, StorageValue, StorageValue);
+
+/// History Reader
+#[auto_impl(&, Arc, Box)]
+pub trait HistoryReader: Send {
+    /// Returns the changes of the account in the given block range, in ascending block order.
+    ///
+    /// The blocks are looked up in the account history index. Returns at most `limit` changes.
+    fn account_history(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<AccountHistoryEntry>>;
+
+    /// Returns the changes of the storage slot in the given block range, in ascending block order.
+    ///
+    /// The blocks are looked up in the storage history index. Returns at most `limit` changes.
+    fn storage_history(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<StorageHistoryEntry>>;
+}
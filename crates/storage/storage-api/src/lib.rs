@@ -82,6 +82,15 @@ pub use stats::*;
 mod primitives;
 pub use primitives::*;
 
+mod block_access_list;
+pub use block_access_list::*;
+
+mod history_reader;
+pub use history_reader::*;
+
+mod log_index;
+pub use log_index::*;
+
 mod block_indices;
 pub use block_indices::*;
 
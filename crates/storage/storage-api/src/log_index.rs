@@ -0,0 +1,60 @@
+use alloc::vec::Vec;
+use alloy_consensus::TxReceipt;
+use alloy_primitives::{Address, BlockNumber, Log, B256};
+use auto_impl::auto_impl;
+use core::ops::RangeInclusive;
+use reth_storage_errors::provider::ProviderResult;
+
+/// Candidate blocks returned by a [`LogIndexProvider`] lookup.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogIndexBlocks {
+    /// Blocks in the requested range that may contain matching logs, in ascending order.
+    ///
+    /// Only blocks up to and including `last_indexed_block` are covered.
+    pub blocks: Vec<BlockNumber>,
+    /// Last block covered by the log index.
+    ///
+    /// Blocks above it are not indexed yet and need to be checked by other means, e.g. by the
+    /// header bloom.
+    pub last_indexed_block: BlockNumber,
+}
+
+/// Log index reader.
+///
+/// The log index maps log emitter addresses and log topics to the blocks in which they occur.
+///
+/// The index has block granularity, it doesn't record the transactions or logs that matched, so
+/// the logs of the candidate blocks still need to be filtered from their receipts.
+#[auto_impl(&, Arc, Box)]
+pub trait LogIndexProvider: Send {
+    /// Returns the blocks in the given range that contain logs matching the filter.
+    ///
+    /// A log matches if it was emitted by any of the `addresses` and, for each position `i`, its
+    /// `i`-th topic is any of `topics[i]`. Empty sets match anything.
+    ///
+    /// Returns `None` if the log index is disabled, or if the filter has neither addresses nor
+    /// topics, in which case the index cannot narrow down the range.
+    fn log_index_blocks(
+        &self,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<LogIndexBlocks>>;
+}
+
+/// Calls `on_address` with the emitter address and `on_topic` with each topic of every log in the
+/// given receipts, i.e. with the keys the logs are indexed by.
+///
+/// Keys are passed as often as they occur, deduplicating them is up to the caller.
+pub fn for_each_log_key<'a, R>(
+    receipts: impl IntoIterator<Item = &'a R>,
+    mut on_address: impl FnMut(Address),
+    mut on_topic: impl FnMut(B256),
+) where
+    R: TxReceipt<Log = Log> + 'a,
+{
+    for log in receipts.into_iter().flat_map(|receipt| receipt.logs()) {
+        on_address(log.address);
+        log.topics().iter().copied().for_each(&mut on_topic);
+    }
+}
@@ -1,12 +1,13 @@
 //! Various noop implementations for traits.
 
 use crate::{
-    AccountReader, BlockBodyIndicesProvider, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, BlockSource, BytecodeReader, ChangeSetReader,
-    HashedPostStateProvider, HeaderProvider, NodePrimitivesProvider, PruneCheckpointReader,
+    AccountHistoryEntry, AccountReader, BlockAccessListProvider, BlockBodyIndicesProvider,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource,
+    BytecodeReader, ChangeSetReader, HashedPostStateProvider, HeaderProvider, HistoryReader,
+    LogIndexBlocks, LogIndexProvider, NodePrimitivesProvider, PruneCheckpointReader,
     ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader, StateProofProvider,
     StateProvider, StateProviderBox, StateProviderFactory, StateReader, StateRootProvider,
-    StorageRootProvider, TransactionVariant, TransactionsProvider, TrieReader,
+    StorageHistoryEntry, StorageRootProvider, TransactionVariant, TransactionsProvider, TrieReader,
 };
 
 #[cfg(feature = "db-api")]
@@ -619,6 +620,44 @@ impl<C: Send + Sync, N: Send + Sync> BlockBodyIndicesProvider for NoopProvider<C
     }
 }
 
+impl<C: Send + Sync, N: Send + Sync> BlockAccessListProvider for NoopProvider<C, N> {
+    fn block_access_list(&self, _number: BlockNumber) -> ProviderResult<Option<Bytes>> {
+        Ok(None)
+    }
+}
+
+impl<C: Send + Sync, N: Send + Sync> HistoryReader for NoopProvider<C, N> {
+    fn account_history(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<AccountHistoryEntry>> {
+        Ok(Vec::new())
+    }
+
+    fn storage_history(
+        &self,
+        _address: Address,
+        _storage_key: StorageKey,
+        _range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<StorageHistoryEntry>> {
+        Ok(Vec::new())
+    }
+}
+
+impl<C: Send + Sync, N: Send + Sync> LogIndexProvider for NoopProvider<C, N> {
+    fn log_index_blocks(
+        &self,
+        _addresses: &[Address],
+        _topics: &[Vec<B256>],
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<LogIndexBlocks>> {
+        Ok(None)
+    }
+}
+
 #[cfg(feature = "db-api")]
 impl<ChainSpec: Send + Sync, N: NodePrimitives> DBProvider for NoopProvider<ChainSpec, N> {
     type Tx = TxMock;
@@ -1,6 +1,9 @@
+use crate::HeaderProvider;
 use alloc::vec::Vec;
-use reth_prune_types::{PruneCheckpoint, PruneSegment};
-use reth_storage_errors::provider::ProviderResult;
+use alloy_primitives::BlockNumber;
+use reth_primitives_traits::BlockHeader;
+use reth_prune_types::{PruneCheckpoint, PruneMode, PruneModes, PruneSegment};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
 /// The trait for fetching prune checkpoint related data.
 #[auto_impl::auto_impl(&)]
@@ -25,3 +28,39 @@ pub trait PruneCheckpointWriter {
         checkpoint: PruneCheckpoint,
     ) -> ProviderResult<()>;
 }
+
+/// Resolves [`PruneMode::Age`] against the header timestamps of the provider, relative to the
+/// `tip` block. Other modes are returned as is.
+///
+/// See [`PruneMode::resolve_age`].
+pub fn resolve_prune_mode<Provider: HeaderProvider>(
+    provider: &Provider,
+    mode: PruneMode,
+    tip: BlockNumber,
+) -> ProviderResult<PruneMode> {
+    mode.resolve_age(tip, |block| block_timestamp(provider, block))
+}
+
+/// Resolves all [`PruneMode::Age`] modes against the header timestamps of the provider, relative
+/// to the `tip` block.
+///
+/// See [`PruneModes::resolve_age`].
+pub fn resolve_prune_modes<Provider: HeaderProvider>(
+    provider: &Provider,
+    modes: &PruneModes,
+    tip: BlockNumber,
+) -> ProviderResult<PruneModes> {
+    modes.resolve_age(tip, |block| block_timestamp(provider, block))
+}
+
+/// Returns the timestamp of the block with the given number, used to resolve
+/// [`PruneMode::Age`].
+pub fn block_timestamp<Provider: HeaderProvider>(
+    provider: &Provider,
+    block: BlockNumber,
+) -> ProviderResult<u64> {
+    Ok(provider
+        .header_by_number(block)?
+        .ok_or_else(|| ProviderError::HeaderNotFound(block.into()))?
+        .timestamp())
+}
@@ -60,6 +60,10 @@ pub mod updates;
 
 pub mod added_removed_keys;
 
+/// Statistics of state witnesses.
+mod witness;
+pub use witness::{WitnessEntryStats, WitnessStats, WitnessTrieStats};
+
 /// Utilities used by other modules in this crate.
 mod utils;
 
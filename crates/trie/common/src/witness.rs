@@ -0,0 +1,251 @@
+//! Size and access statistics of state witnesses.
+
+use crate::{RlpNode, TrieAccount, TrieNode, EMPTY_ROOT_HASH};
+use alloc::vec::Vec;
+use alloy_primitives::{keccak256, map::B256Map, Bytes, B256};
+use alloy_rlp::Decodable;
+
+/// Number and total size of witness entries.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WitnessEntryStats {
+    /// Number of entries.
+    pub count: u64,
+    /// Total size of the entries in bytes.
+    pub bytes: u64,
+}
+
+impl WitnessEntryStats {
+    /// Records an entry of the given size.
+    pub const fn record(&mut self, bytes: usize) {
+        self.count += 1;
+        self.bytes += bytes as u64;
+    }
+}
+
+/// Statistics of the witness nodes of one kind of trie.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct WitnessTrieStats {
+    /// All nodes of the trie.
+    pub total: WitnessEntryStats,
+    /// Nodes by their depth, i.e. the length of their path in nibbles.
+    pub by_depth: Vec<WitnessEntryStats>,
+}
+
+impl WitnessTrieStats {
+    fn record(&mut self, depth: usize, bytes: usize) {
+        self.total.record(bytes);
+        if self.by_depth.len() <= depth {
+            self.by_depth.resize(depth + 1, WitnessEntryStats::default());
+        }
+        self.by_depth[depth].record(bytes);
+    }
+}
+
+/// Statistics of a state witness, see [`WitnessStats::new`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct WitnessStats {
+    /// Account trie nodes.
+    pub account_trie: WitnessTrieStats,
+    /// Storage trie nodes of all accounts.
+    pub storage_trie: WitnessTrieStats,
+    /// Number of distinct storage tries with at least one node in the witness.
+    pub storage_tries: u64,
+    /// Contract bytecodes.
+    pub bytecodes: WitnessEntryStats,
+    /// Number of nodes that are part of the proofs of more than one account or storage slot.
+    pub shared_nodes: u64,
+    /// Nodes that are not reachable from the state root.
+    pub unreferenced: WitnessEntryStats,
+}
+
+impl WitnessStats {
+    /// Computes the statistics of a witness for the state with the given root.
+    ///
+    /// The trie nodes are walked from the state root, descending into the storage trie of every
+    /// revealed account. Nodes embedded in their parent are accounted for as part of the parent.
+    pub fn new(state_root: B256, state: &[Bytes], codes: &[Bytes]) -> Self {
+        let mut walker = WitnessWalker {
+            nodes: state.iter().map(|node| (keccak256(node), node)).collect(),
+            targets: B256Map::default(),
+            stats: Self::default(),
+        };
+        walker.visit_hash(state_root, 0, TrieKind::Account);
+
+        let WitnessWalker { nodes, targets, mut stats } = walker;
+        stats.shared_nodes = targets.values().filter(|targets| **targets > 1).count() as u64;
+        for (hash, node) in nodes {
+            if !targets.contains_key(&hash) {
+                stats.unreferenced.record(node.len());
+            }
+        }
+        for code in codes {
+            stats.bytecodes.record(code.len());
+        }
+
+        stats
+    }
+
+    /// Returns the total number of trie nodes in the witness.
+    pub const fn total_nodes(&self) -> u64 {
+        self.account_trie.total.count + self.storage_trie.total.count + self.unreferenced.count
+    }
+
+    /// Returns the total size of the witness in bytes, including bytecodes.
+    pub const fn total_bytes(&self) -> u64 {
+        self.account_trie.total.bytes +
+            self.storage_trie.total.bytes +
+            self.unreferenced.bytes +
+            self.bytecodes.bytes
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TrieKind {
+    Account,
+    Storage,
+}
+
+/// Walks the witness nodes, counting for every node the number of proofs it is part of.
+#[derive(Debug)]
+struct WitnessWalker<'a> {
+    nodes: B256Map<&'a Bytes>,
+    /// The number of proof targets below each visited node.
+    targets: B256Map<u64>,
+    stats: WitnessStats,
+}
+
+impl WitnessWalker<'_> {
+    /// Visits the node with the given hash, returning the number of proof targets below it.
+    fn visit_hash(&mut self, hash: B256, depth: usize, kind: TrieKind) -> u64 {
+        let Some(node) = self.nodes.get(&hash).copied() else { return 0 };
+
+        // Nodes can be referenced multiple times, e.g. by accounts with the same storage.
+        if let Some(targets) = self.targets.get_mut(&hash) {
+            *targets += 1;
+            return 1
+        }
+
+        match kind {
+            TrieKind::Account => self.stats.account_trie.record(depth, node.len()),
+            TrieKind::Storage => {
+                if depth == 0 {
+                    self.stats.storage_tries += 1;
+                }
+                self.stats.storage_trie.record(depth, node.len());
+            }
+        }
+        // Mark as visited before descending, the count is updated below.
+        self.targets.insert(hash, 0);
+
+        let targets = match TrieNode::decode(&mut &node[..]) {
+            Ok(node) => self.visit_node(node, depth, kind),
+            Err(_) => 1,
+        };
+        self.targets.insert(hash, targets);
+        targets
+    }
+
+    fn visit_child(&mut self, child: &RlpNode, depth: usize, kind: TrieKind) -> u64 {
+        if let Some(hash) = child.as_hash() {
+            self.visit_hash(hash, depth, kind)
+        } else if let Ok(node) = TrieNode::decode(&mut &child[..]) {
+            self.visit_node(node, depth, kind)
+        } else {
+            0
+        }
+    }
+
+    fn visit_node(&mut self, node: TrieNode, depth: usize, kind: TrieKind) -> u64 {
+        match node {
+            // A node without revealed children ends the proof of a missing key.
+            TrieNode::Branch(branch) => branch
+                .stack
+                .iter()
+                .map(|child| self.visit_child(child, depth + 1, kind))
+                .sum::<u64>()
+                .max(1),
+            TrieNode::Extension(extension) => {
+                self.visit_child(&extension.child, depth + extension.key.len(), kind).max(1)
+            }
+            TrieNode::Leaf(leaf) => {
+                if kind == TrieKind::Account &&
+                    let Ok(account) = TrieAccount::decode(&mut &leaf.value[..]) &&
+                    account.storage_root != EMPTY_ROOT_HASH
+                {
+                    self.visit_hash(account.storage_root, 0, TrieKind::Storage);
+                }
+                1
+            }
+            TrieNode::EmptyRoot => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BranchNode, LeafNode, Nibbles, TrieMask};
+    use alloy_consensus::constants::KECCAK_EMPTY;
+    use alloy_primitives::U256;
+    use alloy_rlp::Encodable;
+
+    fn encode(node: &TrieNode) -> Bytes {
+        let mut buf = Vec::new();
+        node.encode(&mut buf);
+        buf.into()
+    }
+
+    fn account_leaf(key: &[u8], storage_root: B256) -> TrieNode {
+        let account =
+            TrieAccount { nonce: 0, balance: U256::from(1), storage_root, code_hash: KECCAK_EMPTY };
+        TrieNode::Leaf(LeafNode::new(Nibbles::from_nibbles(key), alloy_rlp::encode(account)))
+    }
+
+    #[test]
+    fn witness_stats() {
+        let storage_leaf = encode(&TrieNode::Leaf(LeafNode::new(
+            Nibbles::from_nibbles([0; 64]),
+            alloy_rlp::encode(U256::from(u64::MAX)),
+        )));
+        let storage_root = keccak256(&storage_leaf);
+
+        // Two accounts with the same storage below a single branch.
+        let leaf_a = encode(&account_leaf(&[1; 63], storage_root));
+        let leaf_b = encode(&account_leaf(&[2; 63], storage_root));
+        let root = encode(&TrieNode::Branch(BranchNode::new(
+            vec![RlpNode::word_rlp(&keccak256(&leaf_a)), RlpNode::word_rlp(&keccak256(&leaf_b))],
+            TrieMask::new(0b110),
+        )));
+        let unreferenced = Bytes::from_static(&[0xc0]);
+
+        let stats = WitnessStats::new(
+            keccak256(&root),
+            &[root.clone(), leaf_a.clone(), leaf_b.clone(), storage_leaf.clone(), unreferenced],
+            &[Bytes::from_static(&[0x60, 0x00])],
+        );
+
+        assert_eq!(stats.account_trie.total.count, 3);
+        assert_eq!(
+            stats.account_trie.by_depth,
+            vec![
+                WitnessEntryStats { count: 1, bytes: root.len() as u64 },
+                WitnessEntryStats { count: 2, bytes: (leaf_a.len() + leaf_b.len()) as u64 },
+            ]
+        );
+        assert_eq!(stats.storage_tries, 1);
+        assert_eq!(
+            stats.storage_trie.total,
+            WitnessEntryStats { count: 1, bytes: storage_leaf.len() as u64 }
+        );
+        assert_eq!(stats.bytecodes, WitnessEntryStats { count: 1, bytes: 2 });
+        // The root is part of both account proofs, the storage leaf is shared by both accounts.
+        assert_eq!(stats.shared_nodes, 2);
+        assert_eq!(stats.unreferenced, WitnessEntryStats { count: 1, bytes: 1 });
+        assert_eq!(stats.total_nodes(), 5);
+    }
+}
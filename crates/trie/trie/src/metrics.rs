@@ -1,4 +1,4 @@
-use crate::{stats::TrieStats, trie::TrieType};
+use crate::{stats::TrieStats, trie::TrieType, WitnessStats};
 use metrics::{Counter, Histogram};
 use reth_metrics::Metrics;
 
@@ -122,3 +122,50 @@ impl TrieNodeIterMetrics {
         self.leaf_nodes_returned_total.increment(1);
     }
 }
+
+/// Metrics for generated state witnesses, see [`WitnessStats`].
+#[derive(Clone, Metrics)]
+#[metrics(scope = "trie.witness")]
+pub struct WitnessMetrics {
+    /// The number of account trie nodes in a witness.
+    account_nodes: Histogram,
+    /// The size of the account trie nodes in a witness in bytes.
+    account_bytes: Histogram,
+    /// The depth of the deepest account trie node in a witness.
+    account_max_depth: Histogram,
+    /// The number of storage trie nodes in a witness.
+    storage_nodes: Histogram,
+    /// The size of the storage trie nodes in a witness in bytes.
+    storage_bytes: Histogram,
+    /// The number of storage tries in a witness.
+    storage_tries: Histogram,
+    /// The number of bytecodes in a witness.
+    bytecodes: Histogram,
+    /// The size of the bytecodes in a witness in bytes.
+    bytecode_bytes: Histogram,
+    /// The number of nodes shared between multiple proofs in a witness.
+    shared_nodes: Histogram,
+    /// The total size of a witness in bytes.
+    total_bytes: Histogram,
+}
+
+impl WitnessMetrics {
+    /// Create new metrics for witnesses generated by the given source.
+    pub fn new(source: &'static str) -> Self {
+        Self::new_with_labels(&[("source", source)])
+    }
+
+    /// Record witness stats as metrics.
+    pub fn record(&self, stats: &WitnessStats) {
+        self.account_nodes.record(stats.account_trie.total.count as f64);
+        self.account_bytes.record(stats.account_trie.total.bytes as f64);
+        self.account_max_depth.record(stats.account_trie.by_depth.len().saturating_sub(1) as f64);
+        self.storage_nodes.record(stats.storage_trie.total.count as f64);
+        self.storage_bytes.record(stats.storage_trie.total.bytes as f64);
+        self.storage_tries.record(stats.storage_tries as f64);
+        self.bytecodes.record(stats.bytecodes.count as f64);
+        self.bytecode_bytes.record(stats.bytecodes.bytes as f64);
+        self.shared_nodes.record(stats.shared_nodes as f64);
+        self.total_bytes.record(stats.total_bytes() as f64);
+    }
+}
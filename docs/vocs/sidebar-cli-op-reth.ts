@@ -88,6 +88,10 @@ export const opRethCliSidebar: SidebarItem = {
                     text: "op-reth db repair-trie",
                     link: "/cli/op-reth/db/repair-trie"
                 },
+                {
+                    text: "op-reth db state-root",
+                    link: "/cli/op-reth/db/state-root"
+                },
                 {
                     text: "op-reth db static-file-header",
                     link: "/cli/op-reth/db/static-file-header",
@@ -136,6 +140,10 @@ export const opRethCliSidebar: SidebarItem = {
                                 {
                                     text: "op-reth db settings set account_changesets",
                                     link: "/cli/op-reth/db/settings/set/account_changesets"
+                                },
+                                {
+                                    text: "op-reth db settings set log_index",
+                                    link: "/cli/op-reth/db/settings/set/log_index"
                                 }
                             ]
                         }
@@ -144,6 +152,40 @@ export const opRethCliSidebar: SidebarItem = {
                 {
                     text: "op-reth db account-storage",
                     link: "/cli/op-reth/db/account-storage"
+                },
+                {
+                    text: "op-reth db backfill-history",
+                    link: "/cli/op-reth/db/backfill-history"
+                }
+            ]
+        },
+        {
+            text: "op-reth exex",
+            link: "/cli/op-reth/exex",
+            collapsed: true,
+            items: [
+                {
+                    text: "op-reth exex wal",
+                    link: "/cli/op-reth/exex/wal",
+                    collapsed: true,
+                    items: [
+                        {
+                            text: "op-reth exex wal list",
+                            link: "/cli/op-reth/exex/wal/list"
+                        },
+                        {
+                            text: "op-reth exex wal show",
+                            link: "/cli/op-reth/exex/wal/show"
+                        },
+                        {
+                            text: "op-reth exex wal prune",
+                            link: "/cli/op-reth/exex/wal/prune"
+                        },
+                        {
+                            text: "op-reth exex wal verify",
+                            link: "/cli/op-reth/exex/wal/verify"
+                        }
+                    ]
                 }
             ]
         },
@@ -197,6 +239,10 @@ export const opRethCliSidebar: SidebarItem = {
                             link: "/cli/op-reth/stage/unwind/num-blocks"
                         }
                     ]
+                },
+                {
+                    text: "op-reth stage plan",
+                    link: "/cli/op-reth/stage/plan"
                 }
             ]
         },
@@ -227,6 +273,10 @@ export const opRethCliSidebar: SidebarItem = {
                 {
                     text: "op-reth p2p bootnode",
                     link: "/cli/op-reth/p2p/bootnode"
+                },
+                {
+                    text: "op-reth p2p crawl",
+                    link: "/cli/op-reth/p2p/crawl"
                 }
             ]
         },
@@ -92,6 +92,10 @@ export const rethCliSidebar: SidebarItem = {
                     text: "reth db repair-trie",
                     link: "/cli/reth/db/repair-trie"
                 },
+                {
+                    text: "reth db state-root",
+                    link: "/cli/reth/db/state-root"
+                },
                 {
                     text: "reth db static-file-header",
                     link: "/cli/reth/db/static-file-header",
@@ -140,6 +144,10 @@ export const rethCliSidebar: SidebarItem = {
                                 {
                                     text: "reth db settings set account_changesets",
                                     link: "/cli/reth/db/settings/set/account_changesets"
+                                },
+                                {
+                                    text: "reth db settings set log_index",
+                                    link: "/cli/reth/db/settings/set/log_index"
                                 }
                             ]
                         }
@@ -148,6 +156,40 @@ export const rethCliSidebar: SidebarItem = {
                 {
                     text: "reth db account-storage",
                     link: "/cli/reth/db/account-storage"
+                },
+                {
+                    text: "reth db backfill-history",
+                    link: "/cli/reth/db/backfill-history"
+                }
+            ]
+        },
+        {
+            text: "reth exex",
+            link: "/cli/reth/exex",
+            collapsed: true,
+            items: [
+                {
+                    text: "reth exex wal",
+                    link: "/cli/reth/exex/wal",
+                    collapsed: true,
+                    items: [
+                        {
+                            text: "reth exex wal list",
+                            link: "/cli/reth/exex/wal/list"
+                        },
+                        {
+                            text: "reth exex wal show",
+                            link: "/cli/reth/exex/wal/show"
+                        },
+                        {
+                            text: "reth exex wal prune",
+                            link: "/cli/reth/exex/wal/prune"
+                        },
+                        {
+                            text: "reth exex wal verify",
+                            link: "/cli/reth/exex/wal/verify"
+                        }
+                    ]
                 }
             ]
         },
@@ -205,6 +247,10 @@ export const rethCliSidebar: SidebarItem = {
                             link: "/cli/reth/stage/unwind/num-blocks"
                         }
                     ]
+                },
+                {
+                    text: "reth stage plan",
+                    link: "/cli/reth/stage/plan"
                 }
             ]
         },
@@ -235,6 +281,10 @@ export const rethCliSidebar: SidebarItem = {
                 {
                     text: "reth p2p bootnode",
                     link: "/cli/reth/p2p/bootnode"
+                },
+                {
+                    text: "reth p2p crawl",
+                    link: "/cli/reth/p2p/crawl"
                 }
             ]
         },
@@ -249,6 +299,32 @@ export const rethCliSidebar: SidebarItem = {
         {
             text: "reth re-execute",
             link: "/cli/reth/re-execute"
+        },
+        {
+            text: "reth stateless",
+            link: "/cli/reth/stateless",
+            collapsed: true,
+            items: [
+                {
+                    text: "reth stateless export",
+                    link: "/cli/reth/stateless/export"
+                },
+                {
+                    text: "reth stateless validate",
+                    link: "/cli/reth/stateless/validate"
+                }
+            ]
+        },
+        {
+            text: "reth engine",
+            link: "/cli/reth/engine",
+            collapsed: true,
+            items: [
+                {
+                    text: "reth engine replay",
+                    link: "/cli/reth/engine/replay"
+                }
+            ]
         }
     ]
 };